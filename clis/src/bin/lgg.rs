@@ -1,7 +1,9 @@
 use anyhow::Result;
-use lgg_cli::{BaseCli, LggCli};
-use lgg_core::Lgg;
+use lgg_cli::{init_logger, BaseCli, LggCli, LggCommand};
+use lgg_core::{Config, Lgg};
+use std::collections::HashMap;
 use std::process::ExitCode;
+use std::time::Instant;
 
 fn main() -> ExitCode {
     match run() {
@@ -14,8 +16,34 @@ fn main() -> ExitCode {
 }
 
 fn run() -> Result<()> {
-    let cli = BaseCli::new();
-    let lgg = Lgg::new()?;
+    let started = Instant::now();
+    let loaded = Config::load();
+    let elapsed = started.elapsed();
+    // Aliases come from the config, so it's loaded before reading the arguments. A config that
+    // doesn't load fails after them, so `--help` and `--version` work anyway.
+    let no_aliases = HashMap::new();
+    let aliases = loaded.as_ref().map_or(&no_aliases, |config| &config.aliases);
+    let cli = BaseCli::<LggCommand>::with_aliases(aliases)?;
+    init_logger("lgg", cli.verbose);
+    let fixes_config = cli.command.as_ref().is_some_and(LggCommand::fixes_config);
+    let mut config = match loaded {
+        Ok(config) => config,
+        Err(error) if fixes_config => {
+            eprintln!("lgg: {error}");
+            Config::defaults()
+        }
+        Err(error) => return Err(error),
+    };
+    log::info!("config loaded in {elapsed:?}");
+    for warning in &config.warnings {
+        eprintln!("lgg: {warning}");
+    }
+    cli.override_config(&mut config)?;
+    let mut lgg = Lgg::with_config(config)?;
+    cli.override_today(&mut lgg)?;
     let lgg_cli = LggCli::new(cli, lgg);
-    lgg_cli.run()
+    let started = Instant::now();
+    let result = lgg_cli.run();
+    log::info!("done in {:?}", started.elapsed());
+    result
 }
@@ -1,7 +1,8 @@
 use anyhow::Result;
-use lgg_cli::{BaseCli, TodoCli};
-use lgg_core::Lgg;
+use lgg_cli::{init_logger, BaseCli, TodoCli, TodoCommand};
+use lgg_core::{Config, Lgg};
 use std::process::ExitCode;
+use std::time::Instant;
 
 fn main() -> ExitCode {
     match run() {
@@ -14,8 +15,20 @@ fn main() -> ExitCode {
 }
 
 fn run() -> Result<()> {
-    let cli = BaseCli::new();
-    let lgg = Lgg::new()?;
+    let cli = BaseCli::<TodoCommand>::new();
+    init_logger("todo", cli.verbose);
+    let started = Instant::now();
+    let mut config = Config::load()?;
+    log::info!("config loaded in {:?}", started.elapsed());
+    for warning in &config.warnings {
+        eprintln!("todo: {warning}");
+    }
+    cli.override_config(&mut config)?;
+    let mut lgg = Lgg::with_config(config)?;
+    cli.override_today(&mut lgg)?;
     let todo_cli = TodoCli::new(cli, lgg);
-    todo_cli.run()
+    let started = Instant::now();
+    let result = todo_cli.run();
+    log::info!("done in {:?}", started.elapsed());
+    result
 }
@@ -1,14 +1,355 @@
 use crate::{
-    common::{create_editor_buffer, open_file_in_editor, resolve_editor, CliModeResult}, render::Renderer,
+    common::{
+        ask, create_seeded_editor_buffer, find_editor, notify, open_file_in_editor,
+        read_passphrase, read_text, resolve_editor, CliModeResult, Format, Output, Period,
+    }, render::{use_hyperlinks, Renderer},
     BaseCli,
     RenderOptions,
 };
-use anyhow::Result;
+#[cfg(unix)]
+use crate::common::agent;
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, Days, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use clap::{ArgGroup, Subcommand};
 use lgg_core::{
-    JournalEntry, JournalQueryResult, JournalWriteEntry, Lgg, QueryError,
-    ReadEntriesOptions,
+    anniversaries_in, compose, entry_template, get_config_value, set_config_value, AmbiguousDate, Config, DateFilter, DateRule, DayFileChange, DefaultAction, EntryChange, JournalEntry, ReadTodoOptions, TodoStatus, JournalQueryResult, JournalWriteEntry, Lgg, Pin, QueryError,
+    ReadEntriesOptions, TemplatePart, TimeSource, TitleEnd, TodoWriteEntry, CONFIG_KEYS,
 };
+use lgg_core::action_items::{self, ActionItem};
+use lgg_core::convert::{csv, json, org};
+use lgg_core::filter::Filter;
+use lgg_core::{journals, pomodoro, review, web_links, week_plan};
+use lgg_core::stats;
 use lgg_core::entries::QueryTagsResult;
+use std::{
+    fs,
+    io::{self, IsTerminal, Write},
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Days ahead the agenda looks for due todos.
+const AGENDA_DAYS: u64 = 7;
+/// Most entries printed by `--search`.
+const SEARCH_LIMIT: usize = 50;
+/// Lines printed before and after the matches of `--search`, without `--full`.
+const SEARCH_CONTEXT: usize = 2;
+/// What `lgg remind` says when there is no entry today.
+const REMIND_MESSAGE: &str = "You haven't journaled today";
+
+#[derive(Subcommand, Debug)]
+pub enum LggCommand {
+    /// Prints all your entries in another format (e.g., `lgg export --format org > journal.org`)
+    Export {
+        #[arg(long, value_enum)]
+        format: Format,
+    },
+    /// Adds the entries of a file in another format to your journal (e.g., `lgg import --format org journal.org`)
+    /// or of voice notes, with the `transcriber_cmd` of the config (e.g., `lgg import --audio notes/`)
+    Import {
+        #[arg(long, visible_alias = "from", value_enum, required_unless_present = "audio")]
+        format: Option<Format>,
+        /// The file to import.
+        #[arg(required_unless_present = "audio")]
+        file: Option<PathBuf>,
+        /// A directory of voice notes, or one audio file, to transcribe into `@voice` entries.
+        #[arg(long, conflicts_with_all = ["format", "file"])]
+        audio: Option<PathBuf>,
+    },
+    /// Opens your $EDITOR with a section per day, to write entries for several days at once
+    /// (e.g., `lgg compose --days 'last week'`)
+    Compose {
+        /// A day or a range of days (e.g., `yesterday`, `last week`, `14/08/2025`)
+        #[arg(long)]
+        days: String,
+    },
+    /// Prints statistics about your entries (e.g., `lgg stats --words --from 'last month'`)
+    #[command(group(ArgGroup::new("report").args(["words", "sentiment", "extremes"]).required(true).multiple(true)))]
+    Stats {
+        /// Most frequent words and pairs of words. Common words are skipped, add your own with
+        /// `stopwords` in the config.
+        #[arg(long)]
+        words: bool,
+        /// Weekly trend of the mood of your entries, from their positive and negative words.
+        /// Needs lgg built with the `analysis` feature.
+        #[arg(long)]
+        sentiment: bool,
+        /// The longest and shortest entries, by their words.
+        #[arg(long)]
+        extremes: bool,
+        /// How many words and pairs of words to print (50 by default), or entries with
+        /// `--extremes` (5 by default).
+        #[arg(long)]
+        top: Option<usize>,
+        /// Only entries from, or on, this date (e.g., `last month`, `14/08/25`)
+        #[arg(long)]
+        from: Option<String>,
+        /// Only entries until this date. Default is today.
+        #[arg(long, requires = "from")]
+        to: Option<String>,
+        /// Only entries with the given tags.
+        #[arg(long, short, num_args(1..))]
+        tags: Option<Vec<String>>,
+    },
+    /// Finds things to do in your entries (`TODO:` lines, `[] buy milk`, "I need to ...") and asks
+    /// which ones to add to your todos (e.g., `lgg scan-todos --from 'last week'`)
+    ScanTodos {
+        /// Only entries from, or on, this date (e.g., `last week`, `14/08/25`)
+        #[arg(long)]
+        from: Option<String>,
+        /// Only entries until this date. Default is today.
+        #[arg(long, requires = "from")]
+        to: Option<String>,
+        /// Adds every action item found without asking.
+        #[arg(long, short)]
+        yes: bool,
+    },
+    /// Prints how an entry would be read, without writing it: the date and time found, where they
+    /// come from, the title, body and tags (e.g., `lgg explain "yesterday at 6am: Note"`)
+    Explain {
+        /// The entry, as you would write it after `lgg`.
+        #[arg(required = true)]
+        text: Vec<String>,
+    },
+    /// Checks your day files and reports what doesn't follow the format (e.g., `lgg doctor --fix`)
+    Doctor {
+        /// Rewrites missing or invalid day headers with the date of the file name.
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Opens your $EDITOR with the entries, done todos and numbers of the last week or month, to
+    /// write a review saved as an `@review` entry (e.g., `lgg review --period month`)
+    Review {
+        #[arg(long, value_enum, default_value_t = Period::Week)]
+        period: Period,
+    },
+    /// Opens your $EDITOR to plan the next week, with its days, your `[commitments]` and the open
+    /// todos due in it (e.g., `lgg plan week`). Run it again to edit the plan.
+    Plan {
+        #[command(subcommand)]
+        period: PlanPeriod,
+    },
+    /// Keeps track of habits (e.g., `lgg habit add exercise --days mon,wed,fri`,
+    /// `lgg habit done exercise`)
+    Habit {
+        #[command(subcommand)]
+        action: HabitAction,
+    },
+    /// Runs a timer and writes an entry when it ends, with the time spent
+    /// (e.g., `lgg pomodoro 25 "@deepwork writing spec"`)
+    Pomodoro {
+        /// Minutes of the timer.
+        #[arg(default_value_t = 25)]
+        minutes: u32,
+        /// What you work on, the title of the entry. Default is "Pomodoro".
+        text: Vec<String>,
+    },
+    /// Pins a day, or the latest entry with a matching title, to find it with `lgg --pinned` and at
+    /// the top of the agenda (e.g., `lgg pin 2025-01-01`, `lgg pin "insurance numbers"`)
+    Pin {
+        /// A day, or words of the title of an entry.
+        #[arg(required = true)]
+        query: Vec<String>,
+    },
+    /// Unpins a day or an entry pinned with `lgg pin` (e.g., `lgg unpin 2025-01-01`)
+    Unpin {
+        /// A day, or words of the title of an entry.
+        #[arg(required = true)]
+        query: Vec<String>,
+    },
+    /// Writes the text as the title of an entry now, without reading a date in it or opening an
+    /// editor, and only prints the new entry. For launchers like Raycast or Alfred
+    /// (e.g., `lgg quick "buy milk @errands"`)
+    Quick {
+        /// The title of the entry.
+        #[arg(required = true)]
+        text: Vec<String>,
+    },
+    /// Writes an entry now with a link and the title of its page, tagged `@link`
+    /// (e.g., `lgg url https://example.com/post`)
+    Url {
+        /// The link, starting with `http://` or `https://`.
+        link: String,
+    },
+    /// Sends a desktop notification if you haven't written today, unless it's one of your
+    /// `quiet_days`, and for the todos of `[todo.reminders]` about to be due (e.g.,
+    /// `lgg remind --daily 21:00`, to run it from systemd or launchd)
+    Remind {
+        /// Keeps running, and checks every day at this time (HH:MM). Without it, checks now.
+        #[arg(long)]
+        daily: Option<String>,
+    },
+    /// Keeps the passphrase of the private entries in memory, so the commands that follow don't
+    /// ask for it until it expires or you run `lgg lock` (e.g., `lgg agent --ttl 30 &`)
+    Agent {
+        /// Minutes the passphrase is kept after it was typed.
+        #[arg(long, default_value_t = 15)]
+        ttl: u64,
+    },
+    /// Makes `lgg agent` forget the passphrase right away (e.g., `lgg lock`)
+    Lock,
+    /// Pulls the changes made on your other devices, merges them with yours and pushes the result,
+    /// through the `[sync]` of the config: git, rsync, WebDAV or S3 (e.g., `lgg sync`)
+    Sync,
+    /// Prints the entries added, changed and removed since a git commit, a date or a copy of the
+    /// journal, without changing anything (e.g., `lgg diff --from 'last week'`,
+    /// `lgg diff --from ~/backups/journal`)
+    Diff {
+        /// A directory with a copy of the journal, a commit of its git repository (`HEAD~3`,
+        /// a branch or tag) or a date, the last commit before it. A range of dates is its first
+        /// day.
+        #[arg(long)]
+        from: String,
+        /// Like `--from`, but a range of dates is the end of its last day. Default is the
+        /// journal as it is now.
+        #[arg(long)]
+        to: Option<String>,
+    },
+    /// Prints the entries as a timeline, a day after another with its entries, to see at a
+    /// glance when you wrote (e.g., `lgg timeline --from 'last month'`)
+    Timeline {
+        /// Entries from, or on, this date (e.g., `last month`, `14/08/25`)
+        #[arg(long, default_value = "this month")]
+        from: String,
+        /// Entries until this date. Default is today.
+        #[arg(long)]
+        to: Option<String>,
+        /// Only entries with the given tags.
+        #[arg(long, short, num_args(1..))]
+        tags: Option<Vec<String>>,
+    },
+    /// Prints the entries most like one, by the words and tags they share, to find old notes
+    /// about the same thing (e.g., `lgg related "2025-08-14 09:00"`, `lgg related yesterday`)
+    Related {
+        /// The entry: its date and time (the `id` of `--output json`), or a day with one entry.
+        #[arg(required = true)]
+        entry: Vec<String>,
+        /// How many entries to print.
+        #[arg(long, default_value_t = 5)]
+        top: usize,
+    },
+    /// Prints the entries that link to a day with `[[2025-08-15]]`, or to one of its entries with
+    /// `[[2025-08-15T08:03]]` (e.g., `lgg backlinks 2025-08-15`, `lgg backlinks yesterday`)
+    Backlinks {
+        #[arg(required = true)]
+        day: Vec<String>,
+    },
+    /// Pipes the entries of a day to the `summarizer_cmd` of the config (like a local LLM) and
+    /// saves what it prints as a `@summary` entry (e.g., `lgg summarize --on yesterday`)
+    Summarize {
+        /// The day to summarize.
+        #[arg(long, default_value = "yesterday")]
+        on: String,
+    },
+    /// Adds the tags of the `[auto_tags]` rules of the config to the entries already written that
+    /// match them, as new entries get them (e.g., `lgg retag --apply-rules --from "this year"`)
+    Retag {
+        /// Applies the `[auto_tags]` rules.
+        #[arg(long, required = true)]
+        apply_rules: bool,
+        /// Only entries from, or on, this date (e.g., `this year`, `14/08/25`). Default is all of
+        /// them.
+        #[arg(long)]
+        from: Option<String>,
+        /// Only entries until this date. Default is today.
+        #[arg(long, requires = "from")]
+        to: Option<String>,
+    },
+    /// Works on the tags of your entries (e.g., `lgg tags normalize`)
+    Tags {
+        #[command(subcommand)]
+        action: TagsAction,
+    },
+    /// Writes the files lgg keeps next to your day files (e.g., `lgg index files`)
+    Index {
+        #[command(subcommand)]
+        action: IndexAction,
+    },
+    /// Asks for the basic options and writes them to config.toml (journal directory, editor, date
+    /// order and default time). Run it again to change them.
+    Init,
+    /// Reads and changes your config.toml (e.g., `lgg config set default_time 08:00`)
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+impl LggCommand {
+    /// Whether it fixes a config that doesn't load, so it runs with the default config instead.
+    pub fn fixes_config(&self) -> bool {
+        matches!(
+            self,
+            LggCommand::Init
+                | LggCommand::Config {
+                    action: ConfigAction::Edit | ConfigAction::Path | ConfigAction::Set { .. }
+                }
+        )
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Prints the value of an option (e.g., `lgg config get default_time`, `lgg config get synonyms.ytd`)
+    Get { key: String },
+    /// Writes an option to config.toml, keeping its comments. The new value is checked first.
+    Set {
+        key: String,
+        /// The value. It can start with `-`, for aliases (e.g., `lgg config set aliases.week "--from monday"`).
+        #[arg(allow_hyphen_values = true)]
+        value: String,
+    },
+    /// Prints every option with the value in use, defaults and overrides included.
+    List,
+    /// Opens config.toml in your $EDITOR.
+    Edit,
+    /// Prints the path of config.toml.
+    Path,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PlanPeriod {
+    /// The week after this one, Monday to Sunday.
+    Week,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TagsAction {
+    /// Rewrites the `[tag_aliases]` of the config in your day files as their canonical tag.
+    Normalize,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum IndexAction {
+    /// Writes the `index.md` of every month and year, with the titles of their entries linking to
+    /// their day file. With `index_files = true` in the config they are also written with each day.
+    Files,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HabitAction {
+    /// Adds a habit, due every day or on some days of the week.
+    Add {
+        name: String,
+        /// Days of the week it's due (e.g., `mon,wed,fri`). Default is every day.
+        #[arg(long)]
+        days: Option<String>,
+    },
+    /// Marks a habit as done today, or on another day.
+    Done {
+        name: String,
+        /// The day it was done (e.g., `yesterday`, `14/08/25`)
+        #[arg(long)]
+        on: Option<String>,
+    },
+    /// Prints a grid of the last weeks of every habit, with their streaks.
+    Show {
+        /// How many weeks to show, up to 520 (ten years).
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=520))]
+        weeks: u32,
+    },
+}
 
 enum PrintResult {
     Entries(JournalQueryResult),
@@ -16,26 +357,39 @@ enum PrintResult {
 }
 
 pub struct LggCli {
-    cli: BaseCli,
+    cli: BaseCli<LggCommand>,
     renderer: Renderer,
     lgg: Lgg,
 }
 impl LggCli {
-    pub fn new(cli: BaseCli, lgg: Lgg) -> Self {
+    pub fn new(cli: BaseCli<LggCommand>, lgg: Lgg) -> Self {
         let options = cli.load();
 
         let renderer = Renderer::new(Some(RenderOptions {
             date_format: lgg.config.journal_date_format.to_string(),
+            locale: lgg.config.locale,
             use_color: options.use_color,
-            short_mode: options.short_mode,
+            style: options.style,
+            hyperlinks: use_hyperlinks(lgg.config.hyperlinks, options.use_color),
+            quiet: options.quiet,
+            images: options.images,
+            tags: lgg.config.tag_pattern.clone(),
         }));
         LggCli { cli, renderer, lgg }
     }
 
     pub fn run(&self) -> Result<()> {
+        if self.cli.journal.as_deref() == Some(journals::ALL_JOURNALS) {
+            return self.all_journals_mode();
+        }
+
+        if let CliModeResult::Finish = self.command_mode()? {
+            return Ok(());
+        };
+
         if self.cli.path {
             self.renderer
-                .print_info(&format!("{}", self.lgg.config.journal_dir.display()));
+                .print_result(&format!("{}", self.lgg.config.journal_dir.display()));
             return Ok(());
         }
 
@@ -51,98 +405,1445 @@ impl LggCli {
             return Ok(());
         };
 
-        if let CliModeResult::Finish = self.editor_mode()? {
+        if let CliModeResult::Finish = self.default_mode()? {
+            return Ok(());
+        };
+
+        Ok(())
+    }
+
+    pub fn command_mode(&self) -> Result<CliModeResult> {
+        match &self.cli.command {
+            Some(LggCommand::Export { format }) => {
+                let result = self.lgg.journal.read_entries(&ReadEntriesOptions::default());
+                let output = match format {
+                    Format::Org => org::journal_to_org(&result.entries),
+                    Format::Taskwarrior => bail!("Taskwarrior can only be used with todos."),
+                };
+                print!("{output}");
+                // Errors go to stderr, so they don't end up in the exported file.
+                for error in &result.errors {
+                    if let QueryError::FileError { path, error } = error {
+                        eprintln!("lgg: could not process '{}': {}", path.display(), error);
+                    }
+                }
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Import {
+                audio: Some(audio), ..
+            }) => {
+                let result = self.lgg.import_voice_notes(audio)?;
+                let message = format!(
+                    "Imported {} voice notes from {}",
+                    result.entries.len(),
+                    audio.display()
+                );
+                self.print_new_entries(&message, &result.entries);
+                self.print_import_errors(&result.errors);
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Import { format, file, .. }) => {
+                // Without --audio, clap requires both.
+                let (Some(format), Some(file)) = (format, file) else {
+                    bail!("Expected a --format and a file to import.");
+                };
+                let content = fs::read_to_string(file)
+                    .with_context(|| format!("reading {}", file.display()))?;
+                let result = match format {
+                    Format::Org => org::org_to_journal(&content, &self.lgg.config.tag_pattern),
+                    Format::Taskwarrior => bail!("Taskwarrior can only be used with todos."),
+                };
+                let imported = self.lgg.journal.import_entries(result.entries)?;
+                self.renderer.print_info(&format!(
+                    "Imported {imported} entries from {}",
+                    file.display()
+                ));
+                self.print_import_errors(&result.errors);
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Compose { days }) => {
+                self.warn_ambiguous_dates(&self.lgg.ambiguous_dates(days));
+                let dates = match self.lgg.parse_dates(days, None) {
+                    Some(DateFilter::Single(date)) => vec![date],
+                    Some(DateFilter::Range(start, end)) => start
+                        .iter_days()
+                        .take_while(|d| *d <= end)
+                        .collect(),
+                    None => bail!("Could not understand the days '{days}'."),
+                };
+                let editor = resolve_editor(&self.lgg.config.editor)?;
+                let template = compose::compose_template(&dates);
+                let temp_dir = self.lgg.config.temp_dir.as_deref();
+                let input = create_seeded_editor_buffer(&editor, &template, temp_dir)?;
+                let config = &self.lgg.config;
+                let result =
+                    compose::parse_compose_buffer(&input, config.default_time, &config.tag_pattern);
+                if result.entries.is_empty() {
+                    self.renderer.print_info("No entries to save.");
+                } else {
+                    for entry in &result.entries {
+                        self.check_future_date(entry.date)?;
+                    }
+                    let new_entries = self.lgg.journal.create_entries(result.entries)?;
+                    let message = format!("Added {} new entries", new_entries.len());
+                    self.print_new_entries(&message, &new_entries);
+                }
+                self.print_import_errors(&result.errors);
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Stats {
+                words,
+                sentiment,
+                extremes,
+                top,
+                from,
+                to,
+                tags,
+            }) => {
+                let options = ReadEntriesOptions {
+                    dates: self.parse_from_to(from.as_deref(), to.as_deref())?,
+                    tags: tags.as_ref(),
+                    ..Default::default()
+                };
+                let result = self.lgg.journal.read_entries(&options);
+                if *words {
+                    let top = top.unwrap_or(50);
+                    let report =
+                        stats::word_frequencies(&result.entries, &self.lgg.config.stopwords, top);
+                    if report.words.is_empty() {
+                        self.renderer.print_result("No words found.");
+                    } else {
+                        self.renderer.print_count_table("Word", "Count", &report.words);
+                        println!();
+                        self.renderer
+                            .print_count_table("Bigram", "Count", &report.bigrams);
+                    }
+                }
+                if *sentiment {
+                    if *words {
+                        println!();
+                    }
+                    self.print_sentiment(&result.entries)?;
+                }
+                if *extremes {
+                    if *words || *sentiment {
+                        println!();
+                    }
+                    let found = stats::extremes(&result.entries, top.unwrap_or(5));
+                    if found.longest.is_empty() {
+                        self.renderer.print_result("No entries found.");
+                    } else {
+                        self.renderer.print_word_counts("Longest", &found.longest);
+                        println!();
+                        self.renderer.print_word_counts("Shortest", &found.shortest);
+                    }
+                }
+                if !result.errors.is_empty() {
+                    self.print_errors(&result.errors.iter().collect());
+                }
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::ScanTodos { from, to, yes }) => {
+                self.scan_todos(from.as_deref(), to.as_deref(), *yes)?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Explain { text }) => {
+                self.explain(&text.join(" "))?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Doctor { fix }) => {
+                let journal = &self.lgg.journal;
+                let mut errors = journal.check_files();
+                if *fix {
+                    let mut fixed = 0;
+                    for error in &errors {
+                        if let QueryError::FileError { path, .. } = error
+                            && journal.fix_day_header(path)?
+                        {
+                            self.renderer
+                                .print_info(&format!("Fixed the day header of {}", path.display()));
+                            fixed += 1;
+                        }
+                    }
+                    if fixed > 0 {
+                        errors = journal.check_files();
+                    }
+                }
+                if errors.is_empty() {
+                    self.renderer.print_result("No problems found.");
+                } else {
+                    self.print_errors(&errors.iter().collect());
+                }
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Review { period }) => {
+                self.review_mode(*period)?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Plan { period }) => {
+                match period {
+                    PlanPeriod::Week => self.plan_week_mode()?,
+                }
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Habit { action }) => {
+                self.habit_mode(action)?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Pomodoro { minutes, text }) => {
+                self.pomodoro_mode(*minutes, &text.join(" "))?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Pin { query }) => {
+                self.pin_mode(&query.join(" "), true)?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Unpin { query }) => {
+                self.pin_mode(&query.join(" "), false)?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Quick { text }) => {
+                self.quick_mode(&text.join(" "))?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Url { link }) => {
+                self.url_mode(link)?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Remind { daily }) => {
+                self.remind_mode(daily.as_deref())?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Agent { ttl }) => {
+                self.agent_mode(*ttl)?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Lock) => {
+                self.lock_mode()?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Sync) => {
+                self.sync_mode()?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Diff { from, to }) => {
+                self.diff_mode(from, to.as_deref())?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Timeline { from, to, tags }) => {
+                let options = ReadEntriesOptions {
+                    dates: self.parse_from_to(Some(from), to.as_deref())?,
+                    tags: tags.as_ref(),
+                    ..Default::default()
+                };
+                let mut result = self.lgg.journal.read_entries(&options);
+                result.entries.sort_by_key(|entry| (entry.date, entry.time));
+                if result.entries.is_empty() {
+                    self.renderer.print_result("No entries found.");
+                } else {
+                    self.renderer.print_timeline(&result.entries);
+                }
+                if !result.errors.is_empty() {
+                    self.print_errors(&result.errors.iter().collect());
+                }
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Related { entry, top }) => {
+                self.related_mode(&entry.join(" "), *top)?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Backlinks { day }) => {
+                let day = day.join(" ");
+                self.warn_ambiguous_dates(&self.lgg.ambiguous_dates(&day));
+                let Some(DateFilter::Single(date)) = self.lgg.parse_dates(&day, None) else {
+                    bail!("Could not understand the day '{day}'.");
+                };
+                let passphrase = self.private_passphrase()?;
+                let result = self.lgg.journal.backlinks(date, passphrase.as_deref());
+                if let Output::Json = self.cli.output {
+                    print!("{}", json::journal_to_json(&result.entries));
+                    // Errors go to stderr, so they don't end up in the json output.
+                    for error in &result.errors {
+                        if let QueryError::FileError { path, error } = error {
+                            eprintln!("lgg: could not process '{}': {}", path.display(), error);
+                        }
+                    }
+                    return Ok(CliModeResult::Finish);
+                }
+                if result.entries.is_empty() {
+                    self.renderer.print_result(&format!("No entries link to {date}."));
+                } else {
+                    for entry in &result.entries {
+                        self.renderer.print_journal_entry_line(entry);
+                    }
+                }
+                if !result.errors.is_empty() {
+                    self.print_errors(&result.errors.iter().collect());
+                }
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Summarize { on }) => {
+                self.warn_ambiguous_dates(&self.lgg.ambiguous_dates(on));
+                let Some(DateFilter::Single(date)) = self.lgg.parse_dates(on, None) else {
+                    bail!("Could not understand the day '{on}'.");
+                };
+                let summary = self.lgg.summarize(date)?;
+                let message = format!("Added the summary to {}", summary.path.display());
+                self.print_new_entries(&message, &[summary]);
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Retag { from, to, .. }) => {
+                self.retag_mode(from.as_deref(), to.as_deref())?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Tags { action }) => {
+                match action {
+                    TagsAction::Normalize => self.normalize_tags_mode()?,
+                }
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Index { action }) => {
+                match action {
+                    IndexAction::Files => self.index_files_mode()?,
+                }
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Init) => {
+                self.init_mode()?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(LggCommand::Config { action }) => {
+                self.config_mode(action)?;
+                Ok(CliModeResult::Finish)
+            }
+            None => Ok(CliModeResult::NothingToDo),
+        }
+    }
+
+    /// `lgg review`: opens a buffer to look back on the last `period` and saves what's left of
+    /// it as an entry of today.
+    fn review_mode(&self, period: Period) -> Result<()> {
+        let (start, end) = match self.lgg.parse_dates(period.keyword().as_ref(), None) {
+            Some(DateFilter::Single(date)) => (date, date),
+            Some(DateFilter::Range(start, end)) => (start, end),
+            None => bail!("Could not find the dates of the last {}.", period.name()),
+        };
+        let dates = DateFilter::Range(start, end);
+        let entries = self.lgg.journal.read_entries(&ReadEntriesOptions {
+            dates: Some(dates),
+            ..Default::default()
+        });
+        let done = self.lgg.todos.read_entries(&ReadTodoOptions {
+            done_date: Some(dates),
+            status: Some(&[TodoStatus::Done]),
+            ..Default::default()
+        });
+
+        let editor = resolve_editor(&self.lgg.config.editor)?;
+        let template = review::review_template(
+            start,
+            end,
+            &entries.entries,
+            &done.todos,
+            &self.lgg.config.tag_pattern,
+        );
+        let temp_dir = self.lgg.config.temp_dir.as_deref();
+        let input = create_seeded_editor_buffer(&editor, &template, temp_dir)?;
+        let body = review::read_review_buffer(&input);
+        if body.is_empty() {
+            self.renderer.print_info("No review to save.");
+            return Ok(());
+        }
+        let entry_to_create = JournalWriteEntry {
+            date: self.lgg.config.reference_date,
+            time: Local::now().time(),
+            title: review::review_title(period.name(), start, end),
+            body,
+            tags: vec![review::REVIEW_TAG.to_string()],
+        };
+        let preview = || self.lgg.journal.preview_entries(vec![entry_to_create.clone()]);
+        if !self.show_changes(preview)? {
+            return Ok(());
+        }
+        let new_entry = self.lgg.journal.create_entry(entry_to_create)?;
+        let message = format!("Added new entry to {}", new_entry.path.display());
+        self.print_new_entries(&message, &[new_entry]);
+        Ok(())
+    }
+
+    /// `lgg plan week`: opens the plan of next week if it was written this week, or writes it
+    /// from a buffer with the days of the week.
+    fn plan_week_mode(&self) -> Result<()> {
+        let today = self.lgg.config.reference_date;
+        let (start, end) = week_plan::next_week(today);
+        let title = week_plan::plan_title(start, end);
+        // Written during the week before the one it plans.
+        let this_week = DateFilter::Range(start - Days::new(7), start - Days::new(1));
+        let written = self.lgg.journal.read_entries(&ReadEntriesOptions {
+            dates: Some(this_week),
+            ..Default::default()
+        });
+        if let Some(plan) = written.entries.iter().find(|entry| entry.title == title) {
+            self.open_entry_in_editor(plan)?;
+            return Ok(());
+        }
+
+        let open = self.lgg.todos.read_entries(&ReadTodoOptions {
+            status: Some(&TodoStatus::OPEN),
+            ..Default::default()
+        });
+        let commitments = &self.lgg.config.commitments;
+        let editor = resolve_editor(&self.lgg.config.editor)?;
+        let template = week_plan::plan_template(start, commitments, &open.todos);
+        let temp_dir = self.lgg.config.temp_dir.as_deref();
+        let input = create_seeded_editor_buffer(&editor, &template, temp_dir)?;
+        // Read as a review: without the comment lines.
+        let body = review::read_review_buffer(&input);
+        if body.is_empty() {
+            self.renderer.print_info("No plan to save.");
+            return Ok(());
+        }
+        let entry_to_create = JournalWriteEntry {
+            date: today,
+            time: Local::now().time(),
+            title,
+            body,
+            tags: vec![week_plan::PLAN_TAG.to_string()],
+        };
+        let preview = || self.lgg.journal.preview_entries(vec![entry_to_create.clone()]);
+        if !self.show_changes(preview)? {
+            return Ok(());
+        }
+        let new_entry = self.lgg.journal.create_entry(entry_to_create)?;
+        let message = format!("Added new entry to {}", new_entry.path.display());
+        self.print_new_entries(&message, &[new_entry]);
+        Ok(())
+    }
+
+    /// `lgg habit`: adds habits, marks them as done and prints their grid.
+    fn habit_mode(&self, action: &HabitAction) -> Result<()> {
+        let habits = &self.lgg.habits;
+        let today = self.lgg.config.reference_date;
+        match action {
+            HabitAction::Add { name, days } => {
+                let days = match days {
+                    Some(days) => match self.lgg.parse_weekdays(days) {
+                        Some(days) => days,
+                        None => bail!("Could not understand the days '{days}', like mon,wed,fri."),
+                    },
+                    None => Vec::new(),
+                };
+                let habit = habits.add_habit(name, days)?;
+                self.renderer
+                    .print_info(&format!("Added habit `{}`", habit.name));
+            }
+            HabitAction::Done { name, on } => {
+                let habit = habits.find_habit(name)?;
+                let date = match on.as_deref().map(|on| (on, self.lgg.parse_dates(on, None))) {
+                    None => today,
+                    Some((_, Some(DateFilter::Single(date)))) => date,
+                    Some((on, _)) => bail!("Could not understand the day '{on}'."),
+                };
+                if date > today {
+                    bail!("{date} is in the future, a habit can only be done today or before.");
+                }
+                if !habits.tick(&habit, date)? {
+                    self.renderer
+                        .print_info(&format!("`{}` was already done on {date}.", habit.name));
+                    return Ok(());
+                }
+                let ticks = habits.read_ticks()?;
+                let streak = habit.streaks(&ticks[&habit.name], today).current;
+                let message = match streak {
+                    0 | 1 => format!("Done `{}` on {date}.", habit.name),
+                    _ => format!("Done `{}` on {date}, {streak} in a row.", habit.name),
+                };
+                self.renderer.print_info(&message);
+            }
+            HabitAction::Show { weeks } => {
+                let list = habits.read_habits()?;
+                if list.is_empty() {
+                    self.renderer
+                        .print_result("No habits yet. Add one with `lgg habit add`.");
+                } else {
+                    let ticks = habits.read_ticks()?;
+                    self.renderer.print_habit_grid(&list, &ticks, *weeks, today);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `lgg pomodoro`: counts down `minutes` on the terminal, then writes an entry titled `text`
+    /// at the time it started. Stopping it early writes nothing.
+    fn pomodoro_mode(&self, minutes: u32, text: &str) -> Result<()> {
+        if minutes == 0 {
+            bail!("A pomodoro needs at least one minute.");
+        }
+        let title = pomodoro::pomodoro_title(text);
+        // On the day of `--today`, like the other entries written now.
+        let (date, start) = (self.lgg.config.reference_date, Local::now().time());
+        let end = Instant::now() + Duration::from_secs(u64::from(minutes) * 60);
+        let interactive = io::stdout().is_terminal();
+        loop {
+            let left = end.saturating_duration_since(Instant::now());
+            if interactive {
+                let seconds = left.as_millis().div_ceil(1000);
+                print!("\r{:02}:{:02} {title} ", seconds / 60, seconds % 60);
+                io::stdout().flush()?;
+            }
+            if left.is_zero() {
+                break;
+            }
+            thread::sleep(left.min(Duration::from_secs(1)));
+        }
+        if interactive {
+            // Rings the terminal bell.
+            println!("\x07");
+        }
+
+        let finished = Local::now().time();
+        let entry_to_create = pomodoro::pomodoro_entry(date, start, finished, minutes, text);
+        let preview = || self.lgg.journal.preview_entries(vec![entry_to_create.clone()]);
+        if !self.show_changes(preview)? {
+            return Ok(());
+        }
+        let new_entry = self.lgg.journal.create_entry(entry_to_create)?;
+        let message = format!("Added new entry to {}", new_entry.path.display());
+        self.print_new_entries(&message, &[new_entry]);
+        Ok(())
+    }
+
+    /// `lgg quick`: writes `text` as the title of an entry today at the current time. Nothing in it
+    /// is read as a date, and only the new entry is printed.
+    fn quick_mode(&self, text: &str) -> Result<()> {
+        let title = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if title.is_empty() {
+            bail!("Nothing to write, the entry needs a title.");
+        }
+        let entry_to_create = self.seal_private(JournalWriteEntry {
+            date: self.lgg.config.reference_date,
+            time: Local::now().time(),
+            title,
+            body: String::new(),
+            tags: Vec::new(),
+        })?;
+        let preview = || self.lgg.journal.preview_entries(vec![entry_to_create.clone()]);
+        if !self.show_changes(preview)? {
+            return Ok(());
+        }
+        let new_entry = self.lgg.journal.create_entry(entry_to_create)?;
+        match self.cli.output {
+            Output::Json => print!("{}", json::journal_to_json(&[new_entry])),
+            _ => self.renderer.print_saved_entry_line(&new_entry),
+        }
+        Ok(())
+    }
+
+    /// `lgg url`: writes `link` today at the current time, titled with its page when it can be
+    /// fetched. Private entries keep the bare link.
+    fn url_mode(&self, link: &str) -> Result<()> {
+        let Some(url) = web_links::lone_url(link) else {
+            bail!("'{link}' is not a link, it should start with http:// or https://.");
+        };
+        let entry = JournalWriteEntry {
+            date: self.lgg.config.reference_date,
+            time: Local::now().time(),
+            title: url.to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        };
+        // Private entries are never fetched, so the link doesn't leave this computer.
+        let page_title = if self.cli.private { None } else { web_links::fetch_title(url) };
+        let entry_to_create = match page_title {
+            Some(page_title) => web_links::titled_link(entry, url, &page_title),
+            None if self.cli.private => entry,
+            None => {
+                self.renderer
+                    .print_info("Could not fetch the title of the page, saving the bare link.");
+                entry
+            }
+        };
+        let entry_to_create = self.seal_private(entry_to_create)?;
+        let preview = || self.lgg.journal.preview_entries(vec![entry_to_create.clone()]);
+        if !self.show_changes(preview)? {
+            return Ok(());
+        }
+        let new_entry = self.lgg.journal.create_entry(entry_to_create)?;
+        let message = format!("Added new entry to {}", new_entry.path.display());
+        self.print_new_entries(&message, &[new_entry]);
+        Ok(())
+    }
+
+    /// With `fetch_titles` in the config, `entry` titled with the page of its link when it's only
+    /// a link. Private entries are never fetched.
+    fn with_page_title(&self, entry: JournalWriteEntry) -> JournalWriteEntry {
+        if !self.lgg.config.fetch_titles || self.cli.private {
+            return entry;
+        }
+        web_links::with_page_title(entry)
+    }
+
+    /// `lgg retag --apply-rules`: adds the tags of the `[auto_tags]` rules to the entries from
+    /// `from` to `to` that match them.
+    fn retag_mode(&self, from: Option<&str>, to: Option<&str>) -> Result<()> {
+        if self.lgg.config.auto_tags.is_empty() {
+            bail!("There are no rules to apply, add them to [auto_tags] in the config.");
+        }
+        let options = ReadEntriesOptions {
+            dates: self.parse_from_to(from, to)?,
+            ..Default::default()
+        };
+        let changes = self.lgg.journal.retag_changes(&options)?;
+        if changes.is_empty() {
+            self.renderer.print_result("No entries to retag.");
+            return Ok(());
+        }
+        if !self.show_changes(|| Ok(changes.clone()))? {
+            return Ok(());
+        }
+        self.lgg.journal.write_changes(&changes)?;
+        let days = changes.len();
+        self.renderer.print_info(&format!(
+            "Retagged entries of {days} day{}",
+            if days == 1 { "" } else { "s" }
+        ));
+        Ok(())
+    }
+
+    /// `lgg tags normalize`: writes the `[tag_aliases]` of every entry as their canonical tag.
+    fn normalize_tags_mode(&self) -> Result<()> {
+        if self.lgg.config.tag_aliases.is_empty() {
+            bail!("There are no aliases to normalize, add them to [tag_aliases] in the config.");
+        }
+        let changes = self.lgg.journal.normalize_tags_changes()?;
+        if changes.is_empty() {
+            self.renderer.print_result("No tags to normalize.");
+            return Ok(());
+        }
+        if !self.show_changes(|| Ok(changes.clone()))? {
+            return Ok(());
+        }
+        self.lgg.journal.write_changes(&changes)?;
+        let days = changes.len();
+        self.renderer.print_info(&format!(
+            "Normalized the tags of {days} day{}",
+            if days == 1 { "" } else { "s" }
+        ));
+        Ok(())
+    }
+
+    /// `lgg index files`: writes the index files of every month and year of the journal.
+    fn index_files_mode(&self) -> Result<()> {
+        let changed = self.lgg.journal.write_all_index_files()?;
+        if changed == 0 {
+            self.renderer.print_result("The index files are up to date.");
             return Ok(());
+        }
+        self.renderer.print_info(&format!(
+            "Wrote {changed} index file{}",
+            if changed == 1 { "" } else { "s" }
+        ));
+        Ok(())
+    }
+
+    /// `lgg pin` (`pin`) and `lgg unpin`: the day `query`, or the latest entry with a title
+    /// matching it.
+    fn pin_mode(&self, query: &str, pin: bool) -> Result<()> {
+        let (target, title) = self.find_pin(query)?;
+        let pins = self.lgg.journal.pins();
+        let changed = if pin {
+            pins.add(target, &title)?
+        } else {
+            pins.remove(target)?
+        };
+        let what = match target {
+            Pin::Day(date) => date.to_string(),
+            Pin::Entry(date, _) => format!("\"{title}\" of {date}"),
         };
+        let message = match (pin, changed) {
+            (true, true) => format!("Pinned {what}."),
+            (true, false) => format!("{what} is already pinned."),
+            (false, true) => format!("Unpinned {what}."),
+            (false, false) => format!("{what} is not pinned."),
+        };
+        self.renderer.print_info(&message);
+        Ok(())
+    }
 
+    /// The pin of `query`, a day or the latest entry with a matching title, and the title.
+    fn find_pin(&self, query: &str) -> Result<(Pin, String)> {
+        match self.lgg.parse_dates(query, None) {
+            Some(DateFilter::Single(date)) => {
+                self.warn_ambiguous_dates(&self.lgg.ambiguous_dates(query));
+                return Ok((Pin::Day(date), String::new()));
+            }
+            Some(DateFilter::Range(..)) => bail!("Pin one day at a time, not '{query}'."),
+            None => {}
+        }
+        let options = ReadEntriesOptions {
+            title: Some(query),
+            ..Default::default()
+        };
+        let result = self.lgg.journal.read_entries(&options);
+        match result.entries.iter().max_by_key(|e| (e.date, e.time)) {
+            Some(entry) => Ok((Pin::Entry(entry.date, entry.time), entry.title.clone())),
+            None => bail!("No day or entry title matches '{query}'."),
+        }
+    }
+
+    /// `lgg remind`: reminds you to write today, or with `daily` every day at that time, and of
+    /// the todos of `[todo.reminders]`. With `daily`, it runs until it's stopped and reminds of
+    /// each todo when its time comes.
+    fn remind_mode(&self, daily: Option<&str>) -> Result<()> {
+        let now = Local::now().naive_local();
+        let Some(daily) = daily else {
+            self.remind(now.date());
+            self.remind_todos(NaiveDateTime::MIN, now);
+            return Ok(());
+        };
+        let Ok(time) = NaiveTime::parse_from_str(daily, "%H:%M") else {
+            bail!("Invalid time '{daily}'. Expected HH:MM, like 21:00.");
+        };
+        self.remind_todos(NaiveDateTime::MIN, now);
+        let mut checked = now;
+        loop {
+            let now = Local::now().naive_local();
+            let mut next = now.date().and_time(time);
+            if next <= now {
+                next += TimeDelta::days(1);
+            }
+            let reminders = &self.lgg.config.todo_reminders;
+            let wake = if reminders.is_empty() {
+                next
+            } else {
+                let upcoming = self.lgg.todos.read_reminders(reminders);
+                let upcoming = upcoming.into_iter().map(|(at, _)| at).find(|at| *at > now);
+                // The todo list is read again at least every hour, in case it changed.
+                [Some(next), Some(now + TimeDelta::hours(1)), upcoming]
+                    .into_iter()
+                    .flatten()
+                    .min()
+                    .unwrap_or(next)
+            };
+            thread::sleep((wake - now).to_std()?);
+            if wake == next {
+                self.remind(next.date());
+            }
+            self.remind_todos(checked, wake);
+            checked = wake;
+        }
+    }
+
+    /// Reminds of the todos of `[todo.reminders]` whose time to be reminded of is after `after`
+    /// and not after `until`, unless they're due by then.
+    fn remind_todos(&self, after: NaiveDateTime, until: NaiveDateTime) {
+        let format = &self.lgg.config.todo_datetime_format;
+        for (at, todo) in self.lgg.todos.read_reminders(&self.lgg.config.todo_reminders) {
+            let Some(due_date) = todo.due_date else {
+                continue;
+            };
+            if at > after && at <= until && due_date > until {
+                self.notify(&format!("{} is due {}", todo.title, due_date.format(format)));
+            }
+        }
+    }
+
+    /// Runs the passphrase agent until it's killed.
+    fn agent_mode(&self, ttl: u64) -> Result<()> {
+        #[cfg(unix)]
+        {
+            let path = agent::socket_path();
+            let listener = agent::listen(&path)?;
+            self.renderer.print_info(&format!(
+                "Keeping the passphrase for {ttl} minutes, on {}",
+                path.display()
+            ));
+            agent::serve(listener, std::time::Duration::from_secs(ttl * 60))
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = ttl;
+            bail!("The passphrase agent needs unix sockets, which this system doesn't have.")
+        }
+    }
+
+    /// Makes the agent forget the passphrase.
+    fn lock_mode(&self) -> Result<()> {
+        #[cfg(not(unix))]
+        self.renderer.print_result("No agent is running.");
+        #[cfg(unix)]
+        if agent::lock(&agent::socket_path()) {
+            self.renderer.print_result("The passphrase was forgotten.");
+        } else {
+            self.renderer.print_result("No agent is running.");
+        }
+        Ok(())
+    }
+
+    /// Sends the reminder if there is no day file for `date` and it's not a quiet day. Without
+    /// notifications, it's printed instead.
+    fn remind(&self, date: NaiveDate) {
+        if self.lgg.config.quiet_days.contains(&date.weekday())
+            || self.lgg.journal.day_file(date).exists()
+        {
+            return;
+        }
+        self.notify(REMIND_MESSAGE);
+    }
+
+    /// Sends `message` as a desktop notification, or prints it if it can't be sent.
+    fn notify(&self, message: &str) {
+        if let Err(e) = notify("lgg", message) {
+            eprintln!("lgg: could not send the notification: {e}");
+            self.renderer.print_result(message);
+        }
+    }
+
+    /// `lgg sync`: syncs the journal and prints what changed, and the entries changed on both
+    /// sides where the local one was kept.
+    fn sync_mode(&self) -> Result<()> {
+        let summary = self.lgg.sync()?;
+        let changes = [
+            ("Pulled", &summary.pulled),
+            ("Pushed", &summary.pushed),
+            ("Merged", &summary.merged),
+            ("Removed", &summary.removed),
+        ];
+        for (what, paths) in changes {
+            for path in paths {
+                self.renderer.print_info(&format!("{what} {}", path.display()));
+            }
+        }
+        if changes.iter().all(|(_, paths)| paths.is_empty()) {
+            self.renderer.print_result("Already in sync.");
+        } else {
+            self.renderer.print_result(&format!(
+                "Pulled {} files, pushed {}, merged {} and removed {}.",
+                summary.pulled.len(),
+                summary.pushed.len(),
+                summary.merged.len(),
+                summary.removed.len()
+            ));
+        }
+        for conflict in &summary.conflicts {
+            self.renderer.print_result(conflict);
+        }
+        Ok(())
+    }
+
+    /// `lgg related`: the `top` entries most like the one `query` names, as in
+    /// [`Self::find_entry`].
+    fn related_mode(&self, query: &str, top: usize) -> Result<()> {
+        let entry = self.find_entry(query)?;
+        let related = self.lgg.journal.related_entries(&entry, top);
+        let entries: Vec<JournalEntry> = related.into_iter().map(|r| r.entry).collect();
+        if let Output::Json = self.cli.output {
+            print!("{}", json::journal_to_json(&entries));
+            return Ok(());
+        }
+        if entries.is_empty() {
+            self.renderer.print_result("No related entries found.");
+            return Ok(());
+        }
+        self.renderer.print_info(&format!("Entries like \"{}\":", entry.title));
+        for entry in &entries {
+            self.renderer.print_journal_entry_line(entry);
+        }
+        Ok(())
+    }
+
+    /// The entry `query` names: a date and a time (`2025-08-14 09:00`, `yesterday at 9:00`), or
+    /// a day with a single entry.
+    fn find_entry(&self, query: &str) -> Result<JournalEntry> {
+        let parse_time = |time: &str| {
+            NaiveTime::parse_from_str(time, "%H:%M:%S")
+                .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+                .ok()
+        };
+        let (day, time) = match query.rsplit_once(' ') {
+            Some((day, time)) if parse_time(time).is_some() => {
+                (day.strip_suffix(" at").unwrap_or(day), parse_time(time))
+            }
+            _ => (query, None),
+        };
+        self.warn_ambiguous_dates(&self.lgg.ambiguous_dates(day));
+        let Some(DateFilter::Single(date)) = self.lgg.parse_dates(day, None) else {
+            bail!("Could not understand the day '{day}'.");
+        };
+        let options = ReadEntriesOptions {
+            dates: Some(DateFilter::Single(date)),
+            ..Default::default()
+        };
+        let mut entries = self.lgg.journal.read_entries(&options).entries;
+        if let Some(time) = time {
+            entries.retain(|entry| entry.time == time);
+        }
+        match entries.len() {
+            0 => bail!("There is no entry on {query}."),
+            1 => Ok(entries.remove(0)),
+            _ => {
+                let times: Vec<String> = entries
+                    .iter()
+                    .map(|entry| format!("{date} {}", entry.time.format("%H:%M")))
+                    .collect();
+                bail!("{query} has {} entries, pick one: {}.", entries.len(), times.join(", "))
+            }
+        }
+    }
+
+    /// `lgg diff`: the entries that changed from `from` to `to`, grouped by how, with the lines
+    /// of the bodies that changed.
+    fn diff_mode(&self, from: &str, to: Option<&str>) -> Result<()> {
+        let changes = self.lgg.diff(from, to)?;
+        if changes.is_empty() {
+            self.renderer.print_result("No entries changed.");
+            return Ok(());
+        }
+        let heading_of = |change: &EntryChange| match change {
+            EntryChange::Added(_) => "Added",
+            EntryChange::Changed { .. } => "Changed",
+            EntryChange::Removed(_) => "Removed",
+        };
+        for heading in ["Added", "Changed", "Removed"] {
+            let group: Vec<&EntryChange> =
+                changes.iter().filter(|c| heading_of(c) == heading).collect();
+            if group.is_empty() {
+                continue;
+            }
+            self.renderer.print_result(&format!("{heading} ({}):", group.len()));
+            for change in group {
+                self.renderer.print_journal_entry_line(change.entry());
+                self.renderer.print_diff(&change.body_diff());
+            }
+        }
+        Ok(())
+    }
+
+    /// `lgg init`: asks for the basic options, writes them to config.toml and creates the journal
+    /// directory. Every answer is checked before moving to the next question.
+    fn init_mode(&self) -> Result<()> {
+        let path = Config::file_path().context("could not find the config directory")?;
+        let mut content = if path.exists() {
+            fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?
+        } else {
+            String::new()
+        };
+        let config = &self.lgg.config;
+        let questions = [
+            (
+                "journal_dir",
+                "Where do you want to keep your journal?",
+                config.journal_dir.display().to_string(),
+            ),
+            (
+                "editor",
+                "Which editor do you want to write with?",
+                find_editor(&config.editor).unwrap_or_default(),
+            ),
+            (
+                "date_order",
+                "How do you write dates? dmy (14/08/2025), mdy (08/14/2025) or ymd (2025/08/14)",
+                get_config_value(&content, "date_order")?
+                    .map_or("dmy".to_string(), |v| v.trim_matches('"').to_string()),
+            ),
+            (
+                "default_time",
+                "Time of the entries written with a date but no time (HH:MM)?",
+                config.default_time.format("%H:%M").to_string(),
+            ),
+        ];
+        let mut journal_dir = config.journal_dir.clone();
+        for (key, question, default) in questions {
+            loop {
+                let answer = ask(question, &default)?;
+                let answer = match answer.strip_prefix("~/") {
+                    Some(rest) if key == "journal_dir" => std::env::home_dir()
+                        .map_or(answer.clone(), |home| home.join(rest).display().to_string()),
+                    _ => answer,
+                };
+                match set_config_value(&content, key, &answer) {
+                    Ok(new_content) => {
+                        content = new_content;
+                        if key == "journal_dir" {
+                            journal_dir = PathBuf::from(answer);
+                        }
+                        break;
+                    }
+                    Err(e) => self.renderer.print_result(&format!("{e} Try again.")),
+                }
+            }
+        }
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("creating config dir {}", dir.display()))?;
+        }
+        fs::write(&path, &content).with_context(|| format!("writing {}", path.display()))?;
+        fs::create_dir_all(&journal_dir)
+            .with_context(|| format!("creating journal dir {}", journal_dir.display()))?;
+        self.renderer
+            .print_info(&format!("Saved your config to {}", path.display()));
+        Ok(())
+    }
+
+    /// `lgg config`: reads and writes config.toml.
+    fn config_mode(&self, action: &ConfigAction) -> Result<()> {
+        let path = Config::file_path().context("could not find the config directory")?;
+        let content = if path.exists() {
+            fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?
+        } else {
+            String::new()
+        };
+        let write = |content: &str| -> Result<()> {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)
+                    .with_context(|| format!("creating config dir {}", dir.display()))?;
+            }
+            fs::write(&path, content).with_context(|| format!("writing {}", path.display()))
+        };
+
+        match action {
+            ConfigAction::Path => println!("{}", path.display()),
+            ConfigAction::Get { key } => {
+                let root = key.split_once('.').map_or(key.as_str(), |(table, _)| table);
+                if !CONFIG_KEYS.contains(&root) {
+                    bail!("Unknown config key `{key}`.");
+                }
+                let value = match get_config_value(&content, key)? {
+                    Some(value) => Some(value),
+                    None => self
+                        .lgg
+                        .config
+                        .values()
+                        .into_iter()
+                        .find_map(|(k, v)| (k == key).then_some(v).flatten()),
+                };
+                match value {
+                    Some(value) => println!("{value}"),
+                    None => self.renderer.print_result(&format!("`{key}` is not set.")),
+                }
+            }
+            ConfigAction::Set { key, value } => {
+                let content = set_config_value(&content, key, value)?;
+                write(&content)?;
+                self.renderer
+                    .print_info(&format!("Set `{key}` in {}", path.display()));
+            }
+            ConfigAction::List => {
+                if let Some(project_file) = &self.lgg.config.project_file {
+                    println!("# with the project config {}", project_file.display());
+                }
+                for (key, value) in self.lgg.config.values() {
+                    match value {
+                        Some(value) => println!("{key} = {value}"),
+                        None if key == "editor" => match find_editor(&None) {
+                            Some(editor) => println!(
+                                "# editor is not set, using \"{editor}\" ($VISUAL, $EDITOR, vim, \
+                                 vi or nano)"
+                            ),
+                            None => println!("# editor is not set, and none was found"),
+                        },
+                        None => println!("# {key} is not set"),
+                    }
+                }
+                if self.cli.today.is_some() {
+                    let today = self.lgg.config.reference_date;
+                    println!("# today is {today}, from --today or LGG_TODAY");
+                }
+                if let Some(synonyms) = get_config_value(&content, "synonyms")? {
+                    println!("\n[synonyms]\n{synonyms}");
+                }
+                if let Some(anniversaries) = get_config_value(&content, "anniversaries")? {
+                    println!("\n[anniversaries]\n{anniversaries}");
+                }
+                if let Some(remote) = get_config_value(&content, "remote")? {
+                    println!("\n[remote]\n{remote}");
+                }
+                if let Some(sync) = get_config_value(&content, "sync")? {
+                    println!("\n[sync]\n{sync}");
+                }
+                if let Some(reminders) = get_config_value(&content, "todo.reminders")? {
+                    println!("\n[todo.reminders]\n{reminders}");
+                }
+            }
+            ConfigAction::Edit => {
+                if !path.exists() {
+                    write("")?;
+                }
+                let editor = resolve_editor(&self.lgg.config.editor)?;
+                open_file_in_editor(&editor, &path)?;
+                let content = fs::read_to_string(&path)
+                    .with_context(|| format!("reading {}", path.display()))?;
+                let warnings = Config::validate(&content)
+                    .with_context(|| format!("{} was saved with errors", path.display()))?;
+                for warning in warnings {
+                    eprintln!("lgg: {warning}");
+                }
+                self.renderer
+                    .print_info(&format!("Edited file {}", path.display()));
+            }
+        }
         Ok(())
     }
 
     pub fn write_mode(&self) -> Result<CliModeResult> {
         let new_entry: JournalEntry;
-        if !self.cli.text.is_empty() {
-            let inline = self.cli.text.join(" ");
-            let parsed_entry = self.lgg.parse_user_input(&inline)?;
-            let entry_to_create = JournalWriteEntry {
+        let inputs = self.cli.inline_entries()?;
+        if inputs.len() > 1 {
+            return self.write_many(&inputs);
+        }
+        if let Some(inline) = inputs.first() {
+            let parsed_entry = self.lgg.parse_user_input(inline)?;
+            if parsed_entry.until.is_some() {
+                return self.write_many(&inputs);
+            }
+            self.warn_ambiguous_dates(&parsed_entry.ambiguous_dates);
+            self.check_future_date(parsed_entry.date)?;
+            let merge_within = self
+                .lgg
+                .config
+                .merge_within_minutes
+                .filter(|_| !parsed_entry.explicit_date && !parsed_entry.explicit_time)
+                .filter(|_| !self.cli.private);
+            let entry_to_create = self.seal_private(self.with_page_title(JournalWriteEntry {
                 date: parsed_entry.date,
                 time: parsed_entry.time,
                 title: parsed_entry.title,
                 body: parsed_entry.body,
                 tags: Vec::new(),
+            }))?;
+
+            let preview = || match merge_within {
+                Some(minutes) => Ok(vec![
+                    self.lgg
+                        .journal
+                        .preview_merge_entry(entry_to_create.clone(), minutes)?,
+                ]),
+                None => self.lgg.journal.preview_entries(vec![entry_to_create.clone()]),
             };
+            if !self.show_changes(preview)? {
+                return Ok(CliModeResult::Finish);
+            }
 
-            new_entry = self.lgg.journal.create_entry(entry_to_create)?;
-            self.renderer
-                .print_info(&format!("Added new entry to {}", new_entry.path.display()));
-            self.renderer.print_journal_entry_line(&new_entry);
+            let merged;
+            (new_entry, merged) = match merge_within {
+                Some(minutes) => self
+                    .lgg
+                    .journal
+                    .create_or_merge_entry(entry_to_create, minutes)?,
+                None => (self.lgg.journal.create_entry(entry_to_create)?, false),
+            };
+            let message = if merged {
+                format!("Appended to the previous entry in {}", new_entry.path.display())
+            } else {
+                format!("Added new entry to {}", new_entry.path.display())
+            };
+            self.print_new_entries(&message, &[new_entry]);
+            Ok(CliModeResult::Finish)
+        } else if self.cli.bulk {
+            self.renderer.print_info("No entries to save.");
             Ok(CliModeResult::Finish)
         } else {
             Ok(CliModeResult::NothingToDo)
         }
     }
 
+    /// Writes several inline entries, or one on several days (`monday..friday: Conference`).
+    /// All of them are parsed before writing any.
+    fn write_many(&self, inputs: &[String]) -> Result<CliModeResult> {
+        let mut entries = Vec::new();
+        for inline in inputs {
+            let parsed_entry = self
+                .lgg
+                .parse_user_input(inline)
+                .with_context(|| format!("reading entry '{inline}'"))?;
+            self.warn_ambiguous_dates(&parsed_entry.ambiguous_dates);
+            self.check_future_date(parsed_entry.until.unwrap_or(parsed_entry.date))?;
+            for date in parsed_entry.dates() {
+                entries.push(self.with_page_title(JournalWriteEntry {
+                    date,
+                    time: parsed_entry.time,
+                    title: parsed_entry.title.clone(),
+                    body: parsed_entry.body.clone(),
+                    tags: Vec::new(),
+                }));
+            }
+        }
+        if self.cli.private {
+            let passphrase = read_passphrase(true)?;
+            entries = entries
+                .into_iter()
+                .map(|entry| self.lgg.journal.sealed_entry(entry, &passphrase))
+                .collect::<Result<_>>()?;
+        }
+
+        if !self.show_changes(|| self.lgg.journal.preview_entries(entries.clone()))? {
+            return Ok(CliModeResult::Finish);
+        }
+        let mut new_entries = Vec::new();
+        for entry in entries {
+            new_entries.push(self.lgg.journal.create_entry(entry)?);
+        }
+        let message = format!("Added {} new entries", new_entries.len());
+        self.print_new_entries(&message, &new_entries);
+        Ok(CliModeResult::Finish)
+    }
+
     pub fn editor_mode(&self) -> Result<CliModeResult> {
         if !self.cli.text.is_empty() {
             return self.write_mode();
         }
 
-        let new_entry: JournalEntry;
-
-        let editor = resolve_editor(&self.lgg.config.editor)?;
-        let input = create_editor_buffer(&editor)?;
-        let trimmed = input.trim();
-        if trimmed.is_empty() {
+        let parts = &self.lgg.config.editor_template;
+        let date_prefix = self.lgg.date_prefix();
+        let last_entry = parts
+            .contains(&TemplatePart::LastEntry)
+            .then(|| self.lgg.journal.last_entry())
+            .flatten();
+        let template = entry_template::entry_template(parts, &date_prefix, last_entry.as_ref());
+        let temp_dir = self.lgg.config.temp_dir.as_deref();
+        let input = match find_editor(&self.lgg.config.editor) {
+            Some(editor) => create_seeded_editor_buffer(&editor, &template, temp_dir)?,
+            None => read_text(
+                "No editor found. Write the entry (the first line is the title) and end it with a \
+                 line with a single `.` or Ctrl-D.",
+            )?,
+        };
+        let input = entry_template::read_entry_buffer(&input, &date_prefix);
+        if input.is_empty() {
             self.renderer
-                .print_info(&"No entry to save, because no text was received.".to_string());
+                .print_info("No entry to save, because no text was received.");
             return Ok(CliModeResult::Finish);
         }
-        let inline = self.cli.text.join(" ");
-        let parsed_entry = self.lgg.parse_user_input(&inline)?;
-        let entry_to_create = JournalWriteEntry {
+        let parsed_entry = self.lgg.parse_user_input(&input)?;
+        self.warn_ambiguous_dates(&parsed_entry.ambiguous_dates);
+        self.check_future_date(parsed_entry.date)?;
+        let entry_to_create = self.seal_private(self.with_page_title(JournalWriteEntry {
             date: parsed_entry.date,
             time: parsed_entry.time,
             title: parsed_entry.title,
             body: parsed_entry.body,
             tags: Vec::new(),
+        }))?;
+        let preview = || self.lgg.journal.preview_entries(vec![entry_to_create.clone()]);
+        if !self.show_changes(preview)? {
+            return Ok(CliModeResult::Finish);
+        }
+
+        let new_entry: JournalEntry = self.lgg.journal.create_entry(entry_to_create)?;
+        let message = format!("Added new entry to {}", new_entry.path.display());
+        self.print_new_entries(&message, &[new_entry]);
+        Ok(CliModeResult::Finish)
+    }
+
+    /// Tells what was written: `message` and the line of each new entry, or only the entries as
+    /// JSON with `--output json`, for scripts to chain on.
+    fn print_new_entries(&self, message: &str, entries: &[JournalEntry]) {
+        if let Output::Json = self.cli.output {
+            print!("{}", json::journal_to_json(entries));
+            return;
+        }
+        self.renderer.print_info(message);
+        for entry in entries {
+            self.renderer.print_saved_entry_line(entry);
+        }
+    }
+
+    /// With `--private`, `entry` sealed with the passphrase.
+    fn seal_private(&self, entry: JournalWriteEntry) -> Result<JournalWriteEntry> {
+        if !self.cli.private {
+            return Ok(entry);
+        }
+        self.lgg.journal.sealed_entry(entry, &read_passphrase(true)?)
+    }
+
+    /// With `--show-private`, the passphrase to open the private entries.
+    fn private_passphrase(&self) -> Result<Option<String>> {
+        self.cli
+            .show_private
+            .then(|| read_passphrase(false))
+            .transpose()
+    }
+
+    /// With `--dry-run` or `-v`, prints the diff of the day files `changes` returns.
+    /// Returns whether to write the entries, which a dry run doesn't.
+    fn show_changes(
+        &self,
+        changes: impl FnOnce() -> Result<Vec<DayFileChange>>,
+    ) -> Result<bool> {
+        if !self.cli.dry_run && self.cli.verbose == 0 {
+            return Ok(true);
+        }
+        for change in changes()? {
+            self.renderer.print_diff(&change.diff());
+        }
+        if self.cli.dry_run {
+            self.renderer.print_info("Dry run, nothing was written.");
+        }
+        Ok(!self.cli.dry_run)
+    }
+
+    /// Applies the `allow_future` config to a new entry on `date`.
+    fn check_future_date(&self, date: NaiveDate) -> Result<()> {
+        if self.lgg.check_future_date(date)? {
+            eprintln!("lgg: {date} is in the future. See your planned entries with `lgg --scheduled`.");
+        }
+        Ok(())
+    }
+
+    /// What `lgg` does without arguments, based on the `default_action` config.
+    pub fn default_mode(&self) -> Result<CliModeResult> {
+        if !self.cli.text.is_empty() || !io::stdin().is_terminal() {
+            return self.editor_mode();
+        }
+        if self.lgg.config.project_file.is_none()
+            && Config::file_path().is_some_and(|path| !path.exists())
+        {
+            self.renderer.print_info(&format!(
+                "No config file found, your journal is in {}. Run `lgg init` to choose another place.",
+                self.lgg.config.journal_dir.display()
+            ));
+        }
+        match self.lgg.config.default_action {
+            DefaultAction::Editor => self.editor_mode(),
+            DefaultAction::Today => self.today_mode(false),
+            DefaultAction::Agenda => self.today_mode(true),
+        }
+    }
+
+    /// Prints today's entries and anniversaries and the pending todos due today (or, for the
+    /// agenda, the anniversaries of next week and the todos overdue and due next week).
+    pub fn today_mode(&self, agenda: bool) -> Result<CliModeResult> {
+        let today = self.lgg.config.reference_date;
+        if agenda {
+            let pinned = self.lgg.journal.read_pinned_entries();
+            if !pinned.entries.is_empty() {
+                self.renderer.print_result("Pinned:");
+                for entry in &pinned.entries {
+                    self.renderer.print_journal_entry_line(entry);
+                }
+                println!();
+            }
+            if !pinned.errors.is_empty() {
+                self.print_errors(&pinned.errors.iter().collect());
+            }
+        }
+        let passphrase = self.private_passphrase()?;
+        let options = ReadEntriesOptions {
+            dates: Some(DateFilter::Single(today)),
+            passphrase: passphrase.as_deref(),
+            ..Default::default()
         };
+        let entries = self.lgg.journal.read_entries(&options);
+        self.print_results(&PrintResult::Entries(entries), false);
 
-        new_entry = self.lgg.journal.create_entry(entry_to_create)?;
-        self.renderer
-            .print_info(&format!("Added new entry to {}", new_entry.path.display()));
-        self.renderer.print_journal_entry_line(&new_entry);
+        let days = if agenda {
+            DateFilter::Range(today, today + Days::new(AGENDA_DAYS))
+        } else {
+            DateFilter::Single(today)
+        };
+        let anniversaries = anniversaries_in(&self.lgg.config.anniversaries, days);
+        if !anniversaries.is_empty() {
+            println!();
+            self.renderer.print_anniversaries(&anniversaries);
+        }
+
+        let mut todos = if agenda {
+            self.lgg.todos.read_agenda(AGENDA_DAYS)
+        } else {
+            let options = ReadTodoOptions {
+                due_date: Some(DateFilter::Single(today)),
+                status: Some(&TodoStatus::OPEN),
+                ..Default::default()
+            };
+            self.lgg.todos.read_entries(&options)
+        };
+        // Not having a todo list yet is fine.
+        todos
+            .errors
+            .retain(|e| !matches!(e, QueryError::FileError { path, .. } if !path.exists()));
+
+        println!();
+        if todos.todos.is_empty() {
+            self.renderer.print_result("No pending todos.");
+        } else {
+            for (index, todo) in todos.numbered() {
+                self.renderer.print_numbered_todo_line(index, todo, true);
+            }
+        }
+        if agenda {
+            let days = self.lgg.config.stale_days;
+            let stale = self.lgg.todos.read_stale(days.into()).todos.len();
+            if stale > self.lgg.config.stale_threshold as usize {
+                self.renderer.print_info(&format!(
+                    "{stale} todos without a due date were added more than {days} days ago. See them with `todo --stale`."
+                ));
+            }
+        }
+        if !todos.errors.is_empty() {
+            self.print_errors(&todos.errors.iter().collect());
+        }
         Ok(CliModeResult::Finish)
     }
 
     pub fn read_mode(&self) -> Result<CliModeResult> {
-        let mut start_date: Option<&str> = None;
-        let mut end_date: Option<&str> = None;
         let mut time: Option<&str> = None;
         let mut tags: Option<Vec<String>> = None;
 
         if self.cli.all_tags {
-            let tags = self.lgg.journal.search_all_tags();
+            let tags = self.lgg.journal.search_all_tags(self.cli.min_count);
             self.print_results(&PrintResult::Tags(tags), self.cli.count);
             return Ok(CliModeResult::Finish);
         }
 
-        if let Some(on) = &self.cli.on {
-            start_date = Some(on);
+        if self.cli.status.is_some() {
+            bail!("--status only works with todos.");
         }
-        if let Some(to) = &self.cli.to {
-            start_date = Some(to);
+        if self.cli.stale.is_some() {
+            bail!("--stale only works with todos.");
         }
-        if let Some(from) = &self.cli.from {
-            match &self.cli.to {
-                Some(to) => {
-                    start_date = Some(from);
-                    end_date = Some(to);
-                }
-                None => {
-                    start_date = Some(from);
-                    end_date = Some(&"today");
+
+        if let Some(query) = &self.cli.search {
+            if let Output::Csv = self.cli.output {
+                bail!("--output csv doesn't work with --search.");
+            }
+            let context = if self.cli.full { usize::MAX } else { SEARCH_CONTEXT };
+            let result = self.lgg.journal.search_entries(query, SEARCH_LIMIT, context)?;
+            if let Output::Json = self.cli.output {
+                print!("{}", json::search_to_json(&result.hits));
+                // Errors go to stderr, so they don't end up in the json output.
+                for error in &result.errors {
+                    if let QueryError::FileError { path, error } = error {
+                        eprintln!("lgg: could not process '{}': {}", path.display(), error);
+                    }
                 }
+                return Ok(CliModeResult::Finish);
+            }
+            if result.hits.is_empty() {
+                self.renderer.print_result("No entries found.");
+            } else {
+                self.renderer.print_search_hits(&result.hits, self.cli.full);
+            }
+            if !result.errors.is_empty() {
+                self.print_errors(&result.errors.iter().collect());
             }
+            return Ok(CliModeResult::Finish);
+        }
+
+        if self.cli.scheduled {
+            let result = self.lgg.journal.read_scheduled_entries();
+            self.print_results(&PrintResult::Entries(result), self.cli.count);
+            return Ok(CliModeResult::Finish);
         }
+
+        if self.cli.pinned {
+            let result = self.lgg.journal.read_pinned_entries();
+            self.print_results(&PrintResult::Entries(result), self.cli.count);
+            return Ok(CliModeResult::Finish);
+        }
+
+        let (start_date, end_date) = self.query_date_args();
         if let Some(has_time) = &self.cli.at {
             time = Some(has_time);
         }
@@ -150,44 +1851,288 @@ impl LggCli {
             tags = Some(has_tags.to_vec());
         }
 
-        if start_date.is_none() && time.is_none() && tags.is_none() {
+        let csv_output = matches!(self.cli.output, Output::Csv | Output::Json);
+        let title = self.cli.title.as_deref();
+        let filter = self.cli.filter.as_deref().map(Filter::parse).transpose()?;
+        if start_date.is_none()
+            && time.is_none()
+            && tags.is_none()
+            && title.is_none()
+            && filter.is_none()
+            && self.cli.min_words.is_none()
+            && self.cli.max_words.is_none()
+            && !csv_output
+            && self.cli.group_by.is_none()
+        {
             return Ok(CliModeResult::NothingToDo);
         }
 
         let dates = match start_date {
-            Some(d) => self.lgg.parse_dates(d, end_date),
+            Some(d) => self.parse_query_dates(d, end_date),
             None => None,
         };
+        let passphrase = self.private_passphrase()?;
         let options = ReadEntriesOptions {
             dates,
             time,
             tags: self.cli.tags.as_ref(),
-            ..Default::default()
+            title,
+            passphrase: passphrase.as_deref(),
+            filter: filter.as_ref(),
+            min_words: self.cli.min_words,
+            max_words: self.cli.max_words,
+            sort: self.cli.sort.into(),
         };
         let result = self.lgg.journal.read_entries(&options);
         self.print_results(&PrintResult::Entries(result), self.cli.count);
+        if self.cli.on.is_some()
+            && !self.cli.count
+            && !csv_output
+            && let Some(dates) = dates
+        {
+            let anniversaries = anniversaries_in(&self.lgg.config.anniversaries, dates);
+            if !anniversaries.is_empty() {
+                println!();
+                self.renderer.print_anniversaries(&anniversaries);
+            }
+        }
         Ok(CliModeResult::Finish)
     }
 
+    /// The start and end dates of `--on`, `--from` and `--to`, as written.
+    fn query_date_args(&self) -> (Option<&str>, Option<&str>) {
+        match (&self.cli.on, &self.cli.from, &self.cli.to) {
+            (_, Some(from), Some(to)) => (Some(from), Some(to)),
+            (_, Some(from), None) => (Some(from), Some("today")),
+            (_, None, Some(to)) => (Some(to), None),
+            (Some(on), None, None) => (Some(on), None),
+            (None, None, None) => (None, None),
+        }
+    }
+
+    /// `--journal all`: the entries of every journal found by `--search` or by `--on`, `--from`,
+    /// `--tags`..., each journal after its name. With `--output json`, the entries have the name
+    /// of their journal.
+    fn all_journals_mode(&self) -> Result<()> {
+        let cli = &self.cli;
+        if cli.command.is_some() || !cli.text.is_empty() || cli.bulk || cli.edit.is_some() {
+            bail!("--journal all only reads entries, choose one journal to write or edit.");
+        }
+        if cli.path || cli.all_tags || cli.scheduled || cli.pinned || cli.count {
+            bail!("--journal all only works with --search and the flags that find entries.");
+        }
+        if let Output::Csv = cli.output {
+            bail!("--output csv doesn't work with --journal all.");
+        }
+        if cli.status.is_some() || cli.stale.is_some() {
+            bail!("--status and --stale only work with todos.");
+        }
+        let config = &self.lgg.config;
+        let json_output = matches!(cli.output, Output::Json);
+        let mut found = false;
+        let mut errors = Vec::new();
+
+        if let Some(query) = &cli.search {
+            let context = if cli.full { usize::MAX } else { SEARCH_CONTEXT };
+            let results = journals::search_all_journals(config, query, SEARCH_LIMIT, context)?;
+            for (name, result) in &results {
+                errors.extend(&result.errors);
+                if json_output {
+                    print!("{}", json::search_to_json_in(name, &result.hits));
+                } else if !result.hits.is_empty() {
+                    self.renderer.print_journal_name(name, found);
+                    self.renderer.print_search_hits(&result.hits, cli.full);
+                }
+                found |= !result.hits.is_empty();
+            }
+            return self.finish_all_journals(found, &errors);
+        }
+
+        let (start_date, end_date) = self.query_date_args();
+        let filter = cli.filter.as_deref().map(Filter::parse).transpose()?;
+        if start_date.is_none()
+            && cli.at.is_none()
+            && cli.tags.is_none()
+            && cli.title.is_none()
+            && filter.is_none()
+            && cli.min_words.is_none()
+            && cli.max_words.is_none()
+        {
+            bail!("--journal all needs something to look for, like --on today or --search words.");
+        }
+        let dates = match start_date {
+            Some(d) => self.parse_query_dates(d, end_date),
+            None => None,
+        };
+        let passphrase = self.private_passphrase()?;
+        let options = ReadEntriesOptions {
+            dates,
+            time: cli.at.as_deref(),
+            tags: cli.tags.as_ref(),
+            title: cli.title.as_deref(),
+            passphrase: passphrase.as_deref(),
+            filter: filter.as_ref(),
+            min_words: cli.min_words,
+            max_words: cli.max_words,
+            sort: cli.sort.into(),
+        };
+        let results = journals::read_all_journals(config, &options)?;
+        for (name, result) in &results {
+            errors.extend(&result.errors);
+            if json_output {
+                print!("{}", json::journal_to_json_in(name, &result.entries));
+            } else if !result.entries.is_empty() {
+                self.renderer.print_journal_name(name, found);
+                let lgg = Lgg::with_config(config.for_journal(name)?)?;
+                let day_file = |date| lgg.journal.day_file(date);
+                self.renderer.print_journal_entries(result, day_file);
+            }
+            found |= !result.entries.is_empty();
+        }
+        self.finish_all_journals(found, &errors)
+    }
+
+    /// Tells when `--journal all` found nothing, and prints the `errors` of its journals.
+    fn finish_all_journals(&self, found: bool, errors: &[&QueryError]) -> Result<()> {
+        if let Output::Json = self.cli.output {
+            // Errors go to stderr, so they don't end up in the json output.
+            for error in errors {
+                if let QueryError::FileError { path, error } = error {
+                    eprintln!("lgg: could not process '{}': {}", path.display(), error);
+                }
+            }
+            return Ok(());
+        }
+        if !found {
+            self.renderer.print_result("No entries found.");
+        }
+        if !errors.is_empty() {
+            self.print_errors(&errors.to_vec());
+        }
+        Ok(())
+    }
+
+    /// Dates to look for, with the `range_order` config applied.
+    fn parse_query_dates(&self, start_date: &str, end_date: Option<&str>) -> Option<DateFilter> {
+        self.warn_ambiguous_dates(&self.lgg.ambiguous_dates(start_date));
+        if let Some(end_date) = end_date {
+            self.warn_ambiguous_dates(&self.lgg.ambiguous_dates(end_date));
+        }
+        let (dates, swapped) = self.lgg.order_dates(self.lgg.parse_dates(start_date, end_date)?);
+        if swapped {
+            eprintln!("lgg: the end date is before the start date, they have been swapped.");
+        }
+        Some(dates)
+    }
+
+    /// `lgg explain`: prints how `input` is read, step by step.
+    fn explain(&self, input: &str) -> Result<()> {
+        let parsed = self.lgg.parse_user_input(input)?;
+        let trace = &parsed.trace;
+        let field = |name: &str, value: &str| println!("{:<10} {value}", format!("{name}:"));
+
+        field("input", input);
+        field("prefix", trace.prefix.as_deref().unwrap_or("none, the whole input is text"));
+        let date = parsed.date.format("%Y-%m-%d (%a)");
+        match (&trace.date_token, &trace.date_rule) {
+            (Some(token), Some(rule)) => {
+                let rule = match rule {
+                    DateRule::IsoDateTime => "an ISO date and time".to_string(),
+                    DateRule::Keyword(keyword) => format!("the keyword `{}`", keyword.as_ref()),
+                    DateRule::Shifted(keyword, days) => {
+                        format!("{days:+} days from the keyword `{}`", keyword.as_ref())
+                    }
+                    DateRule::Offset { base, offset } => format!("`{base}` moved by `{offset}`"),
+                    DateRule::IsoWeek => "an ISO week".to_string(),
+                    DateRule::Format(format) => format!("the format `{format}`"),
+                    DateRule::Range => "a range of days".to_string(),
+                };
+                field("date", &format!("{date}, `{token}` read as {rule}"));
+            }
+            _ => field("date", &format!("{date}, no date, today")),
+        }
+        if let Some(until) = parsed.until {
+            field("until", &format!("{}, written on every day", until.format("%Y-%m-%d (%a)")));
+        }
+        for ambiguous in &parsed.ambiguous_dates {
+            let readings: Vec<String> = ambiguous.readings.iter().map(|d| d.to_string()).collect();
+            field(
+                "ambiguous",
+                &format!("`{}` could be {}", ambiguous.token, readings.join(" or ")),
+            );
+        }
+        let time = parsed.time.format("%H:%M");
+        let time_source = match (parsed.time_source, &trace.time_token) {
+            (TimeSource::Explicit, Some(token)) => format!("from `{token}`"),
+            (TimeSource::Explicit, None) => "from the prefix".to_string(),
+            (TimeSource::Default, Some(token)) => {
+                format!("`{token}` is not a time, default_time of the config")
+            }
+            (TimeSource::Default, None) => "no time, default_time of the config".to_string(),
+            (TimeSource::Now, _) => "no date nor time, the current time".to_string(),
+        };
+        field("time", &format!("{time}, {time_source}"));
+        let title_end = match trace.title_end {
+            TitleEnd::LineBreak => "ends at the line break".to_string(),
+            TitleEnd::Punctuation(mark) => format!("ends at the first `{mark}`"),
+            TitleEnd::EndOfText => "the whole text".to_string(),
+        };
+        field("title", &format!("{} ({title_end})", parsed.title));
+        field("body", if parsed.body.is_empty() { "none" } else { &parsed.body });
+        let tags = if trace.tags.is_empty() {
+            "none".to_string()
+        } else {
+            trace.tags.join(", ")
+        };
+        field("tags", &tags);
+        Ok(())
+    }
+
+    /// Warns about dates that could be read as different days.
+    fn warn_ambiguous_dates(&self, ambiguous: &[AmbiguousDate]) {
+        for date in ambiguous {
+            let readings: Vec<String> = date.readings.iter().map(|d| d.to_string()).collect();
+            eprintln!(
+                "lgg: '{}' could be {}. Using {}, pick the order with `--date-order`.",
+                date.token,
+                readings.join(" or "),
+                readings[0]
+            );
+        }
+    }
+
     pub fn edit_mode(&self) -> Result<CliModeResult> {
-        if let Some(start_date) = &self.cli.edit {
-            let dates = self.lgg.parse_dates(start_date, None);
+        if let Some(query) = &self.cli.edit {
+            let dates = self.lgg.parse_dates(query, None);
+            if dates.is_some() {
+                self.warn_ambiguous_dates(&self.lgg.ambiguous_dates(query));
+            }
+            // Not a date, look for the entry by its title.
+            let by_title = dates.is_none();
             let options = ReadEntriesOptions {
                 dates,
+                title: if by_title {
+                    Some(query)
+                } else {
+                    self.cli.title.as_deref()
+                },
                 ..Default::default()
             };
             let results = self.lgg.journal.read_entries(&options);
+            // Entries are sorted by date: by title we want the most recent one.
+            let found = if by_title {
+                results.entries.last()
+            } else {
+                results.entries.first()
+            };
 
-            return match results.entries.first() {
+            return match found {
                 Some(entry) => {
-                    let editor = resolve_editor(&self.lgg.config.editor)?;
-                    open_file_in_editor(&editor, &entry.path)?;
-                    self.renderer
-                        .print_info(&format!("Edited file {}", entry.path.display()));
+                    self.open_entry_in_editor(entry)?;
                     Ok(CliModeResult::Finish)
                 }
                 None => {
-                    self.renderer.print_info("No entries found to edit.");
+                    self.renderer.print_result("No entries found to edit.");
                     Ok(CliModeResult::Finish)
                 }
             }
@@ -195,35 +2140,74 @@ impl LggCli {
         Ok(CliModeResult::NothingToDo)
     }
 
+    /// Opens the day file of `entry` in the editor.
+    fn open_entry_in_editor(&self, entry: &JournalEntry) -> Result<()> {
+        let editor = resolve_editor(&self.lgg.config.editor)?;
+        let storage = &self.lgg.journal.storage;
+        if storage.on_disk() {
+            open_file_in_editor(&editor, &entry.path)?;
+        } else {
+            // Not a file the editor can open: edited as a copy, then saved back.
+            let content = storage.read_file(&entry.path)?;
+            let temp_dir = self.lgg.config.temp_dir.as_deref();
+            let edited = create_seeded_editor_buffer(&editor, &content, temp_dir)?;
+            storage.write_file(&entry.path, &edited)?;
+        }
+        self.renderer
+            .print_info(&format!("Edited file {}", entry.path.display()));
+        Ok(())
+    }
+
     fn print_results(&self, result: &PrintResult, print_count: bool) {
         let mut errors = Vec::new();
         if print_count {
+            if let (PrintResult::Entries(res), Some(group_by)) = (result, self.cli.group_by) {
+                let groups = stats::count_entries(&res.entries, group_by.into());
+                self.renderer
+                    .print_count_table(group_by.label(), "Entries", &groups);
+                return;
+            }
             match result {
                 PrintResult::Entries(res) => {
                     self.renderer
-                        .print_info(&format!("{} entries found.", res.entries.len()));
+                        .print_result(&format!("{} entries found.", res.entries.len()));
                 }
                 PrintResult::Tags(res) => {
                     self.renderer
-                        .print_info(&format!("{} tags found.", res.tags.len()));
+                        .print_result(&format!("{} tags found.", res.tags.len()));
                 }
             }
 
             return;
         }
 
+        if let (PrintResult::Entries(res), Output::Csv | Output::Json) = (result, self.cli.output) {
+            match self.cli.output {
+                Output::Json => print!("{}", json::journal_to_json(&res.entries)),
+                _ => print!("{}", csv::journal_to_csv(&res.entries)),
+            }
+            // Errors go to stderr, so they don't end up in the csv or json output.
+            for error in &res.errors {
+                if let QueryError::FileError { path, error } = error {
+                    eprintln!("lgg: could not process '{}': {}", path.display(), error);
+                }
+            }
+            return;
+        }
+
         if let PrintResult::Entries(res) = result {
             errors.extend(&res.errors);
             if res.entries.is_empty() {
-                self.renderer.print_info(&"No entries found.".to_string());
+                self.renderer.print_result("No entries found.");
             } else {
-                self.renderer.print_journal_entries(&res);
+                let day_file = |date| self.lgg.journal.day_file(date);
+                self.renderer.print_journal_entries(res, day_file);
             }
         }
         if let PrintResult::Tags(res) = result {
             errors.extend(&res.errors);
             if res.tags.is_empty() {
-                self.renderer.print_info(&"No tags found.".to_string());
+                self.renderer.print_result("No tags found.");
             } else {
                 self.renderer.print_tags(&res.tags);
             }
@@ -233,6 +2217,101 @@ impl LggCli {
         }
     }
 
+    #[cfg(feature = "analysis")]
+    fn print_sentiment(&self, entries: &[JournalEntry]) -> Result<()> {
+        let trend = lgg_core::analysis::weekly_sentiment(entries);
+        if trend.is_empty() {
+            self.renderer.print_result("No entries found.");
+        } else {
+            self.renderer.print_sentiment_trend(&trend);
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "analysis"))]
+    fn print_sentiment(&self, _entries: &[JournalEntry]) -> Result<()> {
+        bail!("this build does not include the `analysis` feature, needed for --sentiment.")
+    }
+
+    /// Dates of `--from` and `--to` (today if not given) of a command. `None` for all dates.
+    fn parse_from_to(&self, from: Option<&str>, to: Option<&str>) -> Result<Option<DateFilter>> {
+        let Some(from) = from else {
+            return Ok(None);
+        };
+        let to = to.unwrap_or("today");
+        match self.parse_query_dates(from, Some(to)) {
+            Some(dates) => Ok(Some(dates)),
+            None => bail!("Could not understand the dates '{from}' to '{to}'."),
+        }
+    }
+
+    /// `lgg scan-todos`: asks whether to add each action item of the entries that is not a todo
+    /// yet. Without a terminal to ask, and without `yes`, they are only listed.
+    fn scan_todos(&self, from: Option<&str>, to: Option<&str>, yes: bool) -> Result<()> {
+        let options = ReadEntriesOptions {
+            dates: self.parse_from_to(from, to)?,
+            ..Default::default()
+        };
+        let result = self.lgg.journal.read_entries(&options);
+        let todos = self.lgg.todos.read_entries(&ReadTodoOptions::default()).todos;
+        let tags = &self.lgg.config.tag_pattern;
+        let items: Vec<ActionItem> = action_items::find_action_items(&result.entries, tags)
+            .into_iter()
+            .filter(|item| !todos.iter().any(|t| t.title.eq_ignore_ascii_case(&item.text)))
+            .collect();
+        if items.is_empty() {
+            self.renderer.print_result("No new action items found.");
+        }
+
+        let interactive = io::stdin().is_terminal();
+        if !yes && !interactive && !items.is_empty() {
+            self.renderer
+                .print_result("Action items found, run with --yes to add them as todos:");
+        }
+        let mut added = 0;
+        for item in &items {
+            let source = format!("{} {}", item.date.format("%Y-%m-%d"), item.time.format("%H:%M"));
+            if !yes {
+                if !interactive {
+                    println!("{source}  {}", item.text);
+                    continue;
+                }
+                let answer = ask(&format!("Add \"{}\" ({source})? y/n/q", item.text), "n")?;
+                match answer.to_lowercase().as_str() {
+                    "y" | "yes" => {}
+                    "q" | "quit" => break,
+                    _ => continue,
+                }
+            }
+            let todo = self.lgg.todos.create_entry(TodoWriteEntry {
+                due_date: None,
+                time: None,
+                title: item.text.clone(),
+                body: format!("From the entry \"{}\" of {source}.", item.entry_title),
+                tags: item.tags.clone(),
+            })?;
+            self.renderer.print_saved_todo_line(None, &todo);
+            added += 1;
+        }
+        if added > 0 {
+            self.renderer.print_info(&format!("Added {added} todos"));
+        }
+        if !result.errors.is_empty() {
+            self.print_errors(&result.errors.iter().collect());
+        }
+        Ok(())
+    }
+
+    fn print_import_errors(&self, errors: &[String]) {
+        if errors.is_empty() {
+            return;
+        }
+        self.renderer.print_md("\n# Errors:");
+        for error in errors {
+            self.renderer.print_md(&format!("* {error}"));
+        }
+    }
+
     fn print_errors(&self, errors: &Vec<&QueryError>) {
         self.renderer.print_md("\n# Errors:");
         for error in errors {
@@ -1,5 +1,5 @@
 mod lgg_cli;
 mod todo_cli;
 
-pub use lgg_cli::LggCli;
-pub use todo_cli::TodoCli;
+pub use lgg_cli::{LggCli, LggCommand};
+pub use todo_cli::{TodoCli, TodoCommand};
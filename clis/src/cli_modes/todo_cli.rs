@@ -1,11 +1,62 @@
 use crate::{
     BaseCli, RenderOptions,
-    common::{CliModeResult, create_editor_buffer, open_file_in_editor, resolve_editor},
-    render::Renderer,
+    common::{
+        CliModeResult, Format, Output, Sort, create_editor_buffer, create_seeded_editor_buffer,
+        find_editor, open_file_in_editor, read_passphrase, read_text, resolve_editor,
+    },
+    render::{use_hyperlinks, BoardColumn, Renderer},
 };
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use lgg_core::convert::{csv, org, taskwarrior};
+use lgg_core::private;
+use lgg_core::stats;
 use lgg_core::entries::QueryTagsResult;
-use lgg_core::{Lgg, QueryError, ReadTodoOptions, TodoEntry, TodoQueryResult, TodoWriteEntry};
+use lgg_core::{
+    AmbiguousDate, DateFilter, Lgg, QueryError, ReadTodoOptions, TodoEntry, TodoQueryResult,
+    TodoStatus, TodoWriteEntry,
+};
+use std::{fs, path::PathBuf};
+
+#[derive(Subcommand, Debug)]
+pub enum TodoCommand {
+    /// Prints all your todos in another format (e.g., `todo export --format org > todos.org`)
+    Export {
+        #[arg(long, value_enum)]
+        format: Format,
+    },
+    /// Adds the todos of a file in another format to your list (e.g., `todo import --format org todos.org`)
+    Import {
+        #[arg(long, visible_alias = "from", value_enum)]
+        format: Format,
+        /// The file to import.
+        file: PathBuf,
+    },
+    /// Opens the todos file in your editor, or only the todo number `n`, as numbered when
+    /// listed (e.g., `todo edit 2`)
+    Edit {
+        n: Option<usize>,
+    },
+    /// Marks the todo number `n` as in progress, or as pending if it already was (e.g., `todo start 2`)
+    Start {
+        n: usize,
+    },
+    /// Marks the todo number `n` as cancelled, so it's closed without counting as done
+    /// (e.g., `todo cancel 2`)
+    Cancel {
+        n: usize,
+    },
+    /// Prints how many todos are open, done and overdue, how late they are done, and the todos
+    /// done per week and per tag
+    Stats,
+    /// Prints the todos in columns side by side: Pending, In Progress and Done, or the open
+    /// todos per tag (e.g., `todo board --by-tag`)
+    Board {
+        /// A column per tag of the open todos, instead of per status.
+        #[arg(long)]
+        by_tag: bool,
+    },
+}
 
 enum PrintResult {
     Todos(TodoQueryResult),
@@ -13,29 +64,44 @@ enum PrintResult {
 }
 
 pub struct TodoCli {
-    cli: BaseCli,
+    cli: BaseCli<TodoCommand>,
     renderer: Renderer,
     lgg: Lgg,
 }
 impl TodoCli {
-    pub fn new(cli: BaseCli, lgg: Lgg) -> Self {
+    pub fn new(cli: BaseCli<TodoCommand>, lgg: Lgg) -> Self {
         let options = cli.load();
 
         let renderer = Renderer::new(Some(RenderOptions {
             date_format: lgg.config.journal_date_format.to_string(),
+            locale: lgg.config.locale,
             use_color: options.use_color,
-            short_mode: options.short_mode,
+            style: options.style,
+            hyperlinks: use_hyperlinks(lgg.config.hyperlinks, options.use_color),
+            quiet: options.quiet,
+            images: options.images,
+            tags: lgg.config.tag_pattern.clone(),
         }));
         TodoCli { cli, renderer, lgg }
     }
 
     pub fn run(&self) -> Result<()> {
+        if let CliModeResult::Finish = self.command_mode()? {
+            return Ok(());
+        };
+
         if self.cli.path {
             self.renderer
-                .print_info(&format!("{}", self.lgg.config.todo_list_dir.display()));
+                .print_result(&format!("{}", self.lgg.config.todo_list_dir.display()));
             return Ok(());
         }
 
+        if self.cli.dry_run {
+            bail!("--dry-run only works with journal entries.");
+        }
+        if let Output::Json = self.cli.output {
+            bail!("--output json only works with journal entries.");
+        }
         if let CliModeResult::Finish = self.write_mode()? {
             return Ok(());
         };
@@ -55,38 +121,208 @@ impl TodoCli {
         Ok(())
     }
 
-    pub fn write_mode(&self) -> Result<CliModeResult> {
-        if !self.cli.text.is_empty() {
-            let new_entry: TodoEntry;
-            let inline = self.cli.text.join(" ");
-            let parsed_entry = self.lgg.parse_user_input(&inline)?;
-            let due_date = if parsed_entry.explicit_date {
-                Some(parsed_entry.date)
-            } else {
-                None
-            };
+    pub fn command_mode(&self) -> Result<CliModeResult> {
+        match &self.cli.command {
+            Some(TodoCommand::Export { format }) => {
+                let result = self.lgg.todos.read_entries(&ReadTodoOptions::default());
+                let tags = &self.lgg.config.tag_pattern;
+                let output = match format {
+                    Format::Org => org::todos_to_org(&result.todos),
+                    Format::Taskwarrior => taskwarrior::todos_to_taskwarrior(&result.todos, tags),
+                };
+                print!("{output}");
+                // Errors go to stderr, so they don't end up in the exported file.
+                for error in &result.errors {
+                    if let QueryError::FileError { path, error } = error {
+                        eprintln!("todo: could not process '{}': {}", path.display(), error);
+                    }
+                }
+                Ok(CliModeResult::Finish)
+            }
+            Some(TodoCommand::Import { format, file }) => {
+                let content = fs::read_to_string(file)
+                    .with_context(|| format!("reading {}", file.display()))?;
+                let config = &self.lgg.config;
+                let result = match format {
+                    Format::Org => {
+                        org::org_to_todos(&content, config.default_time, &config.tag_pattern)
+                    }
+                    Format::Taskwarrior => {
+                        taskwarrior::taskwarrior_to_todos(&content, &config.tag_pattern)
+                    }
+                };
+                let imported = self.lgg.todos.import_entries(result.entries)?;
+                self.renderer.print_info(&format!(
+                    "Imported {imported} todos from {}",
+                    file.display()
+                ));
+                self.print_import_errors(&result.errors);
+                Ok(CliModeResult::Finish)
+            }
+            Some(TodoCommand::Edit { n }) => {
+                self.edit_command(*n)?;
+                Ok(CliModeResult::Finish)
+            }
+            Some(TodoCommand::Start { n }) => {
+                let todos = self.lgg.todos.read_entries(&ReadTodoOptions::default());
+                let todo = Self::numbered_todo(&todos, *n)?;
+                let updated = self.lgg.todos.start_entry(todo)?;
+                self.renderer.print_saved_todo_line(Some(*n), &updated);
+                Ok(CliModeResult::Finish)
+            }
+            Some(TodoCommand::Cancel { n }) => {
+                let todos = self.lgg.todos.read_entries(&ReadTodoOptions::default());
+                let todo = Self::numbered_todo(&todos, *n)?;
+                let updated = self.lgg.todos.cancel_entry(todo)?;
+                self.renderer.print_saved_todo_line(Some(*n), &updated);
+                Ok(CliModeResult::Finish)
+            }
+            Some(TodoCommand::Stats) => {
+                let mut result = self.lgg.todos.read_entries(&ReadTodoOptions::default());
+                let report = stats::todo_stats(&result.todos, self.lgg.config.reference_date);
+                self.renderer.print_todo_stats(&report);
+                // Not having a todo list yet is fine.
+                result
+                    .errors
+                    .retain(|e| !matches!(e, QueryError::FileError { path, .. } if !path.exists()));
+                if !result.errors.is_empty() {
+                    self.print_errors(&result.errors.iter().collect());
+                }
+                Ok(CliModeResult::Finish)
+            }
+            Some(TodoCommand::Board { by_tag }) => {
+                self.board_command(*by_tag);
+                Ok(CliModeResult::Finish)
+            }
+            None => Ok(CliModeResult::NothingToDo),
+        }
+    }
 
-            let time = if parsed_entry.explicit_time {
-                Some(parsed_entry.time)
-            } else {
-                None
-            };
-            let entry_to_create = TodoWriteEntry {
-                due_date,
-                time,
-                title: parsed_entry.title,
-                body: parsed_entry.body,
-                tags: Vec::new(),
-            };
+    /// `todo board`: the todos in a column per status, or the open ones in a column per tag with
+    /// `by_tag`. A todo with several tags is in each of their columns.
+    fn board_command(&self, by_tag: bool) {
+        let mut result = self.lgg.todos.read_entries(&ReadTodoOptions::default());
+        let columns = if by_tag {
+            BoardColumn::by_tag(&result)
+        } else {
+            BoardColumn::by_status(&result)
+        };
+        if columns.iter().all(|column| column.todos.is_empty()) {
+            self.renderer.print_result("No entries found.");
+        } else {
+            self.renderer.print_todo_board(&columns);
+        }
+        // Not having a todo list yet is fine.
+        result
+            .errors
+            .retain(|e| !matches!(e, QueryError::FileError { path, .. } if !path.exists()));
+        if !result.errors.is_empty() {
+            self.print_errors(&result.errors.iter().collect());
+        }
+    }
 
-            new_entry = self.lgg.todos.create_entry(entry_to_create)?;
+    /// The todo number `n` of `todos`, as numbered when listed.
+    fn numbered_todo(todos: &TodoQueryResult, n: usize) -> Result<&TodoEntry> {
+        todos
+            .get(n)
+            .with_context(|| format!("There is no todo number {n}, there are {}.", todos.todos.len()))
+    }
+
+    /// `todo edit [n]`: edits the whole file, or the todo `n` alone in a buffer that is
+    /// read back on save.
+    fn edit_command(&self, n: Option<usize>) -> Result<()> {
+        let editor = resolve_editor(&self.lgg.config.editor)?;
+        let Some(n) = n else {
+            let path = self.lgg.todos.file_path();
+            open_file_in_editor(&editor, &path)?;
             self.renderer
-                .print_info(&format!("Added new todo to {}", new_entry.path.display()));
-            self.renderer.print_todo_entry_line(&new_entry, true);
-            Ok(CliModeResult::Finish)
-        } else {
-            Ok(CliModeResult::NothingToDo)
+                .print_info(&format!("Edited file {}", path.display()));
+            return Ok(());
+        };
+
+        let todos = self.lgg.todos.read_entries(&ReadTodoOptions::default());
+        let todo = Self::numbered_todo(&todos, n)?;
+        if private::is_sealed(&todo.body) {
+            bail!("The todo {n} is private, its text can't be edited.");
+        }
+        let template = self.lgg.todos.edit_template(todo);
+        let temp_dir = self.lgg.config.temp_dir.as_deref();
+        let input = create_seeded_editor_buffer(&editor, &template, temp_dir)?;
+        if input == template {
+            self.renderer.print_info("No changes to save.");
+            return Ok(());
+        }
+        let edited = self.lgg.todos.parse_edit_buffer(&input, todo)?;
+        let updated = self.lgg.todos.update_entry(todo, edited)?;
+        self.renderer
+            .print_info(&format!("Updated todo in {}", updated.path.display()));
+        self.renderer.print_saved_todo_line(None, &updated);
+        Ok(())
+    }
+
+    pub fn write_mode(&self) -> Result<CliModeResult> {
+        let inputs = self.cli.inline_entries()?;
+        if inputs.is_empty() {
+            if self.cli.bulk {
+                self.renderer.print_info("No todos to save.");
+                return Ok(CliModeResult::Finish);
+            }
+            return Ok(CliModeResult::NothingToDo);
+        }
+
+        // All the todos are parsed before writing any.
+        let mut todos = Vec::new();
+        for inline in &inputs {
+            let todo = self
+                .parse_todo(inline)
+                .with_context(|| format!("reading todo '{inline}'"))?;
+            todos.push(todo);
+        }
+        let todos = self.seal_private(todos)?;
+
+        let mut new_entries = Vec::new();
+        for todo in todos {
+            new_entries.push(self.lgg.todos.create_entry(todo)?);
+        }
+        match new_entries.as_slice() {
+            [new_entry] => self
+                .renderer
+                .print_info(&format!("Added new todo to {}", new_entry.path.display())),
+            _ => self
+                .renderer
+                .print_info(&format!("Added {} new todos", new_entries.len())),
         }
+        for new_entry in &new_entries {
+            self.renderer.print_saved_todo_line(None, new_entry);
+        }
+        Ok(CliModeResult::Finish)
+    }
+
+    /// Reads a todo written as free text. It's due on the date and time written, if any: today
+    /// for a time alone.
+    fn parse_todo(&self, input: &str) -> Result<TodoWriteEntry> {
+        let parsed_entry = self.lgg.parse_todo_input(input)?;
+        self.warn_ambiguous_dates(&parsed_entry.ambiguous_dates);
+        let has_due = parsed_entry.explicit_date || parsed_entry.explicit_time;
+        Ok(TodoWriteEntry {
+            due_date: has_due.then_some(parsed_entry.date),
+            time: parsed_entry.explicit_time.then_some(parsed_entry.time),
+            title: parsed_entry.title,
+            body: parsed_entry.body,
+            tags: Vec::new(),
+        })
+    }
+
+    /// With `--private`, `todos` sealed with the passphrase, asked once for all of them.
+    fn seal_private(&self, todos: Vec<TodoWriteEntry>) -> Result<Vec<TodoWriteEntry>> {
+        if !self.cli.private {
+            return Ok(todos);
+        }
+        let passphrase = read_passphrase(true)?;
+        todos
+            .into_iter()
+            .map(|todo| self.lgg.todos.sealed_entry(todo, &passphrase))
+            .collect()
     }
 
     pub fn editor_mode(&self) -> Result<CliModeResult> {
@@ -94,30 +330,26 @@ impl TodoCli {
             return self.write_mode();
         }
 
-        let new_entry: TodoEntry;
-
-        let editor = resolve_editor(&self.lgg.config.editor)?;
-        let input = create_editor_buffer(&editor)?;
+        let temp_dir = self.lgg.config.temp_dir.as_deref();
+        let input = match find_editor(&self.lgg.config.editor) {
+            Some(editor) => create_editor_buffer(&editor, temp_dir)?,
+            None => read_text(
+                "No editor found. Write the todo (the first line is the title) and end it with a \
+                 line with a single `.` or Ctrl-D.",
+            )?,
+        };
         let trimmed = input.trim();
         if trimmed.is_empty() {
             self.renderer
-                .print_info(&"No entry to save, because no text was received.".to_string());
+                .print_info("No entry to save, because no text was received.");
             return Ok(CliModeResult::Finish);
         }
-        let inline = self.cli.text.join(" ");
-        let parsed_entry = self.lgg.parse_user_input(&inline)?;
-        let entry_to_create = TodoWriteEntry {
-            due_date: Some(parsed_entry.date),
-            time: Some(parsed_entry.time),
-            title: parsed_entry.title,
-            body: parsed_entry.body,
-            tags: Vec::new(),
-        };
-
-        new_entry = self.lgg.todos.create_entry(entry_to_create)?;
+        let entry_to_create = self.parse_todo(trimmed)?;
+        let entry_to_create = self.seal_private(vec![entry_to_create])?.remove(0);
+        let new_entry: TodoEntry = self.lgg.todos.create_entry(entry_to_create)?;
         self.renderer
             .print_info(&format!("Added new entry to {}", new_entry.path.display()));
-        self.renderer.print_todo_entry_line(&new_entry, true);
+        self.renderer.print_saved_todo_line(None, &new_entry);
         Ok(CliModeResult::Finish)
     }
 
@@ -128,11 +360,43 @@ impl TodoCli {
         let mut tags: Option<Vec<String>> = None;
 
         if self.cli.all_tags {
-            let tags = self.lgg.todos.search_all_tags();
+            let tags = self.lgg.todos.search_all_tags(self.cli.min_count);
             self.print_results(&PrintResult::Tags(tags), self.cli.count);
             return Ok(CliModeResult::Finish);
         }
 
+        if let Some(days) = self.cli.stale {
+            let days = days.unwrap_or(self.lgg.config.stale_days.into());
+            let result = self.lgg.todos.read_stale(days);
+            if result.todos.is_empty() {
+                self.renderer
+                    .print_result(&format!("No todos without a due date older than {days} days."));
+            } else {
+                self.renderer
+                    .print_stale_todos(&result, self.lgg.config.reference_date);
+            }
+            if !result.errors.is_empty() {
+                self.print_errors(&result.errors.iter().collect());
+            }
+            return Ok(CliModeResult::Finish);
+        }
+
+        if self.cli.search.is_some() {
+            bail!("--search only works with journal entries.");
+        }
+        if self.cli.scheduled {
+            bail!("--scheduled only works with journal entries.");
+        }
+        if self.cli.filter.is_some() {
+            bail!("--filter only works with journal entries.");
+        }
+        if self.cli.min_words.is_some() || self.cli.max_words.is_some() {
+            bail!("--min-words and --max-words only work with journal entries.");
+        }
+        if let Sort::Words = self.cli.sort {
+            bail!("--sort words only works with journal entries.");
+        }
+
         if let Some(on) = &self.cli.on {
             start_date = Some(on);
         }
@@ -147,7 +411,7 @@ impl TodoCli {
                 }
                 None => {
                     start_date = Some(from);
-                    end_date = Some(&"today");
+                    end_date = Some("today");
                 }
             }
         }
@@ -158,27 +422,77 @@ impl TodoCli {
             tags = Some(has_tags.to_vec());
         }
 
-        if start_date.is_none() && time.is_none() && tags.is_none() {
+        let csv_output = matches!(self.cli.output, Output::Csv);
+        let title = self.cli.title.as_deref();
+        let status: Option<Vec<TodoStatus>> = self
+            .cli
+            .status
+            .as_ref()
+            .map(|status| status.iter().map(|s| (*s).into()).collect());
+        if start_date.is_none()
+            && time.is_none()
+            && tags.is_none()
+            && title.is_none()
+            && status.is_none()
+            && !csv_output
+            && self.cli.group_by.is_none()
+            && !self.cli.show_private
+        {
             return Ok(CliModeResult::NothingToDo);
         }
 
         let dates = match start_date {
-            Some(d) => self.lgg.parse_dates(d, end_date),
+            Some(d) => self.parse_query_dates(d, end_date),
             None => None,
         };
         let options = ReadTodoOptions {
             due_date: dates,
             time,
             tags: self.cli.tags.as_ref(),
+            title,
+            status: status.as_deref(),
             ..Default::default()
         };
-        let result = self.lgg.todos.read_entries(&options);
+        let mut result = self.lgg.todos.read_entries(&options);
+        // Without `--show-private`, the private todos are shown without their sealed text.
+        let passphrase = self.cli.show_private.then(|| read_passphrase(false)).transpose()?;
+        let errors = &mut result.errors;
+        self.lgg
+            .todos
+            .open_private_entries(&mut result.todos, passphrase.as_deref(), errors);
         self.print_results(&PrintResult::Todos(result), self.cli.count);
         Ok(CliModeResult::Finish)
     }
 
+    /// Dates to look for, with the `range_order` config applied.
+    fn parse_query_dates(&self, start_date: &str, end_date: Option<&str>) -> Option<DateFilter> {
+        self.warn_ambiguous_dates(&self.lgg.ambiguous_dates(start_date));
+        if let Some(end_date) = end_date {
+            self.warn_ambiguous_dates(&self.lgg.ambiguous_dates(end_date));
+        }
+        let (dates, swapped) = self.lgg.order_dates(self.lgg.parse_dates(start_date, end_date)?);
+        if swapped {
+            eprintln!("todo: the end date is before the start date, they have been swapped.");
+        }
+        Some(dates)
+    }
+
+    /// Warns about dates that could be read as different days.
+    fn warn_ambiguous_dates(&self, ambiguous: &[AmbiguousDate]) {
+        for date in ambiguous {
+            let readings: Vec<String> = date.readings.iter().map(|d| d.to_string()).collect();
+            eprintln!(
+                "todo: '{}' could be {}. Using {}, pick the order with `--date-order`.",
+                date.token,
+                readings.join(" or "),
+                readings[0]
+            );
+        }
+    }
+
     pub fn edit_mode(&self) -> Result<CliModeResult> {
         if let Some(start_date) = &self.cli.edit {
+            self.warn_ambiguous_dates(&self.lgg.ambiguous_dates(start_date));
             let dates = self.lgg.parse_dates(start_date, None);
             let options = ReadTodoOptions {
                 due_date: dates,
@@ -195,7 +509,7 @@ impl TodoCli {
                     Ok(CliModeResult::Finish)
                 }
                 None => {
-                    self.renderer.print_info("No entries found to edit.");
+                    self.renderer.print_result("No entries found to edit.");
                     Ok(CliModeResult::Finish)
                 }
             };
@@ -206,32 +520,50 @@ impl TodoCli {
     fn print_results(&self, result: &PrintResult, print_count: bool) {
         let mut errors = Vec::new();
         if print_count {
+            if let (PrintResult::Todos(res), Some(group_by)) = (result, self.cli.group_by) {
+                let groups = stats::count_todos(&res.todos, group_by.into());
+                self.renderer
+                    .print_count_table(group_by.label(), "Entries", &groups);
+                return;
+            }
             match result {
                 PrintResult::Todos(res) => {
                     self.renderer
-                        .print_info(&format!("{} entries found.", res.todos.len()));
+                        .print_result(&format!("{} entries found.", res.todos.len()));
                 }
                 PrintResult::Tags(res) => {
                     self.renderer
-                        .print_info(&format!("{} tags found.", res.tags.len()));
+                        .print_result(&format!("{} tags found.", res.tags.len()));
                 }
             }
 
             return;
         }
 
+        if let (PrintResult::Todos(res), Output::Csv) = (result, self.cli.output) {
+            print!("{}", csv::todos_to_csv(&res.todos));
+            // Errors go to stderr, so they don't end up in the csv file.
+            for error in &res.errors {
+                if let QueryError::FileError { path, error } = error {
+                    eprintln!("todo: could not process '{}': {}", path.display(), error);
+                }
+            }
+            return;
+        }
+
         if let PrintResult::Todos(res) = result {
             errors.extend(&res.errors);
             if res.todos.is_empty() {
-                self.renderer.print_info(&"No entries found.".to_string());
+                self.renderer.print_result("No entries found.");
             } else {
-                self.renderer.print_todos_entries(&res);
+                self.renderer
+                    .print_todos_entries(res, self.lgg.config.reference_date);
             }
         }
         if let PrintResult::Tags(res) = result {
             errors.extend(&res.errors);
             if res.tags.is_empty() {
-                self.renderer.print_info(&"No tags found.".to_string());
+                self.renderer.print_result("No tags found.");
             } else {
                 self.renderer.print_tags(&res.tags);
             }
@@ -241,6 +573,16 @@ impl TodoCli {
         }
     }
 
+    fn print_import_errors(&self, errors: &[String]) {
+        if errors.is_empty() {
+            return;
+        }
+        self.renderer.print_md("\n# Errors:");
+        for error in errors {
+            self.renderer.print_md(&format!("* {error}"));
+        }
+    }
+
     fn print_errors(&self, errors: &Vec<&QueryError>) {
         self.renderer.print_md("\n# Errors:");
         for error in errors {
@@ -0,0 +1,163 @@
+//! The passphrase agent: `lgg agent` keeps the passphrase of the private entries in memory
+//! behind a unix socket, for a while, so the commands that follow don't ask for it again.
+//! `lgg lock` makes it forget the passphrase right away.
+//!
+//! Every private entry derives its key from the passphrase and a salt of its own, so the agent
+//! keeps the passphrase, never on disk. Only the user who started the agent can use the socket:
+//! its mode is 0600. The requests are a line each: `GET`, `PUT <passphrase>` and `LOCK`.
+
+use anyhow::{bail, Context, Result};
+use std::{
+    io::{BufRead, BufReader, Write},
+    os::unix::{
+        fs::PermissionsExt,
+        net::{UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+/// Environment variable with the path of the socket, instead of the default one.
+const SOCKET_VAR: &str = "LGG_AGENT_SOCK";
+
+/// The socket of the agent: `LGG_AGENT_SOCK`, or `lgg-agent.sock` in `$XDG_RUNTIME_DIR` or in
+/// the temporary directory.
+pub fn socket_path() -> PathBuf {
+    if let Some(path) = std::env::var_os(SOCKET_VAR).filter(|path| !path.is_empty()) {
+        return PathBuf::from(path);
+    }
+    let user = std::env::var("USER").unwrap_or_default();
+    match std::env::var_os("XDG_RUNTIME_DIR").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir).join("lgg-agent.sock"),
+        None => std::env::temp_dir().join(format!("lgg-agent-{user}.sock")),
+    }
+}
+
+/// Creates the socket of the agent on `path`, unless another agent is running there.
+pub fn listen(path: &Path) -> Result<UnixListener> {
+    if UnixStream::connect(path).is_ok() {
+        bail!("An agent is already running on {}.", path.display());
+    }
+    // A socket left by an agent that didn't stop cleanly.
+    let _ = std::fs::remove_file(path);
+    let listener = UnixListener::bind(path)
+        .with_context(|| format!("Could not listen on {}", path.display()))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+/// Runs the agent until it's killed. A passphrase is forgotten `ttl` after it was given.
+pub fn serve(listener: UnixListener, ttl: Duration) -> Result<()> {
+    let mut cached: Option<(String, Instant)> = None;
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        if cached.as_ref().is_some_and(|(_, since)| since.elapsed() >= ttl) {
+            cached = None;
+        }
+        // A client that goes away mid-request doesn't stop the agent.
+        let _ = answer(stream, &mut cached);
+    }
+    Ok(())
+}
+
+fn answer(stream: UnixStream, cached: &mut Option<(String, Instant)>) -> Result<()> {
+    // A client that never writes doesn't keep the others waiting.
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    let line = line.trim_end_matches(['\r', '\n']);
+    let reply = match line.split_once(' ').unwrap_or((line, "")) {
+        ("GET", _) => match cached {
+            Some((passphrase, _)) => format!("OK {passphrase}"),
+            None => "NONE".to_string(),
+        },
+        ("PUT", passphrase) if !passphrase.is_empty() => {
+            *cached = Some((passphrase.to_string(), Instant::now()));
+            "OK".to_string()
+        }
+        ("LOCK", _) => {
+            *cached = None;
+            "OK".to_string()
+        }
+        _ => "ERR".to_string(),
+    };
+    writeln!(&stream, "{reply}")?;
+    Ok(())
+}
+
+/// Sends `request` to the agent on `path` and returns its reply, or `None` if no agent is
+/// running there.
+fn request(path: &Path, request: &str) -> Option<String> {
+    let mut stream = UnixStream::connect(path).ok()?;
+    stream.set_read_timeout(Some(Duration::from_secs(2))).ok()?;
+    writeln!(stream, "{request}").ok()?;
+    let mut reply = String::new();
+    BufReader::new(&stream).read_line(&mut reply).ok()?;
+    Some(reply.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// The passphrase the agent on `path` keeps, if any.
+pub fn cached_passphrase(path: &Path) -> Option<String> {
+    request(path, "GET")?.strip_prefix("OK ").map(str::to_string)
+}
+
+/// Gives `passphrase` to the agent on `path`, if one is running.
+pub fn cache_passphrase(path: &Path, passphrase: &str) {
+    request(path, &format!("PUT {passphrase}"));
+}
+
+/// Makes the agent on `path` forget the passphrase. Returns whether an agent was running.
+pub fn lock(path: &Path) -> bool {
+    request(path, "LOCK").is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn start(ttl: Duration) -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent.sock");
+        let listener = listen(&path).unwrap();
+        thread::spawn(move || serve(listener, ttl));
+        (dir, path)
+    }
+
+    #[test]
+    fn keeps_the_passphrase_until_locked() {
+        let (_dir, path) = start(Duration::from_secs(60));
+        assert_eq!(cached_passphrase(&path), None);
+        cache_passphrase(&path, "correct horse battery staple");
+        assert_eq!(cached_passphrase(&path).as_deref(), Some("correct horse battery staple"));
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(lock(&path));
+        assert_eq!(cached_passphrase(&path), None);
+    }
+
+    #[test]
+    fn forgets_the_passphrase_after_its_ttl() {
+        let (_dir, path) = start(Duration::from_millis(50));
+        cache_passphrase(&path, "secret");
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(cached_passphrase(&path), None);
+    }
+
+    #[test]
+    fn without_an_agent_nothing_is_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent.sock");
+        cache_passphrase(&path, "secret");
+        assert_eq!(cached_passphrase(&path), None);
+        assert!(!lock(&path));
+    }
+
+    #[test]
+    fn only_one_agent_runs_on_a_socket() {
+        let (_dir, path) = start(Duration::from_secs(60));
+        assert!(listen(&path).is_err());
+    }
+}
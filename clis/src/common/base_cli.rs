@@ -1,11 +1,22 @@
-use super::style::Style;
-use crate::render::ColorMode;
-use clap::{ArgGroup, Parser, arg};
-use std::io::{self, IsTerminal};
+use super::{
+    date_order::DateOrder, group_by::GroupBy, output::Output, sort::Sort, style::Style,
+    todo_status::TodoStatus,
+};
+use crate::render::{enable_ansi, ColorMode};
+use clap::{ArgAction, ArgGroup, Parser, Subcommand};
+use anyhow::{bail, Context, Result};
+use lgg_core::journals::ALL_JOURNALS;
+use lgg_core::{Config, DateFilter, Lgg};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::io::{self, IsTerminal, Read};
 
 pub struct LoadOptions {
     pub use_color: bool,
-    pub short_mode: bool,
+    pub style: Style,
+    pub quiet: bool,
+    /// Show the local images linked in the entries.
+    pub images: bool,
 }
 
 /// lgg — Simple Markdown journal
@@ -13,23 +24,43 @@ pub struct LoadOptions {
 #[command(
     version,
     about,
-    group(ArgGroup::new("read_mode").args(["on", "from", "to", "at", "tags"]).multiple(true)),
+    group(ArgGroup::new("read_mode").args(["on", "from", "to", "at", "tags", "title", "filter", "min_words", "max_words", "output", "group_by", "search", "scheduled", "pinned", "status", "stale", "show_private"]).multiple(true)),
     group(ArgGroup::new("edit_mode").args(["edit"])),
-    group(ArgGroup::new("write_mode").args(["text"])),
+    group(ArgGroup::new("write_mode").args(["text", "bulk"])),
     group(ArgGroup::new("solo").args(["path", "all_tags"]).conflicts_with_all(["read_mode", "edit_mode", "write_mode"])),
 )]
-pub struct BaseCli {
+pub struct BaseCli<C: Subcommand> {
+    /// Commands of each binary (e.g. `lgg export`).
+    #[command(subcommand)]
+    pub command: Option<C>,
+
     /// Prints the journal root directory
     #[arg(long, short)]
     pub path: bool,
     /// Prints all the tags within all entries.
     #[arg(long)]
     pub all_tags: bool,
+    /// With `--all-tags`, only the tags of at least this many entries (e.g., `lgg --all-tags --min-count 3`).
+    #[arg(long, requires = "all_tags", default_value_t = 1)]
+    pub min_count: usize,
     /// Control ANSI colors in output.
     /// By default, colors are disabled when output is redirected (e.g with `>` or `|`).
     #[arg(long, value_enum, default_value_t = ColorMode::Auto)]
     pub color: ColorMode,
 
+    /// Order of the day and month in the dates you type, for this run only: "dmy", "mdy" or "ymd".
+    /// Replaces your `input_date_formats` (e.g., `lgg --date-order mdy --on 04/03/2025`).
+    #[arg(long, value_enum)]
+    pub date_order: Option<DateOrder>,
+    /// The journal of `[journals]` in your config to read and write, or `all` to find entries in
+    /// all of them at once (e.g., `lgg --journal work today: Standup`, `lgg --journal all --search release`)
+    #[arg(long, short, env = "LGG_JOURNAL")]
+    pub journal: Option<String>,
+    /// The day to use as today for this run, for scripted backfills (e.g., `lgg --today 14/08/2025 yesterday: Title`).
+    /// Relative dates are resolved from it.
+    #[arg(long, env = "LGG_TODAY")]
+    pub today: Option<String>,
+
     /// View entries on a specific date (e.g., `lgg --on yesterday`, `lgg --on 14/08/25`)
     #[arg(long)]
     pub on: Option<String>,
@@ -47,29 +78,144 @@ pub struct BaseCli {
     /// Prints the count of found entries/tags.
     #[arg(long)]
     pub count: bool,
+    /// With `--count`, prints a table of found entries per "month", "weekday" or "tag"
+    /// (e.g., `lgg --count --group-by weekday`). Without any other filter, all entries are counted.
+    #[arg(long, value_enum, requires = "count")]
+    pub group_by: Option<GroupBy>,
 
-    /// Output style: "long" or "short". Short style only shows the date, titles and tags of searched entries.
+    /// Output style: "long", "short" or "toc". Short style only shows the date, titles and tags of searched entries,
+    /// and "toc" only their titles under their year, month and day (e.g., `lgg --from 2024 --style toc`).
     #[arg(long, short, value_enum, env = "LGG_STYLE", default_value_t= Style::Long)]
     pub style: Style,
+    /// Order of found entries: "date", oldest first, or "words", the longest first (e.g.,
+    /// `lgg --from 'last month' --sort words --style short`).
+    #[arg(long, value_enum, default_value_t = Sort::Date)]
+    pub sort: Sort,
+    /// Output format of found entries/todos: "text" or "csv" (e.g., `lgg --from 'last month' --output csv > month.csv`).
+    /// Without any other filter, all entries are printed. "json" prints entries, and the new ones when writing,
+    /// as JSON lines (e.g., `lgg --output json today: Standup | jq -r .path`).
+    #[arg(long, short, value_enum, default_value_t = Output::Text)]
+    pub output: Output,
     /// Search for entries with the given tags (e.g., `lgg --tags dogs cats`)
     #[arg(long, short, num_args(1..))]
     pub tags: Option<Vec<String>>,
+    /// Full-text search in the titles and bodies of your entries, best matches first
+    /// (e.g., `lgg --search "release plan"`). Much faster and ranked with lgg built with the `search` feature.
+    /// Prints the lines around the matches of each body, and with `--output json` where they are.
+    #[arg(long, conflicts_with_all = ["on", "from", "to", "at", "tags", "title", "count"])]
+    pub search: Option<String>,
+    /// Prints the whole body of the entries found by `--search`, not only the lines around the
+    /// matches.
+    #[arg(long, requires = "search")]
+    pub full: bool,
+    /// Lists the entries dated after today, so planned notes aren't forgotten (e.g., `lgg --scheduled`).
+    #[arg(long, conflicts_with_all = ["on", "from", "to", "at", "tags", "title", "search"])]
+    pub scheduled: bool,
+    /// Lists the pinned entries and the entries of the pinned days (see `lgg pin`).
+    #[arg(long, conflicts_with_all = ["on", "from", "to", "at", "tags", "title", "search", "scheduled"])]
+    pub pinned: bool,
+    /// Search for todos with any of the given statuses (e.g., `todo --status pending in-progress`)
+    #[arg(long, num_args(1..), value_enum, conflicts_with_all = ["search", "scheduled"])]
+    pub status: Option<Vec<TodoStatus>>,
+    /// Lists the open todos without a due date added more than these days ago, `stale_days` of
+    /// your config if not given (e.g., `todo --stale 30`)
+    #[arg(long, num_args(0..=1), value_name = "DAYS", conflicts_with_all = ["on", "from", "to", "at", "tags", "title", "status"])]
+    pub stale: Option<Option<u64>>,
+    /// Search for entries whose title contains all the given words, in any order (e.g., `lgg --on today --title kickoff`)
+    #[arg(long)]
+    pub title: Option<String>,
+    /// Search for entries matching an expression on their date, time, words, weekday, title, body
+    /// and tags (e.g., `lgg --filter 'date >= 2025-08-01 && tags.contains("@work") && words > 100'`)
+    #[arg(long, conflicts_with_all = ["search", "scheduled", "pinned"])]
+    pub filter: Option<String>,
+    /// Search for entries with at least this many words in their title and body, the long ones
+    /// (e.g., `lgg --from "last month" --min-words 300`)
+    #[arg(long, value_name = "WORDS", conflicts_with_all = ["search", "scheduled", "pinned"])]
+    pub min_words: Option<usize>,
+    /// Search for entries with at most this many words, the quick ones (e.g., `lgg --max-words 10`)
+    #[arg(long, value_name = "WORDS", conflicts_with_all = ["search", "scheduled", "pinned"])]
+    pub max_words: Option<usize>,
     /// Opens your $EDITOR with a found day file. Only works on single day searches.
-    /// eg. `lgg --edit yesterday`
+    /// If it's not a date, it opens the most recent entry with a matching title.
+    /// eg. `lgg --edit yesterday`, `lgg --edit "Project kickoff"`
     #[arg(long, short)]
     pub edit: Option<String>,
 
+    /// Reads the entries to write from stdin, one per line (e.g., `cat notes.txt | lgg --bulk`).
+    #[arg(long)]
+    pub bulk: bool,
+    /// Writes the new entries without the `default_tags` of the journal in your config
+    /// (e.g., `lgg --journal work --no-default-tags today: Lunch with Ana`).
+    #[arg(long, conflicts_with_all = ["read_mode", "edit_mode"])]
+    pub no_default_tags: bool,
+
+    /// Encrypts the new entries or todos with a passphrase, so they are shown as `[private]` until
+    /// read with `--show-private` (e.g., `lgg --private today: Doctor. Results are fine`). The
+    /// passphrase is asked, or read from `LGG_PASSPHRASE`.
+    #[arg(long, conflicts_with_all = ["read_mode", "edit_mode"])]
+    pub private: bool,
+    /// Shows the private entries or todos found, asking for their passphrase (e.g., `lgg --on today --show-private`).
+    #[arg(long)]
+    pub show_private: bool,
+
+    /// Prints how new entries would change their day files, as a diff, and writes nothing.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Prints what lgg does to stderr: files read, how dates were parsed and how long each step
+    /// took. `-vv` for more detail, `-vvv` for all of it. New entries print the diff of their day
+    /// files before being written.
+    #[arg(long, short, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Only prints what you ask for and the errors, without messages like "Added new entry to",
+    /// to run lgg from scripts and cron.
+    #[arg(long, short)]
+    pub quiet: bool,
+    /// Leaves the local images linked in the entries as links. Without it, they're shown below
+    /// the entry in kitty, iTerm2 and WezTerm. Sixel isn't supported, other terminals keep the
+    /// links.
+    #[cfg(feature = "images")]
+    #[arg(long)]
+    pub no_images: bool,
+
     /// Free text for insert mode (e.g., `lgg yesterday: Title. Body`).
+    /// Separate several entries with `;;` (e.g., `lgg "today: first" ";;" "today at 14:00: second"`).
     #[arg()]
     pub text: Vec<String>,
 }
-impl BaseCli {
+impl<C: Subcommand> Default for BaseCli<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Subcommand> BaseCli<C> {
     pub fn new() -> Self {
-        let cli = BaseCli::parse();
-        cli
+        BaseCli::parse()
+    }
+
+    /// Parses the arguments, with the first one replaced by its arguments when it's one of
+    /// `aliases`: `lgg standup --count` is `lgg --on today --tags @work --count`. Commands win
+    /// over aliases with their name.
+    pub fn with_aliases(aliases: &HashMap<String, String>) -> Result<Self> {
+        let mut args: Vec<OsString> = std::env::args_os().collect();
+        let alias = args
+            .get(1)
+            .and_then(|arg| arg.to_str())
+            .filter(|arg| !C::has_subcommand(arg))
+            .and_then(|arg| Some((arg.to_string(), aliases.get(arg)?)));
+        if let Some((name, value)) = alias {
+            let Some(expanded) = shlex::split(value) else {
+                bail!("Could not read the alias `{name}`, check its quotes.");
+            };
+            args.splice(1..2, expanded.into_iter().map(OsString::from));
+        }
+        Ok(BaseCli::parse_from(args))
     }
 
     pub fn load(&self) -> LoadOptions {
+        let ansi = enable_ansi();
         let use_color = match self.color {
             ColorMode::Always => true,
             ColorMode::Never => false,
@@ -77,18 +223,90 @@ impl BaseCli {
                 if std::env::var_os("NO_COLOR").is_some() {
                     false
                 } else {
-                    io::stdout().is_terminal()
+                    ansi && io::stdout().is_terminal()
                 }
             }
         };
-        let short_mode = match self.style {
-            Style::Short => true,
-            Style::Long => false,
-        };
-
         LoadOptions {
             use_color,
-            short_mode,
+            style: self.style,
+            quiet: self.quiet,
+            #[cfg(feature = "images")]
+            images: use_color && !self.no_images,
+            #[cfg(not(feature = "images"))]
+            images: false,
+        }
+    }
+
+    /// Applies the flags that change the config for this run.
+    pub fn override_config(&self, config: &mut Config) -> Result<()> {
+        // `all` is read journal by journal, from the default config.
+        if let Some(name) = self.journal.as_deref().filter(|name| *name != ALL_JOURNALS) {
+            *config = config.for_journal(name)?;
         }
+        if self.no_default_tags {
+            config.default_tags.clear();
+        }
+        if let Some(date_order) = self.date_order {
+            let date_order: lgg_core::DateOrder = date_order.into();
+            config.input_date_formats = date_order
+                .input_date_formats()
+                .iter()
+                .map(|f| f.to_string())
+                .collect();
+        }
+        Ok(())
+    }
+
+    /// Applies `--today` to `lgg`. It's read from the real today, so `--today yesterday` works.
+    pub fn override_today(&self, lgg: &mut Lgg) -> Result<()> {
+        if let Some(today) = &self.today {
+            match lgg.parse_dates(today, None) {
+                Some(DateFilter::Single(date)) => lgg.set_reference_date(date),
+                Some(DateFilter::Range(..)) => {
+                    bail!("--today must be a single day, not '{today}'.")
+                }
+                None => bail!("Could not understand the date '{today}' of --today."),
+            }
+        }
+        Ok(())
+    }
+
+    /// Entries to write: the free text split on `;;`, or with `--bulk`, every line of stdin.
+    pub fn inline_entries(&self) -> Result<Vec<String>> {
+        let entries: Vec<String> = if self.bulk {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .context("reading entries from stdin")?;
+            input.lines().map(str::to_string).collect()
+        } else {
+            self.text
+                .split(|arg| arg == ";;")
+                .map(|words| words.join(" "))
+                .collect()
+        };
+        Ok(entries
+            .into_iter()
+            .filter(|entry| !entry.trim().is_empty())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LggCommand;
+
+    #[test]
+    fn entries_are_split_on_double_semicolons() {
+        let args = ["lgg", "today:", "first", ";;", "today at 14:00:", "second", ";;", ";;"];
+        let cli = BaseCli::<LggCommand>::try_parse_from(args).unwrap();
+        assert_eq!(
+            cli.inline_entries().unwrap(),
+            ["today: first", "today at 14:00: second"]
+        );
+        let cli = BaseCli::<LggCommand>::try_parse_from(["lgg", "just one; entry"]).unwrap();
+        assert_eq!(cli.inline_entries().unwrap(), ["just one; entry"]);
     }
 }
@@ -0,0 +1,23 @@
+use clap::ValueEnum;
+use lgg_core::DateOrder as CoreDateOrder;
+
+/// Order of the day, month and year in the dates typed in this run.
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum DateOrder {
+    /// 14/08/2025
+    Dmy,
+    /// 08/14/2025
+    Mdy,
+    /// 2025/08/14
+    Ymd,
+}
+
+impl From<DateOrder> for CoreDateOrder {
+    fn from(date_order: DateOrder) -> Self {
+        match date_order {
+            DateOrder::Dmy => CoreDateOrder::Dmy,
+            DateOrder::Mdy => CoreDateOrder::Mdy,
+            DateOrder::Ymd => CoreDateOrder::Ymd,
+        }
+    }
+}
@@ -1,31 +1,319 @@
-use anyhow::Result;
-use std::{fs, path::Path, process::Command};
+use anyhow::{bail, Context, Result};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use tempfile::NamedTempFile;
 
+/// GUI editors that return as soon as the window opens, with the flags that make them wait until
+/// it's closed. The first flag is added when none is given.
+const GUI_EDITORS: &[(&str, &[&str])] = &[
+    ("code", &["--wait", "-w"]),
+    ("code-insiders", &["--wait", "-w"]),
+    ("codium", &["--wait", "-w"]),
+    ("cursor", &["--wait", "-w"]),
+    ("subl", &["--wait", "-w"]),
+    ("zed", &["--wait", "-w"]),
+    ("atom", &["--wait", "-w"]),
+    ("mate", &["--wait", "-w"]),
+    ("bbedit", &["--wait", "-w"]),
+    ("gedit", &["--wait"]),
+    ("kate", &["--block", "-b"]),
+    ("gvim", &["--nofork", "-f"]),
+    ("mvim", &["--nofork", "-f"]),
+];
+
+/// Editors tried, in order, when none is set.
+#[cfg(not(windows))]
+const FALLBACK_EDITORS: &[&str] = &["vim", "vi", "nano"];
+#[cfg(windows)]
+const FALLBACK_EDITORS: &[&str] = &["notepad", "vim", "nano"];
+
+/// The editor to write with: `editor` (from the config), $VISUAL, $EDITOR, or the first of vim, vi
+/// and nano (notepad, vim and nano on Windows) that is installed. `None` if there is none.
+pub fn find_editor(editor: &Option<String>) -> Option<String> {
+    let set = [editor.clone(), std::env::var("VISUAL").ok(), std::env::var("EDITOR").ok()];
+    pick_editor(set, is_installed)
+}
+
+/// The first of the `set` editors that isn't empty, or else the first installed fallback editor.
+fn pick_editor(set: [Option<String>; 3], installed: impl Fn(&str) -> bool) -> Option<String> {
+    set.into_iter()
+        .flatten()
+        .find(|editor| !editor.trim().is_empty())
+        .or_else(|| {
+            FALLBACK_EDITORS
+                .iter()
+                .find(|editor| installed(editor))
+                .map(|editor| editor.to_string())
+        })
+}
+
+/// Like [`find_editor`], for the modes that can't do without one.
 pub fn resolve_editor(editor: &Option<String>) -> Result<String> {
-    let editor = editor
-        .as_deref()
-        .map(str::to_string)
-        .or_else(|| std::env::var("VISUAL").ok())
-        .or_else(|| std::env::var("EDITOR").ok())
-        .unwrap_or_else(|| "vim".into());
-    Ok(editor)
+    find_editor(editor)
+        .context("No editor found. Set `editor` in the config, or $VISUAL or $EDITOR.")
+}
+
+/// Whether `program` is in a directory of the $PATH.
+fn is_installed(program: &str) -> bool {
+    let Some(path) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&path).any(|dir| {
+        dir.join(program).is_file() || dir.join(format!("{program}.exe")).is_file()
+    })
+}
+
+pub fn create_editor_buffer(editor_cmd: &str, temp_dir: Option<&Path>) -> Result<String> {
+    create_seeded_editor_buffer(editor_cmd, "", temp_dir)
+}
+
+/// Opens the editor on a temporary file that already contains `initial` and returns what was saved.
+/// The file is made with [`secure_temp`].
+pub fn create_seeded_editor_buffer(
+    editor_cmd: &str,
+    initial: &str,
+    temp_dir: Option<&Path>,
+) -> Result<String> {
+    let file = secure_temp(temp_dir)?;
+    fs::write(file.path(), initial)?;
+    open_file_in_editor(editor_cmd, file.path())?;
+    Ok(fs::read_to_string(file.path())?)
 }
 
-pub fn create_editor_buffer(editor_cmd: &str) -> Result<String> {
-    let file = tempfile::Builder::new()
-        .prefix("lgg")
-        .suffix(".md")
-        .tempfile()?;
+/// A temporary file only the user can read and write, in `dir` or else in $XDG_RUNTIME_DIR or the
+/// system temp dir. Its content is overwritten with zeros before it's removed.
+pub fn secure_temp(dir: Option<&Path>) -> Result<SecureTemp> {
+    let dir = dir
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from))
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(std::env::temp_dir);
+    let mut builder = tempfile::Builder::new();
+    builder.prefix("lgg").suffix(".md");
+    #[cfg(unix)]
+    builder.permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600));
+    let file = builder
+        .tempfile_in(&dir)
+        .with_context(|| format!("creating a temporary file in {}", dir.display()))?;
+    Ok(SecureTemp { file })
+}
+
+/// Temporary file made by [`secure_temp`].
+pub struct SecureTemp {
+    file: NamedTempFile,
+}
+
+impl SecureTemp {
+    pub fn path(&self) -> &Path {
+        self.file.path()
+    }
+}
 
-    let path = file.path().to_path_buf();
-    open_file_in_editor(editor_cmd, &path)?;
-    Ok(fs::read_to_string(&path)?)
+impl Drop for SecureTemp {
+    fn drop(&mut self) {
+        // The editor may have replaced the file, so it's opened again by its path.
+        if let Ok(mut file) = OpenOptions::new().write(true).open(self.path())
+            && let Ok(metadata) = file.metadata()
+        {
+            let zeros = vec![0; metadata.len() as usize];
+            let _ = file.write_all(&zeros).and_then(|_| file.sync_all());
+        }
+    }
 }
 
+/// Runs `editor_cmd` on `path` and waits for it. The command may have arguments, quoted as in a
+/// shell (`code --wait`, `"/Applications/My Editor" -w`).
 pub fn open_file_in_editor(editor_cmd: &str, path: &Path) -> Result<()> {
-    let status = Command::new(editor_cmd).arg(path).status()?;
+    let args = if cfg!(windows) {
+        Some(split_windows_command(editor_cmd))
+    } else {
+        shlex::split(editor_cmd)
+    };
+    let Some(mut args) = args.filter(|args| !args.is_empty()) else {
+        bail!("Invalid editor command `{editor_cmd}`.");
+    };
+    let program = args.remove(0);
+    if let Some(flag) = missing_wait_flag(&program, &args) {
+        args.push(flag.to_string());
+    }
+    let status = editor_command(&program, &args, path)
+        .status()
+        .with_context(|| format!("could not run the editor `{program}`"))?;
     if !status.success() {
-        anyhow::bail!("Editor exited with status {}", status);
+        bail!("Editor exited with status {}", status);
     }
     Ok(())
 }
+
+/// Splits a command line as Windows does: on spaces outside double quotes. Backslashes are path
+/// separators there (`C:\Program Files\...`), not escapes like in a Unix shell.
+fn split_windows_command(command: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut arg = String::new();
+    let mut in_quotes = false;
+    let mut has_arg = false;
+    for c in command.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_arg = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_arg {
+                    args.push(std::mem::take(&mut arg));
+                    has_arg = false;
+                }
+            }
+            c => {
+                arg.push(c);
+                has_arg = true;
+            }
+        }
+    }
+    if has_arg {
+        args.push(arg);
+    }
+    args
+}
+
+/// The command that opens `path` with `program` and `args`. On Windows it goes through `cmd /C`,
+/// which also runs the `.cmd` launchers of editors like VS Code (`code.cmd`).
+fn editor_command(program: &str, args: &[String], path: &Path) -> Command {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        let mut command = Command::new("cmd");
+        command.args(["/S", "/C"]).raw_arg(cmd_line(program, args, path));
+        command
+    }
+    #[cfg(not(windows))]
+    {
+        let mut command = Command::new(program);
+        command.args(args).arg(path);
+        command
+    }
+}
+
+/// The command line `cmd /S /C` runs to open `path` with `program` and `args`. The arguments with
+/// spaces are quoted, and with /S cmd only removes the outer quotes, so they are kept.
+#[cfg(any(windows, test))]
+fn cmd_line(program: &str, args: &[String], path: &Path) -> String {
+    let path = path.display().to_string();
+    let line = std::iter::once(program)
+        .chain(args.iter().map(String::as_str))
+        .chain(std::iter::once(path.as_str()))
+        .map(|arg| {
+            if arg.contains(' ') || arg.is_empty() {
+                format!("\"{arg}\"")
+            } else {
+                arg.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("\"{line}\"")
+}
+
+/// The flag `program` needs to wait until its window is closed, if it's a GUI editor and `args`
+/// have none.
+fn missing_wait_flag(program: &str, args: &[String]) -> Option<&'static str> {
+    let name = Path::new(program).file_stem()?.to_str()?;
+    let (_, flags) = GUI_EDITORS.iter().find(|(editor, _)| *editor == name)?;
+    let waits = args.iter().any(|arg| flags.contains(&arg.as_str()));
+    (!waits).then_some(flags[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_commands_split_on_spaces_outside_quotes() {
+        assert_eq!(
+            split_windows_command(r#""C:\Program Files\Notepad++\notepad++.exe" -multiInst"#),
+            vec![r"C:\Program Files\Notepad++\notepad++.exe", "-multiInst"]
+        );
+        assert_eq!(
+            split_windows_command(r"C:\tools\hx.exe  --vsplit"),
+            vec![r"C:\tools\hx.exe", "--vsplit"]
+        );
+        assert_eq!(split_windows_command(r#"code "" --wait"#), vec!["code", "", "--wait"]);
+        assert!(split_windows_command("   ").is_empty());
+    }
+
+    #[test]
+    fn cmd_lines_quote_the_arguments_with_spaces() {
+        let args = vec!["--wait".to_string()];
+        let path = Path::new(r"C:\Users\Ana Gil\AppData\Local\Temp\entry.md");
+        assert_eq!(
+            cmd_line(r"C:\Program Files\Microsoft VS Code\bin\code.cmd", &args, path),
+            r#"""C:\Program Files\Microsoft VS Code\bin\code.cmd" --wait "C:\Users\Ana Gil\AppData\Local\Temp\entry.md"""#
+        );
+        assert_eq!(cmd_line("notepad", &[], Path::new("entry.md")), r#""notepad entry.md""#);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn editors_run_with_their_arguments_and_the_file() {
+        let args = vec!["-w".to_string()];
+        let command = editor_command("/Applications/My Editor", &args, Path::new("/tmp/entry.md"));
+        assert_eq!(command.get_program(), "/Applications/My Editor");
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, vec!["-w", "/tmp/entry.md"]);
+    }
+
+    #[test]
+    fn without_any_editor_the_text_is_read_from_the_terminal() {
+        let none = || [None, None, None];
+        assert_eq!(pick_editor(none(), |_| false), None);
+        assert_eq!(pick_editor([Some(" ".into()), Some(String::new()), None], |_| false), None);
+        assert_eq!(pick_editor(none(), |editor| editor == "nano"), Some("nano".to_string()));
+        let set = [None, Some("hx".to_string()), Some("vim".to_string())];
+        assert_eq!(pick_editor(set, |_| true), Some("hx".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn editor_commands_keep_their_quoted_arguments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("entry.md");
+        open_file_in_editor(r#"sh -c 'echo "$0" > "$1"' "two words""#, &path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "two words\n");
+        assert!(open_file_in_editor("'unclosed", &path).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn editor_buffers_are_private_and_wiped_after_the_editor_exits() {
+        let dir = tempfile::tempdir().unwrap();
+        let kept = dir.path().join("kept");
+        let listing = dir.path().join("listing");
+        // The "editor" types a line, keeps a hard link to the buffer and lists its permissions.
+        let editor = format!(
+            "sh -c 'echo typed >> \"$0\" && ln \"$0\" {} && ls -l \"$0\" > {}'",
+            kept.display(),
+            listing.display()
+        );
+        let text = create_seeded_editor_buffer(&editor, "seed\n", Some(dir.path())).unwrap();
+        assert_eq!(text, "seed\ntyped\n");
+        assert!(fs::read_to_string(&listing).unwrap().starts_with("-rw------- "));
+        let mut left: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        left.sort();
+        assert_eq!(left, ["kept", "listing"]);
+        assert_eq!(fs::read(&kept).unwrap(), vec![0; text.len()]);
+    }
+
+    #[test]
+    fn gui_editors_get_their_wait_flag() {
+        assert_eq!(missing_wait_flag("code", &[]), Some("--wait"));
+        assert_eq!(missing_wait_flag("code", &["-w".to_string()]), None);
+        assert_eq!(missing_wait_flag("vim", &[]), None);
+    }
+}
@@ -0,0 +1,10 @@
+use clap::ValueEnum;
+
+/// Formats of other tools `lgg` can export to and import from.
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum Format {
+    /// Emacs org-mode.
+    Org,
+    /// Taskwarrior JSON (`task export`). Todos only.
+    Taskwarrior,
+}
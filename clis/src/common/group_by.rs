@@ -0,0 +1,34 @@
+use clap::ValueEnum;
+use lgg_core::stats;
+
+/// How `--count` groups the found entries/todos.
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum GroupBy {
+    /// Entries per month.
+    Month,
+    /// Entries per day of the week.
+    Weekday,
+    /// Entries per tag, most used first.
+    Tag,
+}
+
+impl GroupBy {
+    /// Name of the grouped column in the printed table.
+    pub fn label(self) -> &'static str {
+        match self {
+            GroupBy::Month => "Month",
+            GroupBy::Weekday => "Weekday",
+            GroupBy::Tag => "Tag",
+        }
+    }
+}
+
+impl From<GroupBy> for stats::GroupBy {
+    fn from(group_by: GroupBy) -> Self {
+        match group_by {
+            GroupBy::Month => stats::GroupBy::Month,
+            GroupBy::Weekday => stats::GroupBy::Weekday,
+            GroupBy::Tag => stats::GroupBy::Tag,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use log::{Level, Log, Metadata, Record};
+
+/// Prints the logs of lgg to stderr, as `lgg: [debug] parsing 2025-08-15.md`.
+struct StderrLogger {
+    /// Name of the binary, the start of every line.
+    name: &'static str,
+}
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // Dependencies (like the search index) log too much to be useful here.
+        let target = metadata.target();
+        target.starts_with("lgg") || target.starts_with(self.name)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let level = record.level().as_str().to_lowercase();
+            eprintln!("{}: [{level}] {}", self.name, record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Prints the logs of `-v` (info), `-vv` (debug) and `-vvv` (trace) to stderr. Without
+/// `verbose` nothing is logged.
+pub fn init_logger(name: &'static str, verbose: u8) {
+    let Some(level) = verbose_level(verbose) else {
+        return;
+    };
+    let logger = Box::leak(Box::new(StderrLogger { name }));
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(level.to_level_filter());
+    }
+}
+
+/// The level logged with `verbose` times `-v`.
+fn verbose_level(verbose: u8) -> Option<Level> {
+    match verbose {
+        0 => None,
+        1 => Some(Level::Info),
+        2 => Some(Level::Debug),
+        _ => Some(Level::Trace),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_v_logs_more() {
+        let levels: Vec<_> = (0..=4).map(verbose_level).collect();
+        let (info, debug, trace) = (Some(Level::Info), Some(Level::Debug), Some(Level::Trace));
+        assert_eq!(levels, [None, info, debug, trace, trace]);
+    }
+
+    #[test]
+    fn only_the_logs_of_lgg_are_printed() {
+        let logger = StderrLogger { name: "todo" };
+        let enabled = |target| logger.enabled(&Metadata::builder().target(target).build());
+        assert!(enabled("lgg_core::journal"));
+        assert!(enabled("todo"));
+        assert!(!enabled("tantivy::indexer"));
+    }
+}
@@ -1,8 +1,33 @@
+#[cfg(unix)]
+pub mod agent;
 mod base_cli;
 mod cli_mode;
+mod date_order;
 mod editor_utils;
+mod format;
+mod group_by;
+mod logger;
+mod notify;
+mod output;
+mod passphrase;
+mod period;
+mod prompt;
+mod sort;
 mod style;
+mod todo_status;
 
 pub use base_cli::BaseCli;
 pub use cli_mode::CliModeResult;
-pub use editor_utils::{create_editor_buffer, open_file_in_editor, resolve_editor};
+pub use editor_utils::{
+    create_editor_buffer, create_seeded_editor_buffer, find_editor, open_file_in_editor,
+    resolve_editor,
+};
+pub use format::Format;
+pub use logger::init_logger;
+pub use notify::notify;
+pub use output::Output;
+pub use passphrase::read_passphrase;
+pub use period::Period;
+pub use prompt::{ask, read_text};
+pub use sort::Sort;
+pub use style::Style;
@@ -0,0 +1,28 @@
+use anyhow::{bail, Context, Result};
+use std::process::Command;
+
+/// Shows a desktop notification with `title` and `message`: with `osascript` on macOS and
+/// `notify-send` elsewhere.
+pub fn notify(title: &str, message: &str) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("osascript");
+        command
+            .arg("-e")
+            .arg(format!("display notification {message:?} with title {title:?}"));
+        command
+    } else if cfg!(windows) {
+        bail!("Desktop notifications are not supported on Windows.");
+    } else {
+        let mut command = Command::new("notify-send");
+        command.arg(title).arg(message);
+        command
+    };
+    let program = command.get_program().to_string_lossy().to_string();
+    let status = command
+        .status()
+        .with_context(|| format!("could not run {program}"))?;
+    if !status.success() {
+        bail!("{program} failed with {status}");
+    }
+    Ok(())
+}
@@ -0,0 +1,11 @@
+use clap::ValueEnum;
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum Output {
+    /// Human readable output.
+    Text,
+    /// One row per entry/todo, for spreadsheets.
+    Csv,
+    /// One JSON object per line for each entry, for scripts. Also describes the new entries.
+    Json,
+}
@@ -0,0 +1,73 @@
+#[cfg(unix)]
+use super::agent;
+use anyhow::{bail, Result};
+use std::io::{self, IsTerminal, Write};
+use termimad::crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    terminal,
+};
+
+/// Environment variable with the passphrase of the private entries, to not be asked for it.
+const PASSPHRASE_VAR: &str = "LGG_PASSPHRASE";
+
+/// The passphrase of the private entries: `LGG_PASSPHRASE`, the one kept by `lgg agent`, or typed
+/// without echo on the terminal. With `confirm`, for new private entries, it's asked twice. A
+/// typed passphrase is given to the agent, if it's running.
+pub fn read_passphrase(confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_VAR)
+        && !passphrase.is_empty()
+    {
+        return Ok(passphrase);
+    }
+    #[cfg(unix)]
+    if let Some(passphrase) = agent::cached_passphrase(&agent::socket_path()) {
+        return Ok(passphrase);
+    }
+    if !io::stdin().is_terminal() {
+        bail!("Private entries need a passphrase. Type it on a terminal or set {PASSPHRASE_VAR}.");
+    }
+    let passphrase = read_hidden("Passphrase: ")?;
+    if passphrase.is_empty() {
+        bail!("The passphrase can't be empty.");
+    }
+    if confirm && read_hidden("Repeat the passphrase: ")? != passphrase {
+        bail!("The passphrases don't match.");
+    }
+    #[cfg(unix)]
+    agent::cache_passphrase(&agent::socket_path(), &passphrase);
+    Ok(passphrase)
+}
+
+/// Reads a line from the terminal without showing it, after printing `prompt` to stderr.
+fn read_hidden(prompt: &str) -> Result<String> {
+    eprint!("{prompt}");
+    io::stderr().flush()?;
+    terminal::enable_raw_mode()?;
+    let line = read_hidden_line();
+    terminal::disable_raw_mode()?;
+    eprintln!();
+    line
+}
+
+fn read_hidden_line() -> Result<String> {
+    let mut line = String::new();
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => return Ok(line),
+            KeyCode::Backspace => {
+                line.pop();
+            }
+            KeyCode::Char('c' | 'd') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                bail!("No passphrase was typed.")
+            }
+            KeyCode::Char(c) => line.push(c),
+            _ => {}
+        }
+    }
+}
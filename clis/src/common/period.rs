@@ -0,0 +1,29 @@
+use clap::ValueEnum;
+use lgg_core::Keyword;
+
+/// Span of time `lgg review` looks back on.
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum Period {
+    /// Last week, Monday to Sunday.
+    Week,
+    /// Last month.
+    Month,
+}
+
+impl Period {
+    /// The keyword of the last one (`last week`).
+    pub fn keyword(self) -> Keyword {
+        match self {
+            Period::Week => Keyword::LastWeek,
+            Period::Month => Keyword::LastMonth,
+        }
+    }
+
+    /// Name of the period in the title of the review.
+    pub fn name(self) -> &'static str {
+        match self {
+            Period::Week => "week",
+            Period::Month => "month",
+        }
+    }
+}
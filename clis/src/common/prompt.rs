@@ -0,0 +1,37 @@
+use anyhow::{Context, Result};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// Asks `question` on the terminal and returns the answer, or `default` if it's left empty.
+pub fn ask(question: &str, default: &str) -> Result<String> {
+    print!("{question} [{default}]: ");
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .context("reading the answer")?;
+    let answer = answer.trim();
+    Ok(if answer.is_empty() {
+        default.to_string()
+    } else {
+        answer.to_string()
+    })
+}
+
+/// Reads text from the terminal until Ctrl-D or a line with a single `.`. `intro` is printed first
+/// when a person is typing.
+pub fn read_text(intro: &str) -> Result<String> {
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        println!("{intro}");
+    }
+    let mut lines = Vec::new();
+    for line in stdin.lock().lines() {
+        let line = line.context("reading the text")?;
+        if line.trim() == "." {
+            break;
+        }
+        lines.push(line);
+    }
+    Ok(lines.join("\n"))
+}
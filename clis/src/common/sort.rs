@@ -0,0 +1,20 @@
+use clap::ValueEnum;
+use lgg_core::EntrySort;
+
+/// The order of the found entries.
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum Sort {
+    /// Oldest first.
+    Date,
+    /// Most words first.
+    Words,
+}
+
+impl From<Sort> for EntrySort {
+    fn from(sort: Sort) -> Self {
+        match sort {
+            Sort::Date => EntrySort::Date,
+            Sort::Words => EntrySort::Words,
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use clap::ValueEnum;
 
-#[derive(Copy, Clone, Debug, ValueEnum)]
+#[derive(Copy, Clone, Debug, PartialEq, ValueEnum)]
 pub enum Style {
     Long,
     Short,
+    /// Only the titles, under their year, month and day.
+    Toc,
 }
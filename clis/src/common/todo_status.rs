@@ -0,0 +1,26 @@
+use clap::ValueEnum;
+use lgg_core::TodoStatus as CoreTodoStatus;
+
+/// Status of the todos to list.
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum TodoStatus {
+    /// `- [ ]`
+    Pending,
+    /// `- [/]`
+    InProgress,
+    /// `- [x]`
+    Done,
+    /// `- [-]`
+    Cancelled,
+}
+
+impl From<TodoStatus> for CoreTodoStatus {
+    fn from(status: TodoStatus) -> Self {
+        match status {
+            TodoStatus::Pending => CoreTodoStatus::Pending,
+            TodoStatus::InProgress => CoreTodoStatus::InProgress,
+            TodoStatus::Done => CoreTodoStatus::Done,
+            TodoStatus::Cancelled => CoreTodoStatus::Cancelled,
+        }
+    }
+}
@@ -2,6 +2,6 @@ mod cli_modes;
 mod common;
 mod render;
 
-pub use cli_modes::{LggCli, TodoCli};
-pub use common::{BaseCli, CliModeResult};
+pub use cli_modes::{LggCli, LggCommand, TodoCli, TodoCommand};
+pub use common::{init_logger, BaseCli, CliModeResult};
 pub use render::{ColorMode, RenderOptions, Renderer};
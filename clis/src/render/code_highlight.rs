@@ -0,0 +1,118 @@
+use std::sync::OnceLock;
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{Theme, ThemeSet},
+    parsing::SyntaxSet,
+    util::{LinesWithEndings, as_24_bit_terminal_escaped},
+};
+
+/// Theme of the code blocks, the closest of the bundled ones to One Dark.
+const THEME: &str = "base16-ocean.dark";
+
+/// A piece of a markdown body: plain markdown or a fenced code block.
+pub enum Block<'a> {
+    Markdown(&'a str),
+    /// The code between the fences, and the language after the opening one (`rust`, or empty).
+    Code { lang: &'a str, code: &'a str },
+}
+
+/// `md` split at its ```` ``` ```` fences. A block left open runs to the end of `md`.
+pub fn fenced_blocks(md: &str) -> Vec<Block<'_>> {
+    let mut blocks = Vec::new();
+    let mut start = 0;
+    let mut open: Option<(&str, usize)> = None;
+    let mut offset = 0;
+    for line in md.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let trimmed = line.trim();
+        match open {
+            None => {
+                if let Some(info) = trimmed.strip_prefix("```") {
+                    if line_start > start {
+                        blocks.push(Block::Markdown(&md[start..line_start]));
+                    }
+                    let lang = info.split_whitespace().next().unwrap_or("");
+                    open = Some((lang, offset));
+                }
+            }
+            Some((lang, code_start)) => {
+                if trimmed == "```" {
+                    let code = &md[code_start..line_start];
+                    blocks.push(Block::Code { lang, code });
+                    open = None;
+                    start = offset;
+                }
+            }
+        }
+    }
+    match open {
+        Some((lang, code_start)) => blocks.push(Block::Code {
+            lang,
+            code: &md[code_start..],
+        }),
+        None if start < md.len() => blocks.push(Block::Markdown(&md[start..])),
+        None => {}
+    }
+    blocks
+}
+
+/// `code` with the terminal colors of `lang` (`rust`, `py`, `sh`), plain if it's not known.
+pub fn highlight_code(code: &str, lang: &str) -> String {
+    static SYNTAXES: OnceLock<(SyntaxSet, Theme)> = OnceLock::new();
+    // Loading the syntaxes takes a while, only done once there is code to show.
+    let (syntaxes, theme) = SYNTAXES.get_or_init(|| {
+        let themes = ThemeSet::load_defaults();
+        (SyntaxSet::load_defaults_newlines(), themes.themes[THEME].clone())
+    });
+    let syntax = syntaxes
+        .find_syntax_by_token(lang)
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    let mut out = String::new();
+    for line in LinesWithEndings::from(code) {
+        match highlighter.highlight_line(line, syntaxes) {
+            Ok(ranges) => out.push_str(&as_24_bit_terminal_escaped(&ranges, false)),
+            Err(_) => out.push_str(line),
+        }
+    }
+    // Back to the terminal colors before the last line ends.
+    out.truncate(out.trim_end_matches('\n').len());
+    out.push_str("\x1b[0m\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_blocks_are_split_from_the_markdown() {
+        let md = "Fixed it:\n```rust title\nfn main() {}\n```\nThen:\n```sh\nls\n";
+        let blocks: Vec<(&str, &str)> = fenced_blocks(md)
+            .into_iter()
+            .map(|block| match block {
+                Block::Markdown(md) => ("md", md),
+                Block::Code { lang, code } => (lang, code),
+            })
+            .collect();
+        assert_eq!(
+            blocks,
+            [
+                ("md", "Fixed it:\n"),
+                ("rust", "fn main() {}\n"),
+                ("md", "Then:\n"),
+                ("sh", "ls\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn highlighted_code_keeps_its_text() {
+        let highlighted = highlight_code("fn main() {}\n", "rust");
+        assert!(highlighted.contains("\x1b[38;2;"));
+        assert!(highlighted.ends_with("\x1b[0m\n"));
+        let plain = regex::Regex::new("\x1b\\[[0-9;]*m").unwrap().replace_all(&highlighted, "");
+        assert_eq!(plain, "fn main() {}\n");
+    }
+}
@@ -6,3 +6,16 @@ pub enum ColorMode {
     Always,
     Never,
 }
+
+/// Turns on ANSI colors in the Windows console, and returns whether it has them. Consoles older
+/// than Windows 10 can't show them. Always `true` elsewhere.
+pub fn enable_ansi() -> bool {
+    #[cfg(windows)]
+    {
+        termimad::crossterm::ansi_support::supports_ansi()
+    }
+    #[cfg(not(windows))]
+    {
+        true
+    }
+}
@@ -0,0 +1,79 @@
+use lgg_core::Hyperlinks;
+use std::{env, path::Path};
+
+/// `text` as an OSC 8 hyperlink to the file at `path`, which terminals open on click.
+pub fn file_link(path: &Path, text: &str) -> String {
+    format!("\x1b]8;;{}\x1b\\{text}\x1b]8;;\x1b\\", file_url(path))
+}
+
+/// Whether the terminal is known to understand OSC 8 hyperlinks. Others would print them as
+/// garbage, so unknown terminals get none.
+pub fn terminal_supports_hyperlinks() -> bool {
+    let var = |name: &str| env::var(name).unwrap_or_default();
+    if env::var_os("KITTY_WINDOW_ID").is_some() || env::var_os("WT_SESSION").is_some() {
+        return true;
+    }
+    if var("VTE_VERSION").parse::<u32>().is_ok_and(|v| v >= 5000) {
+        return true;
+    }
+    let program = var("TERM_PROGRAM");
+    let known_programs = ["iTerm.app", "WezTerm", "vscode", "ghostty", "Hyper", "rio"];
+    if known_programs.contains(&program.as_str()) {
+        return true;
+    }
+    let term = var("TERM");
+    ["kitty", "alacritty", "foot", "ghostty", "wezterm"]
+        .iter()
+        .any(|name| term.contains(name))
+}
+
+/// `file://` URL of `path`, with the characters URLs can't hold percent-encoded.
+fn file_url(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut url = String::from("file://");
+    if !path.starts_with('/') {
+        // Windows paths, like `C:/Users`.
+        url.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                url.push(byte as char)
+            }
+            _ => url.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    url
+}
+
+/// Whether to print hyperlinks, with the `hyperlinks` config. Only colored output has them.
+pub fn use_hyperlinks(setting: Hyperlinks, use_color: bool) -> bool {
+    use_color
+        && match setting {
+            Hyperlinks::Auto => terminal_supports_hyperlinks(),
+            Hyperlinks::Always => true,
+            Hyperlinks::Never => false,
+        }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_point_to_the_encoded_file_url() {
+        let link = file_link(Path::new("/home/me/journal/2025/08/Día 1.md"), "Walk");
+        assert_eq!(
+            link,
+            "\x1b]8;;file:///home/me/journal/2025/08/D%C3%ADa%201.md\x1b\\Walk\x1b]8;;\x1b\\"
+        );
+        assert_eq!(file_url(Path::new(r"C:\Users\me\todos.md")), "file:///C:/Users/me/todos.md");
+    }
+
+    #[test]
+    fn only_colored_output_has_links() {
+        assert!(use_hyperlinks(Hyperlinks::Always, true));
+        assert!(!use_hyperlinks(Hyperlinks::Always, false));
+        assert!(!use_hyperlinks(Hyperlinks::Never, true));
+    }
+}
@@ -0,0 +1,131 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Width of the previews, in terminal columns. Their height keeps the image proportions.
+const PREVIEW_COLUMNS: u32 = 60;
+
+/// Images larger than this are left as links.
+const MAX_IMAGE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// The kitty protocol takes the image in chunks of at most this many base64 bytes.
+const KITTY_CHUNK: usize = 4096;
+
+/// How the terminal shows images.
+#[derive(Clone, Copy, PartialEq)]
+enum ImageProtocol {
+    /// kitty's graphics protocol, PNG images only.
+    Kitty,
+    /// iTerm2's inline images, also understood by WezTerm.
+    Iterm,
+}
+
+/// Prints the local images `body` links to (`![plan](images/plan.png)`), if the terminal can
+/// show them. Relative paths are read from the folder of `entry_path`. The links are still in the
+/// body, for images and terminals this can't show.
+pub fn print_images(entry_path: &Path, body: &str) {
+    let Some(protocol) = detect_protocol() else {
+        return;
+    };
+    let dir = entry_path.parent().unwrap_or(Path::new(""));
+    for path in image_paths(body, dir) {
+        if let Some(image) = inline_image(&path, protocol) {
+            println!("{image}");
+        }
+    }
+}
+
+/// The image protocol of the terminal, known from its environment variables. Sixel isn't
+/// supported: it can't be told apart this way, and needs the image decoded.
+fn detect_protocol() -> Option<ImageProtocol> {
+    let var = |name: &str| env::var(name).unwrap_or_default();
+    if env::var_os("KITTY_WINDOW_ID").is_some() || var("TERM").contains("kitty") {
+        return Some(ImageProtocol::Kitty);
+    }
+    let program = var("TERM_PROGRAM");
+    if program == "iTerm.app" || program == "WezTerm" || var("LC_TERMINAL") == "iTerm2" {
+        return Some(ImageProtocol::Iterm);
+    }
+    None
+}
+
+/// Paths of the local images in the markdown image links of `body`, relative ones joined to `dir`.
+fn image_paths(body: &str, dir: &Path) -> Vec<PathBuf> {
+    static IMAGE_LINK: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r#"!\[[^\]]*\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#).unwrap()
+    });
+    IMAGE_LINK
+        .captures_iter(body)
+        .map(|capture| capture[1].to_string())
+        .filter(|target| !target.contains("://"))
+        .map(|target| {
+            let target = target.replace("%20", " ");
+            let path = Path::new(&target);
+            if path.is_absolute() {
+                path.to_path_buf()
+            } else {
+                dir.join(path)
+            }
+        })
+        .collect()
+}
+
+/// Escape sequence showing the image at `path`. `None` if it can't be read, is too large, or
+/// isn't a PNG for kitty.
+fn inline_image(path: &Path, protocol: ImageProtocol) -> Option<String> {
+    let size = fs::metadata(path).ok()?.len();
+    if size > MAX_IMAGE_BYTES {
+        return None;
+    }
+    let bytes = fs::read(path).ok()?;
+    let data = STANDARD.encode(&bytes);
+    match protocol {
+        ImageProtocol::Kitty => {
+            if !bytes.starts_with(b"\x89PNG") {
+                return None;
+            }
+            let chunks: Vec<&[u8]> = data.as_bytes().chunks(KITTY_CHUNK).collect();
+            let mut out = String::new();
+            for (i, chunk) in chunks.iter().enumerate() {
+                let more = u8::from(i + 1 < chunks.len());
+                let chunk = std::str::from_utf8(chunk).ok()?;
+                if i == 0 {
+                    out.push_str(&format!(
+                        "\x1b_Ga=T,f=100,c={PREVIEW_COLUMNS},m={more};{chunk}\x1b\\"
+                    ));
+                } else {
+                    out.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+                }
+            }
+            Some(out)
+        }
+        ImageProtocol::Iterm => Some(format!(
+            "\x1b]1337;File=inline=1;size={size};width={PREVIEW_COLUMNS};preserveAspectRatio=1:{data}\x07"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_paths_are_the_local_image_links() {
+        let body = "Plan: ![plan](images/plan.png)\n\
+            ![with title](<my%20photo.jpg> \"Me\") and ![abs](/tmp/a.png)\n\
+            ![remote](https://example.com/a.png) and a [link](notes.md)";
+        let dir = Path::new("/journal/2025/08");
+        assert_eq!(
+            image_paths(body, dir),
+            vec![
+                PathBuf::from("/journal/2025/08/images/plan.png"),
+                PathBuf::from("/journal/2025/08/my photo.jpg"),
+                PathBuf::from("/tmp/a.png"),
+            ]
+        );
+    }
+}
@@ -1,6 +1,12 @@
+#[cfg(feature = "highlight")]
+mod code_highlight;
 mod color_mode;
+mod hyperlink;
+#[cfg(feature = "images")]
+mod image_preview;
 mod renderer;
 mod theme;
 
-pub use color_mode::ColorMode;
-pub use renderer::{RenderOptions, Renderer};
+pub use color_mode::{enable_ansi, ColorMode};
+pub use hyperlink::use_hyperlinks;
+pub use renderer::{BoardColumn, RenderOptions, Renderer};
@@ -1,15 +1,101 @@
+use super::hyperlink::file_link;
+use crate::common::Style;
 use super::theme::OneDark;
-use lgg_core::{JournalEntry, JournalQueryResult, TodoEntry, TodoQueryResult, TodoStatus};
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use lgg_core::stats::{GroupCount, TodoStats};
+use lgg_core::tags::TagPattern;
+use lgg_core::{
+    format_date, Anniversary, Habit, HabitTicks, JournalEntry, JournalQueryResult, Locale, SearchHit, TodoEntry, TodoQueryResult,
+    TodoStatus,
+};
 use termimad::{
     MadSkin,
-    crossterm::style::{Color, Stylize},
+    crossterm::{
+        style::{Color, Stylize},
+        terminal,
+    },
 };
+use std::{collections::BTreeMap, path::PathBuf};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Width of the board when it's not printed to a terminal.
+const BOARD_WIDTH: usize = 80;
+/// Columns of the board are never narrower than this: the ones that don't fit go below.
+const BOARD_MIN_COLUMN: usize = 16;
+/// Spaces between the columns of the board.
+const BOARD_GAP: usize = 3;
+
+/// A column of `todo board`: its title and its todos, with their numbers.
+pub struct BoardColumn<'a> {
+    pub title: String,
+    pub todos: Vec<(usize, &'a TodoEntry)>,
+}
+
+impl<'a> BoardColumn<'a> {
+    /// The columns Pending, In Progress and Done of `todos`.
+    pub fn by_status(todos: &'a TodoQueryResult) -> Vec<Self> {
+        [
+            ("Pending", TodoStatus::Pending),
+            ("In Progress", TodoStatus::InProgress),
+            ("Done", TodoStatus::Done),
+        ]
+        .into_iter()
+        .map(|(title, status)| BoardColumn {
+            title: title.to_string(),
+            todos: todos.numbered().filter(|(_, todo)| todo.status == status).collect(),
+        })
+        .collect()
+    }
+
+    /// A column per tag of the open `todos`, sorted by tag, and "No tag" last. A todo with
+    /// several tags is in each of their columns.
+    pub fn by_tag(todos: &'a TodoQueryResult) -> Vec<Self> {
+        let mut tags: BTreeMap<&str, Vec<(usize, &TodoEntry)>> = BTreeMap::new();
+        let mut untagged = Vec::new();
+        let open = todos
+            .numbered()
+            .filter(|(_, todo)| TodoStatus::OPEN.contains(&todo.status));
+        for (index, todo) in open {
+            if todo.tags.is_empty() {
+                untagged.push((index, todo));
+            }
+            for tag in &todo.tags {
+                tags.entry(tag).or_default().push((index, todo));
+            }
+        }
+        let mut columns: Vec<BoardColumn> = tags
+            .into_iter()
+            .map(|(tag, todos)| BoardColumn {
+                title: tag.to_string(),
+                todos,
+            })
+            .collect();
+        if !untagged.is_empty() {
+            columns.push(BoardColumn {
+                title: "No tag".to_string(),
+                todos: untagged,
+            });
+        }
+        columns
+    }
+}
 
 #[derive(Clone)]
 pub struct RenderOptions {
     pub date_format: String,
+    pub locale: Option<Locale>,
     pub use_color: bool,
-    pub short_mode: bool,
+    /// How entries are printed (`--style`). Todos are printed a line each with any style but long.
+    pub style: Style,
+    /// Link entries and todos to their file (see [`super::use_hyperlinks`]).
+    pub hyperlinks: bool,
+    /// Hide the messages about what was done (`--quiet`), only print what was asked for.
+    pub quiet: bool,
+    /// Show the local images linked in the bodies, see [`super::image_preview`].
+    pub images: bool,
+    /// The tags highlighted in the bodies, see [`lgg_core::Config::tag_pattern`].
+    pub tags: TagPattern,
 }
 
 pub struct Renderer {
@@ -23,8 +109,13 @@ impl Renderer {
             skin: OneDark::default_onedark_skin(),
             opts: config.unwrap_or_else(|| RenderOptions {
                 date_format: "%a, %d %b %Y".to_string(),
+                locale: None,
                 use_color: true,
-                short_mode: false,
+                style: Style::Long,
+                hyperlinks: false,
+                quiet: false,
+                images: false,
+                tags: TagPattern::default(),
             }),
         }
     }
@@ -33,7 +124,16 @@ impl Renderer {
         self.skin.print_text(md);
     }
 
+    /// Prints a message about what was done, like where a new entry was written. Nothing with
+    /// `--quiet`, see [`Renderer::print_result`] for what was asked for.
     pub fn print_info(&self, message: &str) {
+        if !self.opts.quiet {
+            self.print_result(message);
+        }
+    }
+
+    /// Prints a message that answers the command, like a count. Even with `--quiet`.
+    pub fn print_result(&self, message: &str) {
         let md = format!("|-|\n| {message} |\n|-|\n");
         if self.opts.use_color {
             self.print_md(&md);
@@ -42,6 +142,23 @@ impl Renderer {
         }
     }
 
+    /// Prints the line of an entry that was just written. Nothing with `--quiet`.
+    pub fn print_saved_entry_line(&self, entry: &JournalEntry) {
+        if !self.opts.quiet {
+            self.print_journal_entry_line(entry);
+        }
+    }
+
+    /// Prints the line of a todo that was just written or changed, with its number if it has
+    /// one. Nothing with `--quiet`.
+    pub fn print_saved_todo_line(&self, index: Option<usize>, todo: &TodoEntry) {
+        match index {
+            _ if self.opts.quiet => {}
+            Some(index) => self.print_numbered_todo_line(index, todo, true),
+            None => self.print_todo_entry_line(todo, true),
+        }
+    }
+
     pub fn print_journal_entry_line(&self, entry: &JournalEntry) {
         let mut date = entry.date.to_string();
         let mut time = entry.time.format("%H:%M").to_string();
@@ -60,39 +177,83 @@ impl Renderer {
             time = time.with(Color::Blue).to_string();
             title = title.with(Color::Yellow).to_string();
         }
+        if self.opts.hyperlinks {
+            date = file_link(&entry.path, &date);
+        }
         println!("{} {} - {} {}", date, time, title, tags);
     }
 
-    pub fn print_journal_entries<'a>(&self, result: &JournalQueryResult) {
+    /// Prints the anniversaries with their day, and the years of the ones with a year
+    /// (`Thu, 03 Sep 2026 Mum's birthday (36 years)`).
+    pub fn print_anniversaries(&self, anniversaries: &[(NaiveDate, &Anniversary)]) {
+        for (date, anniversary) in anniversaries {
+            let mut day = format_date(*date, &self.opts.date_format, self.opts.locale);
+            let mut name = anniversary.name.clone();
+            if let Some(years) = anniversary.years_on(*date) {
+                name.push_str(&format!(" ({years} years)"));
+            }
+            if self.opts.use_color {
+                day = day.with(Color::Cyan).to_string();
+                name = name.with(Color::Magenta).to_string();
+                println!("🎂 {day} {name}");
+            } else {
+                println!("* {day} {name}");
+            }
+        }
+    }
+
+    /// Prints the entries in full, a line each in short mode, or only their titles in toc mode
+    /// (see [`Renderer::toc_lines`]). In full, each one ends with its words ("214 words") and,
+    /// with hyperlinks, links to its file and to the days it refers to, found with `day_file`.
+    pub fn print_journal_entries(
+        &self,
+        result: &JournalQueryResult,
+        day_file: impl Fn(NaiveDate) -> PathBuf,
+    ) {
+        if self.opts.style == Style::Toc {
+            for line in self.toc_lines(&result.entries) {
+                println!("{line}");
+            }
+            return;
+        }
         for (i, entry) in result.entries.iter().enumerate() {
-            if self.opts.short_mode {
-                self.print_journal_entry_line(&entry);
+            if self.opts.style == Style::Short {
+                self.print_journal_entry_line(entry);
                 continue;
             }
-            let date = entry.date.format(&self.opts.date_format).to_string();
+            let date = format_date(entry.date, &self.opts.date_format, self.opts.locale);
             let time = entry.time.format("%H:%M").to_string();
             let title = entry.title.trim();
             let heading = format!("## {} {}: {}", &date, &time, &title);
 
-            let body = if entry.body.trim().is_empty() {
-                String::new()
-            } else {
-                let mut parsed_body = entry.body.trim_end().to_string();
-                parsed_body = highlight_tags_md(&parsed_body);
-                parsed_body
-            };
+            let body = entry.body.trim_end();
 
-            let md = if body.is_empty() {
-                format!("{heading}\n")
+            if self.opts.use_color {
+                self.print_entry_md(&heading, body);
+                #[cfg(feature = "images")]
+                if self.opts.images {
+                    super::image_preview::print_images(&entry.path, body);
+                }
             } else {
-                format!("{heading}\n{body}\n")
+                print!("{}", entry_md(&heading, body, &self.opts.tags));
+            }
+            let mut footer = match entry.word_count {
+                1 => "1 word".to_string(),
+                words => format!("{words} words"),
             };
-
             if self.opts.use_color {
-                self.print_md(&md);
-            } else {
-                print!("{md}");
+                footer = footer.with(Color::DarkGrey).to_string();
+            }
+            if self.opts.hyperlinks {
+                let name = entry.path.file_name().unwrap_or_default().to_string_lossy();
+                let name = name.as_ref().with(Color::DarkGrey).to_string();
+                footer.push_str(&format!("  {}", file_link(&entry.path, &name)));
+                for link in &entry.links {
+                    let text = format!("→ {link}").with(Color::DarkGrey).to_string();
+                    footer.push_str(&format!("  {}", file_link(&day_file(link.date), &text)));
+                }
             }
+            println!("{footer}");
 
             if i + 1 < result.entries.len() {
                 println!();
@@ -106,10 +267,259 @@ impl Renderer {
         }
     }
 
+    /// The table of contents of `entries`: a line with the year, month and day when they change,
+    /// each one indented under the previous, and then the time and title of each entry. With
+    /// hyperlinks, the days link to their file.
+    fn toc_lines(&self, entries: &[JournalEntry]) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut last: Option<NaiveDate> = None;
+        for entry in entries {
+            let date = entry.date;
+            let new_year = last.is_none_or(|last| last.year() != date.year());
+            let new_month = new_year || last.is_some_and(|last| last.month() != date.month());
+            if new_year {
+                let year = date.year().to_string();
+                lines.push(if self.opts.use_color { year.bold().to_string() } else { year });
+            }
+            if new_month {
+                let mut month = format_date(date, "%B", self.opts.locale);
+                if self.opts.use_color {
+                    month = month.with(Color::Magenta).to_string();
+                }
+                lines.push(format!("  {month}"));
+            }
+            if last != Some(date) {
+                let mut day = format_date(date, &self.opts.date_format, self.opts.locale);
+                if self.opts.use_color {
+                    day = day.with(Color::Cyan).to_string();
+                }
+                if self.opts.hyperlinks {
+                    day = file_link(&entry.path, &day);
+                }
+                lines.push(format!("    {day}"));
+            }
+            last = Some(date);
+            let mut time = entry.time.format("%H:%M").to_string();
+            let mut title = entry.title.trim().to_string();
+            if self.opts.use_color {
+                time = time.with(Color::Blue).to_string();
+                title = title.with(Color::Yellow).to_string();
+            }
+            lines.push(format!("      {time} {title}"));
+        }
+        lines
+    }
+
+    /// Prints `label` and the line of each entry after its words (`214 words 2025-08-15 ...`).
+    pub fn print_word_counts(&self, label: &str, entries: &[&JournalEntry]) {
+        if self.opts.use_color {
+            println!("{}", label.bold());
+        } else {
+            println!("{label}");
+        }
+        let width = entries
+            .iter()
+            .map(|e| e.word_count.to_string().len())
+            .max()
+            .unwrap_or_default();
+        for entry in entries {
+            let unit = if entry.word_count == 1 { "word " } else { "words" };
+            let words = format!("{:>width$} {unit} ", entry.word_count);
+            if self.opts.use_color {
+                print!("{}", words.with(Color::DarkGrey));
+            } else {
+                print!("{words}");
+            }
+            self.print_journal_entry_line(entry);
+        }
+    }
+
+    /// Prints `entries`, sorted, as a vertical timeline: the day on the left, then a line
+    /// joining its entries with their time, title and tags. Days without entries are left out,
+    /// saying how many.
+    pub fn print_timeline(&self, entries: &[JournalEntry]) {
+        for line in self.timeline_lines(entries) {
+            println!("{line}");
+        }
+    }
+
+    /// The lines of the timeline of `entries`, see [`Renderer::print_timeline`].
+    fn timeline_lines(&self, entries: &[JournalEntry]) -> Vec<String> {
+        let (dot, line, gap) = if self.opts.use_color {
+            ("●", "│", "┊")
+        } else {
+            ("*", "|", ":")
+        };
+        let day = |date| format_date(date, &self.opts.date_format, self.opts.locale);
+        let width = entries
+            .iter()
+            .map(|entry| text_width(&day(entry.date)))
+            .max()
+            .unwrap_or_default();
+        let gutter = " ".repeat(width);
+        let paint = |text: &str, color| {
+            if self.opts.use_color {
+                text.with(color).to_string()
+            } else {
+                text.to_string()
+            }
+        };
+
+        let mut lines = Vec::new();
+        for (i, entry) in entries.iter().enumerate() {
+            let previous = i.checked_sub(1).map(|i| entries[i].date);
+            let first_of_day = previous != Some(entry.date);
+            if let Some(previous) = previous.filter(|_| first_of_day) {
+                let (mark, empty) = match (entry.date - previous).num_days() - 1 {
+                    0 => (line, String::new()),
+                    1 => (gap, "  1 day without entries".to_string()),
+                    days => (gap, format!("  {days} days without entries")),
+                };
+                lines.push(format!("{gutter}  {}{empty}", paint(mark, Color::DarkGrey)));
+            }
+            let date = if first_of_day {
+                let date = day(entry.date);
+                let padding = " ".repeat(width - text_width(&date));
+                let mut date = paint(&date, Color::Cyan);
+                if self.opts.hyperlinks {
+                    date = file_link(&entry.path, &date);
+                }
+                format!("{date}{padding}")
+            } else {
+                gutter.clone()
+            };
+            let mark = if first_of_day { dot } else { line };
+            let tags = if entry.tags.is_empty() {
+                String::new()
+            } else if self.opts.use_color {
+                format!(" [{}]", print_colored_list(&entry.tags).join(" - "))
+            } else {
+                format!(" [{}]", entry.tags.join(" - "))
+            };
+            lines.push(format!(
+                "{date}  {} {} {}{tags}",
+                paint(mark, Color::DarkGrey),
+                paint(&entry.time.format("%H:%M").to_string(), Color::Blue),
+                paint(entry.title.trim(), Color::Yellow),
+            ));
+        }
+        lines
+    }
+
+    /// Prints an entry as markdown. Built with the `highlight` feature, its fenced code blocks get
+    /// the colors of their language.
+    fn print_entry_md(&self, heading: &str, body: &str) {
+        #[cfg(feature = "highlight")]
+        if body.contains("```") {
+            use super::code_highlight::{fenced_blocks, highlight_code, Block};
+            self.print_md(heading);
+            for block in fenced_blocks(body) {
+                match block {
+                    Block::Markdown(md) => {
+                        self.print_md(&highlight_tags_md(md.trim_end(), &self.opts.tags))
+                    }
+                    Block::Code { lang, code } => print!("{}", highlight_code(code, lang)),
+                }
+            }
+            return;
+        }
+        self.print_md(&entry_md(heading, body, &self.opts.tags));
+    }
+
+    /// Prints a unified diff, added lines in green and removed ones in red.
+    pub fn print_diff(&self, diff: &str) {
+        for line in diff.lines() {
+            if !self.opts.use_color {
+                println!("{line}");
+                continue;
+            }
+            let line = if line.starts_with("+++") || line.starts_with("---") {
+                line.bold().to_string()
+            } else if line.starts_with('+') {
+                line.with(Color::Green).to_string()
+            } else if line.starts_with('-') {
+                line.with(Color::Red).to_string()
+            } else if line.starts_with("@@") {
+                line.with(Color::Cyan).to_string()
+            } else {
+                line.to_string()
+            };
+            println!("{line}");
+        }
+    }
+
+    /// Prints the name of a journal before its entries, with `--journal all`. A blank line
+    /// separates it from the entries of the previous journal, if `after_another`.
+    pub fn print_journal_name(&self, name: &str, after_another: bool) {
+        if after_another {
+            println!();
+        }
+        if self.opts.use_color {
+            self.print_md(&format!("# {name}"));
+        } else {
+            println!("# {name}");
+        }
+    }
+
+    /// Prints every hit in one line, followed by the part of its body that matched (if any).
+    /// Prints the line of each found entry, then the lines of its body around the matches, the
+    /// matches highlighted. With `full`, the whole body.
+    pub fn print_search_hits(&self, hits: &[SearchHit], full: bool) {
+        for hit in hits {
+            self.print_journal_entry_line(&hit.entry);
+            if full && hit.snippets.is_empty() && !hit.entry.body.trim().is_empty() {
+                for line in hit.entry.body.trim_end().lines() {
+                    println!("    {line}");
+                }
+            }
+            for (i, snippet) in hit.snippets.iter().enumerate() {
+                if i > 0 {
+                    let more = if self.opts.use_color {
+                        "…".with(Color::DarkGrey).to_string()
+                    } else {
+                        "...".to_string()
+                    };
+                    println!("    {more}");
+                }
+                let mut text = String::new();
+                let mut last = 0;
+                for range in &snippet.highlights {
+                    text.push_str(&snippet.text[last..range.start]);
+                    let matched = &snippet.text[range.clone()];
+                    if self.opts.use_color {
+                        text.push_str(&matched.with(Color::Magenta).bold().to_string());
+                    } else {
+                        text.push_str(matched);
+                    }
+                    last = range.end;
+                }
+                text.push_str(&snippet.text[last..]);
+                for line in text.lines() {
+                    println!("    {line}");
+                }
+            }
+        }
+    }
+
     pub fn print_todo_entry_line(&self, entry: &TodoEntry, print_tags: bool) {
+        println!("{}", self.todo_entry_line(entry, print_tags));
+    }
+
+    /// Like [`Renderer::print_todo_entry_line`], after the number of the todo (`3.`).
+    pub fn print_numbered_todo_line(&self, index: usize, entry: &TodoEntry, print_tags: bool) {
+        let number = format!("{index:>2}.");
+        let number = if self.opts.use_color {
+            number.with(Color::DarkGrey).to_string()
+        } else {
+            number
+        };
+        println!("{number} {}", self.todo_entry_line(entry, print_tags));
+    }
+
+    fn todo_entry_line(&self, entry: &TodoEntry, print_tags: bool) -> String {
         let mut date = match entry.due_date {
             Some(dt) => {
-                let d = dt.date().format(&self.opts.date_format).to_string();
+                let d = format_date(dt.date(), &self.opts.date_format, self.opts.locale);
                 format!("- {d}")
             }
             None => "".to_string(),
@@ -120,7 +530,10 @@ impl Renderer {
         };
         let mut title = if self.opts.use_color {
             let icons = todo_icons(&entry.status);
-            let i = icons.color.with(Color::Red);
+            let mut i = icons.color.with(icons.tint).to_string();
+            if self.opts.hyperlinks {
+                i = file_link(&entry.path, &i);
+            }
             let t = entry.title.clone().with(Color::Yellow);
             format!("{i} {t}")
         } else {
@@ -145,16 +558,18 @@ impl Renderer {
         }
 
         if entry.due_date.is_none() {
-            println!("{} {}", title, tags);
+            format!("{} {}", title, tags)
         } else {
-            println!("{} {} {} {}", title, date, time, tags);
+            format!("{} {} {} {}", title, date, time, tags)
         }
     }
 
-    pub fn print_todos_entries<'a>(&self, result: &TodoQueryResult) {
-        for entry in &result.todos {
-            if self.opts.short_mode {
-                self.print_todo_entry_line(&entry, true);
+    /// Prints the todos, in long style with their body and how long ago they were added, as of
+    /// `today`.
+    pub fn print_todos_entries(&self, result: &TodoQueryResult, today: NaiveDate) {
+        for (index, entry) in result.numbered() {
+            if self.opts.style != Style::Long {
+                self.print_numbered_todo_line(index, entry, true);
                 continue;
             }
 
@@ -163,15 +578,23 @@ impl Renderer {
             }
 
             let mut parsed_body = entry.body.trim_end().to_string();
-            parsed_body = highlight_tags_plain(&parsed_body);
+            parsed_body = highlight_tags_plain(&parsed_body, &self.opts.tags);
             let spaces = if self.opts.use_color {
                 " ".repeat(2)
             } else {
                 " ".repeat(4)
             };
 
-            self.print_todo_entry_line(&entry, false);
+            self.print_numbered_todo_line(index, entry, false);
             println!("{spaces}{parsed_body}");
+            if let Some(created) = entry.created {
+                let added = added_ago(created.date(), today);
+                if self.opts.use_color {
+                    println!("{spaces}{}", added.with(Color::DarkGrey));
+                } else {
+                    println!("{spaces}{added}");
+                }
+            }
 
             if self.opts.use_color {
                 self.print_md("---");
@@ -180,40 +603,346 @@ impl Renderer {
             }
         }
     }
-    pub fn print_tags(&self, tags: &Vec<String>) {
+    /// Prints each todo with how long ago it was added, as of `today` (`todo --stale`).
+    pub fn print_stale_todos(&self, result: &TodoQueryResult, today: NaiveDate) {
+        for (index, entry) in result.numbered() {
+            let mut number = format!("{index:>2}.");
+            let mut added = entry
+                .created
+                .map(|created| added_ago(created.date(), today))
+                .unwrap_or_default();
+            if self.opts.use_color {
+                number = number.with(Color::DarkGrey).to_string();
+                added = added.with(Color::DarkGrey).to_string();
+            }
+            println!("{number} {} {added}", self.todo_entry_line(entry, true));
+        }
+    }
+
+    /// Prints `columns` side by side, as wide as the terminal: each todo on a line with its
+    /// number and due day, cut with `…` when it doesn't fit. The columns that don't fit next to
+    /// the others are printed below them.
+    pub fn print_todo_board(&self, columns: &[BoardColumn]) {
+        let terminal = terminal::size().map_or(BOARD_WIDTH, |(width, _)| usize::from(width));
+        for line in self.board_lines(columns, terminal) {
+            println!("{line}");
+        }
+    }
+
+    /// The lines of the board of `columns` in a terminal `terminal` columns wide.
+    fn board_lines(&self, columns: &[BoardColumn], terminal: usize) -> Vec<String> {
+        let fit = (terminal + BOARD_GAP) / (BOARD_MIN_COLUMN + BOARD_GAP);
+        let fit = fit.clamp(1, columns.len().max(1));
+        let width = (terminal.saturating_sub(BOARD_GAP * (fit - 1)) / fit).max(BOARD_MIN_COLUMN);
+        let mut lines = Vec::new();
+        for (i, band) in columns.chunks(fit).enumerate() {
+            if i > 0 {
+                lines.push(String::new());
+            }
+            lines.extend(self.board_band(band, width));
+        }
+        lines
+    }
+
+    /// The lines of `columns` of the board side by side, each `width` wide.
+    fn board_band(&self, columns: &[BoardColumn], width: usize) -> Vec<String> {
+        let headers: Vec<(String, String)> = columns
+            .iter()
+            .map(|column| {
+                let header = cut(&format!("{} ({})", column.title, column.todos.len()), width);
+                let colored = if self.opts.use_color {
+                    header.clone().bold().to_string()
+                } else {
+                    header.clone()
+                };
+                (header, colored)
+            })
+            .collect();
+        let mut lines = vec![board_row(&headers, width)];
+        let rule = "─".repeat(width);
+        let rule = if self.opts.use_color {
+            rule.with(Color::DarkGrey).to_string()
+        } else {
+            rule
+        };
+        let rules = vec![(rule.clone(), rule); columns.len()];
+        lines.push(board_row(&rules, width));
+
+        let rows = columns.iter().map(|c| c.todos.len()).max().unwrap_or_default();
+        for row in 0..rows {
+            let cells: Vec<(String, String)> = columns
+                .iter()
+                .map(|column| match column.todos.get(row) {
+                    Some((index, todo)) => self.board_cell(*index, todo, width),
+                    None => (String::new(), String::new()),
+                })
+                .collect();
+            lines.push(board_row(&cells, width));
+        }
+        lines
+    }
+
+    /// A todo in a column of the board `width` wide, as plain text and as printed.
+    fn board_cell(&self, index: usize, todo: &TodoEntry, width: usize) -> (String, String) {
+        let number = format!("{index:>2}. ");
+        let due = todo
+            .due_date
+            .map(|due| format!(" {}", due.format("%d/%m")))
+            .unwrap_or_default();
+        let text = cut(&format!("{}{due}", todo.title), width - text_width(&number));
+        let plain = format!("{number}{text}");
+        if !self.opts.use_color {
+            return (plain.clone(), plain);
+        }
+        let colored = format!("{}{}", number.with(Color::DarkGrey), text.with(Color::Yellow));
+        (plain, colored)
+    }
+
+    pub fn print_tags(&self, tags: &[String]) {
         let tags = if tags.is_empty() {
             String::new()
         } else if self.opts.use_color {
-            let colored_tags = print_colored_list(&tags);
-            format!("{}", colored_tags.join(" - "))
+            let colored_tags = print_colored_list(tags);
+            colored_tags.join(" - ")
         } else {
-            format!("{}", tags.join(" - "))
+            tags.join(" - ")
         };
         println!("{}", tags);
     }
+
+    /// Prints a two columns table: the group (e.g., `Month`) and its count (e.g., `Entries`).
+    pub fn print_count_table(&self, label: &str, count_label: &str, groups: &[GroupCount]) {
+        let width = groups
+            .iter()
+            .map(|g| text_width(&g.key))
+            .chain([label.len()])
+            .max()
+            .unwrap_or_default();
+        let header = format!("{}  {count_label}", pad(label, width));
+        if self.opts.use_color {
+            println!("{}", header.bold());
+        } else {
+            println!("{header}");
+        }
+        for group in groups {
+            println!("{}  {}", pad(&group.key, width), group.count);
+        }
+    }
+
+    /// Prints the counts of `todo stats`, then the todos done per week and per tag.
+    pub fn print_todo_stats(&self, stats: &TodoStats) {
+        println!(
+            "Open: {} ({} in progress, {} overdue)",
+            stats.open, stats.in_progress, stats.overdue
+        );
+        println!("Done: {}", stats.done);
+        if stats.cancelled > 0 {
+            println!("Cancelled: {}", stats.cancelled);
+        }
+        if let Some(delay) = stats.average_delay {
+            let hours = delay.num_hours();
+            let when = if hours < 0 { "before" } else { "after" };
+            let hours = hours.abs();
+            println!(
+                "Done on average {}d {}h {when} the due date",
+                hours / 24,
+                hours % 24
+            );
+        }
+        if !stats.done_per_week.is_empty() {
+            println!();
+            self.print_count_table("Week", "Done", &stats.done_per_week);
+        }
+        if !stats.tags.is_empty() {
+            println!();
+            let width = stats
+                .tags
+                .iter()
+                .map(|t| text_width(&t.tag))
+                .chain(["Tag".len()])
+                .max()
+                .unwrap_or_default();
+            let header = format!("{:<width$}  Open  Done", "Tag");
+            if self.opts.use_color {
+                println!("{}", header.bold());
+            } else {
+                println!("{header}");
+            }
+            for tag in &stats.tags {
+                println!("{}  {:<4}  {}", pad(&tag.tag, width), tag.open, tag.done);
+            }
+        }
+    }
+
+    /// Prints a row per habit with a column per day of the last `weeks` weeks, from Monday, and
+    /// its streaks: `■` done, `□` missed, `·` not due (or before it was first done).
+    pub fn print_habit_grid(
+        &self,
+        habits: &[Habit],
+        ticks: &HabitTicks,
+        weeks: u32,
+        today: NaiveDate,
+    ) {
+        let monday = today - Days::new(today.weekday().num_days_from_monday().into());
+        let start = monday - Days::new(7 * u64::from(weeks.max(1) - 1));
+        let width = habits
+            .iter()
+            .map(|h| text_width(&h.name))
+            .max()
+            .unwrap_or_default();
+        let mut header = format!("{:width$}", "");
+        for week in 0..weeks.max(1) {
+            let date = start + Days::new(7 * u64::from(week));
+            header.push_str(&format!(" {:<7}", date.format("%d/%m")));
+        }
+        if self.opts.use_color {
+            println!("{}", header.bold());
+        } else {
+            println!("{header}");
+        }
+
+        let empty = Default::default();
+        for habit in habits {
+            let done = ticks.get(&habit.name).unwrap_or(&empty);
+            let mut row = pad(&habit.name, width);
+            for date in start.iter_days().take_while(|date| *date <= today) {
+                if date.weekday() == Weekday::Mon {
+                    row.push(' ');
+                }
+                let (symbol, plain, color) = if done.contains(&date) {
+                    ('■', 'x', Color::Green)
+                } else if !habit.is_due(date) || done.first().is_none_or(|first| date < *first) {
+                    ('·', '.', Color::DarkGrey)
+                } else if date < today {
+                    ('□', '-', Color::Red)
+                } else {
+                    ('□', '-', Color::Reset)
+                };
+                if self.opts.use_color {
+                    row.push_str(&symbol.with(color).to_string());
+                } else {
+                    row.push(plain);
+                }
+            }
+            let streaks = habit.streaks(done, today);
+            let padding = " ".repeat((monday + Days::new(6) - today).num_days() as usize);
+            println!(
+                "{row}{padding}  {} in a row (best {})",
+                streaks.current, streaks.best
+            );
+        }
+    }
+
+    /// Prints the weekly sentiment as a sparkline, from the saddest (`▁`) to the happiest (`█`) week.
+    /// Weeks without entries are blank.
+    #[cfg(feature = "analysis")]
+    pub fn print_sentiment_trend(&self, trend: &[lgg_core::analysis::WeekSentiment]) {
+        const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        let sparkline: String = trend
+            .iter()
+            .map(|week| match week.score {
+                Some(score) => {
+                    let level = ((score + 1.0) / 2.0 * (BARS.len() - 1) as f32).round();
+                    BARS[(level as usize).min(BARS.len() - 1)]
+                }
+                None => ' ',
+            })
+            .collect();
+        let (first, last) = (&trend[0], &trend[trend.len() - 1]);
+        let scored: Vec<f32> = trend.iter().filter_map(|w| w.score).collect();
+        let average = scored.iter().sum::<f32>() / scored.len() as f32;
+        let entries: usize = trend.iter().map(|w| w.entries).sum();
+
+        println!("{}  {}  {}", first.week, sparkline, last.week);
+        self.print_info(&format!(
+            "Average mood {average:+.2} (from -1 to +1), {entries} entries in {} weeks.",
+            trend.len()
+        ));
+    }
 }
 
-fn highlight_tags_md(body: &str) -> String {
-    let re = regex::Regex::new(r"(?m)(^|\s)@([A-Za-z0-9_][\w-]*)").unwrap();
-    re.replace_all(body, "$1`@$2`").to_string()
+/// `heading` followed by `body`, its `tags` highlighted.
+fn entry_md(heading: &str, body: &str, tags: &TagPattern) -> String {
+    if body.trim().is_empty() {
+        format!("{heading}\n")
+    } else {
+        format!("{heading}\n{}\n", highlight_tags_md(body, tags))
+    }
 }
 
-fn highlight_tags_plain(body: &str) -> String {
-    let re = regex::Regex::new(r"(?m)(^|\s)@([A-Za-z0-9_][\w-]*)").unwrap();
-    re.replace_all(body, |capture: &regex::Captures<'_>| {
-        let tag = colorize_value(&capture[2]);
-        format!("{}@{}", &capture[1], &tag)
+fn highlight_tags_md(body: &str, tags: &TagPattern) -> String {
+    tags.replace(body, |tag| format!("`{tag}`"))
+}
+
+fn highlight_tags_plain(body: &str, tags: &TagPattern) -> String {
+    tags.replace(body, |tag| {
+        let sigil_len = tag.chars().next().map_or(0, char::len_utf8);
+        format!("{}{}", &tag[..sigil_len], colorize_value(&tag[sigil_len..]))
     })
-    .to_string()
 }
 
-pub fn print_colored_list(values: &Vec<String>) -> Vec<String> {
+/// `text` cut to `width` columns of the terminal, ending with `…` when it's wider. It's cut
+/// between graphemes, so emoji and accented letters stay whole, and CJK characters take two
+/// columns.
+fn cut(text: &str, width: usize) -> String {
+    if text_width(text) <= width {
+        return text.to_string();
+    }
+    let mut cut = String::new();
+    let mut used = 0;
+    for grapheme in text.graphemes(true) {
+        used += grapheme.width();
+        if used > width.saturating_sub(1) {
+            break;
+        }
+        cut.push_str(grapheme);
+    }
+    cut.push('…');
+    cut
+}
+
+/// Columns of the terminal `text` takes: two for CJK characters and most emoji, none for
+/// combining marks like the vowels of Arabic and Hebrew.
+fn text_width(text: &str) -> usize {
+    text.width()
+}
+
+/// `text` followed by the spaces it takes to fill `width` columns of the terminal.
+fn pad(text: &str, width: usize) -> String {
+    format!("{text}{}", " ".repeat(width.saturating_sub(text_width(text))))
+}
+
+/// A line of the board: its `cells`, as plain text and as printed, padded to `width` and apart.
+fn board_row(cells: &[(String, String)], width: usize) -> String {
+    let mut row = String::new();
+    for (i, (plain, printed)) in cells.iter().enumerate() {
+        if i > 0 {
+            row.push_str(&" ".repeat(BOARD_GAP));
+        }
+        row.push_str(printed);
+        row.push_str(&" ".repeat(width.saturating_sub(text_width(plain))));
+    }
+    row.trim_end().to_string()
+}
+
+pub fn print_colored_list(values: &[String]) -> Vec<String> {
     values.iter().map(|v| colorize_value(v)).collect()
 }
 
+/// `added 12 days ago`
+fn added_ago(created: NaiveDate, today: NaiveDate) -> String {
+    match (today - created).num_days() {
+        0 => "added today".to_string(),
+        1 => "added yesterday".to_string(),
+        days if days < 0 => format!("added on {}", created.format("%Y-%m-%d")),
+        days => format!("added {days} days ago"),
+    }
+}
+
 struct Icons {
     color: &'static str,
     no_color: &'static str,
+    tint: Color,
 }
 
 fn todo_icons(status: &TodoStatus) -> Icons {
@@ -221,10 +950,22 @@ fn todo_icons(status: &TodoStatus) -> Icons {
         TodoStatus::Pending => Icons {
             color: "☐",
             no_color: "[ ]",
+            tint: Color::Red,
+        },
+        TodoStatus::InProgress => Icons {
+            color: "◐",
+            no_color: "[/]",
+            tint: Color::Magenta,
         },
         TodoStatus::Done => Icons {
             color: "☑",
-            no_color: "[ ]",
+            no_color: "[x]",
+            tint: Color::Red,
+        },
+        TodoStatus::Cancelled => Icons {
+            color: "☒",
+            no_color: "[-]",
+            tint: Color::DarkGrey,
         },
     }
 }
@@ -256,3 +997,196 @@ fn colorize_value(val: &str) -> String {
     let idx = stable_index(val, palette.len());
     format!("{}", val.with(palette[idx]))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide_and_right_to_left_text_is_measured_in_columns() {
+        assert_eq!(text_width("東京タワー"), 10);
+        // The vowel marks of Arabic and the niqqud of Hebrew take no column.
+        assert_eq!(text_width("مَرْحَبًا"), 5);
+        assert_eq!(text_width("שָׁלוֹם"), 4);
+        assert_eq!(pad("東京", 6), "東京  ");
+        assert_eq!(pad("שָׁלוֹם", 6), "שָׁלוֹם  ");
+    }
+
+    #[test]
+    fn cut_text_fits_its_columns() {
+        assert_eq!(cut("東京のホテルを予約", 8), "東京の…");
+        assert_eq!(text_width(&cut("東京のホテルを予約", 8)), 7);
+        assert_eq!(cut("مَرْحَبًا بالعالم", 6), "مَرْحَبًا…");
+        assert_eq!(cut("Family 👨‍👩‍👧 day", 9), "Family …");
+        assert_eq!(cut("שָׁלוֹם", 4), "שָׁלוֹם");
+    }
+
+    #[test]
+    fn board_columns_line_up_with_wide_text() {
+        let cells = |texts: &[&str]| -> Vec<(String, String)> {
+            texts.iter().map(|t| (t.to_string(), t.to_string())).collect()
+        };
+        let rows = [
+            board_row(&cells(&["東京", "x"]), 6),
+            board_row(&cells(&["שָׁלוֹם", "x"]), 6),
+            board_row(&cells(&["abc", "x"]), 6),
+        ];
+        for row in &rows {
+            let (first, _) = row.rsplit_once('x').unwrap();
+            assert_eq!(text_width(first), 6 + BOARD_GAP);
+        }
+    }
+
+    #[test]
+    fn board_groups_todos_in_their_columns_side_by_side() {
+        let renderer = Renderer::new(Some(RenderOptions {
+            date_format: "%a, %d %b %Y".to_string(),
+            locale: None,
+            use_color: false,
+            style: Style::Long,
+            hyperlinks: false,
+            quiet: false,
+            images: false,
+            tags: TagPattern::default(),
+        }));
+        let todo = |title: &str, status: TodoStatus, tags: &[&str]| TodoEntry {
+            due_date: None,
+            done_date: None,
+            created: None,
+            title: title.to_string(),
+            body: String::new(),
+            path: PathBuf::new(),
+            status,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        };
+        let mut milk = todo("Buy milk @home", TodoStatus::Pending, &["@home"]);
+        milk.due_date = NaiveDate::from_ymd_opt(2025, 8, 20).unwrap().and_hms_opt(9, 0, 0);
+        let todos = TodoQueryResult {
+            todos: vec![
+                milk,
+                todo("Write spec @work @home", TodoStatus::InProgress, &["@work", "@home"]),
+                todo("Ship @work", TodoStatus::Done, &["@work"]),
+                todo("Call mom", TodoStatus::Pending, &[]),
+                todo("Old idea", TodoStatus::Cancelled, &[]),
+            ],
+            indexes: vec![1, 2, 3, 4, 5],
+            errors: Vec::new(),
+        };
+
+        let lines = renderer.board_lines(&BoardColumn::by_status(&todos), 80);
+        let row = |cells: [&str; 3]| format!("{:<24}   {:<24}   {}", cells[0], cells[1], cells[2]);
+        let rule = "─".repeat(24);
+        assert_eq!(
+            lines,
+            vec![
+                row(["Pending (2)", "In Progress (1)", "Done (1)"]),
+                format!("{rule}   {rule}   {rule}"),
+                row([" 1. Buy milk @home 20/08", " 2. Write spec @work @h…", " 3. Ship @work"]),
+                " 4. Call mom".to_string(),
+            ]
+        );
+
+        let columns = BoardColumn::by_tag(&todos);
+        let titles: Vec<_> = columns.iter().map(|column| column.title.as_str()).collect();
+        assert_eq!(titles, ["@home", "@work", "No tag"]);
+        let numbers: Vec<Vec<usize>> = columns
+            .iter()
+            .map(|column| column.todos.iter().map(|(index, _)| *index).collect())
+            .collect();
+        assert_eq!(numbers, [vec![1, 2], vec![2], vec![4]]);
+        // Columns that don't fit next to the others go below them.
+        let lines = renderer.board_lines(&columns, 40);
+        assert_eq!(lines[0], format!("{:<18}   {}", "@home (2)", "@work (1)"));
+        assert_eq!(lines[5], "No tag (1)");
+    }
+
+    #[test]
+    fn timeline_joins_the_entries_of_each_day() {
+        let renderer = Renderer::new(Some(RenderOptions {
+            date_format: "%a, %d %b".to_string(),
+            locale: None,
+            use_color: false,
+            style: Style::Long,
+            hyperlinks: false,
+            quiet: false,
+            images: false,
+            tags: TagPattern::default(),
+        }));
+        let entry = |date: &str, time: &str, title: &str, tags: &[&str]| JournalEntry {
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            time: chrono::NaiveTime::parse_from_str(time, "%H:%M").unwrap(),
+            title: title.to_string(),
+            body: String::new(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            links: Vec::new(),
+            path: PathBuf::new(),
+            word_count: 0,
+            char_count: 0,
+        };
+        let entries = [
+            entry("2025-08-14", "09:00", "Standup @work", &["@work"]),
+            entry("2025-08-14", "18:30", "Run", &[]),
+            entry("2025-08-15", "10:00", "Dentist", &[]),
+            entry("2025-08-18", "08:00", "Back @work @travel", &["@work", "@travel"]),
+        ];
+        assert_eq!(
+            renderer.timeline_lines(&entries),
+            vec![
+                "Thu, 14 Aug  * 09:00 Standup @work [@work]",
+                "             | 18:30 Run",
+                "             |",
+                "Fri, 15 Aug  * 10:00 Dentist",
+                "             :  2 days without entries",
+                "Mon, 18 Aug  * 08:00 Back @work @travel [@work - @travel]",
+            ]
+        );
+    }
+
+    #[test]
+    fn toc_nests_titles_under_their_year_month_and_day() {
+        let renderer = Renderer::new(Some(RenderOptions {
+            date_format: "%a, %d %b %Y".to_string(),
+            locale: None,
+            use_color: false,
+            style: Style::Toc,
+            hyperlinks: false,
+            quiet: false,
+            images: false,
+            tags: TagPattern::default(),
+        }));
+        let entry = |date: &str, time: &str, title: &str| JournalEntry {
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            time: chrono::NaiveTime::parse_from_str(time, "%H:%M").unwrap(),
+            title: title.to_string(),
+            body: "Not in the toc.".to_string(),
+            tags: Vec::new(),
+            links: Vec::new(),
+            path: PathBuf::new(),
+            word_count: 0,
+            char_count: 0,
+        };
+        let entries = [
+            entry("2024-12-31", "23:00", "New Year's Eve"),
+            entry("2025-08-15", "09:00", "Standup @work"),
+            entry("2025-08-15", "18:00", "Walk"),
+            entry("2025-09-01", "08:30", "Back to school"),
+        ];
+        assert_eq!(
+            renderer.toc_lines(&entries),
+            vec![
+                "2024",
+                "  December",
+                "    Tue, 31 Dec 2024",
+                "      23:00 New Year's Eve",
+                "2025",
+                "  August",
+                "    Fri, 15 Aug 2025",
+                "      09:00 Standup @work",
+                "      18:00 Walk",
+                "  September",
+                "    Mon, 01 Sep 2025",
+                "      08:30 Back to school",
+            ]
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use std::{
+    fs,
+    io::Write,
+    path::Path,
+    process::{Command, Output, Stdio},
+};
+
+/// Runs the `lgg` binary with a config of its own in `home`, whose journal is `home/journal`.
+fn lgg(home: &Path, args: &[&str]) -> Output {
+    lgg_command(home, args).output().unwrap()
+}
+
+/// Like [`lgg`], typing `input` on its stdin.
+fn lgg_with_input(home: &Path, args: &[&str], input: &str) -> Output {
+    let mut child = lgg_command(home, args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn lgg_command(home: &Path, args: &[&str]) -> Command {
+    let config = home.join(".config").join("lgg");
+    fs::create_dir_all(&config).unwrap();
+    let journal = home.join("journal");
+    fs::write(config.join("config.toml"), format!("journal_dir = {:?}\n", journal)).unwrap();
+    let mut command = Command::new(env!("CARGO_BIN_EXE_lgg"));
+    command
+        .args(["--color", "never"])
+        .args(args)
+        .current_dir(home)
+        .env("HOME", home)
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("XDG_DATA_HOME")
+        .env_remove("LGG_JOURNAL")
+        .env_remove("LGG_TODAY");
+    command
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn quiet_writes_without_printing_anything() {
+    let home = tempfile::tempdir().unwrap();
+    let output = lgg(home.path(), &["--quiet", "today: Quiet title. Quiet body"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+    assert!(output.stderr.is_empty());
+
+    let output = lgg(home.path(), &["today: Loud title"]);
+    assert!(stdout(&output).contains("Added new entry"));
+
+    let output = lgg(home.path(), &["--quiet", "--on", "today"]);
+    let read = stdout(&output);
+    assert!(read.contains("Quiet title") && read.contains("Loud title"));
+}
+
+#[test]
+fn quick_entries_are_written_now_with_the_whole_text_as_title() {
+    let home = tempfile::tempdir().unwrap();
+    let text = "tomorrow: buy milk @errands";
+    let output = lgg(home.path(), &["--today", "2025-08-14", "--output", "json", "quick", text]);
+    assert!(output.status.success());
+    let written = stdout(&output);
+    assert!(written.contains(r#""date":"2025-08-14""#));
+    assert!(written.contains(r#""title":"tomorrow: buy milk @errands","tags":["@errands"]"#));
+    let day = fs::read_to_string(home.path().join("journal/2025/08/2025-08-14.md")).unwrap();
+    assert!(day.contains(" - tomorrow: buy milk @errands"));
+    assert!(!home.path().join("journal/2025/08/2025-08-15.md").exists());
+}
+
+#[test]
+fn init_writes_the_answers_to_the_config_and_creates_the_journal() {
+    let home = tempfile::tempdir().unwrap();
+    // The journal, the editor, the date order, a wrong time and then the right one.
+    let answers = "~/notes\nnano -w\nmdy\n25:00\n07:30\n";
+    let output = lgg_with_input(home.path(), &["init"], answers);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("Try again."));
+    let notes = home.path().join("notes");
+    assert!(notes.is_dir());
+    let config = fs::read_to_string(home.path().join(".config/lgg/config.toml")).unwrap();
+    assert!(config.contains(&format!("journal_dir = {:?}", notes.display().to_string())));
+    assert!(config.contains(r#"editor = "nano -w""#));
+    assert!(config.contains(r#"date_order = "mdy""#));
+    assert!(config.contains(r#"default_time = "07:30""#));
+}
@@ -0,0 +1,4 @@
+//! Offline text analysis of journal entries. Enabled with the `analysis` feature.
+pub mod sentiment;
+
+pub use sentiment::{sentiment_score, weekly_sentiment, WeekSentiment};
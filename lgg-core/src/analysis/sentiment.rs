@@ -0,0 +1,146 @@
+//! Lexicon based sentiment: every known positive word adds one, every negative word subtracts one,
+//! and a negation right before a word (`not good`) flips it.
+use crate::JournalEntry;
+use chrono::{Datelike, Days, NaiveDate};
+use std::collections::BTreeMap;
+
+const POSITIVE: &[&str] = &[
+    "amazing", "awesome", "beautiful", "better", "best", "calm", "celebrate", "cheerful",
+    "comfortable", "confident", "delighted", "easy", "energetic", "enjoy", "enjoyed", "excited",
+    "fantastic", "fine", "fun", "glad", "good", "grateful", "great", "happy", "healthy", "hope",
+    "inspired", "joy", "kind", "laugh", "laughed", "love", "loved", "lovely", "lucky", "nice",
+    "peaceful", "productive", "proud", "relaxed", "relieved", "rested", "success", "thankful",
+    "win", "wonderful",
+];
+
+const NEGATIVE: &[&str] = &[
+    "afraid", "angry", "annoyed", "anxious", "awful", "bad", "bored", "broke", "confused", "cried",
+    "cry", "depressed", "difficult", "disappointed", "exhausted", "fail", "failed", "fear",
+    "frustrated", "guilty", "hard", "hate", "hated", "hurt", "ill", "lonely", "lost", "mad",
+    "miserable", "nervous", "pain", "sad", "scared", "sick", "stress", "stressed", "terrible",
+    "tired", "upset", "worried", "worse", "worst", "wrong",
+];
+
+const NEGATIONS: &[&str] = &["not", "no", "never", "don't", "didn't", "isn't", "wasn't"];
+
+/// Sentiment of a text, from -1.0 (only negative words) to 1.0 (only positive words).
+/// Texts without any known word are neutral (0.0).
+pub fn sentiment_score(text: &str) -> f32 {
+    let mut score = 0i32;
+    let mut matches = 0i32;
+    let mut negated = false;
+    for raw in text.split_whitespace() {
+        let word = raw
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase();
+        let polarity = if POSITIVE.contains(&word.as_str()) {
+            1
+        } else if NEGATIVE.contains(&word.as_str()) {
+            -1
+        } else {
+            0
+        };
+        if polarity != 0 {
+            score += if negated { -polarity } else { polarity };
+            matches += 1;
+        }
+        negated = NEGATIONS.contains(&word.as_str());
+    }
+
+    if matches == 0 {
+        0.0
+    } else {
+        score as f32 / matches as f32
+    }
+}
+
+/// Average sentiment of the entries written in a week.
+#[derive(Debug, PartialEq)]
+pub struct WeekSentiment {
+    /// Monday of the week.
+    pub week: NaiveDate,
+    /// `None` when there are no entries that week.
+    pub score: Option<f32>,
+    pub entries: usize,
+}
+
+/// Sentiment per week, from the week of the oldest entry to the week of the newest one.
+pub fn weekly_sentiment(entries: &[JournalEntry]) -> Vec<WeekSentiment> {
+    let mut weeks: BTreeMap<NaiveDate, (f32, usize)> = BTreeMap::new();
+    for entry in entries {
+        let week = monday(entry.date);
+        let score = sentiment_score(&format!("{}\n{}", entry.title, entry.body));
+        let (total, count) = weeks.entry(week).or_default();
+        *total += score;
+        *count += 1;
+    }
+
+    let (Some(first), Some(last)) = (weeks.keys().next(), weeks.keys().next_back()) else {
+        return Vec::new();
+    };
+    let mut trend = Vec::new();
+    let mut week = *first;
+    while week <= *last {
+        let (total, count) = weeks.get(&week).copied().unwrap_or_default();
+        trend.push(WeekSentiment {
+            week,
+            score: (count > 0).then(|| total / count as f32),
+            entries: count,
+        });
+        week = week + Days::new(7);
+    }
+    trend
+}
+
+fn monday(date: NaiveDate) -> NaiveDate {
+    date - Days::new(date.weekday().num_days_from_monday() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::text_counts;
+    use chrono::NaiveTime;
+    use std::path::PathBuf;
+
+    fn entry(d: u32, body: &str) -> JournalEntry {
+        let (word_count, char_count) = text_counts("Day", body);
+        JournalEntry {
+            date: NaiveDate::from_ymd_opt(2025, 8, d).unwrap(),
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            title: "Day".to_string(),
+            body: body.to_string(),
+            tags: Vec::new(),
+            links: Vec::new(),
+            path: PathBuf::new(),
+            word_count,
+            char_count,
+        }
+    }
+
+    #[test]
+    fn scores_known_words_and_negations() {
+        assert_eq!(sentiment_score("A great, happy day."), 1.0);
+        assert_eq!(sentiment_score("Tired and sad."), -1.0);
+        assert_eq!(sentiment_score("Not bad. Good lunch."), 1.0);
+        assert_eq!(sentiment_score("Happy but tired"), 0.0);
+        assert_eq!(sentiment_score("Went to the office."), 0.0);
+    }
+
+    #[test]
+    fn averages_weeks_and_keeps_empty_ones() {
+        let entries = vec![
+            entry(4, "Great start."),
+            entry(6, "Stressed."),
+            entry(20, "Happy."),
+        ];
+        let trend = weekly_sentiment(&entries);
+
+        assert_eq!(trend.len(), 3);
+        assert_eq!(trend[0].week, NaiveDate::from_ymd_opt(2025, 8, 4).unwrap());
+        assert_eq!(trend[0].score, Some(0.0));
+        assert_eq!(trend[0].entries, 2);
+        assert_eq!(trend[1].score, None);
+        assert_eq!(trend[2].score, Some(1.0));
+    }
+}
@@ -0,0 +1,134 @@
+//! Days to remember every year, from the `[anniversaries]` of the config.
+use crate::utils::date_utils::DateFilter;
+use chrono::{format, Datelike, NaiveDate};
+use regex::Regex;
+
+/// A day of the year to remember (`"03/09/1990" = "Mum's birthday"`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anniversary {
+    pub month: u32,
+    pub day: u32,
+    /// Year of the first one, when it's known, to count the years.
+    pub year: Option<i32>,
+    pub name: String,
+}
+
+impl Anniversary {
+    /// Reads a key of `[anniversaries]` with `formats`, the `input_date_formats`: a full date
+    /// (`03/09/1990`), or the day and month of one of them without the year (`14/02`).
+    pub fn parse(date: &str, name: &str, formats: &[&str]) -> Option<Self> {
+        let date = date.trim();
+        if let Some(full) = formats
+            .iter()
+            .find_map(|fmt| NaiveDate::parse_from_str(date, fmt).ok())
+        {
+            return Some(Self {
+                month: full.month(),
+                day: full.day(),
+                year: Some(full.year()),
+                name: name.to_string(),
+            });
+        }
+        formats.iter().find_map(|fmt| {
+            let mut parsed = format::Parsed::new();
+            let fmt = without_year(fmt);
+            format::parse(&mut parsed, date, format::StrftimeItems::new(&fmt)).ok()?;
+            let (month, day) = (parsed.month()?, parsed.day()?);
+            // 2000 was a leap year, 29/02 is a day of the year.
+            NaiveDate::from_ymd_opt(2000, month, day)?;
+            Some(Self {
+                month,
+                day,
+                year: None,
+                name: name.to_string(),
+            })
+        })
+    }
+
+    /// Whether it's on `date`. In years without 29 February, those are on the 28th.
+    pub fn falls_on(&self, date: NaiveDate) -> bool {
+        if (self.month, self.day) == (date.month(), date.day()) {
+            return true;
+        }
+        (self.month, self.day) == (2, 29)
+            && (date.month(), date.day()) == (2, 28)
+            && NaiveDate::from_ymd_opt(date.year(), 2, 29).is_none()
+    }
+
+    /// Years since the first one, on `date`: the age of a birthday. `None` without a year.
+    pub fn years_on(&self, date: NaiveDate) -> Option<i32> {
+        let years = date.year() - self.year?;
+        let before = (date.month(), date.day()) < (self.month, self.day) && !self.falls_on(date);
+        Some(if before { years - 1 } else { years })
+    }
+}
+
+/// The anniversaries on the days of `dates`, in order, with their day.
+pub fn anniversaries_in(
+    anniversaries: &[Anniversary],
+    dates: DateFilter,
+) -> Vec<(NaiveDate, &Anniversary)> {
+    let (start, end) = match dates {
+        DateFilter::Single(date) => (date, date),
+        DateFilter::Range(start, end) => (start.min(end), start.max(end)),
+    };
+    start
+        .iter_days()
+        .take_while(|date| *date <= end)
+        .flat_map(|date| {
+            anniversaries
+                .iter()
+                .filter(move |anniversary| anniversary.falls_on(date))
+                .map(move |anniversary| (date, anniversary))
+        })
+        .collect()
+}
+
+/// `format` without its year and the separator next to it: `%d/%m/%Y` is `%d/%m`.
+fn without_year(format: &str) -> String {
+    let re = Regex::new(r"%[Yy][-/. ]|[-/. ]%[Yy]").unwrap();
+    re.replace(format, "").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn reads_dates_with_and_without_year() {
+        let formats = ["%d/%m/%Y", "%Y-%m-%d"];
+        let birthday = Anniversary::parse("03/09/1990", "Mum's birthday", &formats).unwrap();
+        assert_eq!((birthday.month, birthday.day, birthday.year), (9, 3, Some(1990)));
+        let valentine = Anniversary::parse("14/02", "Anniversary", &formats).unwrap();
+        assert_eq!((valentine.month, valentine.day, valentine.year), (2, 14, None));
+        let iso = Anniversary::parse("07-21", "Wedding", &formats).unwrap();
+        assert_eq!((iso.month, iso.day), (7, 21));
+        assert!(Anniversary::parse("31/02", "Never", &formats).is_none());
+        assert!(Anniversary::parse("someday", "Never", &formats).is_none());
+
+        let us = Anniversary::parse("02/14", "Anniversary", &["%m/%d/%Y"]).unwrap();
+        assert_eq!((us.month, us.day), (2, 14));
+    }
+
+    #[test]
+    fn counts_years_and_finds_them_in_dates() {
+        let formats = ["%d/%m/%Y"];
+        let birthday = Anniversary::parse("03/09/1990", "Mum's birthday", &formats).unwrap();
+        let leap = Anniversary::parse("29/02/2000", "Leap", &formats).unwrap();
+        assert_eq!(birthday.years_on(date(2025, 9, 3)), Some(35));
+        assert_eq!(birthday.years_on(date(2025, 9, 2)), Some(34));
+        assert!(leap.falls_on(date(2025, 2, 28)));
+        assert!(!leap.falls_on(date(2024, 2, 28)));
+        assert_eq!(leap.years_on(date(2025, 2, 28)), Some(25));
+
+        let all = [birthday, leap];
+        let found = anniversaries_in(&all, DateFilter::Range(date(2025, 9, 1), date(2025, 9, 7)));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, date(2025, 9, 3));
+        assert!(anniversaries_in(&all, DateFilter::Single(date(2025, 9, 4))).is_empty());
+    }
+}
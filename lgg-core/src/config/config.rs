@@ -1,24 +1,383 @@
+use super::unknown_keys;
+use crate::anniversaries::Anniversary;
+use crate::journal::auto_tags::{self, AutoTagRule};
+use crate::journal::device;
+use crate::journal::week_plan::Commitment;
+use crate::journals::{self, JournalConfig};
 use crate::keywords::Keywords;
-use anyhow::{Context, Result};
-use chrono::{Local, NaiveDate, NaiveTime};
+use crate::storage::{RemoteConfig, REMOTE_PASSWORD_ENV};
+use crate::summarize;
+use crate::sync::SyncConfig;
+use crate::tags::{TagAliases, TagPattern};
+use crate::todos::reminders;
+use crate::utils::parse_input::parse_weekdays;
+use crate::utils::path_utils::windows_reserved_name;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{Local, Locale, NaiveDate, NaiveTime, TimeDelta, Timelike, Weekday};
 use directories::BaseDirs;
 use serde::Deserialize;
-use std::{collections::HashMap, fs, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    env, fs,
+    hash::Hash,
+    path::{Path, PathBuf},
+};
+use strum_macros::AsRefStr;
+use toml_edit::{Array, Value};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 struct ConfigFile {
     journal_dir: Option<PathBuf>,
     todo_list_dir: Option<PathBuf>,
     editor: Option<String>,
     default_time: Option<String>,
     journal_date_format: Option<String>,
-    todo_datetime_format: Option<String>,
+    todo_datetime_format: Option<Formats>,
     input_date_formats: Option<Vec<String>>,
+    date_order: Option<DateOrder>,
+    obsidian: Option<bool>,
+    merge_within_minutes: Option<u32>,
+    default_action: Option<DefaultAction>,
+    stopwords: Option<Vec<String>>,
+    parse_mode: Option<ParseMode>,
+    time_precision: Option<TimePrecision>,
+    locale: Option<String>,
+    range_order: Option<RangeOrder>,
+    allow_future: Option<AllowFuture>,
+    editor_template: Option<Vec<TemplatePart>>,
+    temp_dir: Option<PathBuf>,
+    state_dir: Option<PathBuf>,
+    stale_days: Option<u32>,
+    stale_threshold: Option<u32>,
+    hyperlinks: Option<Hyperlinks>,
+    quiet_days: Option<Vec<String>>,
+    device_id: Option<String>,
+    summarizer_cmd: Option<String>,
+    transcriber_cmd: Option<String>,
+    fetch_titles: Option<bool>,
+    index_files: Option<bool>,
+    default_tags: Option<Vec<String>>,
+    ignored_tags: Option<Vec<String>>,
+    tag_pattern: Option<String>,
     /// Optional table:
     /// [synonyms]
     /// ytd = "yesterday"
     /// ayer = "yesterday"
-    synonyms: Option<HashMap<String, String>>,
+    /// "day before yesterday" = "yesterday - 1"
+    /// anteayer = { offset = -2 }
+    synonyms: Option<HashMap<String, Synonym>>,
+    /// Optional table of days to remember, as the dates you type, with or without the year:
+    /// [anniversaries]
+    /// "14/02" = "Anniversary"
+    /// "03/09/1990" = "Mum's birthday"
+    anniversaries: Option<HashMap<String, String>>,
+    /// Optional table of shorthand commands, expanded to their arguments:
+    /// [aliases]
+    /// standup = "--on today --tags @work --style short"
+    aliases: Option<HashMap<String, String>>,
+    /// Optional table of things done every week, with their weekdays:
+    /// [commitments]
+    /// "Team standup" = "mon,tue,wed,thu,fri"
+    commitments: Option<HashMap<String, String>>,
+    /// Optional table of tags added to the entries matching a regex:
+    /// [auto_tags]
+    /// "standup|retro" = "@meetings"
+    auto_tags: Option<HashMap<String, String>>,
+    /// Optional table of tags read as another one:
+    /// [tag_aliases]
+    /// "@dogs" = "@dog"
+    tag_aliases: Option<HashMap<String, String>>,
+    /// Optional tables of other journals, used with `--journal`:
+    /// [journals.work]
+    /// journal_dir = "/home/me/work/journal"
+    journals: Option<HashMap<String, JournalConfig>>,
+    /// Optional table of the server the day files are kept on:
+    /// [remote]
+    /// kind = "webdav"
+    /// url = "https://cloud.example.com/remote.php/dav/files/me/journal"
+    /// user = "me"
+    remote: Option<RemoteConfig>,
+    /// Optional table of where `lgg sync` syncs the journal with:
+    /// [sync]
+    /// kind = "git"
+    sync: Option<SyncConfig>,
+    /// Optional table of the todos:
+    /// [todo.reminders]
+    /// "@bills" = "3 days before"
+    todo: Option<TodoTable>,
+}
+
+/// The `[todo]` table of the config.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TodoTable {
+    /// Tags of todos, with how long before their due date `lgg remind` reminds of them.
+    reminders: Option<HashMap<String, String>>,
+}
+
+/// `todo_datetime_format`: one format, or a list of them.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum Formats {
+    One(String),
+    /// The first one is written, all of them are read.
+    Many(Vec<String>),
+}
+
+/// What a synonym stands for: a keyword, maybe some days from it (`"yesterday - 1"`), or
+/// days from `keyword` as a table (`{ offset = -2 }`, from `today` if there is no `keyword`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum Synonym {
+    Keyword(String),
+    Offset {
+        keyword: Option<String>,
+        offset: i64,
+    },
+}
+
+impl Synonym {
+    /// As a target of [`Keywords::extend`] (`today -2`).
+    fn target(&self) -> String {
+        match self {
+            Synonym::Keyword(keyword) => keyword.clone(),
+            Synonym::Offset { keyword, offset } => {
+                format!("{} {offset:+}", keyword.as_deref().unwrap_or("today"))
+            }
+        }
+    }
+}
+
+/// Name of the config of a project, looked for from the current directory up.
+const PROJECT_FILE: &str = ".lgg.toml";
+
+impl ConfigFile {
+    /// `self` as the config of the project file at `path`. A `.lgg.toml` comes with any
+    /// repository cloned, so the options that run commands or send the journal somewhere are
+    /// left out and added to `warnings`. Its directories are relative to it.
+    fn for_project(mut self, path: &Path, warnings: &mut Vec<String>) -> ConfigFile {
+        let ignored = [
+            ("editor", self.editor.take().is_some()),
+            ("summarizer_cmd", self.summarizer_cmd.take().is_some()),
+            ("transcriber_cmd", self.transcriber_cmd.take().is_some()),
+            ("[sync]", self.sync.take().is_some()),
+            ("[remote]", self.remote.take().is_some()),
+        ];
+        for (key, _) in ignored.iter().filter(|(_, set)| *set) {
+            warnings.push(format!(
+                "{}: Ignored `{key}`, only the global config can set it.",
+                path.display()
+            ));
+        }
+        if let Some(root) = path.parent() {
+            let relative = |dir: Option<PathBuf>| dir.map(|dir| root.join(dir));
+            self.journal_dir = relative(self.journal_dir);
+            self.todo_list_dir = relative(self.todo_list_dir);
+            self.state_dir = relative(self.state_dir);
+            self.temp_dir = relative(self.temp_dir);
+        }
+        self
+    }
+
+    /// `self` with the options set in `other` replaced. The synonyms, anniversaries, aliases,
+    /// commitments, auto tags, tag aliases, journals and todo reminders of both are kept.
+    fn merge(self, other: ConfigFile) -> ConfigFile {
+        let todo = match (self.todo, other.todo) {
+            (Some(todo), Some(other)) => Some(TodoTable {
+                reminders: merge_map(todo.reminders, other.reminders),
+            }),
+            (todo, other) => other.or(todo),
+        };
+        ConfigFile {
+            journal_dir: other.journal_dir.or(self.journal_dir),
+            todo_list_dir: other.todo_list_dir.or(self.todo_list_dir),
+            editor: other.editor.or(self.editor),
+            default_time: other.default_time.or(self.default_time),
+            journal_date_format: other.journal_date_format.or(self.journal_date_format),
+            todo_datetime_format: other.todo_datetime_format.or(self.todo_datetime_format),
+            input_date_formats: other.input_date_formats.or(self.input_date_formats),
+            date_order: other.date_order.or(self.date_order),
+            obsidian: other.obsidian.or(self.obsidian),
+            merge_within_minutes: other.merge_within_minutes.or(self.merge_within_minutes),
+            default_action: other.default_action.or(self.default_action),
+            stopwords: other.stopwords.or(self.stopwords),
+            parse_mode: other.parse_mode.or(self.parse_mode),
+            time_precision: other.time_precision.or(self.time_precision),
+            locale: other.locale.or(self.locale),
+            range_order: other.range_order.or(self.range_order),
+            allow_future: other.allow_future.or(self.allow_future),
+            editor_template: other.editor_template.or(self.editor_template),
+            temp_dir: other.temp_dir.or(self.temp_dir),
+            state_dir: other.state_dir.or(self.state_dir),
+            stale_days: other.stale_days.or(self.stale_days),
+            stale_threshold: other.stale_threshold.or(self.stale_threshold),
+            hyperlinks: other.hyperlinks.or(self.hyperlinks),
+            quiet_days: other.quiet_days.or(self.quiet_days),
+            device_id: other.device_id.or(self.device_id),
+            summarizer_cmd: other.summarizer_cmd.or(self.summarizer_cmd),
+            transcriber_cmd: other.transcriber_cmd.or(self.transcriber_cmd),
+            fetch_titles: other.fetch_titles.or(self.fetch_titles),
+            index_files: other.index_files.or(self.index_files),
+            default_tags: other.default_tags.or(self.default_tags),
+            ignored_tags: other.ignored_tags.or(self.ignored_tags),
+            tag_pattern: other.tag_pattern.or(self.tag_pattern),
+            remote: other.remote.or(self.remote),
+            sync: other.sync.or(self.sync),
+            todo,
+            synonyms: merge_map(self.synonyms, other.synonyms),
+            anniversaries: merge_map(self.anniversaries, other.anniversaries),
+            aliases: merge_map(self.aliases, other.aliases),
+            commitments: merge_map(self.commitments, other.commitments),
+            auto_tags: merge_map(self.auto_tags, other.auto_tags),
+            tag_aliases: merge_map(self.tag_aliases, other.tag_aliases),
+            journals: merge_map(self.journals, other.journals),
+        }
+    }
+}
+
+/// The entries of `base` and `over`, the ones of `over` winning when both have the same key.
+fn merge_map<K: Eq + Hash, V>(
+    base: Option<HashMap<K, V>>,
+    over: Option<HashMap<K, V>>,
+) -> Option<HashMap<K, V>> {
+    match (base, over) {
+        (Some(mut base), Some(over)) => {
+            base.extend(over);
+            Some(base)
+        }
+        (base, over) => over.or(base),
+    }
+}
+
+/// What `lgg` does when it runs without arguments.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, AsRefStr)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum DefaultAction {
+    /// Opens the editor to write a new entry.
+    #[default]
+    Editor,
+    /// Prints today's entries and the pending todos due today.
+    Today,
+    /// Prints today's entries and the pending todos overdue or due in the next week.
+    Agenda,
+}
+
+/// Order of the day, month and year in the dates you type. Gives the default `input_date_formats`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, AsRefStr)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum DateOrder {
+    /// `14/08/2025`
+    #[default]
+    Dmy,
+    /// `08/14/2025`
+    Mdy,
+    /// `2025/08/14`
+    Ymd,
+}
+
+impl DateOrder {
+    /// Formats tried when `input_date_formats` is not set. Two digit years go first, otherwise
+    /// `%Y` would read `14/08/25` as the year 25. ISO dates (`2025-08-14`) are always accepted.
+    pub fn input_date_formats(self) -> &'static [&'static str] {
+        match self {
+            DateOrder::Dmy => &["%d/%m/%y", "%d/%m/%Y", "%d-%m-%Y", "%d.%m.%Y", "%Y-%m-%d"],
+            DateOrder::Mdy => &["%m/%d/%y", "%m/%d/%Y", "%m-%d-%Y", "%Y-%m-%d"],
+            DateOrder::Ymd => &["%Y-%m-%d", "%Y/%m/%d", "%Y.%m.%d"],
+        }
+    }
+}
+
+/// How day files that don't follow the format are read.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, AsRefStr)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ParseMode {
+    /// Every deviation from the format is an error, and malformed entries are skipped. A file
+    /// without a valid date header is still read with the date of its name (`YYYY-MM-DD.md`).
+    #[default]
+    Strict,
+    /// Recovers what it can without reporting it: a file without a valid date header takes the
+    /// date of its name, and an entry heading without time gets the `default_time`.
+    Lenient,
+}
+
+/// What to do with a date range whose end is before its start (`--from today --to 'last week'`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, AsRefStr)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum RangeOrder {
+    /// The range is invalid and nothing is found.
+    Strict,
+    /// The start and end are swapped.
+    #[default]
+    Swap,
+}
+
+/// What to do with a new entry dated after today.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, AsRefStr)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum AllowFuture {
+    /// The entry is written.
+    Allow,
+    /// The entry is written, with a notice.
+    #[default]
+    Warn,
+    /// The entry is not written.
+    Deny,
+}
+
+/// Whether the files of printed entries and todos are links the terminal can open.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, AsRefStr)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Hyperlinks {
+    /// Links when the output is colored and the terminal is known to support them.
+    #[default]
+    Auto,
+    /// Links whenever the output is colored.
+    Always,
+    /// No links.
+    Never,
+}
+
+/// Parts of the buffer `lgg` opens in the editor for a new entry.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, AsRefStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TemplatePart {
+    /// A comment on how to write the entry.
+    Help,
+    /// The date and time of the entry, as a prefix to change or remove.
+    Date,
+    /// A comment with the title of the previous entry.
+    LastEntry,
+}
+
+/// Precision of the entry times written to day files. Times with seconds written by other tools
+/// are kept as they are.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, AsRefStr)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum TimePrecision {
+    /// `HH:MM`
+    #[default]
+    Minutes,
+    /// `HH:MM:SS`
+    Seconds,
+}
+
+impl TimePrecision {
+    /// `time` without the parts this precision doesn't write.
+    pub fn truncate(self, time: NaiveTime) -> NaiveTime {
+        let seconds = match self {
+            TimePrecision::Minutes => 0,
+            TimePrecision::Seconds => time.second(),
+        };
+        NaiveTime::from_hms_opt(time.hour(), time.minute(), seconds).unwrap_or(time)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -35,40 +394,148 @@ pub struct Config {
     pub journal_date_format: String,
     /// Format for date time in due_date & done_date of todos
     pub todo_datetime_format: String,
-    /// A slice of `chrono` format strings to try when reading entries.
+    /// Formats tried, in order, on the dates of the todo file: `todo_datetime_format` first, then
+    /// the other ones of the config. ISO dates (`2025-09-01 09:00`) are always read too.
+    pub todo_read_formats: Vec<String>,
+    /// `chrono` format strings to try, in order, on the dates you type: inline entries and todos,
+    /// `--on`/`--from`/`--to` and `--edit`. Defaults to the ones of the `date_order`.
     pub input_date_formats: Vec<String>,
     /// The date to use as "today" for relative keywords.
     pub reference_date: NaiveDate,
+    /// Treat `journal_dir` as an Obsidian vault: entries go to its daily notes and tags are written as wiki-links.
+    pub obsidian: bool,
+    /// Inline entries without date or time written less than these minutes after the previous one
+    /// are appended to it instead of creating a new entry. `None` disables merging.
+    pub merge_within_minutes: Option<u32>,
+    /// What to do when `lgg` runs without arguments.
+    pub default_action: DefaultAction,
+    /// Words left out of `lgg stats --words`, besides the common English ones.
+    pub stopwords: Vec<String>,
+    /// How to read day files that don't follow the format.
+    pub parse_mode: ParseMode,
+    /// Whether new entries are written with seconds.
+    pub time_precision: TimePrecision,
+    /// Language of the weekday and month names in day headers and printed dates (e.g. `es_ES`).
+    /// `None` is English.
+    pub locale: Option<Locale>,
+    /// What to do with date ranges whose end is before their start.
+    pub range_order: RangeOrder,
+    /// What to do with new entries dated after today.
+    pub allow_future: AllowFuture,
+    /// What the editor buffer of a new entry starts with. Comment lines are not saved.
+    pub editor_template: Vec<TemplatePart>,
+    /// Where the editor buffers of new entries are made. `None` is $XDG_RUNTIME_DIR, or the
+    /// system temp dir.
+    pub temp_dir: Option<PathBuf>,
+    /// Where lgg keeps its own files, like the search index (see [`crate::AppDirs`]). `None` is
+    /// $XDG_STATE_HOME/lgg, a relative path is inside the journal.
+    pub state_dir: Option<PathBuf>,
+    /// Open todos without a due date added more than these days ago are stale (`todo --stale`).
+    pub stale_days: u32,
+    /// The agenda reminds you of the stale todos when there are more than these.
+    pub stale_threshold: u32,
+    /// Whether printed entries and todos link to their file.
+    pub hyperlinks: Hyperlinks,
+    /// Days of the week `lgg remind` doesn't remind you to write (e.g. the weekend).
+    pub quiet_days: Vec<Weekday>,
+    /// A short name of this computer, stamped on the entries written here so `lgg sync` tells
+    /// apart the ones written at the same minute on different computers. `None` stamps nothing.
+    pub device_id: Option<String>,
+    /// The program `lgg summarize` pipes the entries of a day to, with its arguments (e.g.
+    /// `ollama run llama3 "Summarize this journal day"`). What it prints is the summary.
+    pub summarizer_cmd: Option<String>,
+    /// The program `lgg import --audio` runs for each voice note, with its arguments and then the
+    /// path of the audio file (e.g. `whisper-cli -nt -f`). What it prints is the entry.
+    pub transcriber_cmd: Option<String>,
+    /// Entries that are only a link get the title of its page, fetched with `curl`.
+    pub fetch_titles: bool,
+    /// Whether the months and years of the journal have an `index.md` with the titles of their
+    /// entries, written again with their day files (see [`crate::journal::index_files`]).
+    pub index_files: bool,
+    /// Tags added to every entry written to the journal, like `@personal`. The journals of
+    /// `[journals]` have their own.
+    pub default_tags: Vec<String>,
+    /// Tags left out of queries, tag listings and stats, lowercased: words that look like tags
+    /// but aren't, like `@the` in "meet me @the office".
+    pub ignored_tags: Vec<String>,
+    /// The regex of the tags (see [`crate::tags`]), like `[@#]\w+(?:[.-]\w+)*` to allow dots.
+    /// [`crate::tags::DEFAULT_TAG_PATTERN`] without one.
+    pub tag_pattern: TagPattern,
+    /// The keywords of dates and times, with the `[synonyms]` of this config.
+    pub keywords: Keywords,
+    /// Days to remember every year, from `[anniversaries]`, in the order of the year.
+    pub anniversaries: Vec<Anniversary>,
+    /// Shorthand commands from `[aliases]`: `lgg standup` runs `lgg` with the arguments of
+    /// `standup`.
+    pub aliases: HashMap<String, String>,
+    /// Things done every week on some days, from `[commitments]`, by name. `lgg plan week` lists
+    /// them on their days.
+    pub commitments: Vec<Commitment>,
+    /// Rules of `[auto_tags]` tagging the new entries that match them, sorted by pattern.
+    pub auto_tags: Vec<AutoTagRule>,
+    /// Tags read as another one from `[tag_aliases]`, in queries and tag listings.
+    pub tag_aliases: TagAliases,
+    /// The other journals of `[journals]`, by name (see [`crate::journals`]).
+    pub journals: BTreeMap<String, JournalConfig>,
+    /// The WebDAV server or S3 bucket of `[remote]`, where the day files are kept instead of
+    /// `journal_dir`.
+    pub remote: Option<RemoteConfig>,
+    /// Where `lgg sync` syncs the journal with, from `[sync]`.
+    pub sync: Option<SyncConfig>,
+    /// Tags of todos `lgg remind` reminds of ahead of their due date, with how long before, from
+    /// `[todo.reminders]`.
+    pub todo_reminders: HashMap<String, TimeDelta>,
+    /// Problems of the config file that didn't stop it from loading, like unknown keys.
+    pub warnings: Vec<String>,
+    /// The `.lgg.toml` of the project the current directory is in. Its options win over the
+    /// ones of `config.toml`.
+    pub project_file: Option<PathBuf>,
 }
 
 impl Config {
     /// Public entrypoint: load config from disk (first XDG path, then native), apply defaults,
-    /// and extend the global Keywords registry with user-defined synonyms if present.
+    /// and build the keywords with the user-defined synonyms if present.
     pub fn load() -> Result<Self> {
-        let file_config = Self::read_file_config().unwrap_or_else(|_| ConfigFile {
-            journal_dir: None,
-            todo_list_dir: None,
-            editor: None,
-            default_time: None,
-            synonyms: None,
-            journal_date_format: None,
-            todo_datetime_format: None,
-            input_date_formats: None,
-        });
+        let (mut file_config, mut warnings) = Self::read_file_config()?;
+        let project_file = env::current_dir()
+            .ok()
+            .and_then(|dir| Self::find_project_file(&dir));
+        if let Some(path) = &project_file {
+            let (project, project_warnings) = Self::read_config_at(path)?;
+            warnings.extend(project_warnings);
+            file_config = file_config.merge(project.for_project(path, &mut warnings));
+        }
+        Ok(Self::from_file_config(file_config, warnings, project_file))
+    }
+
+    /// The config with every option at its default, as without a config file. For the commands
+    /// that fix a config file that doesn't load, like `lgg config edit`.
+    pub fn defaults() -> Self {
+        Self::from_file_config(ConfigFile::default(), Vec::new(), None)
+    }
 
+    /// The config of the options of `file_config`, with the defaults of the ones not set.
+    fn from_file_config(
+        file_config: ConfigFile,
+        mut warnings: Vec<String>,
+        project_file: Option<PathBuf>,
+    ) -> Self {
         let default_time = file_config
             .default_time
             .as_deref()
-            .and_then(|time| Self::parse_default_time(&time))
+            .and_then(Self::parse_default_time)
             .unwrap_or_else(Self::default_fallback_time);
 
         let date_format = file_config
             .journal_date_format
             .unwrap_or_else(|| "%A, %d %b %Y".to_string());
 
-        let todo_datetime_format = file_config
-            .todo_datetime_format
-            .unwrap_or_else(|| "%d/%m/%Y %H:%M".to_string());
+        let todo_read_formats = match file_config.todo_datetime_format {
+            Some(Formats::One(format)) => vec![format],
+            Some(Formats::Many(formats)) if !formats.is_empty() => formats,
+            _ => vec!["%d/%m/%Y %H:%M".to_string()],
+        };
+        let todo_datetime_format = todo_read_formats[0].clone();
 
         let journal_dir = file_config
             .journal_dir
@@ -80,21 +547,316 @@ impl Config {
 
         let input_date_formats = file_config
             .input_date_formats
-            .unwrap_or_else(|| ["%d/%m/%Y".to_string()].to_vec());
-
-        // Extend global keyword registry once at startup.
-        Self::load_synonyms(&file_config.synonyms);
+            .unwrap_or_else(|| {
+                let date_order = file_config.date_order.unwrap_or_default();
+                date_order
+                    .input_date_formats()
+                    .iter()
+                    .map(|f| f.to_string())
+                    .collect()
+            });
+        let anniversaries =
+            Self::load_anniversaries(&file_config.anniversaries, &input_date_formats, &mut warnings);
+        let keywords = Self::load_synonyms(&file_config.synonyms);
+        let commitments =
+            Self::load_commitments(&file_config.commitments, &keywords, &mut warnings);
+        let tag_pattern = Self::load_tag_pattern(&file_config.tag_pattern, &mut warnings);
+        let is_tag = |tag: &str| tag_pattern.is_tag(tag);
+        let auto_tags = Self::load_auto_tags(&file_config.auto_tags, is_tag, &mut warnings);
+        // Journals are often synced between computers, so this is checked on every platform.
+        for (key, dir) in [("journal_dir", &journal_dir), ("todo_list_dir", &todo_list_dir)] {
+            if let Some(name) = windows_reserved_name(dir) {
+                warnings.push(format!(
+                    "`{key}` has `{name}` in its path, a name Windows can't use for files."
+                ));
+            }
+        }
+        if let Some(RemoteConfig {
+            user: Some(user),
+            password_cmd: None,
+            ..
+        }) = &file_config.remote
+            && env::var_os(REMOTE_PASSWORD_ENV).is_none()
+        {
+            warnings.push(format!(
+                "Set the password of `{user}` for the [remote] in ${REMOTE_PASSWORD_ENV} or \
+                 `password_cmd`."
+            ));
+        }
 
-        Ok(Self {
+        Self {
             journal_dir,
             todo_list_dir,
             editor: file_config.editor,
             default_time,
             journal_date_format: date_format,
             todo_datetime_format,
+            todo_read_formats,
             input_date_formats,
             reference_date: Local::now().date_naive(),
-        })
+            obsidian: file_config.obsidian.unwrap_or(false),
+            merge_within_minutes: file_config.merge_within_minutes.filter(|m| *m > 0),
+            default_action: file_config.default_action.unwrap_or_default(),
+            stopwords: file_config.stopwords.unwrap_or_default(),
+            parse_mode: file_config.parse_mode.unwrap_or_default(),
+            time_precision: file_config.time_precision.unwrap_or_default(),
+            locale: file_config
+                .locale
+                .as_deref()
+                .and_then(|locale| Locale::try_from(locale).ok()),
+            range_order: file_config.range_order.unwrap_or_default(),
+            allow_future: file_config.allow_future.unwrap_or_default(),
+            editor_template: file_config
+                .editor_template
+                .unwrap_or_else(|| vec![TemplatePart::Help, TemplatePart::Date]),
+            temp_dir: file_config.temp_dir,
+            state_dir: file_config.state_dir,
+            stale_days: file_config.stale_days.unwrap_or(30),
+            stale_threshold: file_config.stale_threshold.unwrap_or(5),
+            hyperlinks: file_config.hyperlinks.unwrap_or_default(),
+            quiet_days: file_config
+                .quiet_days
+                .iter()
+                .flatten()
+                .filter_map(|day| day.parse().ok())
+                .collect(),
+            device_id: file_config.device_id.filter(|id| device::is_valid_id(id)),
+            summarizer_cmd: file_config
+                .summarizer_cmd
+                .filter(|command| summarize::split_command(command).is_some()),
+            transcriber_cmd: file_config
+                .transcriber_cmd
+                .filter(|command| summarize::split_command(command).is_some()),
+            fetch_titles: file_config.fetch_titles.unwrap_or(false),
+            index_files: file_config.index_files.unwrap_or(false),
+            default_tags: file_config
+                .default_tags
+                .into_iter()
+                .flatten()
+                .filter(|tag| is_tag(tag))
+                .collect(),
+            ignored_tags: file_config
+                .ignored_tags
+                .into_iter()
+                .flatten()
+                .filter(|tag| is_tag(tag))
+                .map(|tag| tag.to_lowercase())
+                .collect(),
+            tag_pattern: tag_pattern.clone(),
+            keywords,
+            anniversaries,
+            aliases: file_config.aliases.unwrap_or_default(),
+            commitments,
+            auto_tags,
+            tag_aliases: TagAliases::new(
+                file_config
+                    .tag_aliases
+                    .into_iter()
+                    .flatten()
+                    .filter(|(alias, tag)| is_tag(alias) && is_tag(tag)),
+            ),
+            journals: file_config
+                .journals
+                .into_iter()
+                .flatten()
+                .filter(|(name, _)| journals::is_valid_name(name))
+                .map(|(name, mut journal)| {
+                    journal.default_tags.retain(|tag| is_tag(tag));
+                    (name, journal)
+                })
+                .collect(),
+            remote: file_config.remote,
+            sync: file_config.sync,
+            todo_reminders: file_config
+                .todo
+                .and_then(|todo| todo.reminders)
+                .into_iter()
+                .flatten()
+                .filter(|(tag, _)| is_tag(tag))
+                .filter_map(|(tag, before)| {
+                    Some((tag.to_lowercase(), reminders::parse_before(&before)?))
+                })
+                .collect(),
+            warnings,
+            project_file,
+        }
+    }
+
+    /// Checks the content of a `config.toml`: valid TOML, with a known value for every option.
+    /// Returns the problems that don't stop it from loading, like unknown keys.
+    pub fn validate(content: &str) -> Result<Vec<String>> {
+        let file_config = Self::parse_file(content)?;
+        if let Some(time) = &file_config.default_time
+            && Self::parse_default_time(time).is_none()
+        {
+            bail!("Invalid `default_time` \"{time}\". Expected HH:MM, like \"21:00\".");
+        }
+        if let Some(locale) = &file_config.locale
+            && Locale::try_from(locale.as_str()).is_err()
+        {
+            bail!("Unknown `locale` \"{locale}\". Expected a name like \"es_ES\".");
+        }
+        for day in file_config.quiet_days.iter().flatten() {
+            if day.parse::<Weekday>().is_err() {
+                bail!("Unknown day \"{day}\" in `quiet_days`. Expected a weekday, like \"saturday\".");
+            }
+        }
+        if let Some(command) = &file_config.summarizer_cmd
+            && summarize::split_command(command).is_none()
+        {
+            bail!("Invalid `summarizer_cmd` \"{command}\". Expected a program and its arguments.");
+        }
+        if let Some(command) = &file_config.transcriber_cmd
+            && summarize::split_command(command).is_none()
+        {
+            bail!("Invalid `transcriber_cmd` \"{command}\". Expected a program and its arguments.");
+        }
+        if let Some(id) = &file_config.device_id
+            && !device::is_valid_id(id)
+        {
+            bail!("Invalid `device_id` \"{id}\". Use one word of letters, digits, `-` and `_`.");
+        }
+        for name in file_config.aliases.iter().flatten().map(|(name, _)| name) {
+            if name.is_empty() || name.starts_with('-') || name.contains(char::is_whitespace) {
+                bail!("Invalid alias \"{name}\" in [aliases]. Use one word not starting with `-`.");
+            }
+        }
+        let tag_pattern = match &file_config.tag_pattern {
+            Some(pattern) => TagPattern::new(pattern)
+                .map_err(|error| anyhow!("Invalid `tag_pattern` \"{pattern}\": {error}"))?,
+            None => TagPattern::default(),
+        };
+        let is_tag = |tag: &str| tag_pattern.is_tag(tag);
+        for tag in file_config.default_tags.iter().flatten() {
+            if !is_tag(tag) {
+                bail!("Invalid tag \"{tag}\" in `default_tags`. Expected one like \"@work\".");
+            }
+        }
+        for tag in file_config.ignored_tags.iter().flatten() {
+            if !is_tag(tag) {
+                bail!("Invalid tag \"{tag}\" in `ignored_tags`. Expected one like \"@the\".");
+            }
+        }
+        for (pattern, tag) in file_config.auto_tags.iter().flatten() {
+            if let Err(error) = auto_tags::parse_rule(pattern, tag) {
+                bail!("Invalid pattern \"{pattern}\" in [auto_tags]: {error}");
+            }
+            if !is_tag(tag) {
+                bail!(
+                    "Invalid tag \"{tag}\" of \"{pattern}\" in [auto_tags]. Expected one like \
+                     \"@meetings\"."
+                );
+            }
+        }
+        let tag_aliases = file_config.tag_aliases.iter().flatten();
+        for (alias, tag) in tag_aliases.clone() {
+            if !is_tag(alias) || !is_tag(tag) {
+                bail!(
+                    "Invalid alias \"{alias}\" = \"{tag}\" in [tag_aliases]. Expected two tags, like \
+                     \"@dogs\" = \"@dog\"."
+                );
+            }
+            if tag_aliases.clone().any(|(other, _)| other.eq_ignore_ascii_case(tag)) {
+                bail!("The tag \"{tag}\" of \"{alias}\" in [tag_aliases] is an alias itself.");
+            }
+        }
+        for (name, journal) in file_config.journals.iter().flatten() {
+            if !journals::is_valid_name(name) {
+                bail!("Invalid journal \"{name}\" in [journals]. `default` and `all` are taken.");
+            }
+            if let Some(tag) = journal.default_tags.iter().find(|tag| !is_tag(tag)) {
+                bail!(
+                    "Invalid tag \"{tag}\" in the `default_tags` of [journals.{name}]. Expected \
+                     one like \"@work\"."
+                );
+            }
+        }
+        let todo_reminders = file_config.todo.iter().filter_map(|todo| todo.reminders.as_ref());
+        for (tag, before) in todo_reminders.flatten() {
+            if !is_tag(tag) {
+                bail!("Invalid tag \"{tag}\" in [todo.reminders]. Expected one like \"@bills\".");
+            }
+            if reminders::parse_before(before).is_none() {
+                bail!(
+                    "Invalid reminder \"{before}\" of {tag} in [todo.reminders]. Expected a time \
+                     before the due date of up to ten years, like \"3 days before\"."
+                );
+            }
+        }
+        Ok(unknown_keys(content))
+    }
+
+    /// The config file in use, or where it's created if there is none yet.
+    pub fn file_path() -> Option<PathBuf> {
+        let paths = Self::config_file_paths();
+        paths.iter().find(|p| p.exists()).or(paths.first()).cloned()
+    }
+
+    /// Every option with its value in use, written as in `config.toml`. `None` if it's not set.
+    pub fn values(&self) -> Vec<(&'static str, Option<String>)> {
+        let text = |s: &str| Some(Value::from(s).to_string());
+        let list = |items: &[String]| {
+            let array: Array = items.iter().map(String::as_str).collect();
+            Some(Value::Array(array).to_string())
+        };
+        let quiet_days: Vec<String> = self
+            .quiet_days
+            .iter()
+            .map(|day| day.to_string().to_lowercase())
+            .collect();
+        let editor_template: Vec<String> = self
+            .editor_template
+            .iter()
+            .map(|part| part.as_ref().to_string())
+            .collect();
+        vec![
+            ("journal_dir", text(&self.journal_dir.to_string_lossy())),
+            ("todo_list_dir", text(&self.todo_list_dir.to_string_lossy())),
+            ("editor", self.editor.as_deref().and_then(text)),
+            ("default_time", text(&self.default_time.format("%H:%M").to_string())),
+            ("journal_date_format", text(&self.journal_date_format)),
+            (
+                "todo_datetime_format",
+                match self.todo_read_formats.as_slice() {
+                    [_, _, ..] => list(&self.todo_read_formats),
+                    _ => text(&self.todo_datetime_format),
+                },
+            ),
+            ("input_date_formats", list(&self.input_date_formats)),
+            ("obsidian", Some(self.obsidian.to_string())),
+            (
+                "merge_within_minutes",
+                self.merge_within_minutes.map(|m| m.to_string()),
+            ),
+            ("default_action", text(self.default_action.as_ref())),
+            ("stopwords", list(&self.stopwords)),
+            ("parse_mode", text(self.parse_mode.as_ref())),
+            ("time_precision", text(self.time_precision.as_ref())),
+            ("locale", self.locale.and_then(|l| text(&format!("{l:?}")))),
+            ("range_order", text(self.range_order.as_ref())),
+            ("allow_future", text(self.allow_future.as_ref())),
+            ("editor_template", list(&editor_template)),
+            (
+                "temp_dir",
+                self.temp_dir.as_ref().and_then(|dir| text(&dir.to_string_lossy())),
+            ),
+            (
+                "state_dir",
+                self.state_dir.as_ref().and_then(|dir| text(&dir.to_string_lossy())),
+            ),
+            ("stale_days", Some(self.stale_days.to_string())),
+            ("stale_threshold", Some(self.stale_threshold.to_string())),
+            ("hyperlinks", text(self.hyperlinks.as_ref())),
+            ("quiet_days", list(&quiet_days)),
+            ("device_id", self.device_id.as_deref().and_then(text)),
+            ("summarizer_cmd", self.summarizer_cmd.as_deref().and_then(text)),
+            ("transcriber_cmd", self.transcriber_cmd.as_deref().and_then(text)),
+            ("fetch_titles", Some(self.fetch_titles.to_string())),
+            ("index_files", Some(self.index_files.to_string())),
+            ("default_tags", list(&self.default_tags)),
+            ("ignored_tags", list(&self.ignored_tags)),
+            ("tag_pattern", text(self.tag_pattern.as_str())),
+        ]
     }
 
     /// Default fallback time when user didn’t set `default_time` in config.
@@ -148,26 +910,33 @@ impl Config {
         v
     }
 
+    /// The `.lgg.toml` of the project `dir` is in: the first one from `dir` up to the root.
+    pub fn find_project_file(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|dir| dir.join(PROJECT_FILE))
+            .find(|path| path.is_file())
+    }
+
     /// Read the first existing config file and parse it.
-    fn read_file_config() -> Result<ConfigFile> {
-        for path in Self::config_file_paths() {
-            if !path.exists() {
-                continue;
-            }
-            let s =
-                fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
-            return Self::parse_file(&s).with_context(|| format!("parsing {}", path.display()));
-        }
-        Ok(ConfigFile {
-            journal_dir: None,
-            todo_list_dir: None,
-            editor: None,
-            default_time: None,
-            synonyms: None,
-            journal_date_format: None,
-            todo_datetime_format: None,
-            input_date_formats: None,
-        })
+    fn read_file_config() -> Result<(ConfigFile, Vec<String>)> {
+        if let Some(path) = Self::config_file_paths().iter().find(|p| p.exists()) {
+            return Self::read_config_at(path);
+        }
+        log::info!("no config file found, using the defaults");
+        Ok((ConfigFile::default(), Vec::new()))
+    }
+
+    /// Reads and parses the config file at `path`. Invalid TOML and invalid values are errors,
+    /// unknown keys are returned as warnings.
+    fn read_config_at(path: &Path) -> Result<(ConfigFile, Vec<String>)> {
+        log::info!("reading config {}", path.display());
+        let s = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let warnings = Self::validate(&s)
+            .map_err(|e| anyhow!("invalid config {}: {e}", path.display()))?
+            .into_iter()
+            .map(|warning| format!("{}: {warning}", path.display()))
+            .collect();
+        Ok((Self::parse_file(&s)?, warnings))
     }
 
     /// Parse a TOML string into `FileConfig`.
@@ -175,42 +944,99 @@ impl Config {
         Ok(toml::from_str::<ConfigFile>(s)?)
     }
 
-    /// Merge `[synonyms]` into the global Keywords registry.
-    /// Omits synonyms that collide with current canonical Keyword (eg. "today").
-    /// Lowercases both alias and target for case-insensitive behavior.
-    fn load_synonyms(synonyms: &Option<HashMap<String, String>>) {
-        match synonyms {
-            Some(map) if !map.is_empty() => {
-                let pairs: Vec<(String, String)> = map
-                    .iter()
-                    .filter(|(alias, _)| !Keywords::is_canonical(alias))
-                    .map(|(a, t)| (a.clone(), t.clone()))
-                    .collect();
+    /// The `[anniversaries]` whose dates `formats` read, sorted by day of the year. The others
+    /// are added to `warnings`.
+    fn load_anniversaries(
+        anniversaries: &Option<HashMap<String, String>>,
+        formats: &[String],
+        warnings: &mut Vec<String>,
+    ) -> Vec<Anniversary> {
+        let formats: Vec<&str> = formats.iter().map(AsRef::as_ref).collect();
+        let mut loaded = Vec::new();
+        for (date, name) in anniversaries.iter().flatten() {
+            match Anniversary::parse(date, name, &formats) {
+                Some(anniversary) => loaded.push(anniversary),
+                None => warnings.push(format!(
+                    "Unknown date `{date}` in [anniversaries], write it as your dates, like \
+                     `14/02` or `03/09/1990`."
+                )),
+            }
+        }
+        loaded.sort_by(|a, b| (a.month, a.day, &a.name).cmp(&(b.month, b.day, &b.name)));
+        loaded
+    }
 
-                if !pairs.is_empty() {
-                    Keywords::extend(&pairs);
-                }
+    /// The `[commitments]` whose days are weekdays, sorted by name. The others are added to
+    /// `warnings`.
+    fn load_commitments(
+        commitments: &Option<HashMap<String, String>>,
+        keywords: &Keywords,
+        warnings: &mut Vec<String>,
+    ) -> Vec<Commitment> {
+        let mut loaded = Vec::new();
+        for (name, days) in commitments.iter().flatten() {
+            match parse_weekdays(days, keywords).filter(|days| !days.is_empty()) {
+                Some(days) => loaded.push(Commitment {
+                    name: name.clone(),
+                    days,
+                }),
+                None => warnings.push(format!(
+                    "Unknown days `{days}` of \"{name}\" in [commitments], write them like \
+                     `mon,wed,fri`."
+                )),
             }
-            _ => {}
         }
+        loaded.sort_by(|a, b| a.name.cmp(&b.name));
+        loaded
     }
-}
 
-/// Test helper to create a default `Config` for testing purposes.
-///
-/// This is the single source of truth for test configuration.
-/// If you add a field to `Config`, you only need to update it here.
-#[cfg(test)]
-pub fn mk_config(journal_dir: PathBuf, reference_date: Option<NaiveDate>) -> Config {
-    Config {
-        journal_dir,
-        todo_list_dir: PathBuf::from("./lgg/todos"),
-        editor: None,
-        default_time: NaiveTime::from_hms_opt(21, 0, 0).expect("valid time"),
-        reference_date: reference_date.unwrap_or(Local::now().date_naive()),
-        journal_date_format: "%A, %d %b %Y".to_string(),
-        todo_datetime_format: "%d/%b/%Y %H:%M".to_string(),
-        input_date_formats: ["%d/%m/%Y".to_string()].to_vec(),
+    /// The `tag_pattern`, or the default pattern when there is none or it isn't valid, which is
+    /// added to `warnings`.
+    fn load_tag_pattern(tag_pattern: &Option<String>, warnings: &mut Vec<String>) -> TagPattern {
+        if let Some(pattern) = tag_pattern {
+            match TagPattern::new(pattern) {
+                Ok(pattern) => return pattern,
+                Err(error) => warnings.push(format!(
+                    "Ignored the `tag_pattern` \"{pattern}\" ({error}), tags are read with the \
+                     default one."
+                )),
+            }
+        }
+        TagPattern::default()
+    }
+
+    /// The `[auto_tags]` with a valid pattern and tag, sorted by pattern. The others are added to
+    /// `warnings`.
+    fn load_auto_tags(
+        auto_tags: &Option<HashMap<String, String>>,
+        is_tag: impl Fn(&str) -> bool,
+        warnings: &mut Vec<String>,
+    ) -> Vec<AutoTagRule> {
+        let mut loaded = Vec::new();
+        for (pattern, tag) in auto_tags.iter().flatten() {
+            match auto_tags::parse_rule(pattern, tag) {
+                Ok(rule) if is_tag(tag) => loaded.push(rule),
+                _ => warnings.push(format!(
+                    "Skipped the rule \"{pattern}\" = \"{tag}\" of [auto_tags], it needs a valid \
+                     regex and a tag like \"@meetings\"."
+                )),
+            }
+        }
+        loaded.sort_by(|a, b| a.pattern.as_str().cmp(b.pattern.as_str()));
+        loaded
+    }
+
+    /// The built-in keywords with `[synonyms]` added.
+    /// Omits synonyms that collide with current canonical Keyword (eg. "today").
+    /// Lowercases both alias and target for case-insensitive behavior.
+    fn load_synonyms(synonyms: &Option<HashMap<String, Synonym>>) -> Keywords {
+        let pairs: Vec<(String, String)> = synonyms
+            .iter()
+            .flatten()
+            .filter(|(alias, _)| !Keywords::is_canonical(alias))
+            .map(|(a, t)| (a.clone(), t.target()))
+            .collect();
+        Keywords::with_synonyms(&pairs)
     }
 }
 
@@ -218,6 +1044,7 @@ pub fn mk_config(journal_dir: PathBuf, reference_date: Option<NaiveDate>) -> Con
 mod tests {
     use super::*;
     use crate::keywords::{Keyword, Keywords};
+    use crate::tests::mk_config;
     use std::path::Path;
 
     #[test]
@@ -226,7 +1053,7 @@ mod tests {
             let expected_xdg = b.home_dir().join(".config").join("lgg").join("config.toml");
             let expected_native = b.config_dir().join("lgg").join("config.toml");
             let c = Config::config_file_paths();
-            assert_eq!(c.get(0), Some(&expected_xdg));
+            assert_eq!(c.first(), Some(&expected_xdg));
             assert_eq!(c.get(1), Some(&expected_native));
         }
     }
@@ -246,7 +1073,187 @@ mod tests {
     }
 
     #[test]
-    fn parse_file_accepts_synonyms_and_extends_registry() {
+    fn parse_file_accepts_default_action() {
+        let fc = Config::parse_file(r#"default_action = "agenda""#).unwrap();
+        assert_eq!(fc.default_action, Some(DefaultAction::Agenda));
+        assert!(Config::parse_file(r#"default_action = "nothing""#).is_err());
+    }
+
+    #[test]
+    fn parse_file_accepts_one_or_several_todo_datetime_formats() {
+        let fc = Config::parse_file(r#"todo_datetime_format = "%d/%m/%Y""#).unwrap();
+        assert!(matches!(fc.todo_datetime_format, Some(Formats::One(f)) if f == "%d/%m/%Y"));
+        let toml = r#"todo_datetime_format = ["%d/%m/%Y %H:%M", "%Y-%m-%d %H:%M"]"#;
+        let fc = Config::parse_file(toml).unwrap();
+        assert!(matches!(fc.todo_datetime_format, Some(Formats::Many(f)) if f.len() == 2));
+    }
+
+    #[test]
+    fn parse_file_accepts_parse_mode() {
+        let fc = Config::parse_file(r#"parse_mode = "lenient""#).unwrap();
+        assert_eq!(fc.parse_mode, Some(ParseMode::Lenient));
+    }
+
+    #[test]
+    fn parse_file_accepts_date_order() {
+        let fc = Config::parse_file(r#"date_order = "mdy""#).unwrap();
+        assert_eq!(fc.date_order, Some(DateOrder::Mdy));
+    }
+
+    #[test]
+    fn date_order_formats_read_its_dates() {
+        let expected = NaiveDate::from_ymd_opt(2025, 8, 14).unwrap();
+        let parse = |order: DateOrder, input: &str| {
+            order
+                .input_date_formats()
+                .iter()
+                .find_map(|f| NaiveDate::parse_from_str(input, f).ok())
+        };
+        for input in ["14/08/2025", "14/08/25", "14-08-2025", "2025-08-14"] {
+            assert_eq!(parse(DateOrder::Dmy, input), Some(expected), "{input}");
+        }
+        for input in ["08/14/2025", "08/14/25", "2025-08-14"] {
+            assert_eq!(parse(DateOrder::Mdy, input), Some(expected), "{input}");
+        }
+        for input in ["2025/08/14", "2025-08-14"] {
+            assert_eq!(parse(DateOrder::Ymd, input), Some(expected), "{input}");
+        }
+        assert_eq!(parse(DateOrder::Dmy, "08/14/2025"), None);
+    }
+
+    #[test]
+    fn parse_file_accepts_range_order() {
+        let fc = Config::parse_file(r#"range_order = "strict""#).unwrap();
+        assert_eq!(fc.range_order, Some(RangeOrder::Strict));
+    }
+
+    #[test]
+    fn parse_file_accepts_allow_future() {
+        let fc = Config::parse_file(r#"allow_future = "deny""#).unwrap();
+        assert_eq!(fc.allow_future, Some(AllowFuture::Deny));
+    }
+
+    #[test]
+    fn parse_file_accepts_editor_template() {
+        let fc = Config::parse_file(r#"editor_template = ["help", "last_entry"]"#).unwrap();
+        assert_eq!(
+            fc.editor_template,
+            Some(vec![TemplatePart::Help, TemplatePart::LastEntry])
+        );
+        assert!(Config::parse_file(r#"editor_template = ["title"]"#).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_unknown_values() {
+        assert!(Config::validate("default_time = \"07:45\"\nlocale = \"es_ES\"").is_ok());
+        assert!(Config::validate("default_time = \"7.45\"").is_err());
+        assert!(Config::validate("locale = \"elvish\"").is_err());
+        assert!(Config::validate("obsidian = \"yes\"").is_err());
+        assert!(Config::validate("summarizer_cmd = \"llm 'summarize\"").is_err());
+        assert!(Config::validate("transcriber_cmd = \"\"").is_err());
+        assert!(Config::validate("[journals.all]\njournal_dir = \"/tmp\"").is_err());
+        assert!(Config::validate("default_tags = [\"work\"]").is_err());
+        assert!(Config::validate("ignored_tags = [\"@the\", \"a\"]").is_err());
+        assert!(Config::validate("[auto_tags]\n\"standup(\" = \"@meetings\"").is_err());
+        assert!(Config::validate("[auto_tags]\n\"standup\" = \"meetings\"").is_err());
+        assert!(Config::validate("[tag_aliases]\n\"@dogs\" = \"dog\"").is_err());
+        assert!(Config::validate("tag_pattern = \"[@#\"").is_err());
+        assert!(Config::validate("tag_pattern = \"@?\"").is_err());
+        assert!(Config::validate("default_tags = [\"@v1.2\"]").is_err());
+        let dotted = "tag_pattern = '[@#][\\w.]+'\ndefault_tags = [\"@v1.2\"]";
+        assert!(Config::validate(dotted).is_ok());
+        assert!(
+            Config::validate("[tag_aliases]\n\"@dogs\" = \"@dog\"\n\"@pup\" = \"@dogs\"").is_err()
+        );
+        assert!(Config::validate(
+            "[journals.work]\njournal_dir = \"/tmp\"\ndefault_tags = [\"@work\", \"acme\"]"
+        )
+        .is_err());
+        assert_eq!(Config::validate("editr = \"hx\"").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn values_are_written_as_toml() {
+        let config = mk_config(PathBuf::from("/tmp/journal"), None);
+        let values = config.values();
+        let value = |key: &str| values.iter().find(|(k, _)| *k == key).unwrap().1.clone();
+        assert_eq!(value("journal_dir").as_deref(), Some("\"/tmp/journal\""));
+        assert_eq!(value("default_time").as_deref(), Some("\"21:00\""));
+        assert_eq!(value("input_date_formats").as_deref(), Some("[\"%d/%m/%Y\"]"));
+        assert_eq!(value("parse_mode").as_deref(), Some("\"strict\""));
+        assert_eq!(value("obsidian").as_deref(), Some("false"));
+        assert_eq!(value("editor"), None);
+    }
+
+    #[test]
+    fn project_file_is_found_from_subdirectories() {
+        let tmp = tempfile::tempdir().unwrap();
+        let nested = tmp.path().join("src").join("module");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(Config::find_project_file(&nested), None);
+
+        fs::write(tmp.path().join(".lgg.toml"), "journal_dir = \"devlog\"").unwrap();
+        assert_eq!(
+            Config::find_project_file(&nested),
+            Some(tmp.path().join(".lgg.toml"))
+        );
+    }
+
+    #[test]
+    fn project_options_win_over_global_ones() {
+        let global = Config::parse_file(
+            "journal_dir = \"/journal\"\neditor = \"hx\"\n[synonyms]\nytd = \"yesterday\"",
+        )
+        .unwrap();
+        let project =
+            Config::parse_file("journal_dir = \"/devlog\"\n[synonyms]\ntmrw = \"tomorrow\"").unwrap();
+        let merged = global.merge(project);
+        assert_eq!(merged.journal_dir.as_deref(), Some(Path::new("/devlog")));
+        assert_eq!(merged.editor.as_deref(), Some("hx"));
+        assert_eq!(merged.synonyms.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn project_files_cant_run_commands_or_sync() {
+        let project = Config::parse_file(
+            "journal_dir = \"devlog\"\nstate_dir = \".state\"\neditor = \"sh -c evil\"\n\
+             summarizer_cmd = \"curl\"\n[sync]\nkind = \"git\"",
+        )
+        .unwrap();
+        let mut warnings = Vec::new();
+        let project = project.for_project(Path::new("/code/app/.lgg.toml"), &mut warnings);
+        assert_eq!(project.journal_dir.as_deref(), Some(Path::new("/code/app/devlog")));
+        assert_eq!(project.state_dir.as_deref(), Some(Path::new("/code/app/.state")));
+        assert!(project.editor.is_none() && project.summarizer_cmd.is_none());
+        assert!(project.sync.is_none());
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].contains("`editor`"));
+    }
+
+    #[test]
+    fn parse_file_accepts_locale() {
+        let fc = Config::parse_file(r#"locale = "es_ES""#).unwrap();
+        assert_eq!(fc.locale.as_deref(), Some("es_ES"));
+    }
+
+    #[test]
+    fn time_precision_truncates_times() {
+        let fc = Config::parse_file(r#"time_precision = "seconds""#).unwrap();
+        assert_eq!(fc.time_precision, Some(TimePrecision::Seconds));
+
+        let time = NaiveTime::from_hms_milli_opt(9, 5, 30, 250).unwrap();
+        assert_eq!(
+            TimePrecision::Minutes.truncate(time),
+            NaiveTime::from_hms_opt(9, 5, 0).unwrap()
+        );
+        assert_eq!(
+            TimePrecision::Seconds.truncate(time),
+            NaiveTime::from_hms_opt(9, 5, 30).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_file_accepts_synonyms_and_builds_keywords() {
         let toml = r#"
             journal_dir = "/tmp/my-journal"
 
@@ -258,10 +1265,11 @@ mod tests {
         let fc = Config::parse_file(toml).unwrap();
         assert!(fc.synonyms.is_some());
 
-        Config::load_synonyms(&fc.synonyms);
+        let keywords = Config::load_synonyms(&fc.synonyms);
 
-        assert!(Keywords::matches(Keyword::Yesterday, "ytd"));
-        assert!(Keywords::matches(Keyword::Yesterday, "ayer"));
+        assert!(keywords.matches(Keyword::Yesterday, "ytd"));
+        assert!(keywords.matches(Keyword::Yesterday, "ayer"));
+        assert!(!Keywords::global().read().unwrap().matches(Keyword::Yesterday, "ytd"));
     }
 
     #[test]
@@ -277,9 +1285,104 @@ mod tests {
         let fc = Config::parse_file(toml).unwrap();
         assert!(fc.synonyms.is_some());
 
-        Config::load_synonyms(&fc.synonyms);
+        let keywords = Config::load_synonyms(&fc.synonyms);
+
+        assert!(!keywords.matches(Keyword::Yesterday, "today"));
+        assert!(keywords.matches(Keyword::Yesterday, "ytd"));
+    }
+
+    #[test]
+    fn anniversaries_are_read_with_the_input_formats() {
+        let toml = r#"
+            [anniversaries]
+            "03/09/1990" = "Mum's birthday"
+            "14/02" = "Anniversary"
+            "31/02" = "Never"
+        "#;
+
+        let fc = Config::parse_file(toml).unwrap();
+        let formats = vec!["%d/%m/%Y".to_string()];
+        let mut warnings = Vec::new();
+        let anniversaries = Config::load_anniversaries(&fc.anniversaries, &formats, &mut warnings);
+
+        let names: Vec<&str> = anniversaries.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["Anniversary", "Mum's birthday"]);
+        assert_eq!(anniversaries[1].year, Some(1990));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("`31/02`"));
+    }
+
+    #[test]
+    fn commitments_are_read_on_their_weekdays() {
+        let toml = r#"
+            [commitments]
+            "Team standup" = "mon,tue,wed,thu,fri"
+            Gym = "Tue, thursday"
+            Never = "someday"
+        "#;
+
+        let fc = Config::parse_file(toml).unwrap();
+        let mut warnings = Vec::new();
+        let commitments =
+            Config::load_commitments(&fc.commitments, &Keywords::default(), &mut warnings);
+
+        let names: Vec<&str> = commitments.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Gym", "Team standup"]);
+        assert_eq!(commitments[0].days, vec![Weekday::Tue, Weekday::Thu]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("\"Never\""));
+    }
+
+    #[test]
+    fn quiet_days_must_be_weekdays() {
+        assert!(Config::validate("quiet_days = [\"saturday\", \"Sun\"]").is_ok());
+        let err = Config::validate("quiet_days = [\"someday\"]").unwrap_err();
+        assert!(err.to_string().contains("\"someday\""));
+    }
+
+    #[test]
+    fn device_id_is_one_word() {
+        assert!(Config::validate("device_id = \"work-laptop\"").is_ok());
+        let err = Config::validate("device_id = \"my laptop\"").unwrap_err();
+        assert!(err.to_string().contains("`device_id`"));
+    }
+
+    #[test]
+    fn aliases_are_one_word() {
+        assert!(Config::validate("[aliases]\nstandup = \"--on today\"").is_ok());
+        assert!(Config::validate("[aliases]\n\"my week\" = \"--from monday\"").is_err());
+        assert!(Config::validate("[aliases]\n\"--week\" = \"--from monday\"").is_err());
+    }
+
+    #[test]
+    fn todo_reminders_are_tags_with_a_time_before() {
+        let reminders = "[todo.reminders]\n\"@bills\" = \"3 days before\"";
+        assert!(Config::validate(reminders).is_ok());
+        assert!(Config::validate("[todo.reminders]\nbills = \"3 days before\"").is_err());
+        let err = Config::validate("[todo.reminders]\n\"@bills\" = \"soon\"").unwrap_err();
+        assert!(err.to_string().contains("\"soon\""));
+        let too_early = "[todo.reminders]\n\"@bills\" = \"100000000 days before\"";
+        assert!(Config::validate(too_early).is_err());
+        assert!(Config::validate("[todo]\nreminder = {}").is_err());
+    }
 
-        assert!(!Keywords::matches(Keyword::Yesterday, "today"));
-        assert!(Keywords::matches(Keyword::Yesterday, "ytd"));
+    #[test]
+    fn parse_file_accepts_synonyms_with_days() {
+        let toml = r#"
+            [synonyms]
+            "day before yesterday" = "yesterday - 1"
+            anteayer = { offset = -2 }
+            "next friday" = { keyword = "friday", offset = 7 }
+        "#;
+
+        let fc = Config::parse_file(toml).unwrap();
+        let keywords = Config::load_synonyms(&fc.synonyms);
+
+        assert_eq!(
+            keywords.shifted("day before yesterday"),
+            Some((Keyword::Yesterday, -1))
+        );
+        assert_eq!(keywords.shifted("anteayer"), Some((Keyword::Today, -2)));
+        assert_eq!(keywords.shifted("next friday"), Some((Keyword::Friday, 7)));
     }
 }
@@ -0,0 +1,245 @@
+//! Reading and editing `config.toml` while keeping its comments and layout (`lgg config`).
+use super::config::Config;
+use anyhow::{bail, Context, Result};
+use toml_edit::{Document, DocumentMut, Item, Table, Value};
+
+/// Keys `config.toml` understands. `synonyms`, `anniversaries` and `aliases` are tables, their
+/// entries are `synonyms.<alias>`, `anniversaries.<date>` and `aliases.<name>`. `remote` and
+/// `sync` are tables too, set as a whole.
+pub const CONFIG_KEYS: &[&str] = &[
+    "journal_dir",
+    "todo_list_dir",
+    "editor",
+    "default_time",
+    "journal_date_format",
+    "todo_datetime_format",
+    "input_date_formats",
+    "date_order",
+    "obsidian",
+    "merge_within_minutes",
+    "default_action",
+    "stopwords",
+    "parse_mode",
+    "time_precision",
+    "locale",
+    "range_order",
+    "allow_future",
+    "editor_template",
+    "temp_dir",
+    "state_dir",
+    "stale_days",
+    "stale_threshold",
+    "hyperlinks",
+    "quiet_days",
+    "device_id",
+    "summarizer_cmd",
+    "transcriber_cmd",
+    "fetch_titles",
+    "index_files",
+    "default_tags",
+    "ignored_tags",
+    "tag_pattern",
+    "synonyms",
+    "anniversaries",
+    "aliases",
+    "commitments",
+    "auto_tags",
+    "tag_aliases",
+    "journals",
+    "remote",
+    "sync",
+    "todo",
+];
+
+/// Value of `key` (`default_time`, `synonyms.ytd`) as written in `content`. `None` if it's not set.
+pub fn get_config_value(content: &str, key: &str) -> Result<Option<String>> {
+    let doc = parse_document(content)?;
+    let item = match key.split_once('.') {
+        Some((table, name)) => doc.get(table).and_then(|t| t.get(name)),
+        None => doc.get(key),
+    };
+    Ok(item.map(|item| match item.as_value() {
+        // Without the spaces and comments around it.
+        Some(value) => {
+            let mut value = value.clone();
+            value.decor_mut().clear();
+            value.to_string()
+        }
+        None => item.to_string().trim().to_string(),
+    }))
+}
+
+/// `content` with `key` set to `value`. Booleans, numbers and arrays (`["%d/%m/%Y"]`) are written
+/// as such, anything else as a string. Fails if the result isn't a valid config.
+pub fn set_config_value(content: &str, key: &str, value: &str) -> Result<String> {
+    let mut doc = parse_document(content)?;
+    let root = key.split_once('.').map_or(key, |(table, _)| table);
+    if !CONFIG_KEYS.contains(&root) {
+        bail!("Unknown config key `{key}`.");
+    }
+
+    let typed = value
+        .parse::<Value>()
+        .ok()
+        .filter(|v| v.is_bool() || v.is_integer() || v.is_float() || v.is_array());
+    let mut value = typed.unwrap_or_else(|| Value::from(value));
+    value.decor_mut().clear();
+    let item = match key.split_once('.') {
+        Some((table @ ("synonyms" | "anniversaries" | "aliases"), name)) => {
+            let table = doc
+                .entry(table)
+                .or_insert_with(|| Item::Table(Table::new()))
+                .as_table_mut()
+                .with_context(|| format!("`{table}` is not a table."))?;
+            &mut table[name]
+        }
+        Some(_) => {
+            bail!("Only `synonyms`, `anniversaries` and `aliases` have nested keys, not `{key}`.")
+        }
+        None if key == "synonyms" => bail!("Set synonyms one by one, as `synonyms.<alias>`."),
+        None if key == "anniversaries" => {
+            bail!("Set anniversaries one by one, as `anniversaries.<date>`.")
+        }
+        None if key == "aliases" => bail!("Set aliases one by one, as `aliases.<name>`."),
+        None if key == "remote" || key == "sync" => {
+            bail!("Set the [{key}] table with `lgg config edit`.")
+        }
+        None => &mut doc[key],
+    };
+    // Keeps the comment after the old value.
+    if let Some(old) = item.as_value() {
+        *value.decor_mut() = old.decor().clone();
+    }
+    *item = Item::Value(value);
+
+    let content = doc.to_string();
+    Config::validate(&content)?;
+    Ok(content)
+}
+
+/// Keys of `content` lgg doesn't know, with their line and the known key closest to them
+/// (`Unknown key `defualt_time` at line 3, did you mean `default_time`?`).
+pub(crate) fn unknown_keys(content: &str) -> Vec<String> {
+    let Ok(doc) = Document::parse(content) else {
+        return Vec::new();
+    };
+    let table = doc.as_table();
+    table
+        .iter()
+        .filter(|(name, _)| !CONFIG_KEYS.contains(name))
+        .map(|(name, _)| {
+            let line = table
+                .get_key_value(name)
+                .and_then(|(key, _)| key.span())
+                .map(|span| content[..span.start].matches('\n').count() + 1);
+            let mut message = format!("Unknown key `{name}`");
+            if let Some(line) = line {
+                message.push_str(&format!(" at line {line}"));
+            }
+            match closest_key(name) {
+                Some(known) => message.push_str(&format!(", did you mean `{known}`?")),
+                None => message.push('.'),
+            }
+            message
+        })
+        .collect()
+}
+
+/// The known key `name` is most likely a typo of.
+fn closest_key(name: &str) -> Option<&'static str> {
+    CONFIG_KEYS
+        .iter()
+        .map(|key| (edit_distance(name, key), *key))
+        .filter(|(distance, _)| *distance <= 3)
+        .min()
+        .map(|(_, key)| key)
+}
+
+/// Levenshtein distance: how many characters to insert, delete or replace to turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let replace = previous[j] + usize::from(ca != *cb);
+            current.push(replace.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+fn parse_document(content: &str) -> Result<DocumentMut> {
+    content
+        .parse::<DocumentMut>()
+        .context("config.toml is not valid TOML")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = r#"# My journal
+journal_dir = "/tmp/journal" # synced
+
+[synonyms]
+ytd = "yesterday"
+"#;
+
+    #[test]
+    fn gets_values_as_written() {
+        assert_eq!(
+            get_config_value(CONTENT, "journal_dir").unwrap().as_deref(),
+            Some("\"/tmp/journal\"")
+        );
+        assert_eq!(
+            get_config_value(CONTENT, "synonyms.ytd").unwrap().as_deref(),
+            Some("\"yesterday\"")
+        );
+        assert!(get_config_value(CONTENT, "editor").unwrap().is_none());
+    }
+
+    #[test]
+    fn sets_values_keeping_comments() {
+        let content = set_config_value(CONTENT, "default_time", "08:30").unwrap();
+        let content = set_config_value(&content, "obsidian", "true").unwrap();
+        let content = set_config_value(&content, "synonyms.tmrw", "tomorrow").unwrap();
+        let content = set_config_value(&content, "anniversaries.14/02", "Anniversary").unwrap();
+        let content =
+            set_config_value(&content, "input_date_formats", r#"["%d.%m.%Y"]"#).unwrap();
+
+        let content = set_config_value(&content, "journal_dir", "/tmp/other").unwrap();
+
+        assert!(content.starts_with("# My journal\n"));
+        assert!(content.contains("journal_dir = \"/tmp/other\" # synced"));
+        assert!(content.contains("default_time = \"08:30\""));
+        assert!(content.contains("obsidian = true"));
+        assert!(content.contains("tmrw = \"tomorrow\""));
+        assert!(content.contains("[anniversaries]\n\"14/02\" = \"Anniversary\""));
+        assert!(content.contains("input_date_formats = [\"%d.%m.%Y\"]"));
+    }
+
+    #[test]
+    fn finds_unknown_keys_with_suggestions() {
+        let content = "journal_dir = \"/tmp\"\n\ndefualt_time = \"08:00\"\ncolour = \"red\"\n";
+        assert_eq!(
+            unknown_keys(content),
+            vec![
+                "Unknown key `defualt_time` at line 3, did you mean `default_time`?".to_string(),
+                "Unknown key `colour` at line 4.".to_string(),
+            ]
+        );
+        assert!(unknown_keys(CONTENT).is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        assert!(set_config_value(CONTENT, "default_time", "8 in the morning").is_err());
+        assert!(set_config_value(CONTENT, "parse_mode", "sloppy").is_err());
+        assert!(set_config_value(CONTENT, "obsidian", "yes").is_err());
+        assert!(set_config_value(CONTENT, "locale", "xx_XX").is_err());
+        assert!(set_config_value(CONTENT, "colour", "red").is_err());
+        assert!(set_config_value(CONTENT, "editor.name", "hx").is_err());
+    }
+}
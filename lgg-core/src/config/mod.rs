@@ -1,3 +1,9 @@
 mod config;
+mod config_file;
 
-pub use config::Config;
+pub use config::{
+    AllowFuture, Config, DateOrder, DefaultAction, Hyperlinks, ParseMode, RangeOrder,
+    TemplatePart, TimePrecision,
+};
+pub use config_file::{get_config_value, set_config_value, CONFIG_KEYS};
+pub(crate) use config_file::unknown_keys;
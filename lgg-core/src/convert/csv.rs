@@ -0,0 +1,111 @@
+//! CSV export, one row per entry or todo, to load them in a spreadsheet.
+use crate::journal::{text_counts, JournalEntry};
+use crate::todos::{TodoEntry, TodoStatus};
+
+/// Renders journal entries as CSV: `date,time,title,tags,word_count,path`.
+pub fn journal_to_csv(entries: &[JournalEntry]) -> String {
+    let mut out = String::from("date,time,title,tags,word_count,path\n");
+    for entry in entries {
+        let row = [
+            entry.date.format("%Y-%m-%d").to_string(),
+            entry.time.format("%H:%M").to_string(),
+            entry.title.clone(),
+            entry.tags.join(" "),
+            entry.word_count.to_string(),
+            entry.path.display().to_string(),
+        ];
+        push_row(&mut out, &row);
+    }
+    out
+}
+
+/// Renders todos as CSV: `status,due,done,title,tags,word_count,path`.
+pub fn todos_to_csv(todos: &[TodoEntry]) -> String {
+    let mut out = String::from("status,due,done,title,tags,word_count,path\n");
+    for todo in todos {
+        let status = match todo.status {
+            TodoStatus::Pending => "pending",
+            TodoStatus::InProgress => "in_progress",
+            TodoStatus::Done => "done",
+            TodoStatus::Cancelled => "cancelled",
+        };
+        let date = |d: Option<chrono::NaiveDateTime>| {
+            d.map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default()
+        };
+        let row = [
+            status.to_string(),
+            date(todo.due_date),
+            date(todo.done_date),
+            todo.title.clone(),
+            todo.tags.join(" "),
+            text_counts(&todo.title, &todo.body).0.to_string(),
+            todo.path.display().to_string(),
+        ];
+        push_row(&mut out, &row);
+    }
+    out
+}
+
+fn push_row(out: &mut String, fields: &[String]) {
+    let row: Vec<String> = fields.iter().map(|f| escape(f)).collect();
+    out.push_str(&row.join(","));
+    out.push('\n');
+}
+
+/// Quotes a field when it has commas, quotes or line breaks (RFC 4180).
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveTime};
+    use std::path::PathBuf;
+
+    #[test]
+    fn journal_entries_as_csv() {
+        let entries = vec![JournalEntry {
+            date: NaiveDate::from_ymd_opt(2025, 8, 15).unwrap(),
+            time: NaiveTime::from_hms_opt(9, 5, 0).unwrap(),
+            title: "Standup, \"quick\"".to_string(),
+            body: "Talked about @work and @release.".to_string(),
+            tags: vec!["@release".to_string(), "@work".to_string()],
+            links: Vec::new(),
+            path: PathBuf::from("/j/2025/08/2025-08-15.md"),
+            word_count: 7,
+            char_count: 48,
+        }];
+
+        assert_eq!(
+            journal_to_csv(&entries),
+            "date,time,title,tags,word_count,path\n2025-08-15,09:05,\"Standup, \"\"quick\"\"\",@release @work,7,/j/2025/08/2025-08-15.md\n"
+        );
+    }
+
+    #[test]
+    fn todos_as_csv() {
+        let todos = vec![TodoEntry {
+            due_date: NaiveDate::from_ymd_opt(2025, 8, 20)
+                .unwrap()
+                .and_hms_opt(7, 0, 0),
+            done_date: None,
+            created: None,
+            title: "Buy milk".to_string(),
+            body: String::new(),
+            path: PathBuf::from("/t/todos.md"),
+            status: TodoStatus::Pending,
+            tags: Vec::new(),
+        }];
+
+        assert_eq!(
+            todos_to_csv(&todos),
+            "status,due,done,title,tags,word_count,path\npending,2025-08-20 07:00,,Buy milk,,2,/t/todos.md\n"
+        );
+    }
+}
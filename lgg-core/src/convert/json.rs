@@ -0,0 +1,202 @@
+//! JSON output, one object per line for each entry, for scripts to chain on what lgg wrote or
+//! found (e.g. with `jq`).
+use crate::journal::{JournalEntry, SearchHit};
+use chrono::Timelike;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct JsonEntry<'a> {
+    /// Date and time of the entry (`2025-03-04 10:30`), what finds it in its day file.
+    id: String,
+    /// Lossy, as JSON strings can only be UTF-8.
+    path: String,
+    date: String,
+    time: String,
+    title: &'a str,
+    tags: &'a [String],
+    /// The journal of the entry, with `--journal all`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    journal: Option<&'a str>,
+}
+
+/// An entry found by `--search`, with where its matches are.
+#[derive(Serialize)]
+struct JsonHit<'a> {
+    #[serde(flatten)]
+    entry: JsonEntry<'a>,
+    score: f32,
+    snippets: Vec<JsonSnippet<'a>>,
+}
+
+/// Byte offsets in the body: of the snippet (`start`, `end`) and of each of its matches.
+#[derive(Serialize)]
+struct JsonSnippet<'a> {
+    start: usize,
+    end: usize,
+    text: &'a str,
+    matches: Vec<[usize; 2]>,
+}
+
+impl<'a> JsonEntry<'a> {
+    fn new(entry: &'a JournalEntry, journal: Option<&'a str>) -> Self {
+        let time_format = if entry.time.second() == 0 { "%H:%M" } else { "%H:%M:%S" };
+        let date = entry.date.format("%Y-%m-%d").to_string();
+        let time = entry.time.format(time_format).to_string();
+        JsonEntry {
+            id: format!("{date} {time}"),
+            path: entry.path.to_string_lossy().into_owned(),
+            date,
+            time,
+            title: &entry.title,
+            tags: &entry.tags,
+            journal,
+        }
+    }
+}
+
+/// Renders journal entries as JSON lines:
+/// `{"id":"2025-03-04 10:30","path":"...","date":"2025-03-04","time":"10:30","title":"...","tags":[]}`.
+pub fn journal_to_json(entries: &[JournalEntry]) -> String {
+    entries_to_json(entries, None)
+}
+
+/// Like [`journal_to_json`], each entry with the name of its `journal`:
+/// `{"id":...,"tags":[],"journal":"work"}`.
+pub fn journal_to_json_in(journal: &str, entries: &[JournalEntry]) -> String {
+    entries_to_json(entries, Some(journal))
+}
+
+fn entries_to_json(entries: &[JournalEntry], journal: Option<&str>) -> String {
+    let mut out = String::new();
+    for entry in entries {
+        // Only strings, that serde_json can always write.
+        let json = serde_json::to_string(&JsonEntry::new(entry, journal));
+        out.push_str(&json.expect("entry serializes to JSON"));
+        out.push('\n');
+    }
+    out
+}
+
+/// Renders search hits as JSON lines: the entry as in [`journal_to_json`], its `score` and its
+/// `snippets`, with their byte offsets in the body and the ones of their `matches`.
+pub fn search_to_json(hits: &[SearchHit]) -> String {
+    hits_to_json(hits, None)
+}
+
+/// Like [`search_to_json`], each hit with the name of its `journal`.
+pub fn search_to_json_in(journal: &str, hits: &[SearchHit]) -> String {
+    hits_to_json(hits, Some(journal))
+}
+
+fn hits_to_json(hits: &[SearchHit], journal: Option<&str>) -> String {
+    let mut out = String::new();
+    for hit in hits {
+        let snippets = hit
+            .snippets
+            .iter()
+            .map(|snippet| JsonSnippet {
+                start: snippet.start,
+                end: snippet.start + snippet.text.len(),
+                text: &snippet.text,
+                matches: snippet
+                    .highlights
+                    .iter()
+                    .map(|range| [snippet.start + range.start, snippet.start + range.end])
+                    .collect(),
+            })
+            .collect();
+        let json = JsonHit {
+            entry: JsonEntry::new(&hit.entry, journal),
+            score: hit.score,
+            snippets,
+        };
+        // Only strings and numbers, that serde_json can always write (`NaN` as `null`).
+        let json = serde_json::to_string(&json);
+        out.push_str(&json.expect("search hit serializes to JSON"));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveTime};
+    use std::path::PathBuf;
+
+    #[test]
+    fn renders_one_object_per_entry() {
+        let entry = JournalEntry {
+            date: NaiveDate::from_ymd_opt(2025, 3, 4).unwrap(),
+            time: NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+            title: "Call \"Ana\"".to_string(),
+            body: "Not in the output.".to_string(),
+            tags: vec!["@ana".to_string()],
+            links: Vec::new(),
+            path: PathBuf::from("/j/2025/03/2025-03-04.md"),
+            word_count: 6,
+            char_count: 28,
+        };
+        assert_eq!(
+            journal_to_json(std::slice::from_ref(&entry)),
+            "{\"id\":\"2025-03-04 10:30\",\"path\":\"/j/2025/03/2025-03-04.md\",\
+             \"date\":\"2025-03-04\",\"time\":\"10:30\",\"title\":\"Call \\\"Ana\\\"\",\
+             \"tags\":[\"@ana\"]}\n"
+        );
+        assert!(journal_to_json_in("work", &[entry]).ends_with(",\"journal\":\"work\"}\n"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn paths_that_are_not_utf8_are_written_lossily() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let entry = JournalEntry {
+            date: NaiveDate::from_ymd_opt(2025, 3, 4).unwrap(),
+            time: NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+            title: "Plan".to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+            links: Vec::new(),
+            path: PathBuf::from(OsStr::from_bytes(b"/j/caf\xe9.md")),
+            word_count: 1,
+            char_count: 4,
+        };
+        let path = "\"path\":\"/j/caf\u{fffd}.md\"";
+        assert!(journal_to_json(std::slice::from_ref(&entry)).contains(path));
+        let hit = SearchHit {
+            entry,
+            score: 1.0,
+            snippets: Vec::new(),
+        };
+        assert!(search_to_json(&[hit]).contains(path));
+    }
+
+    #[test]
+    fn search_hits_have_the_offsets_of_their_matches() {
+        let body = "Intro.\nThe release plan is ready.".to_string();
+        let hit = SearchHit {
+            entry: JournalEntry {
+                date: NaiveDate::from_ymd_opt(2025, 3, 4).unwrap(),
+                time: NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+                title: "Plan".to_string(),
+                tags: Vec::new(),
+                links: Vec::new(),
+                path: PathBuf::from("/j/2025/03/2025-03-04.md"),
+                body: body.clone(),
+                word_count: 7,
+                char_count: 37,
+            },
+            score: 1.5,
+            snippets: crate::snippets::search_snippets(&body, &["release".to_string()], 0),
+        };
+        let json = search_to_json(&[hit]);
+        assert!(json.starts_with("{\"id\":\"2025-03-04 10:30\","));
+        assert!(json.ends_with(
+            "\"score\":1.5,\"snippets\":[{\"start\":7,\"end\":33,\
+             \"text\":\"The release plan is ready.\",\"matches\":[[11,18]]}]}\n"
+        ));
+        assert_eq!(&body[11..18], "release");
+    }
+}
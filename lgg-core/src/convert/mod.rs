@@ -0,0 +1,12 @@
+//! Converters between the lgg Markdown layout and other tools' formats.
+pub mod csv;
+pub mod json;
+pub mod org;
+pub mod taskwarrior;
+
+/// Entries read from a foreign format, ready to be saved, plus the problems found while reading.
+#[derive(Debug)]
+pub struct ImportResult<T> {
+    pub entries: Vec<T>,
+    pub errors: Vec<String>,
+}
@@ -0,0 +1,381 @@
+//! Emacs org-mode export and import.
+//!
+//! Journal entries become a headline per day with a sub-headline per entry:
+//!
+//! ```org
+//! * <2025-08-15 Fri>
+//! ** 09:00 Standup
+//! Body of the entry.
+//! ```
+//!
+//! Todos become `TODO`/`STARTED`/`DONE`/`CANCELLED` headlines with their planning line:
+//!
+//! ```org
+//! * DONE Send invoices
+//! CLOSED: [2025-08-22 Fri 18:00] DEADLINE: <2025-08-20 Wed 07:00>
+//! ```
+use super::ImportResult;
+use crate::journal::JournalEntry;
+use crate::journal::JournalWriteEntry;
+use crate::todos::{ParsedTodosEntry, TodoEntry, TodoStatus};
+use crate::tags::TagPattern;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `2025-08-15 Fri 07:00`, the day name and time being optional.
+static TIMESTAMP: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\d{4}-\d{2}-\d{2})(?: [^\s\]>]+)?(?: (\d{1,2}:\d{2}))?").unwrap());
+static DEADLINE: Lazy<Regex> = Lazy::new(|| Regex::new(r"DEADLINE: <([^>]+)>").unwrap());
+static SCHEDULED: Lazy<Regex> = Lazy::new(|| Regex::new(r"SCHEDULED: <([^>]+)>").unwrap());
+static CLOSED: Lazy<Regex> = Lazy::new(|| Regex::new(r"CLOSED: \[([^\]]+)\]").unwrap());
+
+/// Renders journal entries as an org document, one level-1 headline per day.
+pub fn journal_to_org(entries: &[JournalEntry]) -> String {
+    let mut sorted: Vec<&JournalEntry> = entries.iter().collect();
+    sorted.sort_by_key(|e| (e.date, e.time));
+
+    let mut out = String::new();
+    let mut current_date = None;
+    for entry in sorted {
+        if current_date != Some(entry.date) {
+            current_date = Some(entry.date);
+            out.push_str(&format!("* <{}>\n", entry.date.format("%Y-%m-%d %a")));
+        }
+        out.push_str(&format!("** {} {}\n", entry.time.format("%H:%M"), entry.title));
+        push_body(&mut out, &entry.body);
+    }
+    out
+}
+
+/// Reads journal entries from an org document written by [`journal_to_org`] (or by hand with the same layout).
+/// Their tags are the matches of `tags`.
+pub fn org_to_journal(content: &str, tags: &TagPattern) -> ImportResult<JournalWriteEntry> {
+    let mut entries: Vec<JournalWriteEntry> = Vec::new();
+    let mut errors = Vec::new();
+    let mut date: Option<NaiveDate> = None;
+    let mut in_entry = false;
+
+    for line in content.lines() {
+        if let Some(headline) = line.strip_prefix("* ") {
+            in_entry = false;
+            date = TIMESTAMP
+                .captures(headline)
+                .and_then(|c| NaiveDate::parse_from_str(&c[1], "%Y-%m-%d").ok());
+            if date.is_none() {
+                errors.push(format!("Expected a date in day headline `{line}`."));
+            }
+        } else if let Some(headline) = line.strip_prefix("** ") {
+            in_entry = false;
+            let Some(date) = date else {
+                errors.push(format!("Entry `{line}` is not under a day headline."));
+                continue;
+            };
+            let parsed = headline.split_once(' ').and_then(|(time, title)| {
+                NaiveTime::parse_from_str(time, "%H:%M")
+                    .ok()
+                    .map(|time| (time, title.trim().to_string()))
+            });
+            match parsed {
+                Some((time, title)) => {
+                    in_entry = true;
+                    entries.push(JournalWriteEntry {
+                        date,
+                        time,
+                        title,
+                        body: String::new(),
+                        tags: Vec::new(),
+                    });
+                }
+                None => errors.push(format!(
+                    "Invalid entry headline `{line}`. Expected `** HH:MM Title`."
+                )),
+            }
+        } else if in_entry && let Some(entry) = entries.last_mut() {
+            push_body_line(&mut entry.body, line);
+        }
+    }
+
+    for entry in entries.iter_mut() {
+        entry.body = entry.body.trim().to_string();
+        entry.tags = tags.extract(&format!("{}\n{}", entry.title, entry.body));
+    }
+
+    ImportResult { entries, errors }
+}
+
+/// Renders todos as `TODO`/`STARTED`/`DONE`/`CANCELLED` headlines.
+pub fn todos_to_org(todos: &[TodoEntry]) -> String {
+    let mut out = String::new();
+    for todo in todos {
+        let keyword = match todo.status {
+            TodoStatus::Pending => "TODO",
+            TodoStatus::InProgress => "STARTED",
+            TodoStatus::Done => "DONE",
+            TodoStatus::Cancelled => "CANCELLED",
+        };
+        out.push_str(&format!("* {keyword} {}\n", todo.title));
+
+        let mut planning = Vec::new();
+        if let Some(done) = todo.done_date {
+            planning.push(format!("CLOSED: [{}]", done.format("%Y-%m-%d %a %H:%M")));
+        }
+        if let Some(due) = todo.due_date {
+            planning.push(format!("DEADLINE: <{}>", due.format("%Y-%m-%d %a %H:%M")));
+        }
+        if !planning.is_empty() {
+            out.push_str(&planning.join(" "));
+            out.push('\n');
+        }
+        push_body(&mut out, &todo.body);
+    }
+    out
+}
+
+/// Reads todos from `TODO`/`STARTED`/`DONE`/`CANCELLED` headlines of any level. Other headlines are ignored.
+/// Timestamps without time get `default_time`, and the tags are the matches of `tags`.
+pub fn org_to_todos(
+    content: &str,
+    default_time: NaiveTime,
+    tags: &TagPattern,
+) -> ImportResult<ParsedTodosEntry> {
+    let mut entries: Vec<ParsedTodosEntry> = Vec::new();
+    let mut errors = Vec::new();
+    let mut in_todo = false;
+
+    for line in content.lines() {
+        if line.starts_with('*') {
+            in_todo = false;
+            let headline = line.trim_start_matches('*').trim_start();
+            let (status, title) = match headline.split_once(' ') {
+                Some(("TODO", title)) => (TodoStatus::Pending, title),
+                Some(("STARTED", title)) => (TodoStatus::InProgress, title),
+                Some(("DONE", title)) => (TodoStatus::Done, title),
+                Some(("CANCELLED", title)) => (TodoStatus::Cancelled, title),
+                _ => continue,
+            };
+            in_todo = true;
+            entries.push(ParsedTodosEntry {
+                due_date: None,
+                done_date: None,
+                created: None,
+                title: title.trim().to_string(),
+                body: String::new(),
+                tags: Vec::new(),
+                status,
+            });
+            continue;
+        }
+        let Some(todo) = entries.last_mut().filter(|_| in_todo) else {
+            continue;
+        };
+
+        let is_planning = ["DEADLINE:", "SCHEDULED:", "CLOSED:"]
+            .iter()
+            .any(|k| line.trim_start().starts_with(k));
+        if todo.body.is_empty() && is_planning {
+            let due = DEADLINE.captures(line).or_else(|| SCHEDULED.captures(line));
+            if let Some(c) = due {
+                todo.due_date = parse_timestamp(&c[1], default_time);
+                if todo.due_date.is_none() {
+                    errors.push(format!("Invalid timestamp in `{}`.", line.trim()));
+                }
+            }
+            if let Some(c) = CLOSED.captures(line) {
+                todo.done_date = parse_timestamp(&c[1], default_time);
+                if todo.done_date.is_none() {
+                    errors.push(format!("Invalid timestamp in `{}`.", line.trim()));
+                }
+            }
+        } else {
+            push_body_line(&mut todo.body, line);
+        }
+    }
+
+    for todo in entries.iter_mut() {
+        todo.body = todo.body.trim().to_string();
+        todo.tags = tags.extract(&format!("{}\n{}", todo.title, todo.body));
+    }
+
+    ImportResult { entries, errors }
+}
+
+fn parse_timestamp(s: &str, default_time: NaiveTime) -> Option<NaiveDateTime> {
+    let c = TIMESTAMP.captures(s)?;
+    let date = NaiveDate::parse_from_str(&c[1], "%Y-%m-%d").ok()?;
+    let time = match c.get(2) {
+        Some(t) => NaiveTime::parse_from_str(t.as_str(), "%H:%M").ok()?,
+        None => default_time,
+    };
+    Some(NaiveDateTime::new(date, time))
+}
+
+/// A body line starting with `*` would be read as a headline, so it's escaped with a comma, org's
+/// own convention. Lines that already start with commas before a `*` get one more, so the
+/// importer can tell them apart.
+fn push_body(out: &mut String, body: &str) {
+    if body.trim().is_empty() {
+        return;
+    }
+    for line in body.trim_end().lines() {
+        if is_escaped_headline(line) {
+            out.push(',');
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+}
+
+/// Undoes the escape of [`push_body`]: drops one of the commas before a `*`.
+fn push_body_line(body: &mut String, line: &str) {
+    let line = match line.strip_prefix(',') {
+        Some(rest) if is_escaped_headline(rest) => rest,
+        _ => line,
+    };
+    body.push_str(line);
+    body.push('\n');
+}
+
+/// Whether `line` is a `*`, maybe after some commas, that the escape of bodies must handle.
+fn is_escaped_headline(line: &str) -> bool {
+    line.trim_start_matches(',').starts_with('*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::text_counts;
+    use std::path::PathBuf;
+
+    fn dt(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDateTime::new(
+            NaiveDate::from_ymd_opt(y, m, d).unwrap(),
+            NaiveTime::from_hms_opt(h, min, 0).unwrap(),
+        )
+    }
+
+    #[test]
+    fn journal_round_trip() {
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let entry = |h, title: &str, body: &str| {
+            let (word_count, char_count) = text_counts(title, body);
+            JournalEntry {
+                date,
+                time: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+                title: title.to_string(),
+                body: body.to_string(),
+                tags: Vec::new(),
+                links: Vec::new(),
+                path: PathBuf::new(),
+                word_count,
+                char_count,
+            }
+        };
+        let entries = vec![
+            entry(18, "Walk", "* a list item\n* another @health"),
+            entry(9, "Standup @work", ""),
+        ];
+
+        let org = journal_to_org(&entries);
+        assert_eq!(
+            org,
+            "* <2025-08-15 Fri>\n** 09:00 Standup @work\n** 18:00 Walk\n,* a list item\n,* another @health\n"
+        );
+
+        let result = org_to_journal(&org, &TagPattern::default());
+        assert!(result.errors.is_empty());
+        assert_eq!(result.entries.len(), 2);
+        assert_eq!(result.entries[0].title, "Standup @work");
+        assert_eq!(result.entries[0].tags, vec!["@work"]);
+        assert_eq!(result.entries[1].body, "* a list item\n* another @health");
+        assert_eq!(result.entries[1].tags, vec!["@health"]);
+    }
+
+    #[test]
+    fn bodies_keep_their_stars_and_commas() {
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let body = "List:\n * indented item\n* item\n,* not escaped\n,,** deeper\n, comma";
+        let (word_count, char_count) = text_counts("Notes", body);
+        let entries = vec![JournalEntry {
+            date,
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            title: "Notes".to_string(),
+            body: body.to_string(),
+            tags: Vec::new(),
+            links: Vec::new(),
+            path: PathBuf::new(),
+            word_count,
+            char_count,
+        }];
+
+        let org = journal_to_org(&entries);
+        assert!(org.ends_with(
+            "List:\n * indented item\n,* item\n,,* not escaped\n,,,** deeper\n, comma\n"
+        ));
+
+        let result = org_to_journal(&org, &TagPattern::default());
+        assert!(result.errors.is_empty());
+        assert_eq!(result.entries[0].body, body);
+    }
+
+    #[test]
+    fn org_to_journal_reports_bad_headlines() {
+        let org = "* Some notes\n** 09:00 Lost\n* <2025-08-15 Fri>\n** Morning Coffee\n";
+        let result = org_to_journal(org, &TagPattern::default());
+        assert!(result.entries.is_empty());
+        assert_eq!(result.errors.len(), 3);
+    }
+
+    #[test]
+    fn todos_round_trip() {
+        let todos = vec![
+            TodoEntry {
+                due_date: Some(dt(2025, 8, 20, 7, 0)),
+                done_date: None,
+                created: None,
+                title: "Buy milk @home".to_string(),
+                body: String::new(),
+                path: PathBuf::new(),
+                status: TodoStatus::Pending,
+                tags: Vec::new(),
+            },
+            TodoEntry {
+                due_date: Some(dt(2025, 8, 20, 7, 0)),
+                done_date: Some(dt(2025, 8, 22, 18, 0)),
+                created: None,
+                title: "Send invoices".to_string(),
+                body: "To everyone.".to_string(),
+                path: PathBuf::new(),
+                status: TodoStatus::Done,
+                tags: Vec::new(),
+            },
+        ];
+
+        let org = todos_to_org(&todos);
+        assert_eq!(
+            org,
+            "* TODO Buy milk @home\nDEADLINE: <2025-08-20 Wed 07:00>\n* DONE Send invoices\nCLOSED: [2025-08-22 Fri 18:00] DEADLINE: <2025-08-20 Wed 07:00>\nTo everyone.\n"
+        );
+
+        let default_time = NaiveTime::from_hms_opt(21, 0, 0).unwrap();
+        let result = org_to_todos(&org, default_time, &TagPattern::default());
+        assert!(result.errors.is_empty());
+        assert_eq!(result.entries.len(), 2);
+        assert_eq!(result.entries[0].due_date, Some(dt(2025, 8, 20, 7, 0)));
+        assert_eq!(result.entries[0].tags, vec!["@home"]);
+        assert!(matches!(result.entries[1].status, TodoStatus::Done));
+        assert_eq!(result.entries[1].done_date, Some(dt(2025, 8, 22, 18, 0)));
+        assert_eq!(result.entries[1].body, "To everyone.");
+    }
+
+    #[test]
+    fn org_to_todos_uses_default_time_and_skips_other_headlines() {
+        let org = "* Project\n** TODO Call Ana\n   SCHEDULED: <2025-08-20 Wed>\n** Notes\nNot a todo.\n";
+        let default_time = NaiveTime::from_hms_opt(21, 0, 0).unwrap();
+        let result = org_to_todos(org, default_time, &TagPattern::default());
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.entries[0].title, "Call Ana");
+        assert_eq!(result.entries[0].due_date, Some(dt(2025, 8, 20, 21, 0)));
+        assert!(result.entries[0].body.is_empty());
+    }
+}
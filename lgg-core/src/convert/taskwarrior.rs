@@ -0,0 +1,276 @@
+//! Taskwarrior export and import (the JSON of `task export` / `task import`).
+//!
+//! - `description` is the todo title and `annotations` its body.
+//! - `tags` are `@tags` and the `project` is a `#project` tag.
+//! - `pending` and `waiting` tasks are pending todos, or in progress ones if they were started.
+//!   `completed` ones are done. Deleted and recurring template tasks are skipped.
+//! - Cancelled todos are exported as `deleted` tasks.
+//! - The `entry` date of a task is the date the todo was added.
+//! - Dates are UTC in Taskwarrior and local time in lgg.
+use super::ImportResult;
+use crate::todos::{ParsedTodosEntry, TodoEntry, TodoStatus};
+use crate::tags::TagPattern;
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Task {
+    description: String,
+    status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<Annotation>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Annotation {
+    #[serde(default)]
+    entry: Option<String>,
+    description: String,
+}
+
+/// Renders todos as a JSON array `task import` understands. Their `tags` are split in Taskwarrior
+/// tags and project.
+pub fn todos_to_taskwarrior(todos: &[TodoEntry], tags: &TagPattern) -> String {
+    todos_to_taskwarrior_at(todos, tags, Local::now().naive_local())
+}
+
+/// `now` is used as creation date of the tasks and annotations of todos added before lgg recorded
+/// it.
+fn todos_to_taskwarrior_at(
+    todos: &[TodoEntry],
+    tags: &TagPattern,
+    now: NaiveDateTime,
+) -> String {
+    let now = to_utc(now);
+    let tasks: Vec<Task> = todos
+        .iter()
+        .map(|todo| {
+            let todo_tags = tags.extract(&format!("{}\n{}", todo.title, todo.body));
+            let project = todo_tags
+                .iter()
+                .find_map(|t| t.strip_prefix('#'))
+                .map(str::to_string);
+            let entry = todo.created.and_then(to_utc).or_else(|| now.clone());
+            let annotations = if todo.body.trim().is_empty() {
+                Vec::new()
+            } else {
+                vec![Annotation {
+                    entry: entry.clone(),
+                    description: todo.body.trim().to_string(),
+                }]
+            };
+            Task {
+                description: todo.title.clone(),
+                status: match todo.status {
+                    TodoStatus::Pending | TodoStatus::InProgress => "pending".to_string(),
+                    TodoStatus::Done => "completed".to_string(),
+                    TodoStatus::Cancelled => "deleted".to_string(),
+                },
+                entry,
+                // lgg doesn't know when a todo was started.
+                start: match todo.status {
+                    TodoStatus::InProgress => now.clone(),
+                    _ => None,
+                },
+                due: todo.due_date.and_then(to_utc),
+                end: todo.done_date.and_then(to_utc),
+                project,
+                tags: todo_tags
+                    .iter()
+                    .filter_map(|t| t.strip_prefix('@'))
+                    .map(str::to_string)
+                    .collect(),
+                annotations,
+            }
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&tasks).expect("tasks are always serializable")
+}
+
+/// Reads todos from the output of `task export`. Both the JSON array of Taskwarrior 2.5+
+/// and the one-task-per-line output of older versions are accepted. The tags of the todos are the
+/// matches of `tags`.
+pub fn taskwarrior_to_todos(content: &str, tags: &TagPattern) -> ImportResult<ParsedTodosEntry> {
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+
+    let tasks: Vec<Task> = if content.trim_start().starts_with('[') {
+        match serde_json::from_str(content) {
+            Ok(tasks) => tasks,
+            Err(e) => {
+                errors.push(format!("Invalid Taskwarrior export: {e}."));
+                Vec::new()
+            }
+        }
+    } else {
+        content
+            .lines()
+            .map(|line| line.trim().trim_end_matches(','))
+            .filter(|line| !line.is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(task) => Some(task),
+                Err(e) => {
+                    errors.push(format!("Invalid task `{line}`: {e}."));
+                    None
+                }
+            })
+            .collect()
+    };
+
+    for task in tasks {
+        let status = match task.status.as_str() {
+            "pending" | "waiting" if task.start.is_some() => TodoStatus::InProgress,
+            "pending" | "waiting" => TodoStatus::Pending,
+            "completed" => TodoStatus::Done,
+            _ => continue,
+        };
+
+        let mut title = task.description.trim().to_string();
+        let title_tags = tags.extract(&title);
+        let mut new_tags: Vec<String> = task.tags.iter().map(|t| format!("@{t}")).collect();
+        if let Some(project) = &task.project {
+            new_tags.push(format!("#{}", project.replace('.', "_")));
+        }
+        for tag in new_tags {
+            if !title_tags.contains(&tag.to_lowercase()) {
+                title.push(' ');
+                title.push_str(&tag);
+            }
+        }
+
+        let body = task
+            .annotations
+            .iter()
+            .map(|a| a.description.trim())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut parse_date = |date: &Option<String>| {
+            let date = date.as_deref()?;
+            let parsed = from_utc(date);
+            if parsed.is_none() {
+                errors.push(format!("Invalid date `{date}` in task `{}`.", task.description));
+            }
+            parsed
+        };
+        let due_date = parse_date(&task.due);
+        let created = parse_date(&task.entry);
+        let done_date = match status {
+            TodoStatus::Done => parse_date(&task.end),
+            _ => None,
+        };
+
+        entries.push(ParsedTodosEntry {
+            due_date,
+            done_date,
+            created,
+            tags: tags.extract(&format!("{title}\n{body}")),
+            title,
+            body,
+            status,
+        });
+    }
+
+    ImportResult { entries, errors }
+}
+
+fn to_utc(local: NaiveDateTime) -> Option<String> {
+    Local
+        .from_local_datetime(&local)
+        .earliest()
+        .map(|d| d.with_timezone(&Utc).format(DATE_FORMAT).to_string())
+}
+
+fn from_utc(date: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(date, DATE_FORMAT)
+        .ok()
+        .map(|d| Utc.from_utc_datetime(&d).with_timezone(&Local).naive_local())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::path::PathBuf;
+
+    fn dt(d: u32, h: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 8, d)
+            .unwrap()
+            .and_hms_opt(h, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn imports_taskwarrior_export() {
+        let json = r#"[
+{"id":1,"description":"Buy milk","status":"pending","project":"home.shopping","tags":["errand"],"due":"20250820T070000Z","uuid":"a"},
+{"id":0,"description":"Send invoices @work","status":"completed","tags":["work"],"end":"20250822T180000Z","annotations":[{"entry":"20250821T100000Z","description":"To everyone."}],"uuid":"b"},
+{"id":0,"description":"Old","status":"deleted","uuid":"c"}
+]"#;
+        let result = taskwarrior_to_todos(json, &TagPattern::default());
+        assert!(result.errors.is_empty());
+        assert_eq!(result.entries.len(), 2);
+
+        let milk = &result.entries[0];
+        assert_eq!(milk.title, "Buy milk @errand #home_shopping");
+        assert_eq!(milk.tags, vec!["#home_shopping", "@errand"]);
+        assert_eq!(milk.due_date, from_utc("20250820T070000Z"));
+        assert!(matches!(milk.status, TodoStatus::Pending));
+
+        let invoices = &result.entries[1];
+        assert_eq!(invoices.title, "Send invoices @work");
+        assert_eq!(invoices.body, "To everyone.");
+        assert_eq!(invoices.done_date, from_utc("20250822T180000Z"));
+        assert!(matches!(invoices.status, TodoStatus::Done));
+    }
+
+    #[test]
+    fn imports_one_task_per_line() {
+        let lines = "{\"description\":\"One\",\"status\":\"pending\"},\n{\"description\":\"Two\",\"status\":\"waiting\"}\nnot json\n";
+        let result = taskwarrior_to_todos(lines, &TagPattern::default());
+        assert_eq!(result.entries.len(), 2);
+        assert_eq!(result.errors.len(), 1);
+    }
+
+    #[test]
+    fn export_round_trip() {
+        let todos = vec![TodoEntry {
+            due_date: Some(dt(20, 7)),
+            done_date: Some(dt(22, 18)),
+            created: None,
+            title: "Send invoices @work #admin".to_string(),
+            body: "To everyone.".to_string(),
+            path: PathBuf::new(),
+            status: TodoStatus::Done,
+            tags: Vec::new(),
+        }];
+
+        let json = todos_to_taskwarrior_at(&todos, &TagPattern::default(), dt(15, 9));
+        let tasks: Vec<Task> = serde_json::from_str(&json).unwrap();
+        assert_eq!(tasks[0].status, "completed");
+        assert_eq!(tasks[0].project.as_deref(), Some("admin"));
+        assert_eq!(tasks[0].tags, vec!["work"]);
+
+        let result = taskwarrior_to_todos(&json, &TagPattern::default());
+        assert!(result.errors.is_empty());
+        let todo = &result.entries[0];
+        assert_eq!(todo.title, "Send invoices @work #admin");
+        assert_eq!(todo.body, "To everyone.");
+        assert_eq!(todo.due_date, Some(dt(20, 7)));
+        assert_eq!(todo.done_date, Some(dt(22, 18)));
+    }
+}
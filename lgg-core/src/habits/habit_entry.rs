@@ -0,0 +1,118 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::collections::{BTreeSet, HashMap};
+
+/// Something to do on some days of the week (`lgg habit add exercise --days mon,wed,fri`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Habit {
+    pub name: String,
+    /// Days of the week it's due. Every day when empty.
+    pub days: Vec<Weekday>,
+}
+
+/// Days each habit was done, by habit name.
+pub type HabitTicks = HashMap<String, BTreeSet<NaiveDate>>;
+
+/// Days in a row a habit was done.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Streaks {
+    /// The streak up to today. Today only ends it once it's over.
+    pub current: usize,
+    /// The longest streak.
+    pub best: usize,
+}
+
+impl Habit {
+    /// Whether it's due on `date`.
+    pub fn is_due(&self, date: NaiveDate) -> bool {
+        self.days.is_empty() || self.days.contains(&date.weekday())
+    }
+
+    /// The streaks of the days `ticks` until `today`. The days it's not due don't break them, and
+    /// count when it was done anyway.
+    pub fn streaks(&self, ticks: &BTreeSet<NaiveDate>, today: NaiveDate) -> Streaks {
+        let Some(first) = ticks.first() else {
+            return Streaks::default();
+        };
+        let mut streaks = Streaks::default();
+        let mut run = 0;
+        for date in first.iter_days().take_while(|date| *date <= today) {
+            if ticks.contains(&date) {
+                run += 1;
+            } else if self.is_due(date) && date < today {
+                run = 0;
+            }
+            streaks.best = streaks.best.max(run);
+        }
+        streaks.current = run;
+        streaks
+    }
+
+    /// The line of the habit in the habits file: `- exercise: mon, wed, fri`.
+    pub(crate) fn to_line(&self) -> String {
+        if self.days.is_empty() {
+            return format!("- {}", self.name);
+        }
+        let days: Vec<String> = self
+            .days
+            .iter()
+            .map(|day| day.to_string().to_lowercase())
+            .collect();
+        format!("- {}: {}", self.name, days.join(", "))
+    }
+
+    /// Reads a line written by [`Habit::to_line`]. `None` if it's not a habit.
+    pub(crate) fn from_line(line: &str) -> Option<Self> {
+        let line = line.trim().strip_prefix("- ")?;
+        let (name, days) = line.split_once(':').unwrap_or((line, ""));
+        let days = days
+            .split(',')
+            .map(str::trim)
+            .filter(|day| !day.is_empty())
+            .map(|day| day.parse().ok())
+            .collect::<Option<Vec<Weekday>>>()?;
+        let name = name.trim();
+        (!name.is_empty()).then(|| Self {
+            name: name.to_string(),
+            days,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 8, d).unwrap()
+    }
+
+    #[test]
+    fn streaks_skip_the_days_it_is_not_due() {
+        // Monday, Wednesday and Friday. 4 August 2025 was a Monday.
+        let habit = Habit {
+            name: "exercise".to_string(),
+            days: vec![Weekday::Mon, Weekday::Wed, Weekday::Fri],
+        };
+        let ticks: BTreeSet<NaiveDate> = [4, 6, 8, 11, 15, 18].into_iter().map(date).collect();
+        // Missed Wednesday the 13th.
+        assert_eq!(habit.streaks(&ticks, date(18)), Streaks { current: 2, best: 4 });
+        // Wednesday the 20th isn't over yet.
+        assert_eq!(habit.streaks(&ticks, date(20)).current, 2);
+        assert_eq!(habit.streaks(&ticks, date(21)).current, 0);
+        assert_eq!(habit.streaks(&BTreeSet::new(), date(21)), Streaks::default());
+    }
+
+    #[test]
+    fn lines_round_trip() {
+        let habit = Habit {
+            name: "read a book".to_string(),
+            days: vec![Weekday::Sat, Weekday::Sun],
+        };
+        assert_eq!(habit.to_line(), "- read a book: sat, sun");
+        assert_eq!(Habit::from_line(&habit.to_line()), Some(habit));
+        let daily = Habit::from_line("- water plants").unwrap();
+        assert!(daily.days.is_empty());
+        assert!(daily.is_due(date(16)));
+        assert!(Habit::from_line("# Habits").is_none());
+    }
+}
@@ -0,0 +1,187 @@
+use super::{
+    habit_entry::{Habit, HabitTicks},
+    habits_paths::{habits_file, month_file},
+};
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The habits and the days they were done. `habits.md` lists them, and each month has a file with
+/// the days of the month each one was done:
+///
+/// ```text
+/// # Habits 2025-08
+///
+/// exercise: 4 6 8 11
+/// read: 1 2 3
+/// ```
+#[derive(Debug)]
+pub struct Habits {
+    pub habits_dir: PathBuf,
+}
+impl Habits {
+    /// The habits, in the order they were added. Empty if there are none yet.
+    pub fn read_habits(&self) -> Result<Vec<Habit>> {
+        let path = habits_file(&self.habits_dir);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+        Ok(content.lines().filter_map(Habit::from_line).collect())
+    }
+
+    /// The habit called `name`, ignoring case.
+    pub fn find_habit(&self, name: &str) -> Result<Habit> {
+        let habits = self.read_habits()?;
+        match habits.into_iter().find(|h| h.name.eq_ignore_ascii_case(name.trim())) {
+            Some(habit) => Ok(habit),
+            None => bail!("There is no habit called `{name}`. Add it with `lgg habit add`."),
+        }
+    }
+
+    /// Adds a habit due on `days`, or every day if there are none.
+    pub fn add_habit(&self, name: &str, days: Vec<Weekday>) -> Result<Habit> {
+        let name = name.trim();
+        if name.is_empty() || name.contains([':', '\n']) {
+            bail!("Invalid habit name `{name}`. It can't be empty or have a `:`.");
+        }
+        let mut habits = self.read_habits()?;
+        if habits.iter().any(|h| h.name.eq_ignore_ascii_case(name)) {
+            bail!("There is already a habit called `{name}`.");
+        }
+        let habit = Habit {
+            name: name.to_string(),
+            days,
+        };
+        habits.push(habit.clone());
+
+        let mut content = String::from("# Habits\n\n");
+        for habit in &habits {
+            content.push_str(&habit.to_line());
+            content.push('\n');
+        }
+        write_file(&habits_file(&self.habits_dir), &content)?;
+        Ok(habit)
+    }
+
+    /// Marks `habit` as done on `date`. Returns `false` if it already was.
+    pub fn tick(&self, habit: &Habit, date: NaiveDate) -> Result<bool> {
+        let path = month_file(&self.habits_dir, date);
+        let mut ticks = HabitTicks::new();
+        read_month_file(&path, &mut ticks)?;
+        if !ticks.entry(habit.name.clone()).or_default().insert(date) {
+            return Ok(false);
+        }
+
+        let mut names: Vec<&String> = ticks.keys().collect();
+        names.sort();
+        let mut content = format!("# Habits {}\n\n", date.format("%Y-%m"));
+        for name in names {
+            let days: Vec<String> = ticks[name].iter().map(|d| d.day().to_string()).collect();
+            content.push_str(&format!("{name}: {}\n", days.join(" ")));
+        }
+        write_file(&path, &content)?;
+        Ok(true)
+    }
+
+    /// The days every habit was done, from the files of all months.
+    pub fn read_ticks(&self) -> Result<HabitTicks> {
+        let mut ticks = HabitTicks::new();
+        if !self.habits_dir.exists() {
+            return Ok(ticks);
+        }
+        let entries = fs::read_dir(&self.habits_dir)
+            .with_context(|| format!("reading {}", self.habits_dir.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            if path != habits_file(&self.habits_dir) {
+                read_month_file(&path, &mut ticks)?;
+            }
+        }
+        Ok(ticks)
+    }
+}
+
+/// Adds the ticks of a month file to `ticks`. Files not named like one (`2025-08.md`) and
+/// missing ones are skipped.
+fn read_month_file(path: &Path, ticks: &mut HabitTicks) -> Result<()> {
+    let month = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| NaiveDate::parse_from_str(&format!("{stem}-01"), "%Y-%m-%d").ok());
+    let Some(month) = month else {
+        return Ok(());
+    };
+    if !path.exists() {
+        return Ok(());
+    }
+    let content =
+        fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    for line in content.lines().filter(|line| !line.starts_with('#')) {
+        let Some((name, days)) = line.split_once(':') else {
+            continue;
+        };
+        let dates = ticks.entry(name.trim().to_string()).or_default();
+        for day in days.split_whitespace() {
+            let date = day.parse::<u32>().ok().and_then(|day| month.with_day(day));
+            match date {
+                Some(date) => {
+                    dates.insert(date);
+                }
+                None => bail!("Invalid day `{day}` in {}.", path.display()),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes `content` to `path`, creating its directory.
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("creating directory {}", parent.display()))?;
+    }
+    fs::write(path, content).with_context(|| format!("writing {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, m, d).unwrap()
+    }
+
+    #[test]
+    fn adds_habits_and_ticks_them_by_month() {
+        let tmp = tempdir().unwrap();
+        let habits = Habits {
+            habits_dir: tmp.path().join("habits"),
+        };
+        let exercise = habits
+            .add_habit("exercise", vec![Weekday::Mon, Weekday::Wed, Weekday::Fri])
+            .unwrap();
+        habits.add_habit("read", Vec::new()).unwrap();
+        assert!(habits.add_habit("Exercise", Vec::new()).is_err());
+        assert!(habits.find_habit("meditate").is_err());
+        assert_eq!(habits.find_habit("READ").unwrap().name, "read");
+        assert_eq!(habits.read_habits().unwrap().len(), 2);
+
+        assert!(habits.tick(&exercise, date(8, 11)).unwrap());
+        assert!(habits.tick(&exercise, date(8, 4)).unwrap());
+        assert!(!habits.tick(&exercise, date(8, 4)).unwrap());
+        let read = habits.find_habit("read").unwrap();
+        assert!(habits.tick(&read, date(9, 1)).unwrap());
+
+        let content = fs::read_to_string(tmp.path().join("habits/2025-08.md")).unwrap();
+        assert_eq!(content, "# Habits 2025-08\n\nexercise: 4 11\n");
+        let ticks = habits.read_ticks().unwrap();
+        assert_eq!(ticks["exercise"].len(), 2);
+        assert!(ticks["read"].contains(&date(9, 1)));
+    }
+}
@@ -0,0 +1,12 @@
+use chrono::{Datelike, NaiveDate};
+use std::path::{Path, PathBuf};
+
+/// Path to the file listing the habits, based on the habits dir.
+pub fn habits_file(root: &Path) -> PathBuf {
+    root.join("habits.md")
+}
+
+/// Path to the file with the ticks of the month of `date` (`{root}/2025-08.md`).
+pub fn month_file(root: &Path, date: NaiveDate) -> PathBuf {
+    root.join(format!("{:04}-{:02}.md", date.year(), date.month()))
+}
@@ -0,0 +1,6 @@
+mod habit_entry;
+mod habits;
+mod habits_paths;
+
+pub use habit_entry::{Habit, HabitTicks, Streaks};
+pub use habits::Habits;
@@ -0,0 +1,145 @@
+//! Things to do written in journal entries (`lgg scan-todos`).
+//!
+//! An action item is a line starting with `TODO:` or an empty checkbox (`[] buy milk`,
+//! `- [ ] buy milk`), or a sentence starting with "I need to".
+use super::JournalEntry;
+use crate::tags::TagPattern;
+use chrono::{NaiveDate, NaiveTime};
+
+const NEED_TO: &str = "i need to ";
+
+/// An action item and the entry it was found in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionItem {
+    /// What to do, ready to be a todo title (`Call the bank`).
+    pub text: String,
+    /// Tags in `text`.
+    pub tags: Vec<String>,
+    pub date: NaiveDate,
+    pub time: NaiveTime,
+    /// Title of the entry.
+    pub entry_title: String,
+}
+
+/// Action items in the titles and bodies of `entries`, in the order they were written, with their
+/// `tags`. The same item found twice is only returned the first time.
+pub fn find_action_items(entries: &[JournalEntry], tags: &TagPattern) -> Vec<ActionItem> {
+    let mut sorted: Vec<&JournalEntry> = entries.iter().collect();
+    sorted.sort_by_key(|e| (e.date, e.time));
+
+    let mut items: Vec<ActionItem> = Vec::new();
+    for entry in sorted {
+        let lines = entry.title.lines().chain(entry.body.lines());
+        for text in lines.flat_map(line_action_items) {
+            if items.iter().any(|i| i.text.eq_ignore_ascii_case(&text)) {
+                continue;
+            }
+            items.push(ActionItem {
+                tags: tags.extract(&text),
+                text,
+                date: entry.date,
+                time: entry.time,
+                entry_title: entry.title.clone(),
+            });
+        }
+    }
+    items
+}
+
+/// Action items of a line: the whole line after a `TODO:` or a checkbox, or else each sentence
+/// starting with "I need to".
+fn line_action_items(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let marked = line.strip_prefix("TODO:").or_else(|| strip_checkbox(line));
+    if let Some(text) = marked {
+        return clean(text).into_iter().collect();
+    }
+    line.split_inclusive(['.', '!', '?', ';'])
+        .filter_map(|sentence| {
+            let sentence = sentence.trim_start();
+            let start = sentence.get(..NEED_TO.len())?;
+            if !start.eq_ignore_ascii_case(NEED_TO) {
+                return None;
+            }
+            clean(&sentence[NEED_TO.len()..])
+        })
+        .collect()
+}
+
+/// The text after an empty checkbox: `[] `, `[ ] `, or either in a list (`- [ ] `, `* [ ] `).
+fn strip_checkbox(line: &str) -> Option<&str> {
+    let line = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .unwrap_or(line);
+    line.strip_prefix("[]").or_else(|| line.strip_prefix("[ ]"))
+}
+
+/// `text` without spaces and the final punctuation, its first letter in upper case. `None` if
+/// nothing is left.
+fn clean(text: &str) -> Option<String> {
+    let text = text.trim().trim_end_matches(['.', '!', '?', ';']).trim_end();
+    let mut chars = text.chars();
+    let first = chars.next()?;
+    Some(first.to_uppercase().chain(chars).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::text_counts;
+    use std::path::PathBuf;
+
+    fn entry(day: u32, title: &str, body: &str) -> JournalEntry {
+        let (word_count, char_count) = text_counts(title, body);
+        JournalEntry {
+            date: NaiveDate::from_ymd_opt(2025, 8, day).unwrap(),
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            title: title.to_string(),
+            body: body.to_string(),
+            tags: Vec::new(),
+            links: Vec::new(),
+            path: PathBuf::new(),
+            word_count,
+            char_count,
+        }
+    }
+
+    #[test]
+    fn finds_marked_lines_and_needs() {
+        let entries = vec![
+            entry(
+                15,
+                "Busy day.",
+                "TODO: send the report @work\n- [ ] buy milk\n[] water plants\n- [x] done already\nFelt tired. I need to call the bank! And rest.",
+            ),
+            entry(14, "I need to sleep more.", "Nothing to do here, no need to worry."),
+        ];
+        let items = find_action_items(&entries, &TagPattern::default());
+        let texts: Vec<&str> = items.iter().map(|i| i.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Sleep more",
+                "Send the report @work",
+                "Buy milk",
+                "Water plants",
+                "Call the bank"
+            ]
+        );
+        assert_eq!(items[0].date, NaiveDate::from_ymd_opt(2025, 8, 14).unwrap());
+        assert_eq!(items[1].entry_title, "Busy day.");
+        assert_eq!(items[1].tags, vec!["@work"]);
+    }
+
+    #[test]
+    fn skips_repeated_and_empty_items() {
+        let entries = vec![
+            entry(14, "Monday.", "TODO: buy milk"),
+            entry(15, "Tuesday.", "TODO: Buy milk.\nTODO:\n- [ ]"),
+        ];
+        let items = find_action_items(&entries, &TagPattern::default());
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].entry_title, "Monday.");
+    }
+}
@@ -0,0 +1,89 @@
+//! Tags added to the entries whose text matches a rule of `[auto_tags]` in the config
+//! (`"standup|retro" = "@meetings"`).
+//!
+//! New entries get them when they are written, and `lgg retag --apply-rules` adds them to the
+//! entries already in the journal (see [`Journal::retag_changes`](super::Journal::retag_changes)).
+use crate::tags::{TagAliases, TagPattern};
+use regex::{Regex, RegexBuilder};
+
+/// A rule of `[auto_tags]`: the entries matching `pattern`, in any case, get `tag`.
+#[derive(Debug, Clone)]
+pub struct AutoTagRule {
+    pub pattern: Regex,
+    pub tag: String,
+}
+
+/// The rule adding `tag` to the entries matching `pattern`.
+pub fn parse_rule(pattern: &str, tag: &str) -> Result<AutoTagRule, regex::Error> {
+    let pattern = RegexBuilder::new(pattern).case_insensitive(true).build()?;
+    Ok(AutoTagRule {
+        pattern,
+        tag: tag.to_string(),
+    })
+}
+
+/// The tags of the `rules` matching the `title` or `body` of an entry, without the ones it already
+/// has (the matches of `tags`), written as them or as one of their `aliases`.
+pub fn missing_tags(
+    rules: &[AutoTagRule],
+    tags: &TagPattern,
+    aliases: &TagAliases,
+    title: &str,
+    body: &str,
+) -> Vec<String> {
+    let text = format!("{title}\n{body}");
+    let mut found = aliases.canonical_tags(&tags.extract(&text));
+    let mut missing: Vec<String> = Vec::new();
+    for rule in rules.iter().filter(|rule| rule.pattern.is_match(&text)) {
+        let tag = aliases.canonical(&rule.tag);
+        if !found.contains(&tag) {
+            found.push(tag);
+            missing.push(rule.tag.clone());
+        }
+    }
+    missing
+}
+
+/// `title` with `tags` at its end, separated by spaces.
+pub fn append_tags(title: &str, tags: &[String]) -> String {
+    let title = title.trim_end();
+    let mut words = Vec::with_capacity(tags.len() + 1);
+    if !title.is_empty() {
+        words.push(title);
+    }
+    words.extend(tags.iter().map(String::as_str));
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matching_rules_add_the_tags_not_there_yet() {
+        let rules = vec![
+            parse_rule("standup|retro", "@meetings").unwrap(),
+            parse_rule(r"\bgym\b", "@health").unwrap(),
+            parse_rule("sprint", "@meetings").unwrap(),
+        ];
+        let aliases = TagAliases::new([("@gym".to_string(), "@health".to_string())]);
+        let none = TagAliases::default();
+        let tags = TagPattern::default();
+        assert_eq!(
+            missing_tags(&rules, &tags, &none, "Sprint Retro", "Then the gym."),
+            vec!["@meetings", "@health"]
+        );
+        let none_missing = Vec::<String>::new();
+        assert_eq!(missing_tags(&rules, &tags, &none, "Standup @Meetings", ""), none_missing);
+        assert_eq!(missing_tags(&rules, &tags, &none, "Gymnastics on TV", ""), none_missing);
+        assert_eq!(missing_tags(&rules, &tags, &aliases, "At the gym @gym", ""), none_missing);
+        assert!(parse_rule("(unclosed", "@x").is_err());
+    }
+
+    #[test]
+    fn tags_go_at_the_end_of_the_title() {
+        let tags = vec!["@work".to_string(), "@meetings".to_string()];
+        assert_eq!(append_tags("Standup. ", &tags), "Standup. @work @meetings");
+        assert_eq!(append_tags("", &tags), "@work @meetings");
+    }
+}
@@ -0,0 +1,160 @@
+//! Editor buffer to write entries for several days at once (`lgg compose`).
+//!
+//! The buffer has one `# YYYY-MM-DD, Weekday` section per day. Inside a section, every
+//! `## HH:MM - Title` (or `## Title`, at the default time) heading starts an entry and the text
+//! below it is the body. Text before the first heading of a section is an entry on its own,
+//! split in title and body like inline entries.
+use super::JournalWriteEntry;
+use super::parse_entries::parse_entry_time;
+use crate::convert::ImportResult;
+use crate::tags::TagPattern;
+use crate::utils::parse_input::split_title_body;
+use chrono::{NaiveDate, NaiveTime};
+
+const HELP_LINE: &str = "<!-- One section per day. Write entries as `## HH:MM - Title` (or `## Title`) followed by their body. Empty days are skipped. -->";
+
+/// Pre-seeded buffer with a section for each day in `dates`.
+pub fn compose_template(dates: &[NaiveDate]) -> String {
+    let mut out = format!("{HELP_LINE}\n\n");
+    for date in dates {
+        out.push_str(&format!("# {}\n\n\n", date.format("%Y-%m-%d, %A")));
+    }
+    out
+}
+
+/// Reads the entries written in a buffer made with [`compose_template`], with the `tags` of the
+/// journal.
+pub fn parse_compose_buffer(
+    content: &str,
+    default_time: NaiveTime,
+    tags: &TagPattern,
+) -> ImportResult<JournalWriteEntry> {
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    let mut date: Option<NaiveDate> = None;
+    // Heading (time and title) of the current entry, `None` for the text before the first heading.
+    let mut heading: Option<(NaiveTime, String)> = None;
+    let mut body: Vec<&str> = Vec::new();
+
+    let mut flush = |date: Option<NaiveDate>, heading: Option<(NaiveTime, String)>, body: &[&str]| {
+        let text = body.join("\n").trim().to_string();
+        let Some(date) = date else {
+            return;
+        };
+        let (time, title, body) = match heading {
+            Some((time, title)) => (time, title, text),
+            None if text.is_empty() => return,
+            None => {
+                let (title, body) = split_title_body(&text, tags);
+                (default_time, title, body)
+            }
+        };
+        entries.push(JournalWriteEntry {
+            date,
+            time,
+            tags: tags.extract(&format!("{title}\n{body}")),
+            title,
+            body,
+        });
+    };
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("<!--") {
+            continue;
+        }
+        if let Some(day) = line.strip_prefix("# ") {
+            flush(date, heading.take(), &body);
+            body.clear();
+            let iso = day.trim().get(..10).unwrap_or_default();
+            date = NaiveDate::parse_from_str(iso, "%Y-%m-%d").ok();
+            if date.is_none() {
+                errors.push(format!("Invalid day section `{line}`. Expected `# YYYY-MM-DD`."));
+            }
+        } else if let Some(title) = line.strip_prefix("## ") {
+            flush(date, heading.take(), &body);
+            body.clear();
+            let parsed_time = title
+                .split_once(" - ")
+                .and_then(|(t, rest)| parse_entry_time(t).map(|t| (t, rest)));
+            heading = Some(match parsed_time {
+                Some((time, rest)) => (time, rest.trim().to_string()),
+                None => (default_time, title.trim().to_string()),
+            });
+        } else {
+            body.push(line);
+        }
+    }
+    flush(date, heading.take(), &body);
+
+    ImportResult { entries, errors }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn template_has_a_section_per_day() {
+        let d1 = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let d2 = NaiveDate::from_ymd_opt(2025, 8, 16).unwrap();
+        let template = compose_template(&[d1, d2]);
+        assert!(template.contains("# 2025-08-15, Friday\n"));
+        assert!(template.contains("# 2025-08-16, Saturday\n"));
+
+        let result = parse_compose_buffer(&template, time(21, 0), &TagPattern::default());
+        assert!(result.entries.is_empty());
+        assert!(result.errors.is_empty());
+    }
+
+    #[test]
+    fn parses_entries_of_each_day() {
+        let buffer = r#"<!-- help -->
+
+# 2025-08-15, Friday
+
+Quick note. With a body @work.
+
+## 09:30 - Standup
+
+Short one.
+
+## Lunch
+
+# 2025-08-16, Saturday
+
+# 2025-08-17, Sunday
+
+## 18:00 - Walk
+"#;
+        let result = parse_compose_buffer(buffer, time(21, 0), &TagPattern::default());
+        assert!(result.errors.is_empty());
+        assert_eq!(result.entries.len(), 4);
+
+        let friday = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        assert_eq!(result.entries[0].date, friday);
+        assert_eq!(result.entries[0].time, time(21, 0));
+        assert_eq!(result.entries[0].title, "Quick note.");
+        assert_eq!(result.entries[0].body, "With a body @work.");
+        assert_eq!(result.entries[0].tags, vec!["@work"]);
+        assert_eq!(result.entries[1].time, time(9, 30));
+        assert_eq!(result.entries[1].body, "Short one.");
+        assert_eq!(result.entries[2].title, "Lunch");
+        assert_eq!(result.entries[2].time, time(21, 0));
+        assert_eq!(
+            result.entries[3].date,
+            NaiveDate::from_ymd_opt(2025, 8, 17).unwrap()
+        );
+    }
+
+    #[test]
+    fn reports_invalid_day_sections() {
+        let buffer = "# Someday\n\n## 10:00 - Lost\n";
+        let result = parse_compose_buffer(buffer, time(21, 0), &TagPattern::default());
+        assert!(result.entries.is_empty());
+        assert_eq!(result.errors.len(), 1);
+    }
+}
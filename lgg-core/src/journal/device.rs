@@ -0,0 +1,57 @@
+//! Device stamps: with `device_id` in the config, the entries written on a computer end with a
+//! comment naming it:
+//!
+//! ```text
+//! <!-- lgg:device laptop -->
+//! ```
+//!
+//! The stamp is hidden when entries are read. When `lgg sync` merges a day file, it tells apart
+//! entries with the same time and title written on different computers.
+
+const PREFIX: &str = "<!-- lgg:device ";
+const SUFFIX: &str = " -->";
+
+/// Whether `id` can be a `device_id`: one word of letters, digits, `-` and `_`.
+pub fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+/// `body` ending with the stamp of `device`.
+pub fn stamp(body: &str, device: &str) -> String {
+    let stamp = format!("{PREFIX}{device}{SUFFIX}");
+    if body.trim().is_empty() {
+        stamp
+    } else {
+        format!("{}\n\n{stamp}", body.trim_end())
+    }
+}
+
+/// `body` without its stamp, and the device of the stamp. `None` when it has no stamp.
+pub fn split_stamp(body: &str) -> (&str, Option<&str>) {
+    let trimmed = body.trim_end();
+    let (rest, last) = trimmed.rsplit_once('\n').unwrap_or(("", trimmed));
+    let device = last
+        .trim()
+        .strip_prefix(PREFIX)
+        .and_then(|last| last.strip_suffix(SUFFIX))
+        .filter(|device| is_valid_id(device));
+    match device {
+        Some(device) => (rest.trim_end(), Some(device)),
+        None => (body, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stamps_are_split_from_the_body() {
+        let body = stamp("Went for a run.\n", "laptop");
+        assert_eq!(body, "Went for a run.\n\n<!-- lgg:device laptop -->");
+        assert_eq!(split_stamp(&body), ("Went for a run.", Some("laptop")));
+        assert_eq!(split_stamp(&stamp("", "phone")), ("", Some("phone")));
+        assert_eq!(split_stamp("<!-- a comment -->"), ("<!-- a comment -->", None));
+        assert!(!is_valid_id("my laptop") && !is_valid_id("a-->"));
+    }
+}
@@ -0,0 +1,113 @@
+//! Editor buffer to write a new entry (`lgg` without text).
+//!
+//! The buffer starts with the parts set in `editor_template`: a help comment, the date prefix of
+//! the entry and a comment with the title of the previous one. Lines starting with `<!--` are
+//! left out when the entry is saved.
+use super::JournalEntry;
+use crate::config::TemplatePart;
+
+const HELP_LINES: &str = "<!-- The first line (or sentence) is the title, the rest is the body. -->
+<!-- Change the date before the colon, or remove it to write the entry now. Lines like this one are not saved. -->";
+
+/// Pre-seeded buffer with the `parts` of the template. `date_prefix` is the date of the entry as
+/// it would be typed, colon included (`15/08/2025 at 10:30:`).
+pub fn entry_template(
+    parts: &[TemplatePart],
+    date_prefix: &str,
+    last_entry: Option<&JournalEntry>,
+) -> String {
+    let mut out = String::new();
+    if parts.contains(&TemplatePart::Help) {
+        out.push_str(HELP_LINES);
+        out.push('\n');
+    }
+    if parts.contains(&TemplatePart::LastEntry)
+        && let Some(entry) = last_entry
+    {
+        out.push_str(&format!(
+            "<!-- Last entry: {} {} - {} -->\n",
+            entry.date.format("%Y-%m-%d"),
+            entry.time.format("%H:%M"),
+            entry.title
+        ));
+    }
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    if parts.contains(&TemplatePart::Date) {
+        out.push_str(date_prefix);
+        out.push(' ');
+    }
+    out
+}
+
+/// The entry written in a buffer made with [`entry_template`], without its comment lines. Empty
+/// if nothing but the date prefix is left.
+pub fn read_entry_buffer(content: &str, date_prefix: &str) -> String {
+    let text = content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("<!--"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let text = text.trim();
+    match text.split_once('\n') {
+        // The prefix alone on its line, as left by editors that trim trailing spaces.
+        Some((first, rest)) if first.trim_end() == date_prefix => {
+            format!("{date_prefix} {}", rest.trim_start())
+        }
+        None if text == date_prefix => String::new(),
+        _ => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveTime};
+    use std::path::PathBuf;
+
+    const PREFIX: &str = "15/08/2025 at 10:30:";
+
+    #[test]
+    fn template_has_the_parts_set() {
+        let entry = JournalEntry {
+            date: NaiveDate::from_ymd_opt(2025, 8, 14).unwrap(),
+            time: NaiveTime::from_hms_opt(21, 0, 0).unwrap(),
+            title: "Dinner with Ana.".to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+            links: Vec::new(),
+            path: PathBuf::new(),
+            word_count: 3,
+            char_count: 16,
+        };
+        let all = [TemplatePart::Help, TemplatePart::Date, TemplatePart::LastEntry];
+        let template = entry_template(&all, PREFIX, Some(&entry));
+        assert!(template.starts_with("<!-- The first line"));
+        assert!(template.contains("<!-- Last entry: 2025-08-14 21:00 - Dinner with Ana. -->\n"));
+        assert!(template.ends_with("\n\n15/08/2025 at 10:30: "));
+
+        assert_eq!(entry_template(&[TemplatePart::Date], PREFIX, None), format!("{PREFIX} "));
+        assert_eq!(entry_template(&[], PREFIX, Some(&entry)), "");
+    }
+
+    #[test]
+    fn reads_the_entry_without_comments() {
+        let template = entry_template(&[TemplatePart::Help, TemplatePart::Date], PREFIX, None);
+        assert_eq!(read_entry_buffer(&template, PREFIX), "");
+        assert_eq!(read_entry_buffer("", PREFIX), "");
+
+        let buffer = format!("{template}Standup.\n<!-- note -->\nShort one.\n");
+        assert_eq!(
+            read_entry_buffer(&buffer, PREFIX),
+            "15/08/2025 at 10:30: Standup.\nShort one."
+        );
+
+        let trimmed = format!("{PREFIX}\nStandup.\nShort one.\n");
+        assert_eq!(
+            read_entry_buffer(&trimmed, PREFIX),
+            "15/08/2025 at 10:30: Standup.\nShort one."
+        );
+        assert_eq!(read_entry_buffer("Just text.", PREFIX), "Just text.");
+    }
+}
@@ -0,0 +1,421 @@
+//! Small expression language to filter entries (`lgg --filter`):
+//!
+//! ```text
+//! date >= 2025-08-01 && tags.contains("@work") && words > 100
+//! (weekday == "sat" || weekday == "sun") && !title.contains("gym")
+//! ```
+//!
+//! The fields are `date` (`2025-08-01`), `time` (`09:30`), `words` (of the title and body),
+//! `weekday` (`"mon"`, `"monday"`), `title`, `body` and `tags`. They are compared with `==`,
+//! `!=`, `<`, `<=`, `>` and `>=`. `title` and `body` have `contains`, `starts_with` and
+//! `ends_with`, ignoring case, and `tags` has `contains`. Conditions are joined with `&&`, `||`,
+//! `!` and parentheses.
+use super::JournalEntry;
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, NaiveDate, NaiveTime, Weekday};
+
+/// A parsed `--filter` expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    expr: Expr,
+}
+
+impl Filter {
+    /// Reads `input`, failing with what's wrong in it.
+    pub fn parse(input: &str) -> Result<Self> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or()?;
+        if let Some(token) = parser.peek() {
+            bail!("Unexpected {} in the filter.", token.describe());
+        }
+        Ok(Self { expr })
+    }
+
+    /// Whether `entry` matches the expression.
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        self.expr.eval(entry)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Date(Op, NaiveDate),
+    Time(Op, NaiveTime),
+    Words(Op, usize),
+    Weekday(Op, Weekday),
+    Text(TextField, TextOp, String),
+    HasTag(String),
+}
+
+impl Expr {
+    fn eval(&self, entry: &JournalEntry) -> bool {
+        match self {
+            Expr::And(a, b) => a.eval(entry) && b.eval(entry),
+            Expr::Or(a, b) => a.eval(entry) || b.eval(entry),
+            Expr::Not(a) => !a.eval(entry),
+            Expr::Date(op, date) => op.test(entry.date, *date),
+            Expr::Time(op, time) => op.test(entry.time, *time),
+            Expr::Words(op, words) => op.test(entry.word_count, *words),
+            Expr::Weekday(op, day) => op.test(entry.date.weekday() == *day, true),
+            Expr::Text(field, op, text) => {
+                let value = match field {
+                    TextField::Title => entry.title.to_lowercase(),
+                    TextField::Body => entry.body.to_lowercase(),
+                };
+                match op {
+                    TextOp::Eq => value.trim() == text,
+                    TextOp::Ne => value.trim() != text,
+                    TextOp::Contains => value.contains(text.as_str()),
+                    TextOp::StartsWith => value.trim_start().starts_with(text.as_str()),
+                    TextOp::EndsWith => value.trim_end().ends_with(text.as_str()),
+                }
+            }
+            Expr::HasTag(tag) => entry
+                .tags
+                .iter()
+                .any(|t| t == tag || t.get(1..) == Some(tag.as_str())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    fn test<T: PartialOrd>(self, a: T, b: T) -> bool {
+        match self {
+            Op::Eq => a == b,
+            Op::Ne => a != b,
+            Op::Lt => a < b,
+            Op::Le => a <= b,
+            Op::Gt => a > b,
+            Op::Ge => a >= b,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TextField {
+    Title,
+    Body,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TextOp {
+    Eq,
+    Ne,
+    Contains,
+    StartsWith,
+    EndsWith,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A field or method name.
+    Ident(String),
+    /// A number, date or time, written without quotes.
+    Literal(String),
+    Str(String),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    Dot,
+    LParen,
+    RParen,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Ident(s) | Token::Literal(s) => format!("`{s}`"),
+            Token::Str(s) => format!("\"{s}\""),
+            Token::Op(op) => format!("`{}`", op_symbol(*op)),
+            Token::And => "`&&`".to_string(),
+            Token::Or => "`||`".to_string(),
+            Token::Not => "`!`".to_string(),
+            Token::Dot => "`.`".to_string(),
+            Token::LParen => "`(`".to_string(),
+            Token::RParen => "`)`".to_string(),
+        }
+    }
+}
+
+fn op_symbol(op: Op) -> &'static str {
+    match op {
+        Op::Eq => "==",
+        Op::Ne => "!=",
+        Op::Lt => "<",
+        Op::Le => "<=",
+        Op::Gt => ">",
+        Op::Ge => ">=",
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let (token, len) = match (c, next) {
+            (c, _) if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            ('=', Some('=')) => (Token::Op(Op::Eq), 2),
+            ('!', Some('=')) => (Token::Op(Op::Ne), 2),
+            ('<', Some('=')) => (Token::Op(Op::Le), 2),
+            ('>', Some('=')) => (Token::Op(Op::Ge), 2),
+            ('<', _) => (Token::Op(Op::Lt), 1),
+            ('>', _) => (Token::Op(Op::Gt), 1),
+            ('!', _) => (Token::Not, 1),
+            ('.', _) => (Token::Dot, 1),
+            ('(', _) => (Token::LParen, 1),
+            (')', _) => (Token::RParen, 1),
+            ('"' | '\'', _) => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|ch| *ch == c)
+                    .with_context(|| format!("Missing the closing {c} in the filter."))?;
+                let text: String = chars[i + 1..i + 1 + end].iter().collect();
+                (Token::Str(text), end + 2)
+            }
+            (c, _) if c.is_ascii_digit() => {
+                let len = chars[i..]
+                    .iter()
+                    .take_while(|ch| ch.is_ascii_digit() || matches!(ch, '-' | ':'))
+                    .count();
+                (Token::Literal(chars[i..i + len].iter().collect()), len)
+            }
+            (c, _) if c.is_alphabetic() || c == '_' => {
+                let len = chars[i..]
+                    .iter()
+                    .take_while(|ch| ch.is_alphanumeric() || **ch == '_')
+                    .count();
+                (Token::Ident(chars[i..i + len].iter().collect()), len)
+            }
+            ('=', _) => bail!("Use `==` to compare in the filter, not `=`."),
+            _ => bail!("Unexpected `{c}` in the filter."),
+        };
+        tokens.push(token);
+        i += len;
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => bail!(
+                "Expected {} in the filter, found {}.",
+                expected.describe(),
+                token.describe()
+            ),
+            None => bail!("Expected {} at the end of the filter.", expected.describe()),
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some(Token::LParen) => {
+                let expr = self.or()?;
+                self.expect(Token::RParen)?;
+                Ok(expr)
+            }
+            Some(Token::Ident(field)) => self.condition(&field),
+            Some(token) => bail!("Expected a field in the filter, found {}.", token.describe()),
+            None => bail!("The filter is incomplete, a condition is missing."),
+        }
+    }
+
+    /// A condition on `field`: a comparison, or a method like `.contains("x")`.
+    fn condition(&mut self, field: &str) -> Result<Expr> {
+        if self.peek() == Some(&Token::Dot) {
+            self.pos += 1;
+            let method = match self.next() {
+                Some(Token::Ident(method)) => method,
+                _ => bail!("Expected a method after `{field}.` in the filter."),
+            };
+            self.expect(Token::LParen)?;
+            let text = match self.next() {
+                Some(Token::Str(text)) => text.to_lowercase(),
+                _ => bail!("`{field}.{method}` takes a quoted text, like \"@work\"."),
+            };
+            self.expect(Token::RParen)?;
+            let op = match method.as_str() {
+                "contains" => TextOp::Contains,
+                "starts_with" => TextOp::StartsWith,
+                "ends_with" => TextOp::EndsWith,
+                _ => bail!("Unknown method `{method}`. Use contains, starts_with or ends_with."),
+            };
+            return match (field, op) {
+                ("title", op) => Ok(Expr::Text(TextField::Title, op, text)),
+                ("body", op) => Ok(Expr::Text(TextField::Body, op, text)),
+                ("tags", TextOp::Contains) => Ok(Expr::HasTag(text)),
+                ("tags", _) => bail!("`tags` only has `contains`."),
+                _ => bail!("`{field}` has no methods, compare it with `==`, `<`, `>`..."),
+            };
+        }
+
+        let op = match self.next() {
+            Some(Token::Op(op)) => op,
+            _ => bail!("Expected a comparison like `==` or `>=` after `{field}` in the filter."),
+        };
+        let value = match self.next() {
+            // Unquoted words too: `weekday == sat`.
+            Some(Token::Literal(value) | Token::Str(value) | Token::Ident(value)) => value,
+            _ => bail!("Expected a value after `{field} {}` in the filter.", op_symbol(op)),
+        };
+        let invalid = |expected: &str| {
+            format!("Invalid value `{value}` for `{field}` in the filter, expected {expected}.")
+        };
+        match field {
+            "date" => {
+                let date = NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+                    .with_context(|| invalid("a date like 2025-08-01"))?;
+                Ok(Expr::Date(op, date))
+            }
+            "time" => {
+                let time = NaiveTime::parse_from_str(&value, "%H:%M")
+                    .with_context(|| invalid("a time like 09:30"))?;
+                Ok(Expr::Time(op, time))
+            }
+            "words" => {
+                let words = value.parse().with_context(|| invalid("a number"))?;
+                Ok(Expr::Words(op, words))
+            }
+            "weekday" => {
+                let day = value
+                    .parse::<Weekday>()
+                    .ok()
+                    .with_context(|| invalid("a day like \"mon\""))?;
+                match op {
+                    Op::Eq | Op::Ne => Ok(Expr::Weekday(op, day)),
+                    _ => bail!("`weekday` can only be compared with `==` and `!=`."),
+                }
+            }
+            "title" | "body" => {
+                let text_field = match field {
+                    "title" => TextField::Title,
+                    _ => TextField::Body,
+                };
+                let text_op = match op {
+                    Op::Eq => TextOp::Eq,
+                    Op::Ne => TextOp::Ne,
+                    _ => bail!("`{field}` can only be compared with `==` and `!=`."),
+                };
+                Ok(Expr::Text(text_field, text_op, value.to_lowercase()))
+            }
+            "tags" => bail!("Use `tags.contains(\"@tag\")` to filter by tags."),
+            _ => bail!(
+                "Unknown field `{field}` in the filter. Use date, time, words, weekday, title, \
+                 body or tags."
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::text_counts;
+    use std::path::PathBuf;
+
+    fn entry(date: &str, time: &str, title: &str, body: &str, tags: &[&str]) -> JournalEntry {
+        let (word_count, char_count) = text_counts(title, body);
+        JournalEntry {
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            time: NaiveTime::parse_from_str(time, "%H:%M").unwrap(),
+            title: title.to_string(),
+            body: body.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            links: Vec::new(),
+            path: PathBuf::new(),
+            word_count,
+            char_count,
+        }
+    }
+
+    #[test]
+    fn evaluates_expressions() {
+        let standup = entry("2025-08-15", "09:30", "Standup", "Shipped the release", &["@work"]);
+        let gym = entry("2025-08-16", "18:00", "Gym", "Legs day", &["#health"]);
+        let matching = |filter: &str| {
+            let filter = Filter::parse(filter).unwrap();
+            [&standup, &gym]
+                .into_iter()
+                .filter(|e| filter.matches(e))
+                .map(|e| e.title.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(matching("date >= 2025-08-01 && tags.contains(\"@work\")"), ["Standup"]);
+        assert_eq!(matching("tags.contains('health')"), ["Gym"]);
+        assert_eq!(matching("words > 3 || weekday == \"sat\""), ["Standup", "Gym"]);
+        assert_eq!(matching("!(time < 12:00) && body.contains(\"LEGS\")"), ["Gym"]);
+        assert_eq!(matching("title == 'standup' && date != 2025-08-16"), ["Standup"]);
+        assert_eq!(matching("weekday == \"friday\" && title.starts_with(\"stand\")"), ["Standup"]);
+    }
+
+    #[test]
+    fn reports_mistakes() {
+        let error = |filter: &str| Filter::parse(filter).unwrap_err().to_string();
+        assert!(error("mood > 3").contains("Unknown field `mood`"));
+        assert!(error("date >= yesterday").contains("expected a date like 2025-08-01"));
+        assert!(error("words = 3").contains("`==`"));
+        assert!(error("(words > 3").contains("`)`"));
+        assert!(error("words > 3 &&").contains("incomplete"));
+        assert!(error("title.contains(\"x").contains("closing"));
+        assert!(error("words > 3 words").contains("Unexpected `words`"));
+    }
+}
@@ -1,21 +1,75 @@
-use chrono::{NaiveDate, NaiveTime};
+use crate::tags::TagPattern;
+use crate::utils::date_utils::format_date;
+use chrono::{Locale, NaiveDate, NaiveTime, Timelike};
+use once_cell::sync::Lazy;
+use regex::Regex;
 
-/// Returns an output like this: `# Friday, 15 Aug 2025`
-pub fn format_day_header(date_format: &str, date: NaiveDate) -> String {
-    format!("# {}\n\n", date.format(date_format).to_string())
+/// Returns an output like this: `# Friday, 15 Aug 2025` (or `# viernes, 15 ago 2025` with the
+/// `es_ES` locale).
+pub fn format_day_header(date_format: &str, date: NaiveDate, locale: Option<Locale>) -> String {
+    format!("# {}\n\n", format_date(date, date_format, locale))
 }
 
 /// Render an entry block. `# 12:30 - Title\nBody`
+///
+/// The time has seconds only when they aren't zero (see [`crate::TimePrecision`]).
+///
+/// Line breaks in the title become spaces, and body lines starting with `# ` or `## ` are escaped
+/// (see [`escape_body`]), so they can't be read back as a new entry (or, in Obsidian notes, as the
+/// end of the entry).
 pub fn format_journal_entry_block(title: &str, body: &str, time: &NaiveTime) -> String {
-    let time = time.format("%H:%M");
+    let time = match time.second() {
+        0 => time.format("%H:%M"),
+        _ => time.format("%H:%M:%S"),
+    };
+    let title = title.replace('\n', " ");
     if body.trim().is_empty() {
         format!("## {time} - {title}\n\n")
     } else {
-        let body = body.trim_end_matches('\n');
+        let body = escape_body(body.trim_end_matches('\n'));
         format!("## {time} - {title}\n\n{body}\n\n")
     }
 }
 
+/// Adds a backslash to body lines starting with `# ` or `## ` (a heading lgg reads), or with
+/// backslashes and one of them (an already escaped one). Markdown renders `\## ` as a literal `## `.
+pub fn escape_body(body: &str) -> String {
+    map_escaped_lines(body, |line| format!("\\{line}"))
+}
+
+/// Reverts [`escape_body`]: removes a backslash from body lines starting with backslashes and
+/// `# ` or `## `.
+pub fn unescape_body(body: &str) -> String {
+    map_escaped_lines(body, |line| line.strip_prefix('\\').unwrap_or(line).to_string())
+}
+
+/// Applies `f` to the lines matching `^\\*##? `, keeping the other ones (and the line breaks) as they are.
+fn map_escaped_lines(body: &str, f: impl Fn(&str) -> String) -> String {
+    static ESCAPED: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\\*##? ").unwrap());
+    if !body.contains("# ") {
+        return body.to_string();
+    }
+    body.split('\n')
+        .map(|line| {
+            if ESCAPED.is_match(line) {
+                f(line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Turns `@tags` (matches of `tags`) into Obsidian wiki-links: `Meeting @work` ->
+/// `Meeting [[work]]`. `#tags` are already Obsidian tags and stay as they are.
+pub fn tags_to_wiki_links(text: &str, tags: &TagPattern) -> String {
+    tags.replace(text, |tag| match tag.strip_prefix('@') {
+        Some(name) => format!("[[{name}]]"),
+        None => tag.to_string(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -35,4 +89,30 @@ mod tests {
         let s = format_journal_entry_block("Title only", "", &t);
         assert_eq!(s, "## 07:05 - Title only\n\n");
     }
+
+    #[test]
+    fn entry_block_escapes_headings_in_body() {
+        let t = NaiveTime::from_hms_opt(7, 5, 0).unwrap();
+        let body = "## Not an entry\n\\## Escaped\n# H1\n### H3";
+        let s = format_journal_entry_block("Notes", body, &t);
+        assert_eq!(
+            s,
+            "## 07:05 - Notes\n\n\\## Not an entry\n\\\\## Escaped\n\\# H1\n### H3\n\n"
+        );
+        assert_eq!(
+            unescape_body("\\## Not an entry\n\\\\## Escaped\n\\# H1\n### H3"),
+            "## Not an entry\n\\## Escaped\n# H1\n### H3"
+        );
+    }
+
+    #[test]
+    fn tags_become_wiki_links() {
+        assert_eq!(
+            tags_to_wiki_links(
+                "@meeting with @work #acme, mail me@example.com",
+                &TagPattern::default()
+            ),
+            "[[meeting]] with [[work]] #acme, mail me@example.com"
+        );
+    }
 }
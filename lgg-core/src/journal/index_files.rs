@@ -0,0 +1,138 @@
+//! The `index.md` files of the months and years of the journal (`index_files` in the config):
+//! the titles of their entries under a heading per day linking to its day file, so the journal
+//! directory is pleasant to browse from other Markdown tools.
+//!
+//! They are written again with the day files of their month, and all of them with
+//! `lgg index files`. They aren't day files, so they are never read as entries.
+use super::journal_entry::JournalEntry;
+use super::journal_paths::{day_file_name, month_folder_name};
+use crate::utils::date_utils::format_date;
+use chrono::{Datelike, Locale, NaiveDate};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Name of the index file in the directory of each month and year.
+pub const INDEX_FILE: &str = "index.md";
+
+/// Whether `path` is an index file rather than a day file.
+pub fn is_index_file(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == INDEX_FILE)
+}
+
+/// The index of the month of `month`, with its `entries` under a heading per day. The days are
+/// written with `date_format`, like the headers of the day files.
+pub fn month_index(
+    month: NaiveDate,
+    entries: &[JournalEntry],
+    date_format: &str,
+    locale: Option<Locale>,
+) -> String {
+    let mut index = format!("# {}\n", format_date(month, "%B %Y", locale));
+    let entries: Vec<&JournalEntry> = entries.iter().collect();
+    push_days(&mut index, "##", "", &entries, date_format, locale);
+    index
+}
+
+/// The index of the year of `year`, with a heading per month linking to its index, and the days
+/// and `entries` of the month under it.
+pub fn year_index(
+    year: NaiveDate,
+    entries: &[JournalEntry],
+    date_format: &str,
+    locale: Option<Locale>,
+) -> String {
+    let mut index = format!("# {}\n", year.year());
+    let mut by_month: BTreeMap<u32, Vec<&JournalEntry>> = BTreeMap::new();
+    for entry in entries {
+        by_month.entry(entry.date.month()).or_default().push(entry);
+    }
+    for entries in by_month.values() {
+        let month = entries[0].date;
+        let folder = month_folder_name(month);
+        let name = format_date(month, "%B", locale);
+        index.push_str(&format!("\n## [{name}]({folder}/{INDEX_FILE})\n"));
+        push_days(&mut index, "###", &format!("{folder}/"), entries, date_format, locale);
+    }
+    index
+}
+
+/// Pushes to `index` a `heading` per day of `entries` linking to its day file in `dir`, and a line
+/// with the time and title of each entry, by date and time.
+fn push_days(
+    index: &mut String,
+    heading: &str,
+    dir: &str,
+    entries: &[&JournalEntry],
+    date_format: &str,
+    locale: Option<Locale>,
+) {
+    let mut entries = entries.to_vec();
+    entries.sort_by_key(|entry| (entry.date, entry.time));
+    let mut day = None;
+    for entry in entries {
+        if day != Some(entry.date) {
+            day = Some(entry.date);
+            let name = format_date(entry.date, date_format, locale);
+            let file = day_file_name(entry.date);
+            index.push_str(&format!("\n{heading} [{name}]({dir}{file})\n\n"));
+        }
+        let line = format!("- {} {}", entry.time.format("%H:%M"), entry.title.trim());
+        index.push_str(line.trim_end());
+        index.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::text_counts;
+    use chrono::NaiveTime;
+    use std::path::PathBuf;
+
+    fn entry(month: u32, day: u32, time: &str, title: &str) -> JournalEntry {
+        let date = NaiveDate::from_ymd_opt(2025, month, day).unwrap();
+        let (word_count, char_count) = text_counts(title, "");
+        JournalEntry {
+            date,
+            time: NaiveTime::parse_from_str(time, "%H:%M").unwrap(),
+            title: title.to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+            links: Vec::new(),
+            path: PathBuf::new(),
+            word_count,
+            char_count,
+        }
+    }
+
+    #[test]
+    fn months_and_years_list_the_titles_of_their_days() {
+        let entries = vec![
+            entry(8, 15, "18:00", "Walk by the river"),
+            entry(8, 15, "09:00", "Standup @work"),
+            entry(9, 1, "08:30", "Back to school"),
+        ];
+        let format = "%A, %d %b %Y";
+        let month = NaiveDate::from_ymd_opt(2025, 8, 1).unwrap();
+        assert_eq!(
+            month_index(month, &entries[..2], format, None),
+            "# August 2025\n\
+             \n## [Friday, 15 Aug 2025](2025-08-15.md)\n\n\
+             - 09:00 Standup @work\n\
+             - 18:00 Walk by the river\n"
+        );
+        assert_eq!(
+            year_index(month, &entries, format, None),
+            "# 2025\n\
+             \n## [August](08/index.md)\n\
+             \n### [Friday, 15 Aug 2025](08/2025-08-15.md)\n\n\
+             - 09:00 Standup @work\n\
+             - 18:00 Walk by the river\n\
+             \n## [September](09/index.md)\n\
+             \n### [Monday, 01 Sep 2025](09/2025-09-01.md)\n\n\
+             - 08:30 Back to school\n"
+        );
+        assert!(is_index_file(Path::new("2025/08/index.md")));
+        assert!(!is_index_file(Path::new("2025/08/2025-08-15.md")));
+    }
+}
@@ -1,23 +1,45 @@
 //! The core `Journal` struct and its associated types, providing the primary API for interaction.
 use super::journal_entry::{
-    JournalEntry, JournalQueryResult, JournalWriteEntry, ReadEntriesOptions,
+    DayFileChange, EntryChange, JournalEntry, JournalQueryResult, JournalSearchResult,
+    EntrySort, JournalWriteEntry, ReadEntriesOptions, RelatedEntry, text_counts,
 };
+#[cfg(feature = "search")]
+use super::search::SearchIndex;
 use super::journal_paths::{day_file, month_dir, year_dir};
+use super::index_files::{self, is_index_file, INDEX_FILE};
 use crate::utils::date_utils::time_is_in_range;
-use crate::journal::format_utils::{format_day_header, format_journal_entry_block};
-use crate::journal::parse_entries::parse_journal_file_content;
+use super::obsidian::ObsidianVault;
+use super::parse_cache::ParseCache;
+use super::pins::{Pins, PINS_FILE};
+use super::links::extract_links;
+use super::related::most_similar;
+use super::device::{split_stamp, stamp};
+use super::auto_tags::{self, append_tags, AutoTagRule};
+use super::private::{is_sealed, open_sealed, seal_entry};
+use crate::journal::format_utils::{
+    format_day_header, format_journal_entry_block, tags_to_wiki_links,
+};
+use crate::journal::parsed_entry::UnparsedSegment;
+use crate::journal::parse_entries::{
+    parse_journal_file_content, parse_obsidian_note_content, split_front_matter, ParsePolicy,
+};
 use crate::utils::parse_input::parse_time_token;
 use crate::utils::date_utils::DateFilter;
-use crate::utils::path_utils::scan_dir_for_md_files;
+use crate::utils::text_utils::title_matches;
 use anyhow::anyhow;
-use anyhow::{Context, Result};
-use chrono::{Datelike, Days, NaiveDate};
-use std::collections::HashSet;
-use std::fs::{self, OpenOptions};
-use std::io::Write;
-use std::path::PathBuf;
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, Days, Locale, NaiveDate, NaiveTime};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use crate::config::{ParseMode, TimePrecision};
 use crate::entries::QueryTagsResult;
 use crate::QueryError;
+use crate::tags::{self, TagAliases, TagPattern};
+use crate::keywords::Keywords;
+use crate::AppDirs;
+use crate::Storage;
 
 /// The central struct for all journal operations.
 ///
@@ -29,6 +51,39 @@ pub struct Journal {
     pub journal_date_format: String,
     /// The date to use as "today" for relative keywords.
     pub reference_date: NaiveDate,
+    /// When set, `journal_dir` is an Obsidian vault and entries go to its daily notes.
+    pub obsidian: Option<ObsidianVault>,
+    /// Day files already parsed in this process. See [`Journal::invalidate`].
+    pub cache: ParseCache,
+    /// How to read day files that don't follow the format.
+    pub parse_mode: ParseMode,
+    /// Time of the entries without one, when `parse_mode` is lenient.
+    pub default_time: NaiveTime,
+    /// Whether new entries are written with seconds.
+    pub time_precision: TimePrecision,
+    /// Language of the weekday and month names of day headers.
+    pub locale: Option<Locale>,
+    /// The words read as times (`--at morning`).
+    pub keywords: Keywords,
+    /// Where the files lgg makes for the journal are kept, like the search index.
+    pub dirs: AppDirs,
+    /// Stamped on the new entries, see [`super::device`].
+    pub device_id: Option<String>,
+    /// Added to the new entries, see [`Journal::with_added_tags`].
+    pub default_tags: Vec<String>,
+    /// The `[auto_tags]` rules, see [`Journal::with_added_tags`].
+    pub auto_tags: Vec<AutoTagRule>,
+    /// The tags found in the entries.
+    pub tag_pattern: TagPattern,
+    /// Tags read as another one, in queries and [`Journal::search_all_tags`].
+    pub tag_aliases: TagAliases,
+    /// Tags left out of queries and [`Journal::search_all_tags`], lowercased.
+    pub ignored_tags: Vec<String>,
+    /// Whether the index files of the month and year of a day file are written with it (see
+    /// [`super::index_files`]).
+    pub index_files: bool,
+    /// Where the day files are read and written, the disk unless it's replaced.
+    pub storage: Arc<dyn Storage>,
 }
 impl Journal {
     /// Parses and saves a new entry from a single string.
@@ -36,74 +91,747 @@ impl Journal {
     /// Returns an [`JournalEntry`] with metadata about the saved entry.
     pub fn create_entry(&self, input: JournalWriteEntry) -> Result<JournalEntry> {
         let date = input.date;
-        let time = input.time;
-        let day_file = day_file(&self.journal_dir, date);
-        if let Some(parent) = day_file.parent() {
-            fs::create_dir_all(parent)
-                .with_context(|| format!("creating parent directory {}", parent.display()))?;
-        }
-
-        let is_new = !day_file.exists();
-        let header = format_day_header(&self.journal_date_format, date);
-        let block = format_journal_entry_block(&input.title, &input.body, &time);
-
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&day_file)
-            .with_context(|| format!("opening {}", day_file.display()))?;
-
-        if is_new {
-            writeln!(file, "{header}\n")
-                .with_context(|| format!("writing day header to {}", day_file.display()))?;
-            write!(file, "{block}")
-                .with_context(|| format!("appending entry to {}", day_file.display()))?;
+        let mut saved = self.write_day_entries(date, vec![input])?;
+        Ok(saved.remove(0))
+    }
+
+    /// `entry` with the `default_tags` and the tags of the `auto_tags` rules it matches that it
+    /// doesn't have yet at the end of its title, so they are saved in the day file like the tags
+    /// typed. Private entries are tagged before they are sealed, so a sealed entry is left as it is.
+    pub fn with_added_tags(&self, mut entry: JournalWriteEntry) -> JournalWriteEntry {
+        if is_sealed(&entry.body) {
+            return entry;
+        }
+        let found = self.tag_pattern.extract(&format!("{}\n{}", entry.title, entry.body));
+        let found = self.tag_aliases.canonical_tags(&found);
+        let mut tags: Vec<String> = self
+            .default_tags
+            .iter()
+            .filter(|tag| !found.contains(&self.tag_aliases.canonical(tag)))
+            .cloned()
+            .collect();
+        let title = append_tags(&entry.title, &tags);
+        let (rules, pattern, aliases) = (&self.auto_tags, &self.tag_pattern, &self.tag_aliases);
+        tags.extend(auto_tags::missing_tags(rules, pattern, aliases, &title, &entry.body));
+        if !tags.is_empty() {
+            entry.title = append_tags(&entry.title, &tags);
+            entry.tags.extend(tags);
+        }
+        entry
+    }
+
+    /// `entry` tagged with [`Journal::with_added_tags`] and then sealed with `passphrase`, so its
+    /// tags are saved in the sealed text.
+    pub fn sealed_entry(
+        &self,
+        entry: JournalWriteEntry,
+        passphrase: &str,
+    ) -> Result<JournalWriteEntry> {
+        seal_entry(self.with_added_tags(entry), passphrase)
+    }
+
+    /// Saves several entries in one pass: entries are grouped by day and every day file is
+    /// written once. Returns the saved entries, sorted by date and time.
+    pub fn create_entries(&self, inputs: Vec<JournalWriteEntry>) -> Result<Vec<JournalEntry>> {
+        let mut by_day: BTreeMap<NaiveDate, Vec<JournalWriteEntry>> = BTreeMap::new();
+        for input in inputs {
+            by_day.entry(input.date).or_default().push(input);
+        }
+
+        let mut saved = Vec::new();
+        for (date, inputs) in by_day {
+            saved.extend(self.write_day_entries(date, inputs)?);
+        }
+        Ok(saved)
+    }
+
+    /// Writes `inputs` (all of them for `date`) to their day file.
+    fn write_day_entries(
+        &self,
+        date: NaiveDate,
+        inputs: Vec<JournalWriteEntry>,
+    ) -> Result<Vec<JournalEntry>> {
+        let saved = self.append_day_entries(date, inputs)?;
+        self.day_file_written(&self.day_file(date));
+        Ok(saved)
+    }
+
+    fn append_day_entries(
+        &self,
+        date: NaiveDate,
+        inputs: Vec<JournalWriteEntry>,
+    ) -> Result<Vec<JournalEntry>> {
+        let (change, new_entries) = self.day_file_change(date, inputs)?;
+        self.storage
+            .write_file(&change.path, &change.after)
+            .with_context(|| format!("writing {}", change.path.display()))?;
+        Ok(new_entries)
+    }
+
+    /// What [`Journal::create_entries`] would change in the day files, without writing them.
+    pub fn preview_entries(&self, inputs: Vec<JournalWriteEntry>) -> Result<Vec<DayFileChange>> {
+        let mut by_day: BTreeMap<NaiveDate, Vec<JournalWriteEntry>> = BTreeMap::new();
+        for input in inputs {
+            by_day.entry(input.date).or_default().push(input);
+        }
+        by_day
+            .into_iter()
+            .map(|(date, inputs)| Ok(self.day_file_change(date, inputs)?.0))
+            .collect()
+    }
+
+    /// How writing `inputs` (all of them for `date`) changes their day file, and the entries as
+    /// they are saved. Nothing is written.
+    fn day_file_change(
+        &self,
+        date: NaiveDate,
+        mut inputs: Vec<JournalWriteEntry>,
+    ) -> Result<(DayFileChange, Vec<JournalEntry>)> {
+        let day_file = self.day_file(date);
+        inputs.sort_by_key(|input| input.time);
+        let mut new_entries: Vec<JournalEntry> = inputs
+            .into_iter()
+            .map(|input| {
+                let input = self.with_added_tags(input);
+                // The tags and links of the saved entry are the ones in its text, like when it's
+                // read back.
+                let text = format!("{}\n{}", input.title, input.body);
+                let (tags, links) = (self.tag_pattern.extract(&text), extract_links(&text));
+                let (word_count, char_count) = text_counts(&input.title, &input.body);
+                let (title, body) = match self.obsidian {
+                    Some(_) => (
+                        tags_to_wiki_links(&input.title, &self.tag_pattern),
+                        tags_to_wiki_links(&input.body, &self.tag_pattern),
+                    ),
+                    None => (input.title, input.body),
+                };
+                let body = match &self.device_id {
+                    Some(device) => stamp(&body, device),
+                    None => body,
+                };
+                JournalEntry {
+                    date,
+                    time: self.time_precision.truncate(input.time),
+                    title,
+                    body,
+                    tags,
+                    links,
+                    path: day_file.clone(),
+                    word_count,
+                    char_count,
+                }
+            })
+            .collect();
+
+        let header = format_day_header(&self.journal_date_format, date, self.locale);
+        let blocks: String = new_entries
+            .iter()
+            .map(|e| format_journal_entry_block(&e.title, &e.body, &e.time))
+            .collect();
+
+        let before = if self.storage.exists(&day_file) {
+            Some(
+                self.storage
+                    .read_file(&day_file)
+                    .with_context(|| format!("reading {}", day_file.display()))?,
+            )
         } else {
-            // Read the file and find, based on time, where to put the new entry.
-            let new_entry = JournalEntry {
-                date,
-                time,
-                title: input.title.to_string(),
-                body: input.body.to_string(),
-                tags: input.tags.clone(),
-                path: day_file.clone(),
-            };
-            let mut result = self.parse_file(&day_file);
+            None
+        };
+        let after = match &before {
+            None => format!("{header}\n\n{blocks}"),
+            // Obsidian notes can have any content, so we never rewrite them: the entry goes at the end.
+            Some(content) if self.obsidian.is_some() => {
+                let separator = if content.is_empty() || content.ends_with("\n\n") {
+                    ""
+                } else if content.ends_with('\n') {
+                    "\n"
+                } else {
+                    "\n\n"
+                };
+                format!("{content}{separator}{blocks}")
+            }
+            Some(content) => {
+                // Malformed entries are kept as they are on rewrite, but a file without a valid
+                // day header can't be rewritten.
+                let policy = self.parse_policy(&day_file);
+                if parse_journal_file_content(content, &policy).date.is_none() {
+                    // TODO: This function should be able to gracefully return errors.
+                    // We need to let the user know that there's a problem with their file.
+                    // We still append the entry because is better than simply erroring out.
+                    format!("{content}{header}\n\n{blocks}")
+                } else {
+                    // Find, based on time, where to put the new entries.
+                    let mut entries = self.parse_file(&day_file).entries;
+                    entries.extend(new_entries.iter().cloned());
+                    self.rewritten_day_content(&day_file, content, date, entries)
+                }
+            }
+        };
+        let change = DayFileChange {
+            path: day_file,
+            before,
+            after,
+        };
+        hide_device_stamps(&mut new_entries);
+        Ok((change, new_entries))
+    }
 
-            if !result.errors.is_empty() {
-                // TODO: This function should be able to gracefully return errors.
-                // We need to let the user know that there's a problem with their file.
-                // We still append the entry because is better than simply erroring out.
-                writeln!(file, "{header}\n")
-                    .with_context(|| format!("writing day header to {}", day_file.display()))?;
-                write!(file, "{block}")
-                    .with_context(|| format!("appending entry to {}", day_file.display()))?;
-
-                return Ok(new_entry);
+    /// Like [`Journal::create_entry`], but if the last entry of the day was written less than
+    /// `within` minutes before the new one, the new text is appended to its body instead.
+    /// Returns the merged (or new) entry and whether it was merged.
+    pub fn create_or_merge_entry(
+        &self,
+        input: JournalWriteEntry,
+        within: u32,
+    ) -> Result<(JournalEntry, bool)> {
+        let Some((entries, merged)) = self.merge_into_last_entry(&input, within) else {
+            return Ok((self.create_entry(input)?, false));
+        };
+        let day_file = self.day_file(input.date);
+        self.rewrite_day_file(&day_file, input.date, entries)?;
+        self.day_file_written(&day_file);
+        Ok((merged, true))
+    }
+
+    /// What [`Journal::create_or_merge_entry`] would change in the day file, without writing it.
+    pub fn preview_merge_entry(
+        &self,
+        input: JournalWriteEntry,
+        within: u32,
+    ) -> Result<DayFileChange> {
+        let Some((entries, _)) = self.merge_into_last_entry(&input, within) else {
+            return Ok(self.day_file_change(input.date, vec![input])?.0);
+        };
+        let day_file = self.day_file(input.date);
+        let before = self
+            .storage
+            .read_file(&day_file)
+            .with_context(|| format!("reading {}", day_file.display()))?;
+        let after = self.rewritten_day_content(&day_file, &before, input.date, entries);
+        Ok(DayFileChange {
+            path: day_file,
+            before: Some(before),
+            after,
+        })
+    }
+
+    /// The entries of the day of `input`, the text of `input` appended to the last one if it was
+    /// written less than `within` minutes before, and that entry. `None` if there's none to merge
+    /// into.
+    fn merge_into_last_entry(
+        &self,
+        input: &JournalWriteEntry,
+        within: u32,
+    ) -> Option<(Vec<JournalEntry>, JournalEntry)> {
+        let day_file = self.day_file(input.date);
+        // Obsidian notes are never rewritten, and a file we can't parse can't be merged into.
+        if self.obsidian.is_some() || !self.storage.exists(&day_file) {
+            return None;
+        }
+        let result = self.parse_file(&day_file);
+        if !result.errors.is_empty() {
+            return None;
+        }
+
+        let mut entries = result.entries;
+        let last = entries
+            .iter_mut()
+            .filter(|e| e.time <= input.time)
+            .max_by_key(|e| e.time)
+            .filter(|e| (input.time - e.time).num_minutes() < i64::from(within))
+            .filter(|e| !is_sealed(split_stamp(&e.body).0))?;
+
+        let text = if input.body.is_empty() {
+            input.title.clone()
+        } else {
+            format!("{} {}", input.title, input.body)
+        };
+        // The text goes before the device stamp, which stays the one of the entry.
+        let (body, device) = split_stamp(&last.body);
+        let body = if body.is_empty() {
+            text
+        } else {
+            format!("{body}\n\n{text}")
+        };
+        let device = device.map(str::to_string);
+        let text = format!("{}\n{}", last.title, body);
+        last.tags = self.tag_pattern.extract(&text);
+        last.links = extract_links(&text);
+        (last.word_count, last.char_count) = text_counts(&last.title, &body);
+        let merged = JournalEntry {
+            body: body.clone(),
+            ..last.clone()
+        };
+        last.body = match device {
+            Some(device) => stamp(&body, &device),
+            None => body,
+        };
+        Some((entries, merged))
+    }
+
+    /// Keeps the search index, if there's one, up to date after writing `day_file`.
+    #[cfg(feature = "search")]
+    fn index_written_file(&self, day_file: &Path) {
+        let search_dir = self.dirs.search_dir();
+        if !SearchIndex::exists(&search_dir) {
+            return;
+        }
+        // A failed update isn't worth failing the write: the next search syncs the file again.
+        if let Ok(mut index) = SearchIndex::open(&search_dir) {
+            let _ = index.update_file(self, day_file);
+        }
+    }
+
+    #[cfg(not(feature = "search"))]
+    fn index_written_file(&self, _day_file: &Path) {}
+
+    /// Keeps the search index and the index files up to date after writing `day_file`.
+    fn day_file_written(&self, day_file: &Path) {
+        self.index_written_file(day_file);
+        if !self.index_files || self.obsidian.is_some() {
+            return;
+        }
+        let Some(date) = self.parse_policy(day_file).file_date else {
+            return;
+        };
+        // Like the search index, they aren't worth failing the write: `lgg index files` fixes them.
+        if let Err(error) = self.write_index_files(date) {
+            log::warn!("writing the index files of {date}: {error:#}");
+        }
+    }
+
+    /// Writes the index files of the month and year of `date` (see [`super::index_files`]).
+    fn write_index_files(&self, date: NaiveDate) -> Result<()> {
+        let first = NaiveDate::from_ymd_opt(date.year(), 1, 1).unwrap();
+        let last = NaiveDate::from_ymd_opt(date.year(), 12, 31).unwrap();
+        let mut entries = self.read_range_date_entry(first, last).entries;
+        let (format, locale) = (&self.journal_date_format, self.locale);
+        let index = index_files::year_index(date, &entries, format, locale);
+        self.write_index_file(&year_dir(&self.journal_dir, date).join(INDEX_FILE), &index)?;
+        entries.retain(|entry| entry.date.month() == date.month());
+        let index = index_files::month_index(date, &entries, format, locale);
+        self.write_index_file(&month_dir(&self.journal_dir, date).join(INDEX_FILE), &index)?;
+        Ok(())
+    }
+
+    /// Writes the index files of every month and year with entries, whether `index_files` is on
+    /// or not (`lgg index files`). Returns how many of them changed.
+    pub fn write_all_index_files(&self) -> Result<usize> {
+        if self.obsidian.is_some() {
+            bail!("Index files are only written in lgg journals, not in Obsidian vaults.");
+        }
+        let mut by_year: BTreeMap<i32, Vec<JournalEntry>> = BTreeMap::new();
+        for entry in self.search_all_files().entries {
+            by_year.entry(entry.date.year()).or_default().push(entry);
+        }
+        let (format, locale) = (&self.journal_date_format, self.locale);
+        let mut changed = 0;
+        for entries in by_year.into_values() {
+            let year = entries[0].date;
+            let index = index_files::year_index(year, &entries, format, locale);
+            let path = year_dir(&self.journal_dir, year).join(INDEX_FILE);
+            changed += usize::from(self.write_index_file(&path, &index)?);
+            let mut by_month: BTreeMap<u32, Vec<JournalEntry>> = BTreeMap::new();
+            for entry in entries {
+                by_month.entry(entry.date.month()).or_default().push(entry);
+            }
+            for entries in by_month.into_values() {
+                let month = entries[0].date;
+                let index = index_files::month_index(month, &entries, format, locale);
+                let path = month_dir(&self.journal_dir, month).join(INDEX_FILE);
+                changed += usize::from(self.write_index_file(&path, &index)?);
             }
+        }
+        Ok(changed)
+    }
 
-            result.entries.push(new_entry);
-            result.entries.sort_by_key(|e| e.time);
-            let mut new_content = header;
-            for entry in result.entries {
-                let block = format_journal_entry_block(&entry.title, &entry.body, &entry.time);
+    /// Writes `content` to the index file at `path`, unless it's already there. Returns whether
+    /// the file changed.
+    fn write_index_file(&self, path: &Path, content: &str) -> Result<bool> {
+        if self.storage.read_file(path).is_ok_and(|old| old == content) {
+            return Ok(false);
+        }
+        self.storage
+            .write_file(path, content)
+            .with_context(|| format!("writing {}", path.display()))?;
+        Ok(true)
+    }
+
+    /// Writes all the `entries` of `date`, sorted by time (see [`Journal::rewritten_day_content`]).
+    fn rewrite_day_file(
+        &self,
+        day_file: &Path,
+        date: NaiveDate,
+        entries: Vec<JournalEntry>,
+    ) -> Result<()> {
+        let content = self
+            .storage
+            .read_file(day_file)
+            .with_context(|| format!("reading {}", day_file.display()))?;
+        let new_content = self.rewritten_day_content(day_file, &content, date, entries);
+        self.storage
+            .write_file(day_file, &new_content)
+            .with_context(|| format!("writing {}", day_file.display()))?;
+        Ok(())
+    }
+
+    /// `content` of `day_file` with all the `entries` of `date`, sorted by time, keeping the YAML
+    /// front matter of the file (if any).
+    ///
+    /// Text of the file that isn't an entry (free notes, malformed entries...) is kept too: after the
+    /// entry it followed, or after the header if it was before any entry.
+    fn rewritten_day_content(
+        &self,
+        day_file: &Path,
+        content: &str,
+        date: NaiveDate,
+        entries: Vec<JournalEntry>,
+    ) -> String {
+        let policy = self.parse_policy(day_file);
+        let unparsed = parse_journal_file_content(content, &policy).unparsed;
+        self.day_content(content, date, entries, unparsed)
+    }
+
+    /// The day file of `date` with the front matter of `content`, the `entries` sorted by time,
+    /// and each `unparsed` segment after the entry it followed.
+    fn day_content(
+        &self,
+        content: &str,
+        date: NaiveDate,
+        mut entries: Vec<JournalEntry>,
+        mut unparsed: Vec<UnparsedSegment>,
+    ) -> String {
+        entries.sort_by_key(|e| e.time);
+        let mut new_content = split_front_matter(content)
+            .0
+            .unwrap_or_default()
+            .to_string();
+        new_content.push_str(&format_day_header(&self.journal_date_format, date, self.locale));
+
+        let mut push_unparsed = |new_content: &mut String, after: Option<(NaiveTime, String)>| {
+            unparsed.retain(|segment| {
+                if segment.after != after {
+                    return true;
+                }
+                new_content.push_str(&segment.text);
+                new_content.push_str("\n\n");
+                false
+            });
+        };
+        push_unparsed(&mut new_content, None);
+        for entry in entries {
+            let block = format_journal_entry_block(&entry.title, &entry.body, &entry.time);
+
+            new_content.push_str(&block);
+            push_unparsed(&mut new_content, Some((entry.time, entry.title)));
+        }
+        // The entries they followed are gone, better at the end than lost.
+        for segment in unparsed {
+            new_content.push_str(&segment.text);
+            new_content.push_str("\n\n");
+        }
+        new_content
+    }
 
-                new_content.push_str(&block);
+    /// Merges two versions of the day file at `path` that both changed since `base`, entry by
+    /// entry. Entries are told apart by their time and title, and by their order among the ones
+    /// with the same time and title: the ones added or changed on either side are kept, and the
+    /// ones removed on one side and left as they were on the other are dropped. When both sides
+    /// changed the same entry, the `local` one is kept and the entry is returned as a conflict.
+    /// Text that isn't an entry is merged the same way. Files that aren't day files can't be
+    /// merged: `local` is kept.
+    pub fn merge_day_versions(
+        &self,
+        path: &Path,
+        base: Option<&str>,
+        local: &str,
+        remote: &str,
+    ) -> (String, Vec<String>) {
+        let Some(date) = self.parse_policy(path).file_date else {
+            let conflict = format!("{}: changed on both sides, kept the local one", path.display());
+            return (local.to_string(), vec![conflict]);
+        };
+        // Entries with the same time and title written on different computers are different
+        // entries, when they have a device stamp.
+        type Key = (NaiveTime, String, Option<String>);
+        let by_key = |content: &str| -> BTreeMap<Key, Vec<JournalEntry>> {
+            let mut by_key: BTreeMap<Key, Vec<JournalEntry>> = BTreeMap::new();
+            for entry in self.parse_content(path, content).0 {
+                let device = split_stamp(&entry.body).1.map(str::to_string);
+                by_key.entry((entry.time, entry.title.clone(), device)).or_default().push(entry);
+            }
+            by_key
+        };
+        let base_entries = base.map(by_key).unwrap_or_default();
+        let mut local_entries = by_key(local);
+        let mut remote_entries = by_key(remote);
+
+        let keys: BTreeSet<Key> = local_entries
+            .keys()
+            .chain(remote_entries.keys())
+            .cloned()
+            .collect();
+        let mut entries = Vec::new();
+        let mut conflicts = Vec::new();
+        for key in keys {
+            let base = base_entries.get(&key).map(Vec::as_slice).unwrap_or_default();
+            let local = local_entries.remove(&key).unwrap_or_default();
+            let remote = remote_entries.remove(&key).unwrap_or_default();
+            let mut local = local.into_iter();
+            let mut remote = remote.into_iter();
+            for i in 0.. {
+                let unchanged = |entry: &JournalEntry| {
+                    base.get(i).is_some_and(|base| base.body == entry.body)
+                };
+                match (local.next(), remote.next()) {
+                    (Some(local), Some(remote)) => {
+                        if local.body != remote.body && !unchanged(&remote) {
+                            if unchanged(&local) {
+                                entries.push(remote);
+                                continue;
+                            }
+                            let time = local.time.format("%H:%M");
+                            conflicts.push(format!(
+                                "{date} {time} - {}: changed on both sides, kept the local one",
+                                local.title
+                            ));
+                        }
+                        entries.push(local);
+                    }
+                    // Removed on the other side, and not changed on this one.
+                    (Some(entry), None) | (None, Some(entry)) if unchanged(&entry) => {}
+                    (Some(entry), None) | (None, Some(entry)) => entries.push(entry),
+                    (None, None) => break,
+                }
             }
+        }
+
+        // Text that isn't an entry, told apart by the text itself.
+        let policy = self.parse_policy(path);
+        let unparsed = |content: &str| parse_journal_file_content(content, &policy).unparsed;
+        let texts = |segments: &[UnparsedSegment]| -> HashSet<String> {
+            segments.iter().map(|segment| segment.text.clone()).collect()
+        };
+        let base_texts = texts(&base.map(unparsed).unwrap_or_default());
+        let (mut local_unparsed, remote_unparsed) = (unparsed(local), unparsed(remote));
+        let (local_texts, remote_texts) = (texts(&local_unparsed), texts(&remote_unparsed));
+        // Removed on the other side, and there since `base` on this one.
+        let removed = |text: &String, other: &HashSet<String>| {
+            base_texts.contains(text) && !other.contains(text)
+        };
+        local_unparsed.retain(|segment| !removed(&segment.text, &remote_texts));
+        local_unparsed.extend(remote_unparsed.into_iter().filter(|segment| {
+            !local_texts.contains(&segment.text) && !removed(&segment.text, &local_texts)
+        }));
+        (self.day_content(local, date, entries, local_unparsed), conflicts)
+    }
 
-            fs::write(&day_file, new_content)?;
+    /// How the entries of the day files in `from`, under `from_root`, changed in `to`, under
+    /// `to_root`. The oldest first, with their path in `journal_dir`.
+    pub fn diff_snapshots(
+        &self,
+        from: &dyn Storage,
+        from_root: &Path,
+        to: &dyn Storage,
+        to_root: &Path,
+    ) -> Result<Vec<EntryChange>> {
+        let read_files = |storage: &dyn Storage, root: &Path| -> Result<BTreeMap<PathBuf, String>> {
+            if !storage.exists(root) {
+                return Ok(BTreeMap::new());
+            }
+            let mut files = BTreeMap::new();
+            for path in storage.list_files(root)? {
+                let content = storage
+                    .read_file(&path)
+                    .with_context(|| format!("reading {}", path.display()))?;
+                if let Ok(relative) = path.strip_prefix(root) {
+                    files.insert(relative.to_path_buf(), content);
+                }
+            }
+            Ok(files)
+        };
+        let before = read_files(from, from_root)?;
+        let after = read_files(to, to_root)?;
+
+        // Entries with the same time and title are told apart by their order in the day.
+        type Key = (NaiveDate, NaiveTime, String, usize);
+        let mut changes = Vec::new();
+        let relatives: BTreeSet<&PathBuf> = before.keys().chain(after.keys()).collect();
+        for relative in relatives {
+            let (old, new) = (before.get(relative), after.get(relative));
+            if old == new {
+                continue;
+            }
+            let path = self.journal_dir.join(relative);
+            let by_key = |content: Option<&String>| -> BTreeMap<Key, JournalEntry> {
+                let Some(content) = content else {
+                    return BTreeMap::new();
+                };
+                let mut entries = self.parse_content(&path, content).0;
+                hide_device_stamps(&mut entries);
+                let mut seen: BTreeMap<(NaiveDate, NaiveTime, String), usize> = BTreeMap::new();
+                entries
+                    .into_iter()
+                    .map(|entry| {
+                        let count = seen
+                            .entry((entry.date, entry.time, entry.title.clone()))
+                            .or_default();
+                        *count += 1;
+                        ((entry.date, entry.time, entry.title.clone(), *count - 1), entry)
+                    })
+                    .collect()
+            };
+            let (mut old, mut new) = (by_key(old), by_key(new));
+            let keys: BTreeSet<Key> = old.keys().chain(new.keys()).cloned().collect();
+            for key in keys {
+                match (old.remove(&key), new.remove(&key)) {
+                    (Some(before), Some(after))
+                        if before.body != after.body || before.tags != after.tags =>
+                    {
+                        changes.push(EntryChange::Changed { before, after })
+                    }
+                    (Some(entry), None) => changes.push(EntryChange::Removed(entry)),
+                    (None, Some(entry)) => changes.push(EntryChange::Added(entry)),
+                    _ => {}
+                }
+            }
         }
+        changes.sort_by_key(|change| (change.entry().date, change.entry().time));
+        Ok(changes)
+    }
 
-        Ok(JournalEntry {
-            date,
-            time,
-            title: input.title,
-            body: input.body,
-            tags: input.tags,
-            path: day_file,
+    /// Saves entries coming from another tool, skipping the ones already in the journal
+    /// (same date, time and title). Returns how many entries were saved.
+    pub fn import_entries(&self, entries: Vec<JournalWriteEntry>) -> Result<usize> {
+        let mut imported = 0;
+        for entry in entries {
+            let existing = self.read_single_date_entry(entry.date);
+            let is_duplicate = existing
+                .entries
+                .iter()
+                .any(|e| e.time == entry.time && e.title == entry.title);
+            if is_duplicate {
+                continue;
+            }
+            self.create_entry(entry)?;
+            imported += 1;
+        }
+        Ok(imported)
+    }
+
+    /// How adding the tags of the `auto_tags` rules to the entries of `options` that match them
+    /// changes their day files. Nothing is written, see [`Journal::write_changes`]. Private
+    /// entries and the day files that can't be parsed are left as they are.
+    pub fn retag_changes(&self, options: &ReadEntriesOptions) -> Result<Vec<DayFileChange>> {
+        self.entry_changes(options, |entry| {
+            let body = split_stamp(&entry.body).0;
+            let (rules, pattern, aliases) = (&self.auto_tags, &self.tag_pattern, &self.tag_aliases);
+            let tags = auto_tags::missing_tags(rules, pattern, aliases, &entry.title, body);
+            if tags.is_empty() {
+                return false;
+            }
+            entry.title = append_tags(&entry.title, &tags);
+            true
         })
     }
 
+    /// How writing the `tag_aliases` of every entry as their canonical tag changes the day files.
+    /// Nothing is written, see [`Journal::write_changes`].
+    pub fn normalize_tags_changes(&self) -> Result<Vec<DayFileChange>> {
+        self.entry_changes(&ReadEntriesOptions::default(), |entry| {
+            let title = self.tag_aliases.normalize(&self.tag_pattern, &entry.title);
+            let body = self.tag_aliases.normalize(&self.tag_pattern, &entry.body);
+            if title == entry.title && body == entry.body {
+                return false;
+            }
+            entry.title = title;
+            entry.body = body;
+            true
+        })
+    }
+
+    /// How the day files of the entries of `options` change when `change` is applied to those
+    /// entries, with the text they have in the file. `change` returns whether it changed the
+    /// entry. Private entries and the day files that can't be parsed are left as they are.
+    fn entry_changes(
+        &self,
+        options: &ReadEntriesOptions,
+        mut change: impl FnMut(&mut JournalEntry) -> bool,
+    ) -> Result<Vec<DayFileChange>> {
+        if self.obsidian.is_some() {
+            bail!("Obsidian notes are never rewritten, their entries can't be changed.");
+        }
+        let mut by_day: BTreeMap<NaiveDate, HashSet<(NaiveTime, String)>> = BTreeMap::new();
+        for entry in self.read_entries(options).entries {
+            by_day
+                .entry(entry.date)
+                .or_default()
+                .insert((entry.time, entry.title));
+        }
+
+        let mut changes = Vec::new();
+        for (date, selected) in by_day {
+            let day_file = self.day_file(date);
+            let result = self.parse_file(&day_file);
+            if !result.errors.is_empty() {
+                continue;
+            }
+            let mut entries = result.entries;
+            let mut changed = false;
+            for entry in &mut entries {
+                if is_sealed(split_stamp(&entry.body).0)
+                    || !selected.contains(&(entry.time, entry.title.clone()))
+                {
+                    continue;
+                }
+                changed |= change(entry);
+            }
+            if !changed {
+                continue;
+            }
+            let before = self
+                .storage
+                .read_file(&day_file)
+                .with_context(|| format!("reading {}", day_file.display()))?;
+            let after = self.rewritten_day_content(&day_file, &before, date, entries);
+            changes.push(DayFileChange {
+                path: day_file,
+                before: Some(before),
+                after,
+            });
+        }
+        Ok(changes)
+    }
+
+    /// Writes the day files of `changes`, like the ones of [`Journal::retag_changes`] and
+    /// [`Journal::normalize_tags_changes`].
+    pub fn write_changes(&self, changes: &[DayFileChange]) -> Result<()> {
+        for change in changes {
+            self.storage
+                .write_file(&change.path, &change.after)
+                .with_context(|| format!("writing {}", change.path.display()))?;
+            self.day_file_written(&change.path);
+        }
+        Ok(())
+    }
+
+    /// Path of the file holding the entries of `date`.
+    pub fn day_file(&self, date: NaiveDate) -> PathBuf {
+        match &self.obsidian {
+            Some(vault) => vault.day_file(&self.journal_dir, date),
+            None => day_file(&self.journal_dir, date),
+        }
+    }
+
+    /// Root of the directory tree holding the day files.
+    pub(crate) fn notes_dir(&self) -> PathBuf {
+        match &self.obsidian {
+            Some(vault) => vault.notes_dir(&self.journal_dir),
+            None => self.journal_dir.clone(),
+        }
+    }
+
     /// Reads and returns all entries, the results can be filtered by `options`.
     ///
     /// This is the primary query function for retrieving entries. It is designed to be
@@ -136,53 +864,209 @@ impl Journal {
         }
 
         entries.sort_by_key(|k| k.date);
-
-        if let Some(time) = &options.time {
-            if let Some(parsed_time) = parse_time_token(time) {
-                entries = entries
-                    .into_iter()
-                    .filter(|entry| time_is_in_range(parsed_time, entry.time))
-                    .collect();
+        log::debug!("read {} entries, {} errors", entries.len(), errors.len());
+        hide_device_stamps(&mut entries);
+        open_private_entries(&mut entries, &self.tag_pattern, options.passphrase, &mut errors);
+        if !self.tag_aliases.is_empty() || !self.ignored_tags.is_empty() {
+            for entry in &mut entries {
+                entry.tags = tags::read_tags(&entry.tags, &self.tag_aliases, &self.ignored_tags);
             }
         }
 
+        if let Some(time) = &options.time
+            && let Some(parsed_time) = parse_time_token(time, &self.keywords)
+        {
+            entries.retain(|entry| time_is_in_range(parsed_time, entry.time));
+        }
+
         if let Some(tags) = &options.tags {
             let found_tags: Vec<String> = tags
-                .into_iter()
-                .map(|t| t.trim().to_ascii_lowercase())
+                .iter()
+                .map(|t| self.tag_aliases.canonical(t))
                 .collect();
 
-            entries = entries
-                .into_iter()
-                .filter(|e| found_tags.iter().any(|t| e.tags.contains(t)))
-                .collect();
+            entries.retain(|e| found_tags.iter().any(|t| e.tags.contains(t)));
+        }
+
+        if let Some(title) = options.title {
+            entries.retain(|e| title_matches(&e.title, title));
         }
 
+        if let Some(filter) = options.filter {
+            entries.retain(|e| filter.matches(e));
+        }
+
+        let min_words = options.min_words.unwrap_or(0);
+        let max_words = options.max_words.unwrap_or(usize::MAX);
+        entries.retain(|e| (min_words..=max_words).contains(&e.word_count));
+        if options.sort == EntrySort::Words {
+            entries.sort_by_key(|e| Reverse(e.word_count));
+        }
+
+        JournalQueryResult { entries, errors }
+    }
+
+    /// Entries dated after the reference date (today), the soonest first.
+    pub fn read_scheduled_entries(&self) -> JournalQueryResult {
+        let mut result = self.search_all_files();
+        result.entries.retain(|e| e.date > self.reference_date);
+        hide_device_stamps(&mut result.entries);
+        open_private_entries(&mut result.entries, &self.tag_pattern, None, &mut result.errors);
+        result.entries.sort_by_key(|e| (e.date, e.time));
+        result
+    }
+
+    /// The pinned days and entries of the journal.
+    pub fn pins(&self) -> Pins {
+        Pins {
+            path: self.journal_dir.join(PINS_FILE),
+        }
+    }
+
+    /// The pinned entries, and all the entries of the pinned days, oldest first.
+    pub fn read_pinned_entries(&self) -> JournalQueryResult {
+        let pins = match self.pins().read() {
+            Ok(pins) => pins,
+            Err(error) => {
+                let path = self.pins().path;
+                let errors = vec![QueryError::FileError { path, error }];
+                return JournalQueryResult {
+                    entries: Vec::new(),
+                    errors,
+                };
+            }
+        };
+        let mut dates: Vec<NaiveDate> = pins.iter().map(|pin| pin.date()).collect();
+        dates.sort();
+        dates.dedup();
+
+        let mut entries = Vec::new();
+        let mut errors = Vec::new();
+        for date in dates {
+            let result = self.read_single_date_entry(date);
+            entries.extend(
+                result
+                    .entries
+                    .into_iter()
+                    .filter(|entry| pins.iter().any(|pin| pin.matches(entry))),
+            );
+            errors.extend(result.errors);
+        }
+        entries.sort_by_key(|e| (e.date, e.time));
+        hide_device_stamps(&mut entries);
+        open_private_entries(&mut entries, &self.tag_pattern, None, &mut errors);
         JournalQueryResult { entries, errors }
     }
 
-    pub fn search_all_tags(&self) -> QueryTagsResult {
-        let mut tags: Vec<String> = Vec::new();
+    /// The latest entry on or before the reference date, looking back up to a year.
+    pub fn last_entry(&self) -> Option<JournalEntry> {
+        self.reference_date
+            .iter_days()
+            .rev()
+            .take(366)
+            .find_map(|date| {
+                let result = self.read_single_date_entry(date);
+                result.entries.into_iter().max_by_key(|e| e.time)
+            })
+    }
+
+    /// Full-text search of `query` in the titles and bodies of all entries, best matches first,
+    /// with `context` lines around the matches of their bodies.
+    ///
+    /// With the `search` feature, entries are ranked by an index kept in the `state_dir`, which is
+    /// first updated with the files changed since the last search. Without it, all the entries
+    /// containing every word of `query` are returned, newest first.
+    pub fn search_entries(
+        &self,
+        query: &str,
+        limit: usize,
+        context: usize,
+    ) -> Result<JournalSearchResult> {
+        #[cfg(feature = "search")]
+        let result = self.search_index(query, limit, context);
+        #[cfg(not(feature = "search"))]
+        let result = Ok(self.search_linear(query, limit, context));
+        result
+    }
+
+    #[cfg(feature = "search")]
+    fn search_index(
+        &self,
+        query: &str,
+        limit: usize,
+        context: usize,
+    ) -> Result<JournalSearchResult> {
+        let mut index = SearchIndex::open(&self.dirs.search_dir())?;
+        let errors = index.sync(self)?;
+        let hits = index.search(query, limit, context, &self.tag_pattern)?;
+        Ok(JournalSearchResult { hits, errors })
+    }
+
+    #[cfg(not(feature = "search"))]
+    fn search_linear(&self, query: &str, limit: usize, context: usize) -> JournalSearchResult {
+        let JournalQueryResult {
+            mut entries,
+            mut errors,
+        } = self.search_all_files();
+        hide_device_stamps(&mut entries);
+        // Private entries are only matched by their `[private]` title.
+        open_private_entries(&mut entries, &self.tag_pattern, None, &mut errors);
+        entries.retain(|e| title_matches(&format!("{}\n{}", e.title, e.body), query));
+        entries.sort_by_key(|e| std::cmp::Reverse((e.date, e.time)));
+        let words = super::snippets::query_words(query);
+        let hits = entries
+            .into_iter()
+            .take(limit)
+            .map(|entry| super::journal_entry::SearchHit {
+                snippets: super::snippets::search_snippets(&entry.body, &words, context),
+                entry,
+                score: 0.0,
+            })
+            .collect();
+        JournalSearchResult { hits, errors }
+    }
+
+    /// The `n` entries most like `entry`, by the words and tags they share weighted by TF-IDF,
+    /// most alike first. Private entries are left out.
+    pub fn related_entries(&self, entry: &JournalEntry, n: usize) -> Vec<RelatedEntry> {
+        let mut entries = self.search_all_files().entries;
+        entries.retain(|e| !is_sealed(split_stamp(&e.body).0));
+        hide_device_stamps(&mut entries);
+        most_similar(entry, &entries, n)
+    }
+
+    /// The entries that link to `date` (`[[2025-08-15]]`, or to one of its entries with
+    /// `[[2025-08-15T08:03]]`), oldest first. Private entries are only found when opened with
+    /// `passphrase`.
+    pub fn backlinks(&self, date: NaiveDate, passphrase: Option<&str>) -> JournalQueryResult {
+        let JournalQueryResult {
+            mut entries,
+            mut errors,
+        } = self.search_all_files();
+        hide_device_stamps(&mut entries);
+        open_private_entries(&mut entries, &self.tag_pattern, passphrase, &mut errors);
+        entries.retain(|entry| entry.links.iter().any(|link| link.date == date));
+        entries.sort_by_key(|entry| (entry.date, entry.time));
+        JournalQueryResult { entries, errors }
+    }
+
+    pub fn search_all_tags(&self, min_count: usize) -> QueryTagsResult {
+        let mut tags_of_entries: Vec<Vec<String>> = Vec::new();
         let mut errors = Vec::new();
 
-        if let Ok(files) = scan_dir_for_md_files(&self.journal_dir) {
+        if let Ok(files) = self.storage.list_files(&self.notes_dir()) {
+            log::debug!("found {} files in {}", files.len(), self.notes_dir().display());
             for file in files {
                 let parse_result = self.parse_file(&file);
                 for entry in parse_result.entries {
-                    tags.extend(entry.tags);
+                    let aliases = &self.tag_aliases;
+                    tags_of_entries.push(tags::read_tags(&entry.tags, aliases, &self.ignored_tags));
                 }
                 errors.extend(parse_result.errors);
             }
         }
 
-        tags = tags
-            .iter()
-            .map(|mat| mat.as_str().to_string().trim().to_ascii_lowercase())
-            .collect::<HashSet<_>>()
-            .into_iter()
-            .collect();
-        tags.sort();
-
+        let tags = tags::tags_with_min_count(tags_of_entries, min_count);
         QueryTagsResult { tags, errors }
     }
 
@@ -190,7 +1074,7 @@ impl Journal {
         let mut entries = Vec::new();
         let mut errors = Vec::new();
 
-        if let Ok(files) = scan_dir_for_md_files(&self.journal_dir) {
+        if let Ok(files) = self.storage.list_files(&self.notes_dir()) {
             for file in files {
                 let parse_result = self.parse_file(&file);
                 entries.extend(parse_result.entries);
@@ -216,52 +1100,188 @@ impl Journal {
     ///
     /// A `QueryResult` containing a `Vec<JournalEntry>` and `errors` in case some where found,
     /// if the path isn't valid, the file is empty or the header is malformed or a specific entry is invalid.
-    pub fn parse_file(&self, path: &PathBuf) -> JournalQueryResult {
+    pub fn parse_file(&self, path: &Path) -> JournalQueryResult {
         let mut entries = Vec::new();
         let mut errors = Vec::new();
-        if !path.exists() {
+        if !self.storage.exists(path) {
             errors.push(QueryError::FileError {
-                path: path.clone(),
+                path: path.to_path_buf(),
                 error: anyhow!(format!("File does not exist in path: {}", path.display())),
             });
             return JournalQueryResult { entries, errors };
         }
-        match fs::read_to_string(&path) {
-            Ok(file_content) => {
-                let parse_result = parse_journal_file_content(&file_content);
-                for entry in parse_result.entries {
-                    entries.push(JournalEntry {
-                        date: entry.date,
-                        time: entry.time,
-                        title: entry.title,
-                        body: entry.body,
-                        tags: entry.tags,
-                        path: path.clone(),
-                    });
+        let stamp = self.storage.stamp(path);
+        let cached = stamp.as_ref().and_then(|stamp| self.cache.get(path, stamp));
+        let read = match cached {
+            Some(cached) => {
+                log::trace!("{} is cached", path.display());
+                Ok(cached)
+            }
+            None => self.storage.read_file(path).map(|file_content| {
+                log::trace!("parsing {}", path.display());
+                let parsed = self.parse_content(path, &file_content);
+                if let Some(stamp) = &stamp {
+                    self.cache
+                        .insert(path, stamp, parsed.0.clone(), parsed.1.clone());
                 }
-
-                for error in parse_result.errors {
+                parsed
+            }),
+        };
+        match read {
+            Ok((parsed_entries, parse_errors)) => {
+                entries.extend(parsed_entries);
+                for error in parse_errors {
                     errors.push(QueryError::FileError {
-                        path: path.clone(),
+                        path: path.to_path_buf(),
                         error: anyhow!(error),
                     });
                 }
             }
             Err(error) => {
                 errors.push(QueryError::FileError {
-                    path: path.clone(),
-                    error: error.into(),
+                    path: path.to_path_buf(),
+                    error,
                 });
             }
         }
         JournalQueryResult { entries, errors }
     }
 
+    /// Entries and parse errors of the content of the day file at `path`.
+    fn parse_content(&self, path: &Path, file_content: &str) -> (Vec<JournalEntry>, Vec<String>) {
+        let parse_result = match &self.obsidian {
+            Some(vault) => match vault.date_from_path(&self.journal_dir, path) {
+                Some(date) => parse_obsidian_note_content(file_content, date, &self.tag_pattern),
+                // Any other note of the vault, not a journal file.
+                None => return (Vec::new(), Vec::new()),
+            },
+            None if is_index_file(path) => return (Vec::new(), Vec::new()),
+            None => parse_journal_file_content(file_content, &self.parse_policy(path)),
+        };
+        let entries = parse_result
+            .entries
+            .into_iter()
+            .map(|entry| {
+                // Counted without the device stamp, which is hidden when the entry is read.
+                let (word_count, char_count) =
+                    text_counts(&entry.title, split_stamp(&entry.body).0);
+                JournalEntry {
+                    date: entry.date,
+                    time: entry.time,
+                    title: entry.title,
+                    body: entry.body,
+                    tags: entry.tags,
+                    links: entry.links,
+                    path: path.to_path_buf(),
+                    word_count,
+                    char_count,
+                }
+            })
+            .collect();
+        (entries, parse_result.errors)
+    }
+
+    /// Parse rules for the day file at `path`, which is named after its date (`YYYY-MM-DD.md`).
+    fn parse_policy(&self, path: &Path) -> ParsePolicy {
+        let file_date = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok());
+        ParsePolicy {
+            mode: self.parse_mode,
+            default_time: self.default_time,
+            file_date,
+            date_format: self.journal_date_format.clone(),
+            locale: self.locale,
+            tags: self.tag_pattern.clone(),
+        }
+    }
+
+    /// Problems of every day file, read in strict mode whatever the `parse_mode` is.
+    pub fn check_files(&self) -> Vec<QueryError> {
+        let mut errors = Vec::new();
+        let Ok(files) = self.storage.list_files(&self.notes_dir()) else {
+            return errors;
+        };
+        for path in files.into_iter().filter(|path| !is_index_file(path)) {
+            let content = match self.storage.read_file(&path) {
+                Ok(content) => content,
+                Err(error) => {
+                    errors.push(QueryError::FileError { path, error });
+                    continue;
+                }
+            };
+            let parse_errors = match &self.obsidian {
+                Some(_) => self.parse_content(&path, &content).1,
+                None => {
+                    let policy = ParsePolicy {
+                        mode: ParseMode::Strict,
+                        ..self.parse_policy(&path)
+                    };
+                    parse_journal_file_content(&content, &policy).errors
+                }
+            };
+            for error in parse_errors {
+                errors.push(QueryError::FileError {
+                    path: path.clone(),
+                    error: anyhow!(error),
+                });
+            }
+        }
+        errors
+    }
+
+    /// Writes the day header of the file at `path` when it's missing or invalid, with the date
+    /// of the file name. Returns whether the file changed.
+    pub fn fix_day_header(&self, path: &Path) -> Result<bool> {
+        // Obsidian daily notes don't have a day header.
+        if self.obsidian.is_some() {
+            return Ok(false);
+        }
+        let policy = self.parse_policy(path);
+        let Some(date) = policy.file_date else {
+            return Ok(false);
+        };
+        let content = self
+            .storage
+            .read_file(path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        if parse_journal_file_content(&content, &policy).date.is_some() {
+            return Ok(false);
+        }
+
+        let (front_matter, rest) = split_front_matter(&content);
+        // A broken header is replaced, otherwise the first line is already part of the day.
+        let rest = match rest.split_once('\n') {
+            Some((first, rest)) if first.starts_with("# ") => rest,
+            None if rest.starts_with("# ") => "",
+            _ => rest,
+        };
+        let new_content = format!(
+            "{}{}{}",
+            front_matter.unwrap_or_default(),
+            format_day_header(&self.journal_date_format, date, self.locale),
+            rest.trim_start_matches('\n')
+        );
+        self.storage
+            .write_file(path, &new_content)
+            .with_context(|| format!("writing {}", path.display()))?;
+        self.day_file_written(path);
+        Ok(true)
+    }
+
+    /// Forgets the day files parsed so far, so the next queries read them again from disk.
+    /// Files are already read again when their modification time or size changes, this is
+    /// for changes that keep both (or to free memory).
+    pub fn invalidate(&self) {
+        self.cache.clear();
+    }
+
     fn read_single_date_entry(&self, date: NaiveDate) -> JournalQueryResult {
         let mut entries = Vec::new();
         let mut errors = Vec::new();
-        let day_file = day_file(&self.journal_dir, date);
-        if day_file.exists() {
+        let day_file = self.day_file(date);
+        if self.storage.exists(&day_file) {
             let parse_result = self.parse_file(&day_file);
             entries.extend(parse_result.entries);
             errors.extend(parse_result.errors);
@@ -284,22 +1304,36 @@ impl Journal {
 
         let mut start_date = range_start;
 
+        if self.obsidian.is_some() {
+            // The vault layout is user defined, so we can't skip whole years or months.
+            while start_date <= range_end {
+                let day_file = self.day_file(start_date);
+                if self.storage.exists(&day_file) {
+                    let parse_result = self.parse_file(&day_file);
+                    entries.extend(parse_result.entries);
+                    errors.extend(parse_result.errors);
+                }
+                start_date = start_date.checked_add_days(Days::new(1)).unwrap();
+            }
+            return JournalQueryResult { entries, errors };
+        }
+
         while start_date <= range_end {
             let year_dir = year_dir(&self.journal_dir, start_date);
-            if !year_dir.exists() {
+            if !self.storage.exists(&year_dir) {
                 let next_year = start_date.year() + 1;
                 start_date = NaiveDate::from_ymd_opt(next_year, 1, 1).unwrap();
                 continue;
             }
             let month_dir = month_dir(&self.journal_dir, start_date);
-            if !month_dir.exists() && start_date.month() < 12 {
+            if !self.storage.exists(&month_dir) && start_date.month() < 12 {
                 let year = start_date.year();
                 let next_month = start_date.month() + 1;
                 start_date = NaiveDate::from_ymd_opt(year, next_month, 1).unwrap();
                 continue;
             }
             let day_file = day_file(&self.journal_dir, start_date);
-            if day_file.exists() {
+            if self.storage.exists(&day_file) {
                 let parse_result = self.parse_file(&day_file);
                 entries.extend(parse_result.entries);
                 errors.extend(parse_result.errors);
@@ -312,10 +1346,53 @@ impl Journal {
     }
 }
 
+/// Takes the device stamps out of the bodies of `entries`, they are only kept in the files.
+fn hide_device_stamps(entries: &mut [JournalEntry]) {
+    for entry in entries {
+        let (body, device) = split_stamp(&entry.body);
+        if device.is_some() {
+            entry.body = body.to_string();
+        }
+    }
+}
+
+/// Opens the private entries of `entries` with `passphrase`. Without one, or when it's wrong,
+/// they keep their `[private]` title and lose the sealed body.
+fn open_private_entries(
+    entries: &mut [JournalEntry],
+    tags: &TagPattern,
+    passphrase: Option<&str>,
+    errors: &mut Vec<QueryError>,
+) {
+    for entry in entries.iter_mut().filter(|e| is_sealed(&e.body)) {
+        match passphrase.map(|passphrase| open_sealed(&entry.body, passphrase)) {
+            Some(Ok((title, body))) => {
+                let text = format!("{title}\n{body}");
+                entry.tags = tags.extract(&text);
+                entry.links = extract_links(&text);
+                entry.title = title;
+                entry.body = body;
+            }
+            Some(Err(error)) => {
+                entry.body.clear();
+                let time = entry.time.format("%H:%M");
+                errors.push(QueryError::FileError {
+                    path: entry.path.clone(),
+                    error: anyhow!("{error} for the private entry at {time}"),
+                });
+            }
+            None => entry.body.clear(),
+        }
+        (entry.word_count, entry.char_count) = text_counts(&entry.title, &entry.body);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::journal::Pin;
     use crate::tests::mk_config;
+    use crate::{FsStorage, MemoryStorage};
     use chrono::{Local, NaiveTime};
     use std::fs;
     use tempfile::tempdir;
@@ -326,9 +1403,25 @@ mod tests {
         let config = mk_config(root, reference_date);
 
         let j = Journal {
+            dirs: AppDirs::for_journal(config.state_dir.as_deref(), &config.journal_dir),
+            device_id: None,
+            default_tags: Vec::new(),
+            auto_tags: Vec::new(),
+            tag_pattern: TagPattern::default(),
+            tag_aliases: TagAliases::default(),
+            ignored_tags: Vec::new(),
+            index_files: false,
+            storage: Arc::new(FsStorage),
             journal_dir: config.journal_dir,
             journal_date_format: config.journal_date_format,
             reference_date: config.reference_date,
+            obsidian: None,
+            cache: ParseCache::default(),
+            parse_mode: ParseMode::Strict,
+            default_time: NaiveTime::from_hms_opt(21, 0, 0).unwrap(),
+            time_precision: TimePrecision::Minutes,
+            locale: None,
+            keywords: Keywords::default(),
         };
         (j, tmp)
     }
@@ -348,10 +1441,293 @@ mod tests {
         assert_eq!(res.path, expected);
         assert!(res.path.exists());
 
-        let s = fs::read_to_string(&res.path).unwrap();
-        assert!(s.starts_with("# "));
-        assert!(s.contains("## "));
-        assert!(s.contains("Test entry"));
+        let s = fs::read_to_string(&res.path).unwrap();
+        assert!(s.starts_with("# "));
+        assert!(s.contains("## "));
+        assert!(s.contains("Test entry"));
+    }
+
+    #[test]
+    fn localized_custom_headers_are_read_back() {
+        let (mut j, _tmp) = mk_journal_with_default(None);
+        j.locale = Some(Locale::fr_FR);
+        j.journal_date_format = "%A %d %B %Y".to_string();
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        for (hour, title) in [(9, "Standup."), (18, "Wrap up.")] {
+            j.create_entry(JournalWriteEntry {
+                date,
+                time: NaiveTime::from_hms_opt(hour, 0, 0).unwrap(),
+                title: title.to_string(),
+                body: "".to_string(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        }
+
+        let content = fs::read_to_string(j.day_file(date)).unwrap();
+        assert!(content.starts_with("# vendredi 15 août 2025\n"));
+        assert_eq!(content.matches("\n# ").count(), 0);
+        let result = j.read_entries(&ReadEntriesOptions {
+            dates: Some(DateFilter::Single(date)),
+            ..Default::default()
+        });
+        assert!(result.errors.is_empty());
+        assert_eq!(result.entries.len(), 2);
+    }
+
+    #[test]
+    fn entries_are_written_and_read_back_in_memory() {
+        let (mut j, _tmp) = mk_journal_with_default(None);
+        j.storage = Arc::new(MemoryStorage::default());
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        for (hour, title) in [(9, "Standup."), (18, "Wrap up.")] {
+            j.create_entry(JournalWriteEntry {
+                date,
+                time: NaiveTime::from_hms_opt(hour, 0, 0).unwrap(),
+                title: title.to_string(),
+                body: "".to_string(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        }
+
+        let options = ReadEntriesOptions {
+            dates: Some(DateFilter::Range(date, date)),
+            ..Default::default()
+        };
+        let result = j.read_entries(&options);
+        assert!(result.errors.is_empty());
+        let titles: Vec<&str> = result.entries.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Standup.", "Wrap up."]);
+        assert!(!j.journal_dir.exists());
+    }
+
+    #[test]
+    fn default_tags_are_saved_with_the_new_entries() {
+        let (mut j, _tmp) = mk_journal_with_default(None);
+        j.storage = Arc::new(MemoryStorage::default());
+        j.default_tags = vec!["@work".to_string(), "@acme".to_string()];
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let saved = j
+            .create_entry(JournalWriteEntry {
+                date,
+                time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                title: "Standup.".to_string(),
+                body: "Release of @ACME next week.".to_string(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        assert_eq!(saved.title, "Standup. @work");
+        assert_eq!(saved.tags, vec!["@acme", "@work"]);
+        let day = j.storage.read_file(&saved.path).unwrap();
+        assert!(day.contains("## 09:00 - Standup. @work\n"));
+    }
+
+    #[test]
+    fn auto_tags_tag_new_entries_and_retag_old_ones() {
+        let (mut j, _tmp) = mk_journal_with_default(None);
+        j.storage = Arc::new(MemoryStorage::default());
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        for (hour, title) in [(9, "Daily standup."), (12, "Lunch."), (17, "Retro @meetings")] {
+            j.create_entry(JournalWriteEntry {
+                date,
+                time: NaiveTime::from_hms_opt(hour, 0, 0).unwrap(),
+                title: title.to_string(),
+                body: String::new(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        }
+        j.auto_tags = vec![auto_tags::parse_rule("standup|retro", "@meetings").unwrap()];
+
+        let options = ReadEntriesOptions::default();
+        let changes = j.retag_changes(&options).unwrap();
+        assert_eq!(changes.len(), 1);
+        j.write_changes(&changes).unwrap();
+        let entries = j.read_entries(&options).entries;
+        let titles: Vec<&str> = entries.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Daily standup. @meetings", "Lunch.", "Retro @meetings"]);
+        assert!(j.retag_changes(&options).unwrap().is_empty());
+
+        let saved = j
+            .create_entry(JournalWriteEntry {
+                date,
+                time: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+                title: "Notes of the STANDUP".to_string(),
+                body: String::new(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        assert_eq!(saved.title, "Notes of the STANDUP @meetings");
+    }
+
+    #[test]
+    fn tag_aliases_are_found_as_their_tag_and_normalized() {
+        let (mut j, _tmp) = mk_journal_with_default(None);
+        j.storage = Arc::new(MemoryStorage::default());
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        for (hour, title) in [(9, "Walk @dogs"), (12, "Vet @dog"), (18, "Cinema @movies")] {
+            j.create_entry(JournalWriteEntry {
+                date,
+                time: NaiveTime::from_hms_opt(hour, 0, 0).unwrap(),
+                title: title.to_string(),
+                body: String::new(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        }
+        j.tag_aliases = TagAliases::new([("@dogs".to_string(), "@dog".to_string())]);
+
+        let tags = vec!["@dogs".to_string()];
+        let options = ReadEntriesOptions {
+            tags: Some(&tags),
+            ..Default::default()
+        };
+        let found = j.read_entries(&options).entries;
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|e| e.tags == vec!["@dog"]));
+        assert_eq!(j.search_all_tags(1).tags, vec!["@dog", "@movies"]);
+
+        let changes = j.normalize_tags_changes().unwrap();
+        assert_eq!(changes.len(), 1);
+        j.write_changes(&changes).unwrap();
+        assert!(changes[0].after.contains("## 09:00 - Walk @dog\n"));
+        assert!(j.normalize_tags_changes().unwrap().is_empty());
+
+        j.ignored_tags = vec!["@movies".to_string()];
+        let all = ReadEntriesOptions::default();
+        assert!(j.read_entries(&all).entries[2].tags.is_empty());
+        assert_eq!(j.search_all_tags(1).tags, vec!["@dog"]);
+        assert!(j.search_all_tags(3).tags.is_empty());
+    }
+
+    #[test]
+    fn each_journal_reads_tags_with_its_own_pattern() {
+        let (mut dotted, _tmp) = mk_journal_with_default(None);
+        dotted.storage = Arc::new(MemoryStorage::default());
+        dotted.tag_pattern = TagPattern::new(r"[@#]\w+(?:[.-]\w+)*").unwrap();
+        let (mut plain, _plain_tmp) = mk_journal_with_default(None);
+        plain.storage = Arc::new(MemoryStorage::default());
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        for j in [&dotted, &plain] {
+            j.create_entry(JournalWriteEntry {
+                date,
+                time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                title: "Released @v1.2-beta".to_string(),
+                body: String::new(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        }
+
+        let all = ReadEntriesOptions::default();
+        assert_eq!(dotted.read_entries(&all).entries[0].tags, vec!["@v1.2-beta"]);
+        assert_eq!(plain.read_entries(&all).entries[0].tags, vec!["@v1"]);
+    }
+
+    #[test]
+    fn device_stamps_are_hidden_and_tell_apart_entries_of_the_same_minute() {
+        let (mut j, _tmp) = mk_journal_with_default(None);
+        j.storage = Arc::new(MemoryStorage::default());
+        j.device_id = Some("laptop".to_string());
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let saved = j
+            .create_entry(JournalWriteEntry {
+                date,
+                time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                title: "Standup.".to_string(),
+                body: "Notes.".to_string(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        assert_eq!(saved.body, "Notes.");
+        let local = j.storage.read_file(&saved.path).unwrap();
+        assert!(local.contains("Notes.\n\n<!-- lgg:device laptop -->"));
+        let options = ReadEntriesOptions {
+            dates: Some(DateFilter::Single(date)),
+            ..Default::default()
+        };
+        assert_eq!(j.read_entries(&options).entries[0].body, "Notes.");
+
+        // The same minute and title on another computer is another entry.
+        let remote = local.replace("Notes.", "Other notes.").replace("laptop", "phone");
+        let (merged, conflicts) = j.merge_day_versions(&saved.path, None, &local, &remote);
+        assert!(conflicts.is_empty());
+        assert!(merged.contains("device laptop") && merged.contains("device phone"));
+    }
+
+    #[test]
+    fn merged_days_keep_repeated_entries_and_the_text_of_both_sides() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let path = day_file(&j.journal_dir, NaiveDate::from_ymd_opt(2025, 8, 15).unwrap());
+        let header = "# Friday, 15 Aug 2025\n\n";
+        let (water, walk) = ("## 09:00 - Water\n\nPlants.\n\n", "## 18:00 - Walk\n\n");
+        let base = format!("{header}{water}{water}Old note\n\n");
+        let local = format!("{header}{water}{water}Old note\n\n{walk}");
+        let remote = format!("{header}Remote note\n\n{water}{water}");
+
+        let (merged, conflicts) = j.merge_day_versions(&path, Some(&base), &local, &remote);
+        assert!(conflicts.is_empty());
+        assert_eq!(merged, format!("{header}Remote note\n\n{water}{water}{walk}"));
+        assert_eq!(j.merge_day_versions(&path, Some(&base), &local, &remote).0, merged);
+
+        // Without a base, both sides added the same entries.
+        let twice = format!("{header}{water}{water}");
+        let (merged, _) = j.merge_day_versions(&path, None, &twice, &format!("{header}{water}"));
+        assert_eq!(merged, twice);
+    }
+
+    #[test]
+    fn snapshots_are_diffed_entry_by_entry() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let (before, after) = (MemoryStorage::default(), MemoryStorage::default());
+        let root = Path::new("/backup");
+        let day = "2025/08/2025-08-15.md";
+        let header = "# Friday, 15 Aug 2025\n\n";
+        let standup = "## 09:00 - Standup\n\nShort one.\n\n";
+        let lunch = "## 13:00 - Lunch\n\n";
+        before.write_file(&root.join(day), &format!("{header}{standup}{lunch}")).unwrap();
+        let standup = standup.replace("Short one.", "Long one.");
+        let review = "## 18:00 - Review\n\n";
+        after
+            .write_file(&j.journal_dir.join(day), &format!("{header}{standup}{review}"))
+            .unwrap();
+
+        let changes = j.diff_snapshots(&before, root, &after, &j.journal_dir).unwrap();
+        let summary: Vec<(&str, &str)> = changes
+            .iter()
+            .map(|change| match change {
+                EntryChange::Added(e) => ("added", e.title.as_str()),
+                EntryChange::Changed { after, .. } => ("changed", after.title.as_str()),
+                EntryChange::Removed(e) => ("removed", e.title.as_str()),
+            })
+            .collect();
+        let expected = [("changed", "Standup"), ("removed", "Lunch"), ("added", "Review")];
+        assert_eq!(summary, expected);
+        assert!(changes[0].body_diff().contains("-Short one.\n+Long one."));
+        assert_eq!(changes[2].entry().path, j.journal_dir.join(day));
+    }
+
+    #[test]
+    fn repeated_entries_are_diffed_one_by_one() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let (before, after) = (MemoryStorage::default(), MemoryStorage::default());
+        let root = Path::new("/backup");
+        let day = "2025/08/2025-08-15.md";
+        let header = "# Friday, 15 Aug 2025\n\n";
+        let water = "## 10:00 - Water\n\nA glass.\n\n";
+        before.write_file(&root.join(day), &format!("{header}{water}")).unwrap();
+        after
+            .write_file(&j.journal_dir.join(day), &format!("{header}{water}{water}"))
+            .unwrap();
+
+        let changes = j.diff_snapshots(&before, root, &after, &j.journal_dir).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(&changes[0], EntryChange::Added(e) if e.title == "Water"));
+
+        let changes = j.diff_snapshots(&after, &j.journal_dir, &before, root).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(&changes[0], EntryChange::Removed(e) if e.title == "Water"));
     }
 
     // --- Tests for read_entries ---
@@ -591,6 +1967,133 @@ mod tests {
         assert!(results.entries[0].tags.contains(&"@test".to_string()));
     }
 
+    #[test]
+    fn read_entries_with_title_filter() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        for (h, title) in [(9, "Project kickoff"), (12, "Lunch"), (18, "Kickoff retro")] {
+            j.create_entry(JournalWriteEntry {
+                date,
+                time: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+                title: title.to_string(),
+                body: String::new(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        }
+
+        let options = ReadEntriesOptions {
+            dates: Some(DateFilter::Single(date)),
+            title: Some("kickoff"),
+            ..Default::default()
+        };
+        let results = j.read_entries(&options);
+        assert_eq!(results.entries.len(), 2);
+
+        let options = ReadEntriesOptions {
+            title: Some("kickoff project"),
+            ..Default::default()
+        };
+        let results = j.read_entries(&options);
+        assert_eq!(results.entries.len(), 1);
+        assert_eq!(results.entries[0].title, "Project kickoff");
+    }
+
+    #[test]
+    fn read_entries_by_their_words() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let entries = [
+            (9, "Gym", ""),
+            (12, "Lunch with Ana", "Tacos 🌮 at the new place."),
+            (18, "Retro", "Shipped it. Next sprint is about the search index and the sync."),
+        ];
+        for (h, title, body) in entries {
+            j.create_entry(JournalWriteEntry {
+                date,
+                time: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+                title: title.to_string(),
+                body: body.to_string(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        }
+
+        let all = j.read_entries(&ReadEntriesOptions::default()).entries;
+        let counts: Vec<(usize, usize)> =
+            all.iter().map(|e| (e.word_count, e.char_count)).collect();
+        assert_eq!(counts, vec![(1, 3), (9, 39), (13, 68)]);
+
+        let titles = |min_words, max_words| -> Vec<String> {
+            let options = ReadEntriesOptions {
+                min_words,
+                max_words,
+                ..Default::default()
+            };
+            j.read_entries(&options).entries.into_iter().map(|e| e.title).collect()
+        };
+        assert_eq!(titles(Some(5), None), vec!["Lunch with Ana", "Retro"]);
+        assert_eq!(titles(None, Some(9)), vec!["Gym", "Lunch with Ana"]);
+        assert_eq!(titles(Some(2), Some(9)), vec!["Lunch with Ana"]);
+
+        let options = ReadEntriesOptions {
+            sort: EntrySort::Words,
+            ..Default::default()
+        };
+        let longest = j.read_entries(&options).entries;
+        assert_eq!(longest[0].title, "Retro");
+        assert_eq!(longest[2].title, "Gym");
+    }
+
+    #[test]
+    fn read_scheduled_entries_after_today() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let (j, _tmp) = mk_journal_with_default(Some(anchor));
+        let entries = [
+            (20, 18, "Dentist"),
+            (14, 9, "Past"),
+            (15, 9, "Today"),
+            (20, 9, "Flight"),
+            (16, 9, "Tomorrow"),
+        ];
+        for (day, h, title) in entries {
+            j.create_entry(JournalWriteEntry {
+                date: NaiveDate::from_ymd_opt(2025, 8, day).unwrap(),
+                time: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+                title: title.to_string(),
+                body: String::new(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        }
+
+        let results = j.read_scheduled_entries();
+        assert!(results.errors.is_empty());
+        let titles: Vec<&str> = results.entries.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Tomorrow", "Flight", "Dentist"]);
+    }
+
+    #[test]
+    fn last_entry_is_the_latest_until_today() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let (j, _tmp) = mk_journal_with_default(Some(anchor));
+        assert!(j.last_entry().is_none());
+
+        let entries = [(10, 21, "Older"), (12, 18, "Last"), (12, 9, "Morning"), (16, 9, "Soon")];
+        for (day, h, title) in entries {
+            j.create_entry(JournalWriteEntry {
+                date: NaiveDate::from_ymd_opt(2025, 8, day).unwrap(),
+                time: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+                title: title.to_string(),
+                body: String::new(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        }
+
+        assert_eq!(j.last_entry().unwrap().title, "Last");
+    }
+
     #[test]
     fn read_all_files_to_find_tags() {
         let anchor = NaiveDate::from_ymd_opt(2025, 08, 04).unwrap(); // A day in 2025
@@ -688,7 +2191,7 @@ mod tests {
         };
         j.create_entry(entry).unwrap();
 
-        let results = j.search_all_tags();
+        let results = j.search_all_tags(1);
         assert!(results.errors.is_empty());
         assert_eq!(results.tags.len(), 3);
 
@@ -728,4 +2231,634 @@ mod tests {
         assert_eq!(result.errors.len(), 1);
         assert!(matches!(&result.errors[0], QueryError::FileError { .. }));
     }
+
+    #[test]
+    fn create_entry_keeps_front_matter() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let path = day_file(&j.journal_dir, date);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            "---\nmood: good\n---\n# Friday, 15 Aug 2025\n\n## 18:00 - Evening\n\n",
+        )
+        .unwrap();
+
+        let entry = JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            title: "Morning".to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        };
+        j.create_entry(entry).unwrap();
+
+        let s = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            s,
+            "---\nmood: good\n---\n# Friday, 15 Aug 2025\n\n## 09:00 - Morning\n\n## 18:00 - Evening\n\n"
+        );
+    }
+
+    #[test]
+    fn obsidian_vault_appends_to_daily_note() {
+        let (mut j, _tmp) = mk_journal_with_default(None);
+        let vault = ObsidianVault {
+            folder: PathBuf::from("Daily"),
+            format: "%Y-%m-%d".to_string(),
+        };
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let path = vault.day_file(&j.journal_dir, date);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "---\ntags: [daily]\n---\n# Plans\n\n- [ ] Groceries").unwrap();
+        fs::write(j.journal_dir.join("Daily/Ideas.md"), "# Not a daily note").unwrap();
+        j.obsidian = Some(vault);
+
+        let entry = JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            title: "Standup @work".to_string(),
+            body: "Talked with @ana.".to_string(),
+            tags: vec!["@ana".to_string(), "@work".to_string()],
+        };
+        let res = j.create_entry(entry).unwrap();
+        assert_eq!(res.path, path);
+        assert_eq!(res.tags, vec!["@ana", "@work"]);
+
+        let s = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            s,
+            "---\ntags: [daily]\n---\n# Plans\n\n- [ ] Groceries\n\n## 09:00 - Standup [[work]]\n\nTalked with [[ana]].\n\n"
+        );
+
+        let options = ReadEntriesOptions {
+            dates: Some(DateFilter::Single(date)),
+            ..Default::default()
+        };
+        let result = j.read_entries(&options);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.entries[0].tags, vec!["@ana", "@work"]);
+
+        let tags = j.search_all_tags(1);
+        assert!(tags.errors.is_empty());
+        assert_eq!(tags.tags, vec!["@ana", "@work"]);
+    }
+
+    #[test]
+    fn import_entries_skips_existing_ones() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let entry = |h, title: &str| JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+            title: title.to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        };
+
+        let imported = j
+            .import_entries(vec![entry(9, "Standup"), entry(18, "Walk")])
+            .unwrap();
+        assert_eq!(imported, 2);
+        let imported = j
+            .import_entries(vec![entry(9, "Standup"), entry(12, "Lunch")])
+            .unwrap();
+        assert_eq!(imported, 1);
+
+        let result = j.read_single_date_entry(date);
+        assert_eq!(result.entries.len(), 3);
+    }
+
+    #[test]
+    fn create_entries_routes_each_entry_to_its_day() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let d1 = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let d2 = NaiveDate::from_ymd_opt(2025, 8, 16).unwrap();
+        let entry = |date, h, title: &str| JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+            title: title.to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        };
+        j.create_entry(entry(d1, 12, "Lunch")).unwrap();
+
+        let saved = j
+            .create_entries(vec![
+                entry(d2, 10, "Saturday"),
+                entry(d1, 18, "Walk"),
+                entry(d1, 9, "Standup"),
+            ])
+            .unwrap();
+        assert_eq!(saved.len(), 3);
+        assert_eq!(saved[0].title, "Standup");
+        assert_eq!(saved[2].path, day_file(&j.journal_dir, d2));
+
+        let s = fs::read_to_string(day_file(&j.journal_dir, d1)).unwrap();
+        assert_eq!(
+            s,
+            "# Friday, 15 Aug 2025\n\n## 09:00 - Standup\n\n## 12:00 - Lunch\n\n## 18:00 - Walk\n\n"
+        );
+        assert_eq!(j.read_single_date_entry(d2).entries.len(), 1);
+    }
+
+    #[test]
+    fn create_or_merge_entry_appends_within_window() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let entry = |h, m, title: &str, body: &str| JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(h, m, 0).unwrap(),
+            title: title.to_string(),
+            body: body.to_string(),
+            tags: Vec::new(),
+        };
+
+        let (_, merged) = j
+            .create_or_merge_entry(entry(9, 0, "Standup.", ""), 10)
+            .unwrap();
+        assert!(!merged);
+        let (e, merged) = j
+            .create_or_merge_entry(entry(9, 5, "Forgot this.", "About @release"), 10)
+            .unwrap();
+        assert!(merged);
+        assert_eq!(e.body, "Forgot this. About @release");
+        assert_eq!(e.tags, vec!["@release"]);
+        let (_, merged) = j
+            .create_or_merge_entry(entry(9, 30, "Later.", ""), 10)
+            .unwrap();
+        assert!(!merged);
+
+        let s = fs::read_to_string(day_file(&j.journal_dir, date)).unwrap();
+        assert_eq!(
+            s,
+            "# Friday, 15 Aug 2025\n\n## 09:00 - Standup.\n\nForgot this. About @release\n\n## 09:30 - Later.\n\n"
+        );
+    }
+
+    #[test]
+    fn previews_are_not_written() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let entry = |h, title: &str| JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+            title: title.to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        };
+
+        let changes = j.preview_entries(vec![entry(9, "Standup.")]).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].before.is_none());
+        assert!(!changes[0].path.exists());
+
+        j.create_entry(entry(9, "Standup.")).unwrap();
+        j.create_entry(entry(18, "Gym.")).unwrap();
+        let change = j.preview_entries(vec![entry(12, "Lunch.")]).unwrap().remove(0);
+        assert!(change.diff().ends_with(
+            "@@ -2,5 +2,7 @@\n \n ## 09:00 - Standup.\n \n+## 12:00 - Lunch.\n+\n ## 18:00 - Gym.\n \n"
+        ));
+        let merge = j.preview_merge_entry(entry(18, "Tired."), 10).unwrap();
+        assert!(merge.after.contains("## 18:00 - Gym.\n\nTired.\n"));
+        let content = fs::read_to_string(&change.path).unwrap();
+        assert_eq!(change.before.as_deref(), Some(content.as_str()));
+        assert!(!content.contains("Lunch") && !content.contains("Tired"));
+    }
+
+    #[test]
+    fn parsed_files_are_cached_until_they_change() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let entry = JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            title: "Standup.".to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        };
+        j.create_entry(entry).unwrap();
+        let options = ReadEntriesOptions {
+            dates: Some(DateFilter::Single(date)),
+            ..Default::default()
+        };
+        assert_eq!(j.read_entries(&options).entries[0].title, "Standup.");
+        assert_eq!(j.cache.len(), 1);
+
+        // Same size and modification time: the cached entries are used.
+        let path = j.day_file(date);
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::write(&path, content.replace("Standup.", "Meeting.")).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert_eq!(j.read_entries(&options).entries[0].title, "Standup.");
+
+        j.invalidate();
+        assert_eq!(j.cache.len(), 0);
+        assert_eq!(j.read_entries(&options).entries[0].title, "Meeting.");
+
+        // Changed on disk: read again.
+        fs::write(&path, format!("{content}## 10:00 - By hand\n")).unwrap();
+        assert_eq!(j.read_entries(&options).entries.len(), 2);
+    }
+
+    #[test]
+    fn rewrite_keeps_text_that_is_not_an_entry() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let path = j.day_file(date);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            "# Friday, 15 Aug 2025\n\nFree notes.\n\n## 09:00 - Standup\n\n## Lunch, no time\n\n## 18:00 - Walk\n",
+        )
+        .unwrap();
+
+        let entry = JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            title: "Noon".to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        };
+        j.create_entry(entry).unwrap();
+
+        let s = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            s,
+            "# Friday, 15 Aug 2025\n\nFree notes.\n\n## 09:00 - Standup\n\n## Lunch, no time\n\n## 12:00 - Noon\n\n## 18:00 - Walk\n\n"
+        );
+    }
+
+    #[test]
+    fn lenient_mode_gives_time_to_headings_without_one() {
+        let (mut j, _tmp) = mk_journal_with_default(None);
+        j.parse_mode = ParseMode::Lenient;
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let path = j.day_file(date);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            "# Friday, 15 Aug 2025\n\n## Lunch, no time\n\n## 18:00 - Walk\n",
+        )
+        .unwrap();
+
+        let result = j.read_entries(&ReadEntriesOptions {
+            dates: Some(DateFilter::Single(date)),
+            ..Default::default()
+        });
+        assert!(result.errors.is_empty());
+        assert_eq!(result.entries.len(), 2);
+        assert_eq!(result.entries[0].title, "Lunch, no time");
+        assert_eq!(result.entries[0].time, NaiveTime::from_hms_opt(21, 0, 0).unwrap());
+
+        let entry = JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            title: "Noon".to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        };
+        j.create_entry(entry).unwrap();
+
+        // The recovered entry is written back with its time.
+        let s = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            s,
+            "# Friday, 15 Aug 2025\n\n## 12:00 - Noon\n\n## 18:00 - Walk\n\n## 21:00 - Lunch, no time\n\n"
+        );
+    }
+
+    #[test]
+    fn fix_day_header_uses_the_file_name() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let path = j.day_file(date);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "# 15/08/2025\n\n## 09:00 - Standup\n").unwrap();
+
+        let result = j.read_entries(&ReadEntriesOptions {
+            dates: Some(DateFilter::Single(date)),
+            ..Default::default()
+        });
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(j.check_files().len(), 1);
+
+        assert!(j.fix_day_header(&path).unwrap());
+        let s = fs::read_to_string(&path).unwrap();
+        assert_eq!(s, "# Friday, 15 Aug 2025\n\n## 09:00 - Standup\n");
+        assert!(j.check_files().is_empty());
+        assert!(!j.fix_day_header(&path).unwrap());
+
+        // A file without header gets one.
+        fs::write(&path, "## 09:00 - Standup\n").unwrap();
+        assert!(j.fix_day_header(&path).unwrap());
+        let s = fs::read_to_string(&path).unwrap();
+        assert_eq!(s, "# Friday, 15 Aug 2025\n\n## 09:00 - Standup\n");
+    }
+
+    #[test]
+    fn fix_day_header_keeps_localized_headers() {
+        let (mut j, _tmp) = mk_journal_with_default(None);
+        j.locale = Some(Locale::es_ES);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let path = j.day_file(date);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let content = "# viernes, 15 ago 2025\n\n## 09:00 - Standup\n";
+        fs::write(&path, content).unwrap();
+
+        assert!(!j.fix_day_header(&path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+
+        // A broken header is written in the language of the journal.
+        fs::write(&path, "# 15/08/2025\n\n## 09:00 - Standup\n").unwrap();
+        assert!(j.fix_day_header(&path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+    }
+
+    #[test]
+    fn reads_day_files_with_windows_line_endings() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let path = j.day_file(date);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            "# Friday, 15 Aug 2025\r\n\r\n## 09:00 - Standup @work\r\n\r\nShort.\r\nDone.\r\n",
+        )
+        .unwrap();
+
+        let options = ReadEntriesOptions {
+            dates: Some(DateFilter::Single(date)),
+            ..Default::default()
+        };
+        let result = j.read_entries(&options);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.entries[0].title, "Standup @work");
+        assert_eq!(result.entries[0].body, "Short.\nDone.");
+        assert_eq!(result.entries[0].tags, vec!["@work"]);
+
+        // A rewritten file has Unix line endings, which Windows editors read too.
+        j.create_entry(JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(10, 0, 0).unwrap(),
+            title: "Review".to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        })
+        .unwrap();
+        let s = fs::read_to_string(&path).unwrap();
+        assert!(!s.contains('\r'));
+        assert_eq!(j.read_entries(&options).entries.len(), 2);
+    }
+
+    #[test]
+    fn journals_work_in_directories_with_spaces_and_accents() {
+        let (mut j, tmp) = mk_journal_with_default(None);
+        let root = tmp.path().join("Mis Documentos").join("diario de Ana");
+        j.journal_dir = root.clone();
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let saved = j
+            .create_entry(JournalWriteEntry {
+                date,
+                time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                title: "Standup".to_string(),
+                body: String::new(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        // The day file is under the journal with the separators of the platform.
+        assert!(saved.path.starts_with(&root));
+        assert_eq!(saved.path, root.join("2025").join("08").join("2025-08-15.md"));
+        let all = ReadEntriesOptions::default();
+        assert_eq!(j.read_entries(&all).entries[0].path, saved.path);
+    }
+
+    #[test]
+    fn entries_are_written_with_the_configured_precision() {
+        let (mut j, _tmp) = mk_journal_with_default(None);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let path = j.day_file(date);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "# Friday, 15 Aug 2025\n\n## 08:00:15 - From another tool\n").unwrap();
+
+        let entry = |h, m, s| JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(h, m, s).unwrap(),
+            title: format!("At {h}"),
+            body: String::new(),
+            tags: Vec::new(),
+        };
+        let saved = j.create_entry(entry(9, 5, 30)).unwrap();
+        assert_eq!(saved.time, NaiveTime::from_hms_opt(9, 5, 0).unwrap());
+
+        j.time_precision = TimePrecision::Seconds;
+        j.create_entry(entry(10, 5, 30)).unwrap();
+
+        // Seconds written by other tools are kept.
+        let s = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            s,
+            "# Friday, 15 Aug 2025\n\n## 08:00:15 - From another tool\n\n## 09:05 - At 9\n\n## 10:05:30 - At 10\n\n"
+        );
+    }
+
+    #[test]
+    fn private_entries_open_with_the_passphrase() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let entry = |h, title: &str| JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+            title: title.to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        };
+        let private = crate::journal::private::seal_entry(entry(9, "Doctor @health"), "pw");
+        j.create_entry(private.unwrap()).unwrap();
+        // Never merged into.
+        let (_, merged) = j.create_or_merge_entry(entry(9, "Standup."), 10).unwrap();
+        assert!(!merged);
+
+        let mut options = ReadEntriesOptions {
+            dates: Some(DateFilter::Single(date)),
+            ..Default::default()
+        };
+        let result = j.read_entries(&options);
+        assert_eq!(result.entries[0].title, "[private]");
+        assert!(result.entries[0].body.is_empty());
+
+        options.passphrase = Some("wrong");
+        let result = j.read_entries(&options);
+        assert_eq!(result.entries[0].title, "[private]");
+        assert_eq!(result.errors.len(), 1);
+
+        let tags = vec!["@health".to_string()];
+        options.passphrase = Some("pw");
+        options.tags = Some(&tags);
+        let result = j.read_entries(&options);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.entries[0].title, "Doctor @health");
+    }
+
+    #[test]
+    fn private_entries_get_the_default_tags_before_sealing() {
+        let (mut j, _tmp) = mk_journal_with_default(None);
+        j.default_tags = vec!["@work".to_string()];
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let inputs = [(9, "Standup"), (12, "Review @work")].map(|(h, title)| JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+            title: title.to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        });
+        let sealed: Vec<JournalWriteEntry> = inputs
+            .into_iter()
+            .map(|entry| j.sealed_entry(entry, "pw").unwrap())
+            .collect();
+        j.create_entries(sealed).unwrap();
+
+        let options = ReadEntriesOptions {
+            passphrase: Some("pw"),
+            ..Default::default()
+        };
+        let entries = j.read_entries(&options).entries;
+        let titles: Vec<&str> = entries.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Standup @work", "Review @work"]);
+    }
+
+    #[test]
+    fn searches_dont_look_into_private_entries() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let entry = |h, title: &str, body: &str| JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+            title: title.to_string(),
+            body: body.to_string(),
+            tags: Vec::new(),
+        };
+        j.create_entry(entry(9, "Standup", "Release plan.")).unwrap();
+        let private = entry(18, "Doctor", "The release of the results.");
+        j.create_entry(j.sealed_entry(private, "pw").unwrap()).unwrap();
+
+        let result = j.search_entries("release", 10, 2).unwrap();
+        assert!(result.errors.is_empty());
+        let titles: Vec<&str> = result.hits.iter().map(|h| h.entry.title.as_str()).collect();
+        assert_eq!(titles, vec!["Standup"]);
+        // Nor into their sealed text.
+        assert!(j.search_entries("lgg", 10, 2).unwrap().hits.is_empty());
+    }
+
+    #[test]
+    fn reads_pinned_days_and_entries() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let d1 = NaiveDate::from_ymd_opt(2025, 8, 14).unwrap();
+        let d2 = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let entry = |date, h, title: &str| JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+            title: title.to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        };
+        j.create_entry(entry(d1, 9, "Standup")).unwrap();
+        j.create_entry(entry(d1, 18, "Gym")).unwrap();
+        j.create_entry(entry(d2, 9, "Insurance numbers")).unwrap();
+        j.create_entry(entry(d2, 12, "Lunch")).unwrap();
+        assert!(j.read_pinned_entries().entries.is_empty());
+
+        let pins = j.pins();
+        pins.add(
+            Pin::Entry(d2, NaiveTime::from_hms_opt(9, 0, 0).unwrap()),
+            "Insurance numbers",
+        )
+        .unwrap();
+        pins.add(Pin::Day(d1), "").unwrap();
+        let titles: Vec<String> = j
+            .read_pinned_entries()
+            .entries
+            .into_iter()
+            .map(|e| e.title)
+            .collect();
+        assert_eq!(titles, vec!["Standup", "Gym", "Insurance numbers"]);
+    }
+
+    #[test]
+    fn backlinks_are_the_entries_linking_to_a_day() {
+        let (j, _tmp) = mk_journal_with_default(None);
+        let day = |d| NaiveDate::from_ymd_opt(2025, 8, d).unwrap();
+        let entry = |date, title: &str, body: &str| JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            title: title.to_string(),
+            body: body.to_string(),
+            tags: Vec::new(),
+        };
+        j.create_entry(entry(day(14), "Kickoff", "")).unwrap();
+        let saved = j
+            .create_entry(entry(day(16), "Follow-up", "As planned in [[2025-08-14T09:00]]."))
+            .unwrap();
+        assert_eq!(saved.links[0].to_string(), "2025-08-14T09:00");
+        j.create_entry(entry(day(15), "Recap", "See [[2025-08-14]] and [[2025-08-10]]."))
+            .unwrap();
+
+        let titles = |result: JournalQueryResult| -> Vec<String> {
+            result.entries.into_iter().map(|e| e.title).collect()
+        };
+        assert_eq!(titles(j.backlinks(day(14), None)), vec!["Recap", "Follow-up"]);
+        assert!(j.backlinks(day(16), None).entries.is_empty());
+
+        // A private entry is only found, and opened, with the passphrase.
+        let private = entry(day(17), "Talk", "About [[2025-08-14]].");
+        j.create_entry(j.sealed_entry(private, "pw").unwrap()).unwrap();
+        assert_eq!(titles(j.backlinks(day(14), None)), vec!["Recap", "Follow-up"]);
+        let result = j.backlinks(day(14), Some("pw"));
+        assert!(result.errors.is_empty());
+        assert_eq!(result.entries[2].body, "About [[2025-08-14]].");
+        assert_eq!(titles(result), vec!["Recap", "Follow-up", "Talk"]);
+        assert!(!fs::read_to_string(j.day_file(day(17))).unwrap().contains("[[2025-08-14]]"));
+    }
+
+    #[test]
+    fn index_files_are_written_with_the_day_files_and_never_read() {
+        let (mut j, _tmp) = mk_journal_with_default(None);
+        j.index_files = true;
+        let entry = |month, day, h, title: &str| JournalWriteEntry {
+            date: NaiveDate::from_ymd_opt(2025, month, day).unwrap(),
+            time: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+            title: title.to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        };
+        j.create_entry(entry(8, 15, 18, "Walk")).unwrap();
+        j.create_entry(entry(8, 15, 9, "Standup")).unwrap();
+        j.create_entry(entry(9, 1, 8, "Back to school")).unwrap();
+
+        let month_index = j.journal_dir.join("2025/08/index.md");
+        assert_eq!(
+            fs::read_to_string(&month_index).unwrap(),
+            "# August 2025\n\n## [Friday, 15 Aug 2025](2025-08-15.md)\n\n- 09:00 Standup\n- 18:00 Walk\n"
+        );
+        let year_index = fs::read_to_string(j.journal_dir.join("2025/index.md")).unwrap();
+        assert!(year_index.contains("## [September](09/index.md)\n\n### [Monday, 01 Sep 2025]"));
+
+        let read = j.read_entries(&ReadEntriesOptions::default());
+        assert_eq!(read.entries.len(), 3);
+        assert!(read.errors.is_empty() && j.check_files().is_empty());
+
+        fs::remove_file(&month_index).unwrap();
+        assert_eq!(j.write_all_index_files().unwrap(), 1);
+        assert_eq!(j.write_all_index_files().unwrap(), 0);
+        assert!(month_index.exists());
+    }
 }
@@ -1,19 +1,41 @@
 use chrono::{NaiveDate, NaiveTime};
+use std::ops::Range;
 use std::path::PathBuf;
+use unicode_segmentation::UnicodeSegmentation;
+use super::filter::Filter;
+use super::links::EntryLink;
 use crate::QueryError;
 use crate::utils::date_utils::DateFilter;
+use crate::utils::diff::unified_diff;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct JournalEntry {
     pub date: NaiveDate,
     pub time: NaiveTime,
     pub title: String,
     pub body: String,
     pub tags: Vec<String>,
+    /// The days and entries the title and body refer to (`[[2025-08-15]]`).
+    pub links: Vec<EntryLink>,
     pub path: PathBuf,
+    /// Words of the title and body, counted when the entry is read (see [`text_counts`]).
+    pub word_count: usize,
+    /// Characters of the title and body, counted when the entry is read (see [`text_counts`]).
+    pub char_count: usize,
+}
+
+/// The words and characters of an entry with `title` and `body`. Words are separated by
+/// whitespace, and characters are the ones you see (graphemes, so an emoji or an accented letter
+/// is one), spaces included.
+pub fn text_counts(title: &str, body: &str) -> (usize, usize) {
+    let (title, body) = (title.trim(), body.trim());
+    let words = title.split_whitespace().count() + body.split_whitespace().count();
+    let chars = title.graphemes(true).count() + body.graphemes(true).count();
+    (words, chars)
 }
 
 /// Properties to create a new JournalEntry
+#[derive(Debug, Clone, PartialEq)]
 pub struct JournalWriteEntry {
     pub date: NaiveDate,
     pub time: NaiveTime,
@@ -22,6 +44,60 @@ pub struct JournalWriteEntry {
     pub tags: Vec<String>,
 }
 
+/// How writing entries changes a day file (see [`Journal::preview_entries`](super::Journal::preview_entries)).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayFileChange {
+    pub path: PathBuf,
+    /// Content of the file before the change, `None` if it doesn't exist yet.
+    pub before: Option<String>,
+    pub after: String,
+}
+
+impl DayFileChange {
+    /// The change as a unified diff, like `diff -u` prints it.
+    pub fn diff(&self) -> String {
+        let path = self.path.display();
+        let old = match self.before {
+            Some(_) => path.to_string(),
+            None => "/dev/null".to_string(),
+        };
+        let before = self.before.as_deref().unwrap_or_default();
+        format!("--- {old}\n+++ {path}\n{}", unified_diff(before, &self.after, 3))
+    }
+}
+
+/// How an entry differs between two versions of the journal (see
+/// [`Journal::diff_snapshots`](super::Journal::diff_snapshots)). Entries are told apart by their
+/// date, time and title.
+#[derive(Debug, Clone)]
+pub enum EntryChange {
+    Added(JournalEntry),
+    /// The body or the tags changed.
+    Changed {
+        before: JournalEntry,
+        after: JournalEntry,
+    },
+    Removed(JournalEntry),
+}
+
+impl EntryChange {
+    /// The entry as it is after the change, or before it when it was removed.
+    pub fn entry(&self) -> &JournalEntry {
+        match self {
+            EntryChange::Added(entry) | EntryChange::Removed(entry) => entry,
+            EntryChange::Changed { after, .. } => after,
+        }
+    }
+
+    /// The lines of the body that changed, as a unified diff. Empty unless it was changed.
+    pub fn body_diff(&self) -> String {
+        match self {
+            EntryChange::Changed { before, after } => unified_diff(&before.body, &after.body, 1),
+            _ => String::new(),
+        }
+    }
+}
+
 /// The complete result of a query.
 /// Contains successfully parsed entries and any errors.
 #[derive(Debug)]
@@ -35,4 +111,63 @@ pub struct ReadEntriesOptions<'a> {
     pub dates: Option<DateFilter>,
     pub time: Option<&'a str>,
     pub tags: Option<&'a Vec<String>>,
+    /// Fuzzy match on the entry title (see [`crate::utils::text_utils::title_matches`]).
+    pub title: Option<&'a str>,
+    /// Opens the private entries (see [`super::private`]). They are shown as `[private]` without
+    /// it.
+    pub passphrase: Option<&'a str>,
+    /// Only the entries matching a `--filter` expression.
+    pub filter: Option<&'a Filter>,
+    /// Only the entries with at least these words (see [`JournalEntry::word_count`]).
+    pub min_words: Option<usize>,
+    /// Only the entries with at most these words.
+    pub max_words: Option<usize>,
+    /// The order of the found entries.
+    pub sort: EntrySort,
+}
+
+/// The order of the entries read with [`ReadEntriesOptions`].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum EntrySort {
+    /// Oldest first.
+    #[default]
+    Date,
+    /// Most words first, the oldest first when they have as many.
+    Words,
+}
+
+/// An entry found by [`Journal::search_entries`](super::Journal::search_entries).
+#[derive(Debug)]
+pub struct SearchHit {
+    pub entry: JournalEntry,
+    /// Relevance of the entry, higher is better. Always 0 without the `search` feature.
+    pub score: f32,
+    /// The lines of the body around the matched words (see
+    /// [`snippets::search_snippets`](super::snippets::search_snippets)). Empty if only the title
+    /// matched.
+    pub snippets: Vec<SearchSnippet>,
+}
+
+/// An entry found by [`Journal::related_entries`](super::Journal::related_entries).
+#[derive(Debug)]
+pub struct RelatedEntry {
+    pub entry: JournalEntry,
+    /// How alike it is, from 0 (nothing in common) to 1.
+    pub score: f32,
+}
+
+/// A piece of an entry body and the byte ranges of the matched words in it.
+#[derive(Debug)]
+pub struct SearchSnippet {
+    /// Where `text` starts in the body, in bytes.
+    pub start: usize,
+    pub text: String,
+    pub highlights: Vec<Range<usize>>,
+}
+
+/// The result of a full-text search.
+#[derive(Debug)]
+pub struct JournalSearchResult {
+    pub hits: Vec<SearchHit>,
+    pub errors: Vec<QueryError>,
 }
@@ -0,0 +1,67 @@
+//! References to other days in the text of an entry, `[[2025-08-15]]` or `[[2025-08-15T08:03]]`
+//! (see `lgg backlinks`).
+use super::parse_entries::parse_entry_time;
+use chrono::{NaiveDate, NaiveTime};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fmt;
+
+/// A reference to a day, or to the entry at a time of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EntryLink {
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+}
+
+impl fmt::Display for EntryLink {
+    /// The link as it's written, without brackets: `2025-08-15` or `2025-08-15T08:03`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.date.format("%Y-%m-%d"))?;
+        match self.time {
+            Some(time) => write!(f, "T{}", time.format("%H:%M")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The links in `text`, in order and without repeats. Brackets with an invalid date or time
+/// aren't links.
+pub(crate) fn extract_links(text: &str) -> Vec<EntryLink> {
+    static LINK: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"\[\[(\d{4}-\d{2}-\d{2})(?:T(\d{1,2}:\d{2}(?::\d{2})?))?\]\]").unwrap()
+    });
+
+    let mut links: Vec<EntryLink> = Vec::new();
+    for capture in LINK.captures_iter(text) {
+        let Ok(date) = NaiveDate::parse_from_str(&capture[1], "%Y-%m-%d") else {
+            continue;
+        };
+        let time = match capture.get(2).map(|time| parse_entry_time(time.as_str())) {
+            Some(None) => continue,
+            time => time.flatten(),
+        };
+        let link = EntryLink { date, time };
+        if !links.contains(&link) {
+            links.push(link);
+        }
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_to_days_and_times_are_extracted() {
+        let text = "See [[2025-08-15]] and [[2025-08-14T08:03]], again [[2025-08-15]].\n\
+                    Not [[2025-13-01]], [[2025-08-15T25:00]], [[work]] or [2025-08-16].";
+        let links = extract_links(text);
+        let day = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0], EntryLink { date: day, time: None });
+        assert_eq!(links[1].time, NaiveTime::from_hms_opt(8, 3, 0));
+        assert_eq!(links[1].to_string(), "2025-08-14T08:03");
+        assert_eq!(links[0].to_string(), "2025-08-15");
+    }
+}
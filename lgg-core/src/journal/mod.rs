@@ -1,11 +1,37 @@
+pub mod action_items;
+pub mod auto_tags;
+pub mod compose;
+pub mod device;
+pub mod entry_template;
+pub mod filter;
+pub mod index_files;
 mod journal;
 mod journal_entry;
 mod journal_paths;
+mod links;
+mod obsidian;
+mod parse_cache;
+mod pins;
+pub mod pomodoro;
+mod related;
+pub mod private;
+pub mod review;
+pub mod roundtrip;
+#[cfg(feature = "search")]
+mod search;
+pub mod snippets;
+pub mod web_links;
+pub mod week_plan;
 pub mod format_utils;
 pub mod parse_entries;
 pub mod parsed_entry;
 
 pub use journal::Journal;
+pub use links::EntryLink;
+pub use obsidian::ObsidianVault;
+pub use parse_cache::ParseCache;
+pub use pins::{Pin, Pins};
 pub use journal_entry::{
-    JournalEntry, JournalQueryResult, JournalWriteEntry, ReadEntriesOptions,
+    DayFileChange, EntryChange, EntrySort, JournalEntry, JournalQueryResult, JournalSearchResult, JournalWriteEntry, ReadEntriesOptions,
+    RelatedEntry, SearchHit, SearchSnippet, text_counts,
 };
@@ -0,0 +1,169 @@
+//! Interop with Obsidian vaults: where daily notes live and how they are named.
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Daily-notes settings of an Obsidian vault.
+///
+/// They are read from the core "Daily notes" plugin configuration (`.obsidian/daily-notes.json`),
+/// so lgg writes to the same files Obsidian opens when clicking on "Today's note".
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObsidianVault {
+    /// Folder, relative to the vault root, where daily notes are created.
+    pub folder: PathBuf,
+    /// `chrono` format of the note path (without extension) inside `folder`.
+    pub format: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DailyNotesSettings {
+    folder: Option<String>,
+    format: Option<String>,
+}
+
+impl Default for ObsidianVault {
+    /// Obsidian defaults: notes named `YYYY-MM-DD` at the root of the vault.
+    fn default() -> Self {
+        Self {
+            folder: PathBuf::new(),
+            format: "%Y-%m-%d".to_string(),
+        }
+    }
+}
+
+impl ObsidianVault {
+    /// Reads the daily-notes settings of the vault in `vault_root`, falling back to Obsidian defaults.
+    pub fn load(vault_root: &Path) -> Self {
+        let settings_path = vault_root.join(".obsidian").join("daily-notes.json");
+        let settings = fs::read_to_string(settings_path)
+            .ok()
+            .and_then(|s| serde_json::from_str::<DailyNotesSettings>(&s).ok());
+
+        let mut vault = Self::default();
+        if let Some(settings) = settings {
+            if let Some(folder) = settings.folder.filter(|f| !f.trim().is_empty()) {
+                vault.folder = PathBuf::from(folder.trim().trim_matches('/'));
+            }
+            if let Some(format) = settings.format.filter(|f| !f.trim().is_empty()) {
+                vault.format = moment_to_chrono(format.trim());
+            }
+        }
+        vault
+    }
+
+    /// Directory holding all the daily notes.
+    pub fn notes_dir(&self, vault_root: &Path) -> PathBuf {
+        vault_root.join(&self.folder)
+    }
+
+    /// Path of the daily note for `date`.
+    pub fn day_file(&self, vault_root: &Path, date: NaiveDate) -> PathBuf {
+        let name = date.format(&self.format).to_string();
+        self.notes_dir(vault_root).join(format!("{name}.md"))
+    }
+
+    /// Returns the date of a daily note from its path, or `None` if the file is any other note.
+    pub fn date_from_path(&self, vault_root: &Path, path: &Path) -> Option<NaiveDate> {
+        let relative = path
+            .strip_prefix(self.notes_dir(vault_root))
+            .ok()?
+            .with_extension("");
+        let relative = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        NaiveDate::parse_from_str(&relative, &self.format).ok()
+    }
+}
+
+/// Translates the Moment.js tokens Obsidian uses for note names into `chrono` specifiers.
+/// Text inside square brackets is kept as a literal.
+fn moment_to_chrono(format: &str) -> String {
+    const TOKENS: &[(&str, &str)] = &[
+        ("YYYY", "%Y"),
+        ("GGGG", "%G"),
+        ("gggg", "%G"),
+        ("MMMM", "%B"),
+        ("dddd", "%A"),
+        ("MMM", "%b"),
+        ("ddd", "%a"),
+        ("DDDD", "%j"),
+        ("YY", "%y"),
+        ("MM", "%m"),
+        ("DD", "%d"),
+        ("WW", "%V"),
+        ("ww", "%V"),
+        ("M", "%-m"),
+        ("D", "%-d"),
+        ("E", "%u"),
+    ];
+
+    let mut out = String::new();
+    let mut rest = format;
+    'outer: while let Some(ch) = rest.chars().next() {
+        if ch == '['
+            && let Some(end) = rest.find(']')
+        {
+            out.push_str(&rest[1..end].replace('%', "%%"));
+            rest = &rest[end + 1..];
+            continue;
+        }
+        for (token, replacement) in TOKENS {
+            if let Some(stripped) = rest.strip_prefix(token) {
+                out.push_str(replacement);
+                rest = stripped;
+                continue 'outer;
+            }
+        }
+        if ch == '%' {
+            out.push_str("%%");
+        } else {
+            out.push(ch);
+        }
+        rest = &rest[ch.len_utf8()..];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn converts_moment_tokens() {
+        assert_eq!(moment_to_chrono("YYYY-MM-DD"), "%Y-%m-%d");
+        assert_eq!(moment_to_chrono("YYYY/MMMM/dddd D"), "%Y/%B/%A %-d");
+        assert_eq!(moment_to_chrono("[Journal] YYYY-MM-DD"), "Journal %Y-%m-%d");
+    }
+
+    #[test]
+    fn loads_daily_notes_settings() {
+        let tmp = tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join(".obsidian")).unwrap();
+        fs::write(
+            root.join(".obsidian/daily-notes.json"),
+            r#"{"folder": "Daily/", "format": "YYYY/MM/YYYY-MM-DD", "template": ""}"#,
+        )
+        .unwrap();
+
+        let vault = ObsidianVault::load(root);
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let path = vault.day_file(root, date);
+
+        assert_eq!(path, root.join("Daily/2025/08/2025-08-15.md"));
+        assert_eq!(vault.date_from_path(root, &path), Some(date));
+        assert_eq!(vault.date_from_path(root, &root.join("Daily/Ideas.md")), None);
+    }
+
+    #[test]
+    fn missing_settings_use_obsidian_defaults() {
+        let tmp = tempdir().unwrap();
+        assert_eq!(ObsidianVault::load(tmp.path()), ObsidianVault::default());
+    }
+}
@@ -0,0 +1,93 @@
+//! In-process cache of parsed day files, for long-lived embedders (TUI, server) that query the
+//! journal again and again.
+use super::journal_entry::JournalEntry;
+use crate::storage::FileStamp;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// How many day files are kept. The least recently used one is dropped first.
+const CAPACITY: usize = 1024;
+
+/// Parsed day files, reused while the file keeps the same modification time and size.
+#[derive(Default)]
+pub struct ParseCache {
+    state: Mutex<CacheState>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    files: HashMap<PathBuf, CachedFile>,
+    /// Incremented on every access, to know which file was used least recently.
+    clock: u64,
+}
+
+struct CachedFile {
+    stamp: FileStamp,
+    entries: Vec<JournalEntry>,
+    errors: Vec<String>,
+    last_used: u64,
+}
+
+impl ParseCache {
+    /// Entries and parse errors of `path`, if it hasn't changed since it was cached.
+    pub fn get(&self, path: &Path, stamp: &FileStamp) -> Option<(Vec<JournalEntry>, Vec<String>)> {
+        let mut state = self.state.lock().ok()?;
+        state.clock += 1;
+        let clock = state.clock;
+        let cached = state.files.get_mut(path)?;
+        if cached.stamp != *stamp {
+            return None;
+        }
+        cached.last_used = clock;
+        Some((cached.entries.clone(), cached.errors.clone()))
+    }
+
+    pub fn insert(
+        &self,
+        path: &Path,
+        stamp: &FileStamp,
+        entries: Vec<JournalEntry>,
+        errors: Vec<String>,
+    ) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.clock += 1;
+        if state.files.len() >= CAPACITY && !state.files.contains_key(path) {
+            let oldest = state
+                .files
+                .iter()
+                .min_by_key(|(_, f)| f.last_used)
+                .map(|(p, _)| p.clone());
+            if let Some(oldest) = oldest {
+                state.files.remove(&oldest);
+            }
+        }
+        let file = CachedFile {
+            stamp: *stamp,
+            entries,
+            errors,
+            last_used: state.clock,
+        };
+        state.files.insert(path.to_path_buf(), file);
+    }
+
+    /// Forgets all the cached files.
+    pub fn clear(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.files.clear();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().map(|s| s.files.len()).unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for ParseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParseCache").field("files", &self.len()).finish()
+    }
+}
@@ -1,84 +1,305 @@
 //! Parses the content of a daily journal file into structured `Entry` objects.
-use crate::journal::parsed_entry::{ParsedJournalEntry, ReadJournalResult};
-use chrono::{NaiveDate, NaiveTime};
-use crate::utils::parse_input;
+use crate::config::ParseMode;
+use crate::journal::format_utils::unescape_body;
+use crate::journal::links::extract_links;
+use crate::journal::parsed_entry::{ParsedJournalEntry, ReadJournalResult, UnparsedSegment};
+use crate::tags::TagPattern;
+use chrono::{Locale, NaiveDate, NaiveTime};
+use crate::utils::date_utils::format_date;
+use once_cell::sync::Lazy;
+use regex::Regex;
 
-pub fn parse_journal_file_content(content: &str) -> ReadJournalResult {
-    let mut entries = Vec::new();
-    let mut errors = Vec::new();
+/// Rules to read a day file that doesn't follow the format, see [`ParseMode`].
+#[derive(Debug, Clone)]
+pub struct ParsePolicy {
+    pub mode: ParseMode,
+    /// Time of the entries whose heading has no time (lenient mode).
+    pub default_time: NaiveTime,
+    /// Date in the name of the file, used when the header has none.
+    pub file_date: Option<NaiveDate>,
+    /// Format of the day header, see [`crate::Config::journal_date_format`].
+    pub date_format: String,
+    /// Language of the day header, see [`crate::Config::locale`].
+    pub locale: Option<Locale>,
+    /// The tags of the entries, see [`crate::Config::tag_pattern`].
+    pub tags: TagPattern,
+}
+
+impl Default for ParsePolicy {
+    fn default() -> Self {
+        Self {
+            mode: ParseMode::Strict,
+            default_time: NaiveTime::from_hms_opt(21, 0, 0).expect("valid time"),
+            file_date: None,
+            date_format: "%A, %d %b %Y".to_string(),
+            locale: None,
+            tags: TagPattern::default(),
+        }
+    }
+}
+
+pub fn parse_journal_file_content(content: &str, policy: &ParsePolicy) -> ReadJournalResult {
+    let lenient = policy.mode == ParseMode::Lenient;
+    let mut result = ReadJournalResult {
+        date: None,
+        entries: Vec::new(),
+        unparsed: Vec::new(),
+        errors: Vec::new(),
+    };
+    let (_, content) = split_front_matter(content);
     let mut lines = content.lines();
     let header_line = match lines.next() {
         Some(h) => h,
         None => {
-            errors.push(
+            result.errors.push(
                 "Empty file: expected a date header like `# DATE` on the first line.".to_string(),
             );
-            return ReadJournalResult { entries, errors };
+            return result;
         }
     };
 
-    let date = match parse_date_from_header_line(header_line) {
-        Some(d) => d,
-        None => {
-            errors.push(
+    result.date = parse_date_from_header_line(header_line).or_else(|| {
+        // Localized names and custom formats can't be parsed, but a header can still be the one
+        // written for the file date.
+        let date = policy.file_date?;
+        let header = format_date(date, &policy.date_format, policy.locale);
+        (header_line.trim().strip_prefix("# ") == Some(header.as_str())).then_some(date)
+    });
+    let mut content = lines.collect::<Vec<_>>().join("\n");
+    let date = match (result.date, policy.file_date) {
+        (Some(d), _) => d,
+        // The day is still readable with the date of the file name.
+        (None, Some(file_date)) => {
+            if !lenient {
+                result.errors.push(format!(
+                    "Invalid or missing H1 date header: expected first line like `# DATE`, found {header_line}. Using the date of the file name, {file_date}."
+                ));
+            }
+            if header_line.starts_with("# ") {
+                // The header line isn't lost: it's kept as text before the entries.
+                result.unparsed.push(UnparsedSegment {
+                    after: None,
+                    text: header_line.trim().to_string(),
+                });
+            } else {
+                // No header at all, the first line is already part of the day.
+                content = format!("{header_line}\n{content}");
+            }
+            file_date
+        }
+        (None, None) => {
+            result.errors.push(
                 format!("Invalid or missing H1 date header: expected first line like `# DATE`, found {header_line}.").to_string(),
             );
-            return ReadJournalResult { entries, errors };
+            return result;
         }
     };
 
-    let content = lines.collect::<Vec<_>>().join("\n");
+    // Time and title of the last entry found, to know where unparsed text goes.
+    let mut last: Option<(NaiveTime, String)> = None;
     // Split content by the entry delimiter "## ".
-    for block in content.split("\n## ") {
+    for (i, block) in content.split("\n## ").enumerate() {
         // Skip empty blocks that can result from the split (e.g., the content before the first `##`).
         if block.trim().is_empty() {
             continue;
         }
-        if let Some(newline_pos) = block.find('\n') {
-            let heading = &block[..newline_pos];
-            let body = block[newline_pos..].trim().to_string();
-            let tags = parse_input::extract_tags(&block);
-
-            match heading.find(" - ") {
-                Some(separator_pos) => {
-                    let time_str = heading[..separator_pos].trim();
-                    let title = heading[separator_pos + 3..].trim().to_string();
-
-                    match NaiveTime::parse_from_str(time_str, "%H:%M") {
-                        Ok(time) => entries.push(ParsedJournalEntry {
-                            date,
-                            time,
-                            title,
-                            body,
-                            tags,
-                        }),
-                        Err(_) => errors.push(
-                            format!("Invalid time in entry header `{heading}`. Expected a 24-hour time `HH:MM`.").to_string(),
-                        ),
-                    }
-                }
-                None => errors
-                    .push(format!("Invalid H2 entry header: `{heading}`. Expected `HH:MM - Title.` (e.g., `08:03 - Morning coffe`)." ).to_string()),
+        // The first block is the text after the day header: an entry only if it starts with `## `.
+        let block = match (i, block.strip_prefix("## ")) {
+            (0, Some(block)) => block,
+            (0, None) => {
+                result.unparsed.push(UnparsedSegment {
+                    after: None,
+                    text: block.trim().to_string(),
+                });
+                continue;
+            }
+            _ => block,
+        };
+        let parsed = match parse_entry_block(date, block, &policy.tags) {
+            Err(_) if lenient => Ok(recover_entry_block(date, block, policy)),
+            // A lone line that isn't an entry is only worth reporting in strict mode.
+            Err(None) => Err(Some(format!(
+                "Invalid H2 entry header: `{}`. Expected `HH:MM - Title.`",
+                block.trim()
+            ))),
+            other => other,
+        };
+        match parsed {
+            Ok(entry) => {
+                last = Some((entry.time, entry.title.clone()));
+                result.entries.push(entry);
             }
-        } else {
-            // Handle case where an entry is just a single line (e.g. "## 12:34 - Title only")
-            if let Some(separator_pos) = block.find(" - ") {
-                let time_str = block[..separator_pos].trim();
-                let title = block[separator_pos + 3..].trim().to_string();
-                let tags = parse_input::extract_tags(&title);
-                if let Ok(time) = NaiveTime::parse_from_str(time_str, "%H:%M") {
-                    entries.push(ParsedJournalEntry {
+            Err(error) => {
+                result.errors.extend(error);
+                result.unparsed.push(UnparsedSegment {
+                    after: last.clone(),
+                    text: format!("## {}", block.trim_end()),
+                });
+            }
+        }
+    }
+    result
+}
+
+/// Parses an entry block (without its `## `). Returns the error to report, if any, when it's not an entry.
+fn parse_entry_block(
+    date: NaiveDate,
+    block: &str,
+    tags: &TagPattern,
+) -> Result<ParsedJournalEntry, Option<String>> {
+    if let Some(newline_pos) = block.find('\n') {
+        let heading = &block[..newline_pos];
+        let body = unescape_body(&block[newline_pos..]).trim().to_string();
+        let tags = tags.extract(block);
+        let links = extract_links(block);
+
+        match heading.find(" - ") {
+            Some(separator_pos) => {
+                let time_str = heading[..separator_pos].trim();
+                let title = heading[separator_pos + 3..].trim().to_string();
+
+                match parse_entry_time(time_str) {
+                    Some(time) => Ok(ParsedJournalEntry {
                         date,
                         time,
                         title,
-                        body: String::new(),
+                        body,
                         tags,
-                    });
+                        links,
+                    }),
+                    None => Err(Some(
+                        format!("Invalid time in entry header `{heading}`. Expected a 24-hour time `HH:MM` or `HH:MM:SS`.").to_string(),
+                    )),
                 }
             }
+            None => Err(Some(format!("Invalid H2 entry header: `{heading}`. Expected `HH:MM - Title.` (e.g., `08:03 - Morning coffe`)." ).to_string())),
+        }
+    } else {
+        // Handle case where an entry is just a single line (e.g. "## 12:34 - Title only")
+        let (time_str, title) = block.split_once(" - ").ok_or(None)?;
+        let time = parse_entry_time(time_str).ok_or(None)?;
+        let title = title.trim().to_string();
+        let tags = tags.extract(&title);
+        let links = extract_links(&title);
+        Ok(ParsedJournalEntry {
+            date,
+            time,
+            title,
+            body: String::new(),
+            tags,
+            links,
+        })
+    }
+}
+
+/// An entry from a block whose heading has no valid time: the whole heading is the title.
+fn recover_entry_block(date: NaiveDate, block: &str, policy: &ParsePolicy) -> ParsedJournalEntry {
+    let (heading, body) = block.split_once('\n').unwrap_or((block, ""));
+    let title = heading.trim().to_string();
+    let body = unescape_body(body).trim().to_string();
+    let text = format!("{title}\n{body}");
+    ParsedJournalEntry {
+        date,
+        time: policy.default_time,
+        tags: policy.tags.extract(&text),
+        links: extract_links(&text),
+        title,
+        body,
+    }
+}
+
+/// Splits the YAML front matter (`---` fenced block at the very top) from the rest of the file.
+/// The returned front matter includes both fences and the trailing newline, so it can be written back as is.
+pub fn split_front_matter(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+
+    let mut offset = content.len() - rest.len();
+    for line in rest.split_inclusive('\n') {
+        offset += line.len();
+        if line.trim_end() == "---" {
+            return (Some(&content[..offset]), &content[offset..]);
+        }
+    }
+    (None, content)
+}
+
+/// Parses an Obsidian daily note. Unlike journal files, notes don't need a date header and can have
+/// any other content: only `## HH:MM - Title` sections are entries, any other heading ends the entry.
+/// Wiki-links (`[[work]]`) are read as tags.
+pub fn parse_obsidian_note_content(
+    content: &str,
+    date: NaiveDate,
+    tags: &TagPattern,
+) -> ReadJournalResult {
+    let (_, content) = split_front_matter(content);
+    let mut entries = Vec::new();
+    let mut current: Option<(NaiveTime, String, Vec<&str>)> = None;
+
+    for line in content.lines().chain(std::iter::once("# ")) {
+        let is_heading = line.starts_with("# ") || line.starts_with("## ");
+        if !is_heading {
+            if let Some((_, _, body)) = current.as_mut() {
+                body.push(line);
+            }
+            continue;
+        }
+
+        if let Some((time, title, body)) = current.take() {
+            let body = unescape_body(&body.join("\n")).trim().to_string();
+            let text = format!("{title}\n{body}");
+            entries.push(ParsedJournalEntry {
+                date,
+                time,
+                tags: obsidian_tags(&text, tags),
+                links: extract_links(&text),
+                title,
+                body,
+            });
+        }
+
+        if let Some((time_str, title)) = line
+            .strip_prefix("## ")
+            .and_then(|heading| heading.split_once(" - "))
+            && let Some(time) = parse_entry_time(time_str)
+        {
+            current = Some((time, title.trim().to_string(), Vec::new()));
         }
     }
-    ReadJournalResult { entries, errors }
+
+    ReadJournalResult {
+        date: Some(date),
+        entries,
+        unparsed: Vec::new(),
+        errors: Vec::new(),
+    }
+}
+
+/// `@tags` and `[[wiki-links]]` of a note, both returned as `@tag`.
+fn obsidian_tags(text: &str, tags: &TagPattern) -> Vec<String> {
+    static WIKI_LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[\[([^\W\d][\w-]*)\]\]").unwrap());
+
+    let mut found = tags.extract(text);
+    found.extend(
+        WIKI_LINK
+            .captures_iter(text)
+            .map(|c| format!("@{}", c[1].to_lowercase())),
+    );
+    found.sort();
+    found.dedup();
+    found
+}
+
+/// Time of an entry heading: `HH:MM`, `H:MM` or `HH:MM:SS`.
+pub fn parse_entry_time(time: &str) -> Option<NaiveTime> {
+    let time = time.trim();
+    NaiveTime::parse_from_str(time, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+        .ok()
 }
 
 /// Parses a `NaiveDate` from a markdown header line.
@@ -112,7 +333,7 @@ Another paragraph... @health
 
 ### Header 3 is valid
 "#;
-        let result = parse_journal_file_content(content.trim());
+        let result = parse_journal_file_content(content.trim(), &ParsePolicy::default());
         assert_eq!(result.entries.len(), 2);
 
         let expected_date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
@@ -131,14 +352,14 @@ Another paragraph... @health
     #[test]
     fn parse_file_with_no_entries() {
         let content = "# Friday, 15 Aug 2025";
-        let result = parse_journal_file_content(content);
+        let result = parse_journal_file_content(content, &ParsePolicy::default());
         assert!(result.entries.is_empty());
     }
 
     #[test]
     fn parse_file_with_malformed_header_fails() {
         let content = "# Not a date";
-        let result = parse_journal_file_content(content);
+        let result = parse_journal_file_content(content, &ParsePolicy::default());
         assert_eq!(result.errors.len(), 1);
         assert!(result.errors[0].contains("Invalid or missing H1 date header"));
     }
@@ -146,7 +367,7 @@ Another paragraph... @health
     #[test]
     fn parse_empty_file_fails() {
         let content = "";
-        let result = parse_journal_file_content(content);
+        let result = parse_journal_file_content(content, &ParsePolicy::default());
         assert_eq!(result.errors.len(), 1);
         assert!(result.errors[0].contains("Empty file"));
     }
@@ -163,7 +384,7 @@ Body...
 
 Body...
 "#;
-        let result = parse_journal_file_content(content.trim());
+        let result = parse_journal_file_content(content.trim(), &ParsePolicy::default());
         // It should gracefully skip the bad entry and parse the good one.
         assert_eq!(result.entries.len(), 1);
         assert_eq!(result.entries[0].title, "Good entry");
@@ -171,6 +392,134 @@ Body...
         assert!(result.errors[0].contains("Invalid time"));
     }
 
+    #[test]
+    fn keeps_text_that_is_not_an_entry() {
+        let content = r#"# Friday, 15 Aug 2025
+Free notes before any entry.
+
+## 09:00 - Standup
+
+Body.
+
+## Lunch, no time
+With some text.
+## 18:05 - Walk
+"#;
+        let result = parse_journal_file_content(content, &ParsePolicy::default());
+        assert_eq!(result.entries.len(), 2);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(
+            result.unparsed,
+            vec![
+                UnparsedSegment {
+                    after: None,
+                    text: "Free notes before any entry.".to_string(),
+                },
+                UnparsedSegment {
+                    after: Some((NaiveTime::from_hms_opt(9, 0, 0).unwrap(), "Standup".to_string())),
+                    text: "## Lunch, no time\nWith some text.".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn strict_mode_reports_headings_without_time() {
+        let content = "# Friday, 15 Aug 2025\n\n## Lunch\n## 18:05 - Walk\n";
+        let result = parse_journal_file_content(content, &ParsePolicy::default());
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].contains("Invalid H2 entry header: `Lunch`"));
+    }
+
+    #[test]
+    fn lenient_mode_recovers_date_and_times() {
+        let content = r#"# Lunch day
+
+## Lunch with @ana
+
+At the usual place.
+
+## 18:05 - Walk
+"#;
+        let policy = ParsePolicy {
+            mode: ParseMode::Lenient,
+            default_time: NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            file_date: NaiveDate::from_ymd_opt(2025, 8, 15),
+            ..ParsePolicy::default()
+        };
+        let result = parse_journal_file_content(content, &policy);
+        assert!(result.errors.is_empty());
+        // The header isn't valid, so the file can't be rewritten as is.
+        assert_eq!(result.date, None);
+        assert_eq!(result.entries.len(), 2);
+
+        let lunch = &result.entries[0];
+        assert_eq!(lunch.date, NaiveDate::from_ymd_opt(2025, 8, 15).unwrap());
+        assert_eq!(lunch.time, NaiveTime::from_hms_opt(12, 0, 0).unwrap());
+        assert_eq!(lunch.title, "Lunch with @ana");
+        assert_eq!(lunch.body, "At the usual place.");
+        assert_eq!(lunch.tags, vec!["@ana"]);
+        assert_eq!(result.entries[1].title, "Walk");
+
+        // Without a date in the file name there's nothing to recover.
+        let policy = ParsePolicy {
+            file_date: None,
+            ..policy
+        };
+        let result = parse_journal_file_content(content, &policy);
+        assert!(result.entries.is_empty());
+        assert_eq!(result.errors.len(), 1);
+    }
+
+    #[test]
+    fn malformed_header_falls_back_to_file_date() {
+        let policy = ParsePolicy {
+            file_date: NaiveDate::from_ymd_opt(2025, 8, 15),
+            ..ParsePolicy::default()
+        };
+        let result = parse_journal_file_content("# 15/08\n\n## 09:00 - Standup\n", &policy);
+        assert_eq!(result.date, None);
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.entries[0].date, NaiveDate::from_ymd_opt(2025, 8, 15).unwrap());
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].contains("Using the date of the file name, 2025-08-15"));
+
+        // Without any header, the first line is already an entry.
+        let result = parse_journal_file_content("## 09:00 - Standup\n## 10:00 - Coffee", &policy);
+        assert_eq!(result.entries.len(), 2);
+        assert_eq!(result.errors.len(), 1);
+    }
+
+    #[test]
+    fn reads_localized_headers_of_the_file_date() {
+        let content = "# viernes, 15 ago 2025\n\n## 09:00 - Standup\n";
+        let mut policy = ParsePolicy {
+            file_date: NaiveDate::from_ymd_opt(2025, 8, 15),
+            locale: Some(Locale::es_ES),
+            ..ParsePolicy::default()
+        };
+        let result = parse_journal_file_content(content, &policy);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.date, NaiveDate::from_ymd_opt(2025, 8, 15));
+
+        // The header of another day is still wrong.
+        policy.file_date = NaiveDate::from_ymd_opt(2025, 8, 16);
+        let result = parse_journal_file_content(content, &policy);
+        assert_eq!(result.date, None);
+        assert_eq!(result.errors.len(), 1);
+    }
+
+    #[test]
+    fn accepts_seconds_and_single_digit_hours() {
+        let content = "# Friday, 15 Aug 2025\n\n## 9:05 - Early\n\n## 18:05:30 - Walk\n\nBody.\n";
+        let result = parse_journal_file_content(content, &ParsePolicy::default());
+        assert!(result.errors.is_empty());
+        assert_eq!(result.entries[0].time, NaiveTime::from_hms_opt(9, 5, 0).unwrap());
+        assert_eq!(result.entries[1].time, NaiveTime::from_hms_opt(18, 5, 30).unwrap());
+        assert_eq!(result.entries[1].title, "Walk");
+    }
+
     #[test]
     fn parse_entry_with_no_body() {
         let content = r#"# Friday, 15 Aug 2025
@@ -180,7 +529,7 @@ Body...
 
 With a body.
 "#;
-        let result = parse_journal_file_content(content.trim());
+        let result = parse_journal_file_content(content.trim(), &ParsePolicy::default());
         assert_eq!(result.entries.len(), 2);
         assert_eq!(result.entries[0].title, "Title only");
         assert!(result.entries[0].body.is_empty());
@@ -197,11 +546,58 @@ With a body.
 
 With two equal @tags @tags and another @different_tag.
 "#;
-        let result = parse_journal_file_content(content.trim());
+        let result = parse_journal_file_content(content.trim(), &ParsePolicy::default());
         assert_eq!(result.entries.len(), 2);
         assert_eq!(result.entries[0].tags[0], "@tag");
         assert_eq!(result.entries[1].tags.len(), 2);
         assert_eq!(result.entries[1].tags[0], "@different_tag");
         assert_eq!(result.entries[1].tags[1], "@tags");
     }
+
+    #[test]
+    fn skips_front_matter() {
+        let content = "---\ntags: [daily]\n---\n# Friday, 15 Aug 2025\n\n## 12:34 - Title\n";
+        let (front_matter, rest) = split_front_matter(content);
+        assert_eq!(front_matter, Some("---\ntags: [daily]\n---\n"));
+        assert!(rest.starts_with("# Friday"));
+
+        let result = parse_journal_file_content(content, &ParsePolicy::default());
+        assert!(result.errors.is_empty());
+        assert_eq!(result.entries.len(), 1);
+    }
+
+    #[test]
+    fn parse_obsidian_note() {
+        let content = r#"---
+aliases: [today]
+---
+# Daily note
+
+Some text before any entry.
+
+## Tasks
+
+- [ ] Something
+
+## 09:15 - Standup with [[Work]]
+
+Talked about @release.
+
+## 12:00 - Lunch
+
+### Still part of lunch
+## Links
+
+[[Not a tag entry]]
+"#;
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let result = parse_obsidian_note_content(content, date, &TagPattern::default());
+        assert!(result.errors.is_empty());
+        assert_eq!(result.entries.len(), 2);
+        assert_eq!(result.entries[0].title, "Standup with [[Work]]");
+        assert_eq!(result.entries[0].body, "Talked about @release.");
+        assert_eq!(result.entries[0].tags, vec!["@release", "@work"]);
+        assert_eq!(result.entries[1].title, "Lunch");
+        assert_eq!(result.entries[1].body, "### Still part of lunch");
+    }
 }
@@ -1,11 +1,25 @@
+use super::links::EntryLink;
 use chrono::{NaiveDate, NaiveTime};
 
 #[derive(Debug)]
 pub struct ReadJournalResult {
+    /// Date of the day header, `None` when it's missing or invalid.
+    pub date: Option<NaiveDate>,
     pub entries: Vec<ParsedJournalEntry>,
+    /// Text that isn't an entry (free notes, malformed headings...), kept to write it back.
+    pub unparsed: Vec<UnparsedSegment>,
     pub errors: Vec<String>,
 }
 
+/// A piece of a day file that isn't an entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnparsedSegment {
+    /// Time and title of the entry it follows, `None` when it's before the first entry.
+    pub after: Option<(NaiveTime, String)>,
+    /// The text as it is in the file.
+    pub text: String,
+}
+
 #[derive(Debug)]
 pub struct ParsedJournalEntry {
     pub date: NaiveDate,
@@ -13,5 +27,6 @@ pub struct ParsedJournalEntry {
     pub title: String,
     pub body: String,
     pub tags: Vec<String>,
+    pub links: Vec<EntryLink>,
 }
 
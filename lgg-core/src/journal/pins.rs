@@ -0,0 +1,157 @@
+//! Pinned days and entries (`lgg pin`), one per line in `{journal_dir}/.lgg/pins`:
+//!
+//! ```text
+//! 2025-01-01
+//! 2025-03-04 10:30 - Insurance numbers
+//! ```
+//!
+//! A line with only a date pins all the entries of that day, one with a time pins that entry.
+//! The title after ` - ` is there to read the file, it's not used to find the entry.
+use super::JournalEntry;
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, NaiveTime, Timelike};
+use std::fs;
+use std::path::PathBuf;
+
+pub(crate) const PINS_FILE: &str = ".lgg/pins";
+
+/// A pinned day or entry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pin {
+    /// All the entries of a day.
+    Day(NaiveDate),
+    /// The entry of a day at a time.
+    Entry(NaiveDate, NaiveTime),
+}
+
+impl Pin {
+    pub fn date(&self) -> NaiveDate {
+        match self {
+            Pin::Day(date) | Pin::Entry(date, _) => *date,
+        }
+    }
+
+    /// Whether `entry` is pinned by it.
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        match self {
+            Pin::Day(date) => entry.date == *date,
+            Pin::Entry(date, time) => entry.date == *date && entry.time == *time,
+        }
+    }
+
+    /// The line of the pin in the pins file, with `title` after the entries.
+    fn to_line(self, title: &str) -> String {
+        match self {
+            Pin::Day(date) => date.format("%Y-%m-%d").to_string(),
+            Pin::Entry(date, time) => {
+                let time_format = if time.second() == 0 { "%H:%M" } else { "%H:%M:%S" };
+                format!("{} {} - {title}", date.format("%Y-%m-%d"), time.format(time_format))
+            }
+        }
+    }
+
+    /// Reads a line written by [`Pin::to_line`]. `None` if it's not a pin.
+    fn from_line(line: &str) -> Option<Self> {
+        let line = line.trim();
+        let (pin, _title) = line.split_once(" - ").unwrap_or((line, ""));
+        let mut parts = pin.split_whitespace();
+        let date = NaiveDate::parse_from_str(parts.next()?, "%Y-%m-%d").ok()?;
+        let pin = match parts.next() {
+            None => Pin::Day(date),
+            Some(time) => {
+                let time = NaiveTime::parse_from_str(time, "%H:%M:%S")
+                    .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+                    .ok()?;
+                Pin::Entry(date, time)
+            }
+        };
+        parts.next().is_none().then_some(pin)
+    }
+}
+
+/// The pins file of a journal.
+#[derive(Debug)]
+pub struct Pins {
+    pub path: PathBuf,
+}
+
+impl Pins {
+    /// The pins, in the order they were added. Empty if there are none yet.
+    pub fn read(&self) -> Result<Vec<Pin>> {
+        Ok(self.read_lines()?.iter().filter_map(|l| Pin::from_line(l)).collect())
+    }
+
+    /// Pins `pin`, with `title` to read the file. Returns `false` if it already was.
+    pub fn add(&self, pin: Pin, title: &str) -> Result<bool> {
+        let mut lines = self.read_lines()?;
+        if lines.iter().any(|line| Pin::from_line(line) == Some(pin)) {
+            return Ok(false);
+        }
+        lines.push(pin.to_line(title));
+        self.write_lines(&lines)?;
+        Ok(true)
+    }
+
+    /// Unpins `pin`. Returns `false` if it wasn't pinned.
+    pub fn remove(&self, pin: Pin) -> Result<bool> {
+        let mut lines = self.read_lines()?;
+        let count = lines.len();
+        lines.retain(|line| Pin::from_line(line) != Some(pin));
+        if lines.len() == count {
+            return Ok(false);
+        }
+        self.write_lines(&lines)?;
+        Ok(true)
+    }
+
+    fn read_lines(&self) -> Result<Vec<String>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path)
+            .with_context(|| format!("reading {}", self.path.display()))?;
+        Ok(content.lines().map(str::to_string).collect())
+    }
+
+    fn write_lines(&self, lines: &[String]) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("creating directory {}", parent.display()))?;
+        }
+        let mut content = lines.join("\n");
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        fs::write(&self.path, content).with_context(|| format!("writing {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn adds_and_removes_pins() {
+        let tmp = tempdir().unwrap();
+        let pins = Pins {
+            path: tmp.path().join(PINS_FILE),
+        };
+        let day = Pin::Day(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+        let entry = Pin::Entry(
+            NaiveDate::from_ymd_opt(2025, 3, 4).unwrap(),
+            NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+        );
+        assert!(pins.read().unwrap().is_empty());
+        assert!(pins.add(day, "").unwrap());
+        assert!(pins.add(entry, "Insurance numbers").unwrap());
+        assert!(!pins.add(day, "").unwrap());
+        let content = fs::read_to_string(&pins.path).unwrap();
+        assert_eq!(content, "2025-01-01\n2025-03-04 10:30 - Insurance numbers\n");
+        assert_eq!(pins.read().unwrap(), vec![day, entry]);
+
+        assert!(pins.remove(day).unwrap());
+        assert!(!pins.remove(day).unwrap());
+        assert_eq!(pins.read().unwrap(), vec![entry]);
+    }
+}
@@ -0,0 +1,63 @@
+//! Entries of the focus sessions timed by `lgg pomodoro`.
+//!
+//! The entry is written when the timer ends: its title is what was worked on, with its tags
+//! (`@deepwork writing spec`), and its body says how long the session was.
+use super::JournalWriteEntry;
+use chrono::{NaiveDate, NaiveTime};
+
+/// Title of the pomodoros written without a text.
+pub const DEFAULT_TITLE: &str = "Pomodoro";
+
+/// Title of a pomodoro on `text`, [`DEFAULT_TITLE`] when it's empty.
+pub fn pomodoro_title(text: &str) -> &str {
+    match text.trim() {
+        "" => DEFAULT_TITLE,
+        text => text,
+    }
+}
+
+/// The entry of a pomodoro of `minutes` on `text` that started on `date` at `start` and ended at
+/// `end`.
+pub fn pomodoro_entry(
+    date: NaiveDate,
+    start: NaiveTime,
+    end: NaiveTime,
+    minutes: u32,
+    text: &str,
+) -> JournalWriteEntry {
+    JournalWriteEntry {
+        date,
+        time: start,
+        title: pomodoro_title(text).to_string(),
+        body: format!(
+            "{minutes} minute{} of focus, from {} to {}.",
+            if minutes == 1 { "" } else { "s" },
+            start.format("%H:%M"),
+            end.format("%H:%M")
+        ),
+        tags: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn the_entry_says_how_long_the_focus_lasted() {
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let entry = pomodoro_entry(date, time(9, 0), time(9, 25), 25, " @deepwork writing spec ");
+        assert_eq!(entry.date, date);
+        assert_eq!(entry.time, time(9, 0));
+        assert_eq!(entry.title, "@deepwork writing spec");
+        assert_eq!(entry.body, "25 minutes of focus, from 09:00 to 09:25.");
+
+        let entry = pomodoro_entry(date, time(23, 59), time(0, 0), 1, "");
+        assert_eq!(entry.title, DEFAULT_TITLE);
+        assert_eq!(entry.body, "1 minute of focus, from 23:59 to 00:00.");
+    }
+}
@@ -0,0 +1,121 @@
+//! Private entries (`lgg --private`): their title and body are encrypted with a passphrase, and
+//! the day file only has a `## 10:00 - [private]` heading and the sealed text as the body (private
+//! todos, `todo --private`, are sealed the same way):
+//!
+//! ```text
+//! <!-- lgg:private BASE64 -->
+//! ```
+//!
+//! The base64 holds the salt of the key, the nonce and the encrypted `title\nbody`. The key is
+//! derived from the passphrase with Argon2id, and the text encrypted with XChaCha20-Poly1305.
+use super::JournalWriteEntry;
+use anyhow::{anyhow, Context, Result};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    Key, XChaCha20Poly1305, XNonce,
+};
+
+/// Title of the private entries until they are opened.
+pub const PRIVATE_TITLE: &str = "[private]";
+
+const PREFIX: &str = "<!-- lgg:private ";
+const SUFFIX: &str = " -->";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// `entry` with its title and body sealed with `passphrase`.
+pub fn seal_entry(entry: JournalWriteEntry, passphrase: &str) -> Result<JournalWriteEntry> {
+    Ok(JournalWriteEntry {
+        title: PRIVATE_TITLE.to_string(),
+        body: seal_text(&entry.title, &entry.body, passphrase)?,
+        tags: Vec::new(),
+        ..entry
+    })
+}
+
+/// The sealed text of `title` and `body`, the body of a private entry or todo.
+pub fn seal_text(title: &str, body: &str, passphrase: &str) -> Result<String> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = XChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let text = format!("{title}\n{body}");
+    let sealed = cipher
+        .encrypt(&nonce, text.as_bytes())
+        .map_err(|_| anyhow!("could not encrypt the entry"))?;
+
+    let mut bytes = salt.to_vec();
+    bytes.extend_from_slice(&nonce);
+    bytes.extend(sealed);
+    Ok(format!("{PREFIX}{}{SUFFIX}", STANDARD.encode(bytes)))
+}
+
+/// Whether `body` is the sealed text of a private entry.
+pub fn is_sealed(body: &str) -> bool {
+    let body = body.trim();
+    body.starts_with(PREFIX) && body.ends_with(SUFFIX) && !body.contains('\n')
+}
+
+/// The title and body sealed in `body` by [`seal_entry`]. Fails with the wrong passphrase.
+pub fn open_sealed(body: &str, passphrase: &str) -> Result<(String, String)> {
+    let encoded = body
+        .trim()
+        .strip_prefix(PREFIX)
+        .and_then(|rest| rest.strip_suffix(SUFFIX))
+        .context("not a private entry")?;
+    let bytes = STANDARD
+        .decode(encoded)
+        .context("the private entry is damaged")?;
+    if bytes.len() < SALT_LEN + NONCE_LEN {
+        return Err(anyhow!("the private entry is damaged"));
+    }
+    let (salt, rest) = bytes.split_at(SALT_LEN);
+    let (nonce, sealed) = rest.split_at(NONCE_LEN);
+    let cipher = XChaCha20Poly1305::new(&derive_key(passphrase, salt)?);
+    let text = cipher
+        .decrypt(XNonce::from_slice(nonce), sealed)
+        .map_err(|_| anyhow!("wrong passphrase"))?;
+    let text = String::from_utf8(text).context("the private entry is damaged")?;
+    let (title, body) = text.split_once('\n').unwrap_or((&text, ""));
+    Ok((title.to_string(), body.to_string()))
+}
+
+/// The key of `passphrase` with `salt`.
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("could not derive the key: {e}"))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveTime};
+
+    #[test]
+    fn sealed_entries_open_with_their_passphrase() {
+        let entry = JournalWriteEntry {
+            date: NaiveDate::from_ymd_opt(2025, 8, 15).unwrap(),
+            time: NaiveTime::from_hms_opt(10, 0, 0).unwrap(),
+            title: "Doctor".to_string(),
+            body: "Results are fine.\n\nNext visit in a year @health".to_string(),
+            tags: vec!["@health".to_string()],
+        };
+        let sealed = seal_entry(entry.clone(), "hunter2").unwrap();
+        assert_eq!(sealed.title, PRIVATE_TITLE);
+        assert_eq!(sealed.date, entry.date);
+        assert!(sealed.tags.is_empty());
+        assert!(is_sealed(&sealed.body));
+        assert!(!sealed.body.contains("Doctor"));
+
+        let (title, body) = open_sealed(&sealed.body, "hunter2").unwrap();
+        assert_eq!((title.as_str(), body.as_str()), ("Doctor", entry.body.as_str()));
+        let error = open_sealed(&sealed.body, "wrong").unwrap_err();
+        assert_eq!(error.to_string(), "wrong passphrase");
+        assert!(!is_sealed("<!-- a comment -->"));
+    }
+}
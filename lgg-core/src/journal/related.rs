@@ -0,0 +1,119 @@
+//! Entries about the same things as another one (`lgg related`). Each entry is a vector of its
+//! words and tags weighted by TF-IDF, so the words rare in the journal count more than the
+//! everyday ones, and entries are compared by the cosine of their vectors.
+use super::journal_entry::{JournalEntry, RelatedEntry};
+use crate::stats::words::{tokens, STOPWORDS};
+use std::collections::{HashMap, HashSet};
+
+/// How many times each word (lowercase, without stopwords) and tag is in `entry`.
+fn term_counts(entry: &JournalEntry, stopwords: &HashSet<&str>) -> HashMap<String, f32> {
+    let mut counts: HashMap<String, f32> = HashMap::new();
+    let text = format!("{}\n{}", entry.title, entry.body);
+    let words = tokens(&text)
+        .flatten()
+        .filter(|word| !stopwords.contains(word.as_str()));
+    for term in words.chain(entry.tags.iter().cloned()) {
+        *counts.entry(term).or_default() += 1.0;
+    }
+    counts
+}
+
+/// The `n` entries of `corpus` most like `target`, most alike first. `target` itself and the
+/// entries without a word in common with it are left out.
+pub fn most_similar(target: &JournalEntry, corpus: &[JournalEntry], n: usize) -> Vec<RelatedEntry> {
+    let stopwords: HashSet<&str> = STOPWORDS.iter().copied().collect();
+    let documents: Vec<HashMap<String, f32>> = corpus
+        .iter()
+        .map(|entry| term_counts(entry, &stopwords))
+        .collect();
+    let mut containing: HashMap<&str, f32> = HashMap::new();
+    for document in &documents {
+        for term in document.keys() {
+            *containing.entry(term).or_default() += 1.0;
+        }
+    }
+    // Smoothed, so a term in every entry still counts a little.
+    let total = documents.len() as f32;
+    let idf = |term: &str| {
+        let containing = containing.get(term).copied().unwrap_or_default();
+        ((1.0 + total) / (1.0 + containing)).ln() + 1.0
+    };
+    let weights = |counts: &HashMap<String, f32>| -> HashMap<String, f32> {
+        counts
+            .iter()
+            .map(|(term, count)| (term.clone(), count * idf(term)))
+            .collect()
+    };
+    let norm = |weights: &HashMap<String, f32>| weights.values().map(|w| w * w).sum::<f32>().sqrt();
+
+    let target_weights = weights(&term_counts(target, &stopwords));
+    let target_norm = norm(&target_weights);
+    if target_norm == 0.0 {
+        return Vec::new();
+    }
+    let is_target = |entry: &JournalEntry| {
+        entry.date == target.date && entry.time == target.time && entry.title == target.title
+    };
+    let mut related: Vec<RelatedEntry> = corpus
+        .iter()
+        .zip(&documents)
+        .filter(|(entry, _)| !is_target(entry))
+        .filter_map(|(entry, counts)| {
+            let weights = weights(counts);
+            let dot: f32 = weights
+                .iter()
+                .filter_map(|(term, w)| Some(w * target_weights.get(term)?))
+                .sum();
+            let score = dot / (norm(&weights) * target_norm);
+            (score > 0.0).then(|| RelatedEntry {
+                entry: entry.clone(),
+                score,
+            })
+        })
+        .collect();
+    related.sort_by(|a, b| b.score.total_cmp(&a.score));
+    related.truncate(n);
+    related
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::text_counts;
+    use chrono::{NaiveDate, NaiveTime};
+    use std::path::PathBuf;
+
+    fn entry(day: u32, title: &str, body: &str, tags: &[&str]) -> JournalEntry {
+        let (word_count, char_count) = text_counts(title, body);
+        JournalEntry {
+            date: NaiveDate::from_ymd_opt(2025, 8, day).unwrap(),
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            title: title.to_string(),
+            body: body.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            links: Vec::new(),
+            path: PathBuf::from(format!("/j/2025/08/2025-08-{day:02}.md")),
+            word_count,
+            char_count,
+        }
+    }
+
+    #[test]
+    fn entries_sharing_rare_words_come_first() {
+        let corpus = vec![
+            entry(1, "Garden", "Planted tomatoes and basil in the garden.", &["@home"]),
+            entry(2, "Standup", "Talked about the release and the deadline.", &["@work"]),
+            entry(3, "Tomatoes", "The tomatoes need more water, the basil is fine.", &[]),
+            entry(4, "Release", "The release is delayed, new deadline on Friday.", &["@work"]),
+            entry(5, "Walk", "Went for a walk.", &[]),
+        ];
+
+        let related = most_similar(&corpus[0], &corpus, 3);
+        let titles: Vec<&str> = related.iter().map(|r| r.entry.title.as_str()).collect();
+        assert_eq!(titles, vec!["Tomatoes"]);
+        let related = most_similar(&corpus[1], &corpus, 1);
+        assert_eq!(related[0].entry.title, "Release");
+        assert!(related[0].score > 0.0 && related[0].score <= 1.0);
+        assert!(most_similar(&entry(6, "The", "and the", &[]), &corpus, 3).is_empty());
+    }
+}
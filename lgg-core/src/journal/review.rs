@@ -0,0 +1,176 @@
+//! Editor buffer to look back on a week or a month (`lgg review`).
+//!
+//! The buffer lists the entries of the period with their highlight (the first line or sentence of
+//! the body), the todos done in it and some numbers, and ends with an empty `### Reflections`
+//! section. Everything but the comment lines (`<!--`) is saved as the body of the review entry.
+use super::JournalEntry;
+use crate::TodoEntry;
+use crate::tags::TagPattern;
+use crate::utils::parse_input::split_title_body;
+use chrono::NaiveDate;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+const HELP_LINE: &str = "<!-- Change anything and write your thoughts under Reflections. Lines like this one are not saved. -->";
+
+/// Tag of the review entries.
+pub const REVIEW_TAG: &str = "@review";
+
+/// Title of the review entry of the `period` (`week`, `month`) from `start` to `end`.
+pub fn review_title(period: &str, start: NaiveDate, end: NaiveDate) -> String {
+    format!(
+        "Review of the {period} from {} to {} {REVIEW_TAG}",
+        start.format("%Y-%m-%d"),
+        end.format("%Y-%m-%d")
+    )
+}
+
+/// Pre-seeded buffer to review the days from `start` to `end`, with their `entries` and the
+/// todos `done` in them. The dots of the `tags` don't end the highlights.
+pub fn review_template(
+    start: NaiveDate,
+    end: NaiveDate,
+    entries: &[JournalEntry],
+    done: &[TodoEntry],
+    tags: &TagPattern,
+) -> String {
+    let mut out = format!("{HELP_LINE}\n\n");
+
+    out.push_str("### Entries\n\n");
+    if entries.is_empty() {
+        out.push_str("No entries.\n");
+    }
+    for entry in entries {
+        out.push_str(&format!(
+            "- {} {} - {}\n",
+            entry.date.format("%a %d/%m"),
+            entry.time.format("%H:%M"),
+            entry.title
+        ));
+        let (highlight, _) = split_title_body(&entry.body, tags);
+        if !highlight.is_empty() {
+            out.push_str(&format!("  {highlight}\n"));
+        }
+    }
+
+    out.push_str("\n### Todos Done\n\n");
+    if done.is_empty() {
+        out.push_str("No todos done.\n");
+    }
+    for todo in done {
+        out.push_str(&format!("- [x] {}\n", todo.title));
+    }
+
+    let days = (end - start).num_days() + 1;
+    let mut written: Vec<NaiveDate> = entries.iter().map(|entry| entry.date).collect();
+    written.dedup();
+    let words: usize = entries
+        .iter()
+        .map(|entry| {
+            entry.title.split_whitespace().count() + entry.body.split_whitespace().count()
+        })
+        .sum();
+    out.push_str("\n### Numbers\n\n");
+    out.push_str(&format!(
+        "- {} entries on {} of {days} days\n- {words} words\n- {} todos done\n",
+        entries.len(),
+        written.len(),
+        done.len()
+    ));
+    let tags = top_tags(entries);
+    if !tags.is_empty() {
+        out.push_str(&format!("- Tags: {tags}\n"));
+    }
+
+    out.push_str("\n### Reflections\n\n\n");
+    out
+}
+
+/// The review written in a buffer made with [`review_template`], without its comment lines.
+/// Empty if there is nothing left.
+pub fn read_review_buffer(content: &str) -> String {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("<!--"))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// The five tags most used in `entries`, with their count (`@work (5), @home (2)`).
+fn top_tags(entries: &[JournalEntry]) -> String {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for tag in entries.iter().flat_map(|entry| &entry.tags) {
+        *counts.entry(tag).or_default() += 1;
+    }
+    let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+    counts.sort_by_key(|(_, count)| Reverse(*count));
+    counts
+        .iter()
+        .take(5)
+        .map(|(tag, count)| format!("{tag} ({count})"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::text_counts;
+    use crate::TodoStatus;
+    use chrono::NaiveTime;
+    use std::path::PathBuf;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 8, d).unwrap()
+    }
+
+    fn entry(d: u32, title: &str, body: &str, tags: &[&str]) -> JournalEntry {
+        let (word_count, char_count) = text_counts(title, body);
+        JournalEntry {
+            date: date(d),
+            time: NaiveTime::from_hms_opt(9, 30, 0).unwrap(),
+            title: title.to_string(),
+            body: body.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            links: Vec::new(),
+            path: PathBuf::new(),
+            word_count,
+            char_count,
+        }
+    }
+
+    #[test]
+    fn template_lists_the_period() {
+        let entries = vec![
+            entry(11, "Standup", "Shipped the release. Then lunch.", &["@work"]),
+            entry(11, "Gym", "", &[]),
+            entry(13, "Planning", "Long meeting @work", &["@work"]),
+        ];
+        let done = vec![TodoEntry {
+            due_date: None,
+            done_date: None,
+            created: None,
+            title: "Buy milk".to_string(),
+            body: String::new(),
+            path: PathBuf::new(),
+            status: TodoStatus::Done,
+            tags: Vec::new(),
+        }];
+        let template = review_template(date(11), date(17), &entries, &done, &TagPattern::default());
+        assert!(template.contains("- Mon 11/08 09:30 - Standup\n  Shipped the release.\n- Mon"));
+        assert!(template.contains("- [x] Buy milk\n"));
+        assert!(template.contains("- 3 entries on 2 of 7 days\n- 11 words\n- 1 todos done\n"));
+        assert!(template.contains("- Tags: @work (2)\n"));
+        assert!(template.ends_with("### Reflections\n\n\n"));
+
+        let review = read_review_buffer(&template);
+        assert!(review.starts_with("### Entries\n"));
+        assert!(!review.contains("<!--"));
+        assert_eq!(
+            review_title("week", date(11), date(17)),
+            "Review of the week from 2025-08-11 to 2025-08-17 @review"
+        );
+    }
+}
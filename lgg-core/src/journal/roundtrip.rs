@@ -0,0 +1,135 @@
+//! Round-trip guarantees of the day file format.
+//!
+//! Writing entries with [`write_day`] and reading them back with [`read_day`] gives the same
+//! entries, once [`normalize`]d:
+//!
+//! - Times are written as `HH:MM`, or `HH:MM:SS` when they have seconds. Fractions of a second
+//!   are lost.
+//! - Titles are a single line: line breaks become spaces and surrounding whitespace is trimmed.
+//! - Bodies lose their surrounding whitespace and `\r\n` line breaks become `\n`. Any other text
+//!   survives, including Markdown headings (body lines starting with `# ` or `## ` are escaped on
+//!   write).
+//! - Tags are always the `@tags`/`#tags` found in the title and body.
+use super::format_utils::{format_day_header, format_journal_entry_block};
+use super::parse_entries::{parse_journal_file_content, ParsePolicy};
+use super::JournalWriteEntry;
+use crate::tags::TagPattern;
+use crate::TimePrecision;
+use chrono::NaiveDate;
+
+/// Date format of the day header, the one [`read_day`] understands.
+pub const DAY_HEADER_FORMAT: &str = "%A, %d %b %Y";
+
+/// Content of the day file of `date` with `entries` (expected to be of that day), in order.
+pub fn write_day(date: NaiveDate, entries: &[JournalWriteEntry]) -> String {
+    let mut content = format_day_header(DAY_HEADER_FORMAT, date, None);
+    for entry in entries {
+        content.push_str(&format_journal_entry_block(&entry.title, &entry.body, &entry.time));
+    }
+    content
+}
+
+/// Entries of a day file, in order. Any problem in the file is an error.
+pub fn read_day(content: &str) -> Result<Vec<JournalWriteEntry>, Vec<String>> {
+    let result = parse_journal_file_content(content, &ParsePolicy::default());
+    if !result.errors.is_empty() {
+        return Err(result.errors);
+    }
+    Ok(result
+        .entries
+        .into_iter()
+        .map(|entry| JournalWriteEntry {
+            date: entry.date,
+            time: entry.time,
+            title: entry.title,
+            body: entry.body,
+            tags: entry.tags,
+        })
+        .collect())
+}
+
+/// `entry` as it is read back after writing it.
+pub fn normalize(entry: &JournalWriteEntry) -> JournalWriteEntry {
+    let time = TimePrecision::Seconds.truncate(entry.time);
+    let title = entry.title.replace('\n', " ").trim().to_string();
+    let body = entry.body.replace("\r\n", "\n").trim().to_string();
+    let tags = TagPattern::default().extract(&format!("{title}\n{body}"));
+    JournalWriteEntry {
+        date: entry.date,
+        time,
+        title,
+        body,
+        tags,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::parse_entries::parse_obsidian_note_content;
+    use chrono::NaiveTime;
+    use proptest::prelude::*;
+
+    /// Lines that are likely to confuse the parser, mixed with any text.
+    fn line() -> impl Strategy<Value = String> {
+        prop_oneof![
+            "\\PC*",
+            "#{1,3} \\PC*",
+            "\\\\{1,3}## \\PC*",
+            "[ \t\r]*",
+            "\\PC*@[a-z]{1,5}",
+        ]
+    }
+
+    fn entry() -> impl Strategy<Value = JournalWriteEntry> {
+        let title = prop::collection::vec(line(), 1..3).prop_map(|l| l.join("\n"));
+        let body = prop::collection::vec(line(), 0..6).prop_map(|l| l.join("\n"));
+        (0u32..24, 0u32..60, 0u32..60, title, body).prop_map(|(h, m, s, title, body)| {
+            JournalWriteEntry {
+                date: NaiveDate::from_ymd_opt(2025, 8, 15).unwrap(),
+                time: NaiveTime::from_hms_opt(h, m, s).unwrap(),
+                title,
+                body,
+                tags: Vec::new(),
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn write_then_read_is_lossless(entries in prop::collection::vec(entry(), 0..5)) {
+            let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+            let content = write_day(date, &entries);
+            let expected: Vec<JournalWriteEntry> = entries.iter().map(normalize).collect();
+            prop_assert_eq!(read_day(&content), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn bodies_with_headings_survive_in_obsidian_notes() {
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let time = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        let body = "# Plan\nShip it.\n## Risks\nNone.";
+        let note = format!(
+            "# Daily note\n\n{}",
+            format_journal_entry_block("Release", body, &time)
+        );
+        let result = parse_obsidian_note_content(&note, date, &TagPattern::default());
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.entries[0].body, body);
+    }
+
+    #[test]
+    fn bodies_with_headings_survive() {
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let entry = JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            title: "Meeting notes".to_string(),
+            body: "## Agenda\n- Release\n\\## Not escaped".to_string(),
+            tags: Vec::new(),
+        };
+        let content = write_day(date, std::slice::from_ref(&entry));
+        assert_eq!(read_day(&content), Ok(vec![normalize(&entry)]));
+    }
+}
@@ -0,0 +1,335 @@
+//! Full-text index of the journal, kept in [`AppDirs::search_dir`](crate::AppDirs::search_dir).
+//! Enabled with the `search` feature.
+//!
+//! Every entry is a document. Documents are replaced a whole day file at a time: when lgg writes
+//! the file, and before searching for files modified since they were indexed (e.g. in `$EDITOR`).
+use super::device::split_stamp;
+use super::journal_entry::{text_counts, JournalEntry, SearchHit};
+use super::links::extract_links;
+use super::parse_entries::parse_entry_time;
+use super::private::is_sealed;
+use super::snippets::{query_words, search_snippets};
+use super::Journal;
+use crate::tags::TagPattern;
+use crate::QueryError;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tantivy::collector::TopDocs;
+use tantivy::directory::MmapDirectory;
+use tantivy::query::QueryParser;
+use tantivy::schema::{Field, Schema, Value, STORED, STRING, TEXT};
+use tantivy::{doc, Index, IndexWriter, TantivyDocument, Term};
+
+/// Modification time of every indexed file, to find the ones changed since.
+const FILES_STATE: &str = "files.json";
+const WRITER_MEMORY_BUDGET: usize = 15_000_000;
+
+struct Fields {
+    path: Field,
+    date: Field,
+    time: Field,
+    title: Field,
+    body: Field,
+}
+
+pub struct SearchIndex {
+    index: Index,
+    fields: Fields,
+    state_file: PathBuf,
+    /// Path of every indexed file and its modification time (ms since the epoch).
+    indexed: HashMap<PathBuf, u128>,
+}
+
+impl SearchIndex {
+    /// Whether there is already an index in `search_dir`.
+    pub fn exists(search_dir: &Path) -> bool {
+        search_dir.join(FILES_STATE).exists()
+    }
+
+    /// Opens the index in `search_dir`, creating an empty one if needed.
+    pub fn open(search_dir: &Path) -> Result<Self> {
+        let index_dir = search_dir.join("index");
+        fs::create_dir_all(&index_dir)
+            .with_context(|| format!("creating search index {}", index_dir.display()))?;
+
+        let mut builder = Schema::builder();
+        let fields = Fields {
+            path: builder.add_text_field("path", STRING | STORED),
+            date: builder.add_text_field("date", STRING | STORED),
+            time: builder.add_text_field("time", STORED),
+            title: builder.add_text_field("title", TEXT | STORED),
+            body: builder.add_text_field("body", TEXT | STORED),
+        };
+        let index = Index::open_or_create(MmapDirectory::open(&index_dir)?, builder.build())
+            .with_context(|| format!("opening search index {}", index_dir.display()))?;
+
+        let state_file = search_dir.join(FILES_STATE);
+        let indexed = match fs::read_to_string(&state_file) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+            Err(_) => HashMap::new(),
+        };
+
+        Ok(Self {
+            index,
+            fields,
+            state_file,
+            indexed,
+        })
+    }
+
+    /// Indexes again the files modified since the last time, and drops the deleted ones.
+    /// Returns the problems found parsing the files.
+    pub fn sync(&mut self, journal: &Journal) -> Result<Vec<QueryError>> {
+        let files: HashSet<PathBuf> = journal
+            .storage
+            .list_files(&journal.notes_dir())
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        let changed: Vec<PathBuf> = files
+            .iter()
+            .filter(|f| self.indexed.get(*f) != modified(journal, f).as_ref())
+            .cloned()
+            .collect();
+        let deleted: Vec<PathBuf> = self
+            .indexed
+            .keys()
+            .filter(|f| !files.contains(*f))
+            .cloned()
+            .collect();
+        if changed.is_empty() && deleted.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.update_files(journal, &changed, &deleted)
+    }
+
+    /// Replaces the documents of `path` with its current entries.
+    pub fn update_file(&mut self, journal: &Journal, path: &Path) -> Result<Vec<QueryError>> {
+        self.update_files(journal, &[path.to_path_buf()], &[])
+    }
+
+    fn update_files(
+        &mut self,
+        journal: &Journal,
+        changed: &[PathBuf],
+        deleted: &[PathBuf],
+    ) -> Result<Vec<QueryError>> {
+        let mut errors = Vec::new();
+        let mut writer: IndexWriter = self
+            .index
+            .writer_with_num_threads(1, WRITER_MEMORY_BUDGET)?;
+
+        for path in deleted {
+            writer.delete_term(self.path_term(path));
+            self.indexed.remove(path);
+        }
+        for path in changed {
+            writer.delete_term(self.path_term(path));
+            let result = journal.parse_file(path);
+            for entry in result.entries {
+                writer.add_document(doc!(
+                    self.fields.path => path.to_string_lossy().to_string(),
+                    self.fields.date => entry.date.format("%Y-%m-%d").to_string(),
+                    self.fields.time => entry.time.format("%H:%M:%S").to_string(),
+                    self.fields.title => entry.title,
+                    self.fields.body => indexed_body(&entry.body).to_string(),
+                ))?;
+            }
+            errors.extend(result.errors);
+            match modified(journal, path) {
+                Some(mtime) => self.indexed.insert(path.clone(), mtime),
+                None => self.indexed.remove(path),
+            };
+        }
+        writer.commit()?;
+
+        let state = serde_json::to_string(&self.indexed)?;
+        fs::write(&self.state_file, state)
+            .with_context(|| format!("writing {}", self.state_file.display()))?;
+        Ok(errors)
+    }
+
+    /// Best matches for `query`, best first, with `context` lines around the matches of their
+    /// bodies and the `tags` in them. Supports the tantivy query syntax: `"exact phrase"`,
+    /// `+required -excluded`, `title:kickoff`...
+    pub fn search(
+        &self,
+        query: &str,
+        limit: usize,
+        context: usize,
+        tags: &TagPattern,
+    ) -> Result<Vec<SearchHit>> {
+        let searcher = self.index.reader()?.searcher();
+        let mut parser =
+            QueryParser::for_index(&self.index, vec![self.fields.title, self.fields.body]);
+        parser.set_conjunction_by_default();
+        let words = query_words(query);
+        let (query, _) = parser.parse_query_lenient(query);
+
+        let mut hits = Vec::new();
+        for (score, address) in searcher.search(&query, &TopDocs::with_limit(limit))? {
+            let doc: TantivyDocument = searcher.doc(address)?;
+            let text = |field: Field| {
+                doc.get_first(field)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            let (Ok(date), Some(time)) = (
+                NaiveDate::parse_from_str(&text(self.fields.date), "%Y-%m-%d"),
+                parse_entry_time(&text(self.fields.time)),
+            ) else {
+                continue;
+            };
+            let (title, body) = (text(self.fields.title), text(self.fields.body));
+            let snippets = search_snippets(&body, &words, context);
+            let (word_count, char_count) = text_counts(&title, &body);
+            hits.push(SearchHit {
+                entry: JournalEntry {
+                    date,
+                    time,
+                    tags: tags.extract(&format!("{title}\n{body}")),
+                    links: extract_links(&format!("{title}\n{body}")),
+                    title,
+                    body,
+                    path: PathBuf::from(text(self.fields.path)),
+                    word_count,
+                    char_count,
+                },
+                score,
+                snippets,
+            });
+        }
+        Ok(hits)
+    }
+
+    fn path_term(&self, path: &Path) -> Term {
+        Term::from_field_text(self.fields.path, &path.to_string_lossy())
+    }
+}
+
+/// `body` as indexed: without its device stamp, and empty for private entries, which can't be
+/// searched without their passphrase.
+fn indexed_body(body: &str) -> &str {
+    let body = split_stamp(body).0;
+    if is_sealed(body) {
+        ""
+    } else {
+        body
+    }
+}
+
+fn modified(journal: &Journal, path: &Path) -> Option<u128> {
+    let modified = journal.storage.stamp(path)?.modified?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::ParseCache;
+    use crate::{AppDirs, FsStorage, JournalWriteEntry, Keywords, ParseMode, TimePrecision};
+    use std::sync::Arc;
+    use chrono::NaiveTime;
+    use tempfile::tempdir;
+
+    fn write(journal: &Journal, d: u32, time: &str, title: &str, body: &str) {
+        journal
+            .create_entry(JournalWriteEntry {
+                date: NaiveDate::from_ymd_opt(2025, 8, d).unwrap(),
+                time: NaiveTime::parse_from_str(time, "%H:%M").unwrap(),
+                title: title.to_string(),
+                body: body.to_string(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn finds_ranked_entries_with_snippets() {
+        let dir = tempdir().unwrap();
+        let journal = Journal {
+            journal_dir: dir.path().to_path_buf(),
+            journal_date_format: "%A, %d %b %Y".to_string(),
+            reference_date: NaiveDate::from_ymd_opt(2025, 8, 20).unwrap(),
+            obsidian: None,
+            cache: ParseCache::default(),
+            parse_mode: ParseMode::Strict,
+            default_time: NaiveTime::from_hms_opt(21, 0, 0).unwrap(),
+            time_precision: TimePrecision::Minutes,
+            locale: None,
+            keywords: Keywords::default(),
+            dirs: AppDirs::for_journal(Some(Path::new(".lgg")), dir.path()),
+            device_id: None,
+            default_tags: Vec::new(),
+            auto_tags: Vec::new(),
+            tag_pattern: Default::default(),
+            tag_aliases: Default::default(),
+            ignored_tags: Vec::new(),
+            index_files: false,
+            storage: Arc::new(FsStorage),
+        };
+        write(&journal, 14, "09:00", "Standup", "Talked about the release plan.");
+        write(&journal, 15, "18:00", "Walk", "Long walk by the river, then planned the release.");
+
+        let mut index = SearchIndex::open(&journal.dirs.search_dir()).unwrap();
+        assert!(index.sync(&journal).unwrap().is_empty());
+
+        let hits = index.search("release", 10, 2, &journal.tag_pattern).unwrap();
+        assert_eq!(hits.len(), 2);
+        let hits = index.search("\"release plan\"", 10, 2, &journal.tag_pattern).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry.title, "Standup");
+        let snippet = &hits[0].snippets[0];
+        let highlighted: Vec<&str> = snippet
+            .highlights
+            .iter()
+            .map(|r| &snippet.text[r.clone()])
+            .collect();
+        assert_eq!(highlighted, vec!["release", "plan"]);
+    }
+
+    #[test]
+    fn keeps_up_with_writes_and_external_edits() {
+        let dir = tempdir().unwrap();
+        let journal = Journal {
+            journal_dir: dir.path().to_path_buf(),
+            journal_date_format: "%A, %d %b %Y".to_string(),
+            reference_date: NaiveDate::from_ymd_opt(2025, 8, 20).unwrap(),
+            obsidian: None,
+            cache: ParseCache::default(),
+            parse_mode: ParseMode::Strict,
+            default_time: NaiveTime::from_hms_opt(21, 0, 0).unwrap(),
+            time_precision: TimePrecision::Minutes,
+            locale: None,
+            keywords: Keywords::default(),
+            dirs: AppDirs::for_journal(Some(Path::new(".lgg")), dir.path()),
+            device_id: None,
+            default_tags: Vec::new(),
+            auto_tags: Vec::new(),
+            tag_pattern: Default::default(),
+            tag_aliases: Default::default(),
+            ignored_tags: Vec::new(),
+            index_files: false,
+            storage: Arc::new(FsStorage),
+        };
+        write(&journal, 14, "09:00", "Standup", "Nothing new.");
+        let mut index = SearchIndex::open(&journal.dirs.search_dir()).unwrap();
+        index.sync(&journal).unwrap();
+
+        // Written by lgg: the index is updated right away.
+        write(&journal, 14, "10:00", "Dentist", "Checkup.");
+        let index = SearchIndex::open(&journal.dirs.search_dir()).unwrap();
+        assert_eq!(index.search("dentist", 10, 2, &journal.tag_pattern).unwrap().len(), 1);
+
+        // Deleted by hand: gone after the next sync.
+        fs::remove_file(journal.day_file(NaiveDate::from_ymd_opt(2025, 8, 14).unwrap())).unwrap();
+        let mut index = SearchIndex::open(&journal.dirs.search_dir()).unwrap();
+        index.sync(&journal).unwrap();
+        assert!(index.search("dentist", 10, 2, &journal.tag_pattern).unwrap().is_empty());
+    }
+}
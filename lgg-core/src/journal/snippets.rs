@@ -0,0 +1,94 @@
+//! The lines of an entry body around the words `--search` found, to show them instead of the
+//! whole body.
+use super::journal_entry::SearchSnippet;
+use regex::{Regex, RegexBuilder};
+
+/// The words `query` looks for: without the syntax of the search index (`"`, `+word`,
+/// `title:word`) and the excluded ones (`-word`).
+pub fn query_words(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .filter(|word| !word.starts_with('-'))
+        .map(|word| word.rsplit(':').next().unwrap_or(word))
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// The pieces of `body` with any of `words`, ignoring case, each with `context` lines before and
+/// after the ones that have them. Pieces that touch are joined. Empty if no word is in `body`.
+pub fn search_snippets(body: &str, words: &[String], context: usize) -> Vec<SearchSnippet> {
+    let Some(matcher) = matcher(words) else {
+        return Vec::new();
+    };
+    // Where each line starts and ends in `body`, without its line break.
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for line in body.split_inclusive('\n') {
+        lines.push(start..start + line.trim_end_matches(['\n', '\r']).len());
+        start += line.len();
+    }
+
+    let mut windows: Vec<(usize, usize)> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if !matcher.is_match(&body[line.clone()]) {
+            continue;
+        }
+        let (first, last) = (i.saturating_sub(context), i.saturating_add(context));
+        match windows.last_mut() {
+            Some((_, end)) if first <= end.saturating_add(1) => *end = last,
+            _ => windows.push((first, last)),
+        }
+    }
+    windows
+        .into_iter()
+        .map(|(first, last)| {
+            let last = last.min(lines.len() - 1);
+            let (start, end) = (lines[first].start, lines[last].end);
+            let text = body[start..end].to_string();
+            let highlights = matcher.find_iter(&text).map(|m| m.range()).collect();
+            SearchSnippet {
+                start,
+                text,
+                highlights,
+            }
+        })
+        .collect()
+}
+
+/// A regex matching any of `words`, ignoring case. `None` without words.
+fn matcher(words: &[String]) -> Option<Regex> {
+    if words.is_empty() {
+        return None;
+    }
+    let pattern: Vec<String> = words.iter().map(|word| regex::escape(word)).collect();
+    RegexBuilder::new(&pattern.join("|"))
+        .case_insensitive(true)
+        .build()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snippets_keep_the_lines_around_the_matches() {
+        let body = "one\ntwo\nthree Release\nfour\nfive\nsix\nseven\neight\nnine release\nten";
+        let words = query_words("+\"release plan\" -draft title:Nine");
+        assert_eq!(words, vec!["release", "plan", "nine"]);
+
+        let snippets = search_snippets(body, &words, 1);
+        let texts: Vec<&str> = snippets.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["two\nthree Release\nfour", "eight\nnine release\nten"]);
+        let second = &snippets[1];
+        assert_eq!(&body[second.start..second.start + second.text.len()], second.text);
+        let found: Vec<&str> = second.highlights.iter().map(|r| &second.text[r.clone()]).collect();
+        assert_eq!(found, vec!["nine", "release"]);
+
+        assert_eq!(search_snippets(body, &words, 3).len(), 1);
+        assert_eq!(search_snippets(body, &words, usize::MAX)[0].text, body);
+        assert!(search_snippets(body, &["nothing".to_string()], 2).is_empty());
+    }
+}
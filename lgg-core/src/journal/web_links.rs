@@ -0,0 +1,149 @@
+//! Entries that are only a link to a web page (`lgg url`, or `fetch_titles` in the config): the
+//! title of the page is fetched with `curl` and the entry becomes `[Title](url) @link`.
+//!
+//! Fetching gives up after a few seconds, and the entry is saved with the bare link when the
+//! page can't be reached, like when offline.
+use super::JournalWriteEntry;
+use crate::utils::command::run;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Tag of the entries of links with the title of their page.
+pub const LINK_TAG: &str = "@link";
+
+/// Seconds `curl` waits for a page.
+const FETCH_TIMEOUT_SECS: &str = "5";
+/// Largest page read for its title, in bytes.
+const MAX_PAGE_BYTES: &str = "2000000";
+
+/// `text` if it's a single `http(s)://` link and nothing else.
+pub fn lone_url(text: &str) -> Option<&str> {
+    let text = text.trim();
+    let is_url = ["http://", "https://"]
+        .iter()
+        .any(|scheme| text.len() > scheme.len() && text.starts_with(scheme));
+    (is_url && !text.contains(char::is_whitespace)).then_some(text)
+}
+
+/// The `<title>` of an HTML page, on one line and with its entities decoded.
+pub fn page_title(html: &str) -> Option<String> {
+    static TITLE: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap());
+
+    let raw = TITLE.captures(html)?.get(1)?.as_str();
+    let title = decode_entities(&raw.split_whitespace().collect::<Vec<_>>().join(" "));
+    (!title.is_empty()).then_some(title)
+}
+
+/// The title of the page at `url`, `None` if it can't be fetched in time or has none.
+pub fn fetch_title(url: &str) -> Option<String> {
+    let args = [
+        "--silent",
+        "--fail",
+        "--location",
+        "--max-time",
+        FETCH_TIMEOUT_SECS,
+        "--max-filesize",
+        MAX_PAGE_BYTES,
+        url,
+    ];
+    match run("curl", None, &args) {
+        Ok(html) => page_title(&html),
+        Err(error) => {
+            log::debug!("could not fetch the title of {url}: {error:#}");
+            None
+        }
+    }
+}
+
+/// `entry` as a Markdown link to `url` titled `page_title`, tagged `@link`.
+pub fn titled_link(entry: JournalWriteEntry, url: &str, page_title: &str) -> JournalWriteEntry {
+    let page_title = page_title.replace('[', "\\[").replace(']', "\\]");
+    let mut tags = entry.tags;
+    if !tags.iter().any(|tag| tag == LINK_TAG) {
+        tags.push(LINK_TAG.to_string());
+    }
+    JournalWriteEntry {
+        title: format!("[{page_title}]({url}) {LINK_TAG}"),
+        tags,
+        ..entry
+    }
+}
+
+/// `entry` with the title of its page, if it's only a link and the page can be fetched.
+/// Otherwise, `entry` as it is.
+pub fn with_page_title(entry: JournalWriteEntry) -> JournalWriteEntry {
+    if !entry.body.trim().is_empty() {
+        return entry;
+    }
+    let Some(url) = lone_url(&entry.title).map(str::to_string) else {
+        return entry;
+    };
+    match fetch_title(&url) {
+        Some(page_title) => titled_link(entry, &url, &page_title),
+        None => entry,
+    }
+}
+
+/// The few entities page titles have, like `&amp;` and `&#39;`.
+fn decode_entities(text: &str) -> String {
+    static ENTITY: Lazy<Regex> = Lazy::new(|| Regex::new(r"&(#x[0-9a-fA-F]+|#\d+|\w+);").unwrap());
+
+    ENTITY
+        .replace_all(text, |caps: &regex::Captures| {
+            let name = &caps[1];
+            let decoded = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ if name.starts_with("#x") => u32::from_str_radix(&name[2..], 16)
+                    .ok()
+                    .and_then(char::from_u32),
+                _ if name.starts_with('#') => name[1..].parse().ok().and_then(char::from_u32),
+                _ => None,
+            };
+            decoded.map_or_else(|| caps[0].to_string(), String::from)
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveTime};
+
+    #[test]
+    fn only_a_link_is_a_lone_url() {
+        assert_eq!(lone_url(" https://example.com/a.html "), Some("https://example.com/a.html"));
+        assert_eq!(lone_url("http://localhost:8080"), Some("http://localhost:8080"));
+        assert_eq!(lone_url("read https://example.com"), None);
+        assert_eq!(lone_url("https://"), None);
+        assert_eq!(lone_url("ftp://example.com"), None);
+    }
+
+    #[test]
+    fn reads_and_decodes_the_title_of_a_page() {
+        let html = "<html><head><TITLE lang=\"en\">\n  Rust &amp; WebAssembly &#8212;\n  \
+                    Tom&#x27;s notes </TITLE></head></html>";
+        assert_eq!(page_title(html).as_deref(), Some("Rust & WebAssembly — Tom's notes"));
+        assert_eq!(page_title("<title>  </title>"), None);
+        assert_eq!(page_title("<p>No title</p>"), None);
+    }
+
+    #[test]
+    fn a_titled_link_is_markdown_tagged_link() {
+        let entry = JournalWriteEntry {
+            date: NaiveDate::from_ymd_opt(2025, 8, 15).unwrap(),
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            title: "https://example.com".to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        };
+        let linked = titled_link(entry, "https://example.com", "The [best] page");
+        assert_eq!(linked.title, "[The \\[best\\] page](https://example.com) @link");
+        assert_eq!(linked.tags, vec![LINK_TAG]);
+    }
+}
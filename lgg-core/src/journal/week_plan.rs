@@ -0,0 +1,138 @@
+//! Editor buffer to plan the next week (`lgg plan week`).
+//!
+//! The buffer has a section per day of the week, with the `[commitments]` of the config on that
+//! weekday and the open todos due that day, then the open todos that were due before the week
+//! (`### Carried Over`) and an empty `### Goals` section. Like the review, everything but the
+//! comment lines is saved as the body of the plan entry.
+use crate::TodoEntry;
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+const HELP_LINE: &str = "<!-- Plan your week: change anything and write your goals under Goals. Lines like this one are not saved. -->";
+
+/// Tag of the plan entries.
+pub const PLAN_TAG: &str = "@plan";
+
+/// Something done every week on some days, from the `[commitments]` of the config
+/// (`"Team standup" = "mon,tue,wed,thu,fri"`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Commitment {
+    pub name: String,
+    pub days: Vec<Weekday>,
+}
+
+/// Monday and Sunday of the week after the one of `today`.
+pub fn next_week(today: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let days_left = 7 - u64::from(today.weekday().num_days_from_monday());
+    let start = today + Days::new(days_left);
+    (start, start + Days::new(6))
+}
+
+/// Title of the plan entry of the week from `start` to `end`.
+pub fn plan_title(start: NaiveDate, end: NaiveDate) -> String {
+    format!(
+        "Plan of the week from {} to {} {PLAN_TAG}",
+        start.format("%Y-%m-%d"),
+        end.format("%Y-%m-%d")
+    )
+}
+
+/// Pre-seeded buffer to plan the seven days from `start`, with the `commitments` of each weekday
+/// and the `open` todos due in the week or before it. Todos without a due date or due later are
+/// left out.
+pub fn plan_template(start: NaiveDate, commitments: &[Commitment], open: &[TodoEntry]) -> String {
+    let mut out = format!("{HELP_LINE}\n");
+    for date in start.iter_days().take(7) {
+        out.push_str(&format!("\n### {}\n\n", date.format("%a %d/%m")));
+        for commitment in commitments {
+            if commitment.days.contains(&date.weekday()) {
+                out.push_str(&format!("- {}\n", commitment.name));
+            }
+        }
+        for todo in open {
+            if todo.due_date.is_some_and(|due| due.date() == date) {
+                out.push_str(&format!("- [ ] {}\n", todo.title));
+            }
+        }
+    }
+
+    let carried: Vec<&TodoEntry> = open
+        .iter()
+        .filter(|todo| todo.due_date.is_some_and(|due| due.date() < start))
+        .collect();
+    if !carried.is_empty() {
+        out.push_str("\n### Carried Over\n\n");
+        for todo in carried {
+            let due = todo.due_date.expect("filtered by due date");
+            out.push_str(&format!("- [ ] {} (due {})\n", todo.title, due.format("%d/%m")));
+        }
+    }
+
+    out.push_str("\n### Goals\n\n\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TodoStatus;
+    use std::path::PathBuf;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 8, d).unwrap()
+    }
+
+    fn todo(title: &str, due: Option<(u32, u32)>) -> TodoEntry {
+        TodoEntry {
+            due_date: due.map(|(d, h)| date(d).and_hms_opt(h, 0, 0).unwrap()),
+            done_date: None,
+            created: None,
+            title: title.to_string(),
+            body: String::new(),
+            path: PathBuf::new(),
+            status: TodoStatus::Pending,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn next_week_starts_on_the_next_monday() {
+        // Friday 15 and Monday 11 of August 2025.
+        assert_eq!(next_week(date(15)), (date(18), date(24)));
+        assert_eq!(next_week(date(11)), (date(18), date(24)));
+        assert_eq!(next_week(date(17)), (date(18), date(24)));
+    }
+
+    #[test]
+    fn template_has_the_days_commitments_and_todos() {
+        let commitments = vec![
+            Commitment {
+                name: "Team standup".to_string(),
+                days: vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri],
+            },
+            Commitment {
+                name: "Gym".to_string(),
+                days: vec![Weekday::Tue],
+            },
+        ];
+        let open = vec![
+            todo("Call the bank", Some((19, 10))),
+            todo("Renew passport", Some((12, 9))),
+            todo("Read a book", None),
+            todo("Dentist", Some((26, 9))),
+        ];
+        let template = plan_template(date(18), &commitments, &open);
+        assert!(template.contains("\n### Mon 18/08\n\n- Team standup\n\n### Tue 19/08\n"));
+        assert!(template.contains(
+            "### Tue 19/08\n\n- Team standup\n- Gym\n- [ ] Call the bank\n\n"
+        ));
+        assert!(template.contains("### Sun 24/08\n\n\n### Carried Over\n"));
+        assert!(template.contains("- [ ] Renew passport (due 12/08)\n"));
+        assert!(!template.contains("Read a book"));
+        assert!(!template.contains("Dentist"));
+        assert!(template.ends_with("### Goals\n\n\n"));
+        assert_eq!(
+            plan_title(date(18), date(24)),
+            "Plan of the week from 2025-08-18 to 2025-08-24 @plan"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! Other journals than the one of `journal_dir`, from the `[journals]` of the config: one is
+//! used with `--journal work`, and `--journal all` reads all of them at once.
+//!
+//! The journal of `journal_dir` is the `default` one. The others only have their own directory
+//! and `default_tags`, every other option is the one of the config.
+use crate::journal::{JournalQueryResult, JournalSearchResult, ReadEntriesOptions};
+use crate::{Config, Lgg};
+use anyhow::{anyhow, bail, Result};
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::thread;
+
+/// Name of the journal of `journal_dir`.
+pub const DEFAULT_JOURNAL: &str = "default";
+/// `--journal all`: every journal at once.
+pub const ALL_JOURNALS: &str = "all";
+
+/// A journal of `[journals]`:
+/// [journals.work]
+/// journal_dir = "/home/me/work/journal"
+/// default_tags = ["@work"]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JournalConfig {
+    pub journal_dir: PathBuf,
+    /// Tags added to every entry written to this journal, instead of the `default_tags` of the
+    /// config.
+    #[serde(default)]
+    pub default_tags: Vec<String>,
+}
+
+/// Whether `name` can be the name of a journal of `[journals]`: not empty, and not one of the
+/// names `--journal` already has a meaning for.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.trim().is_empty() && name != DEFAULT_JOURNAL && name != ALL_JOURNALS
+}
+
+impl Config {
+    /// The names of the journals, the `default` one first.
+    pub fn journal_names(&self) -> Vec<&str> {
+        let others = self.journals.keys().map(String::as_str);
+        std::iter::once(DEFAULT_JOURNAL).chain(others).collect()
+    }
+
+    /// This config, writing to the journal `name` instead of the default one. The other
+    /// journals are always local directories.
+    pub fn for_journal(&self, name: &str) -> Result<Config> {
+        if name == DEFAULT_JOURNAL {
+            return Ok(self.clone());
+        }
+        let Some(journal) = self.journals.get(name) else {
+            let names = self.journal_names().join(", ");
+            bail!("Unknown journal \"{name}\". The journals of the config are: {names}.");
+        };
+        Ok(Config {
+            journal_dir: journal.journal_dir.clone(),
+            default_tags: journal.default_tags.clone(),
+            remote: None,
+            sync: None,
+            ..self.clone()
+        })
+    }
+}
+
+/// The entries of every journal matching `options`, read at the same time, with the name of
+/// their journal. The default journal is first.
+pub fn read_all_journals(
+    config: &Config,
+    options: &ReadEntriesOptions,
+) -> Result<Vec<(String, JournalQueryResult)>> {
+    in_every_journal(config, |lgg| Ok(lgg.journal.read_entries(options)))
+}
+
+/// [`Journal::search_entries`](crate::journal::Journal::search_entries) in every journal at the
+/// same time, with the name of their journal.
+pub fn search_all_journals(
+    config: &Config,
+    query: &str,
+    limit: usize,
+    context: usize,
+) -> Result<Vec<(String, JournalSearchResult)>> {
+    in_every_journal(config, |lgg| lgg.journal.search_entries(query, limit, context))
+}
+
+/// Runs `read` on every journal of `config`, each one in its own thread.
+fn in_every_journal<T: Send>(
+    config: &Config,
+    read: impl Fn(&Lgg) -> Result<T> + Sync,
+) -> Result<Vec<(String, T)>> {
+    let read = &read;
+    thread::scope(|scope| {
+        let handles: Vec<_> = config
+            .journal_names()
+            .into_iter()
+            .map(|name| {
+                let handle = scope.spawn(move || {
+                    let lgg = Lgg::with_config(config.for_journal(name)?)?;
+                    read(&lgg)
+                });
+                (name, handle)
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|(name, handle)| {
+                let result = handle
+                    .join()
+                    .map_err(|_| anyhow!("reading the journal \"{name}\" failed"))??;
+                Ok((name.to_string(), result))
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::mk_config;
+    use crate::JournalWriteEntry;
+    use chrono::{NaiveDate, NaiveTime};
+    use tempfile::tempdir;
+
+    #[test]
+    fn reads_every_journal_labeled_with_its_name() {
+        let tmp = tempdir().unwrap();
+        let mut config = mk_config(tmp.path().join("journal"), None);
+        config.journals.insert(
+            "work".to_string(),
+            JournalConfig {
+                journal_dir: tmp.path().join("work"),
+                default_tags: vec!["@work".to_string()],
+            },
+        );
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        for (name, title) in [("default", "Walk the dog"), ("work", "Release planning")] {
+            let lgg = Lgg::with_config(config.for_journal(name).unwrap()).unwrap();
+            lgg.journal
+                .create_entry(JournalWriteEntry {
+                    date,
+                    time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                    title: title.to_string(),
+                    body: String::new(),
+                    tags: Vec::new(),
+                })
+                .unwrap();
+        }
+
+        let results = read_all_journals(&config, &ReadEntriesOptions::default()).unwrap();
+        let titles: Vec<(&str, &str)> = results
+            .iter()
+            .flat_map(|(name, result)| {
+                result.entries.iter().map(move |e| (name.as_str(), e.title.as_str()))
+            })
+            .collect();
+        assert_eq!(
+            titles,
+            vec![("default", "Walk the dog"), ("work", "Release planning @work")]
+        );
+
+        let hits = search_all_journals(&config, "release", 10, 2).unwrap();
+        assert_eq!(hits[0].1.hits.len(), 0);
+        assert_eq!(hits[1].1.hits.len(), 1);
+        assert!(config.for_journal("home").is_err());
+        assert!(!is_valid_name("all"));
+    }
+}
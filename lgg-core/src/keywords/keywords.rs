@@ -9,6 +9,8 @@ use strum_macros::{AsRefStr, EnumIter, EnumString};
 #[strum(serialize_all = "kebab-case")]
 pub enum Keyword {
     At,
+    /// Word before the due date of a todo (`on friday`, `by friday`).
+    Due,
     Monday,
     Tuesday,
     Wednesday,
@@ -26,71 +28,92 @@ pub enum Keyword {
     Midnight,
     #[strum(serialize = "last week")]
     LastWeek,
+    #[strum(serialize = "last month")]
     LastMonth,
+    #[strum(serialize = "last year")]
     LastYear,
+    #[strum(serialize = "this week")]
     ThisWeek,
+    #[strum(serialize = "this month")]
     ThisMonth,
+    #[strum(serialize = "this year")]
     ThisYear,
 }
 
-pub struct Keywords;
+/// A registry of the words read as keywords (input → canonical), lowercased for
+/// case-insensitive lookups. Each [`crate::Config`] builds its own from the built-in words and
+/// its `[synonyms]`, so two configs in the same process don't see each other's synonyms.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keywords {
+    words: HashMap<String, Keyword>,
+    /// Words that are some days from a keyword (`"day before yesterday" = "yesterday - 1"`).
+    shifted: HashMap<String, (Keyword, i64)>,
+}
+
+impl Default for Keywords {
+    /// The built-in words: the canonical ones (`today`, `last week`) and `on`/`by` for `due`.
+    fn default() -> Self {
+        let mut m = HashMap::new();
+        m.insert("at".to_string(), Keyword::At);
+        m.insert("due".to_string(), Keyword::Due);
+        m.insert("on".to_string(), Keyword::Due);
+        m.insert("by".to_string(), Keyword::Due);
+        m.insert("monday".to_string(), Keyword::Monday);
+        m.insert("tuesday".to_string(), Keyword::Tuesday);
+        m.insert("wednesday".to_string(), Keyword::Wednesday);
+        m.insert("thursday".to_string(), Keyword::Thursday);
+        m.insert("friday".to_string(), Keyword::Friday);
+        m.insert("saturday".to_string(), Keyword::Saturday);
+        m.insert("sunday".to_string(), Keyword::Sunday);
+        m.insert("today".to_string(), Keyword::Today);
+        m.insert("yesterday".to_string(), Keyword::Yesterday);
+        m.insert("tomorrow".to_string(), Keyword::Tomorrow);
+        m.insert("morning".to_string(), Keyword::Morning);
+        m.insert("noon".to_string(), Keyword::Noon);
+        m.insert("evening".to_string(), Keyword::Evening);
+        m.insert("night".to_string(), Keyword::Night);
+        m.insert("midnight".to_string(), Keyword::Midnight);
+        m.insert("last week".to_string(), Keyword::LastWeek);
+        m.insert("last month".to_string(), Keyword::LastMonth);
+        m.insert("last year".to_string(), Keyword::LastYear);
+        m.insert("this week".to_string(), Keyword::ThisWeek);
+        m.insert("this month".to_string(), Keyword::ThisMonth);
+        m.insert("this year".to_string(), Keyword::ThisYear);
+
+        Self {
+            words: m,
+            shifted: HashMap::new(),
+        }
+    }
+}
 
 impl Keywords {
-    /// Returns the **global keyword registry** (input → canonical).
-    ///
-    /// The registry is:
-    /// - **Initialized once** on first access (via [`Lazy`]).
-    /// - **Thread-safe** (wrapped in [`RwLock`]): many readers or one writer.
-    /// - **Lowercased**: all keys are stored lowercased for case-insensitive lookups.
-    ///
-    /// Seeded entries (canonical → canonical):
-    /// - `"today"` → `"today"`
-    /// - `"yesterday"` → `"yesterday"`
-    /// - `"tomorrow"` → `"tomorrow"`
-    /// - `"last week"` → `"last week"`
-    ///
-    /// You normally **don’t call this directly**; use [`extend`](Self::extend)
-    /// to add synonyms and [`matches`](Self::matches) for checks.
+    /// The **global registry** (built-in words only, unless extended), used by the parsing
+    /// functions when they aren't given one. Kept for callers of the old static API; a
+    /// [`crate::Config`] has its own in `keywords`.
     ///
     /// References:
     /// - once_cell::sync::Lazy: <https://docs.rs/once_cell>
     /// - std::sync::RwLock: <https://doc.rust-lang.org/std/sync/struct.RwLock.html>
-    fn registry() -> &'static RwLock<HashMap<String, Keyword>> {
-        static REGISTRY: Lazy<RwLock<HashMap<String, Keyword>>> = Lazy::new(|| {
-            let mut m = HashMap::new();
-            m.insert("at".to_string(), Keyword::At);
-            m.insert("monday".to_string(), Keyword::Monday);
-            m.insert("tuesday".to_string(), Keyword::Tuesday);
-            m.insert("wednesday".to_string(), Keyword::Wednesday);
-            m.insert("thursday".to_string(), Keyword::Thursday);
-            m.insert("friday".to_string(), Keyword::Friday);
-            m.insert("saturday".to_string(), Keyword::Saturday);
-            m.insert("sunday".to_string(), Keyword::Sunday);
-            m.insert("today".to_string(), Keyword::Today);
-            m.insert("yesterday".to_string(), Keyword::Yesterday);
-            m.insert("tomorrow".to_string(), Keyword::Tomorrow);
-            m.insert("morning".to_string(), Keyword::Morning);
-            m.insert("noon".to_string(), Keyword::Noon);
-            m.insert("evening".to_string(), Keyword::Evening);
-            m.insert("night".to_string(), Keyword::Night);
-            m.insert("midnight".to_string(), Keyword::Midnight);
-            m.insert("last week".to_string(), Keyword::LastWeek);
-            m.insert("last month".to_string(), Keyword::LastMonth);
-            m.insert("last year".to_string(), Keyword::LastYear);
-            m.insert("this week".to_string(), Keyword::ThisWeek);
-            m.insert("this month".to_string(), Keyword::ThisMonth);
-            m.insert("this year".to_string(), Keyword::ThisYear);
-
-            RwLock::new(m)
-        });
+    pub fn global() -> &'static RwLock<Keywords> {
+        static REGISTRY: Lazy<RwLock<Keywords>> = Lazy::new(|| RwLock::new(Keywords::default()));
         &REGISTRY
     }
 
-    /// Extends the global registry with user-defined **synonyms**.
+    /// The built-in words and the `synonyms`.
+    pub fn with_synonyms(synonyms: &[(String, String)]) -> Self {
+        let mut keywords = Self::default();
+        keywords.extend(synonyms);
+        keywords
+    }
+
+    /// Extends the registry with user-defined **synonyms**.
     ///
     /// Each pair is `(alias, target)`. The `target` must be a **known** keyword already
     /// in the registry (typically a canonical constant or an existing synonym that maps
-    /// to a canonical). If `target` isn’t known, the pair is ignored silently.
+    /// to a canonical). If `target` isn’t known, the pair is ignored silently. The target can
+    /// be some days from the keyword (`yesterday - 1`, `today + 2`), and the alias several
+    /// words (`day before yesterday`).
     ///
     /// All keys are normalized to **lowercase** to keep lookups case-insensitive.
     ///
@@ -103,17 +126,27 @@ impl Keywords {
     /// // ytd  = "yesterday"
     /// // ayer = "yesterday"
     /// // tmrw = "tomorrow"
+    /// // "day before yesterday" = "yesterday - 1"
     ///
     /// let pairs: Vec<(String, String)> = cfg.synonyms.iter()
     ///     .map(|(alias, target)| (alias.clone(), target.clone()))
     ///     .collect();
-    /// Keywords::extend(&pairs);
+    /// keywords.extend(&pairs);
     /// ```
-    pub fn extend(synonyms: &[(String, String)]) {
-        let mut reg = Self::registry().write().unwrap();
+    pub fn extend(&mut self, synonyms: &[(String, String)]) {
         for (alias, target) in synonyms {
-            if let Some(&canonical) = reg.get(&target.to_ascii_lowercase()) {
-                reg.insert(alias.to_ascii_lowercase(), canonical);
+            let (target, days) = split_offset(target);
+            let Some(&canonical) = self.words.get(&target.to_ascii_lowercase()) else {
+                continue;
+            };
+            let alias = alias.split_whitespace().collect::<Vec<_>>().join(" ");
+            let alias = alias.to_ascii_lowercase();
+            if days == 0 {
+                self.shifted.remove(&alias);
+                self.words.insert(alias, canonical);
+            } else {
+                self.words.remove(&alias);
+                self.shifted.insert(alias, (canonical, days));
             }
         }
     }
@@ -128,115 +161,188 @@ impl Keywords {
     ///
     /// Example:
     /// ```rs
-    /// use crate::keywords::{Keywords, YESTERDAY};
+    /// use crate::keywords::{Keyword, Keywords};
     ///
-    /// assert!(Keywords::matches(YESTERDAY, "yesterday"));
-    /// assert!(Keywords::matches(YESTERDAY, "YESTERDAY"));
-    /// assert!(!Keywords::matches(YESTERDAY, "today"));
+    /// let keywords = Keywords::default();
+    /// assert!(keywords.matches(Keyword::Yesterday, "yesterday"));
+    /// assert!(keywords.matches(Keyword::Yesterday, "YESTERDAY"));
+    /// assert!(!keywords.matches(Keyword::Yesterday, "today"));
     /// ```
-    pub fn matches(keyword: Keyword, input: &str) -> bool {
-        let reg = Self::registry().read().unwrap();
-        reg.get(&input.to_ascii_lowercase())
-            .map(|&canon| canon == keyword)
-            .unwrap_or(false)
+    pub fn matches(&self, keyword: Keyword, input: &str) -> bool {
+        self.lookup(input) == Some(keyword)
     }
 
-    pub fn find_word(keyword: Keyword, input: &str) -> Option<String> {
-        let lower = input.to_ascii_lowercase();
-        let pattern = format!(r"\b{}\b", regex::escape(keyword.as_ref()));
-        let re = Regex::new(&pattern).unwrap();
-        if re.is_match(&lower) {
-            Some(keyword.as_ref().to_string())
-        } else {
-            None
-        }
+    /// The canonical keyword `input` is (case-insensitively), or a synonym of.
+    pub fn lookup(&self, input: &str) -> Option<Keyword> {
+        self.words.get(&input.to_ascii_lowercase()).copied()
+    }
+
+    /// The keyword and days from it of a shifted synonym (`day before yesterday` is
+    /// `(Yesterday, -1)`).
+    pub fn shifted(&self, input: &str) -> Option<(Keyword, i64)> {
+        self.shifted.get(&input.to_ascii_lowercase()).copied()
+    }
+
+    /// The first word of `input` that is `keyword` or one of its synonyms (`at`, `a las`), as a
+    /// whole word and lowercased.
+    pub fn find_word(&self, keyword: Keyword, input: &str) -> Option<String> {
+        self.find(keyword, input).map(|(_, word)| word)
+    }
+
+    /// Where [`find_word`](Self::find_word) is in `input`, in bytes.
+    pub fn find_position(&self, keyword: Keyword, input: &str) -> Option<usize> {
+        self.find(keyword, input).map(|(pos, _)| pos)
+    }
+
+    /// Position and text of the first match of `keyword` or its synonyms in `input`.
+    fn find(&self, keyword: Keyword, input: &str) -> Option<(usize, String)> {
+        self.find_all(keyword, input).into_iter().next()
     }
 
-    pub fn find_position(keyword: Keyword, input: &str) -> Option<usize> {
+    /// Positions (in bytes) and texts of the words of `input` that are `keyword` or one of its
+    /// synonyms, lowercased. At the same position, the longest one wins (`a las` over `a`).
+    pub fn find_all(&self, keyword: Keyword, input: &str) -> Vec<(usize, String)> {
         let lower = input.to_ascii_lowercase();
-        let pattern = format!(r"\b{}\b", regex::escape(keyword.as_ref()));
+        let mut words: Vec<&str> = self
+            .words
+            .iter()
+            .filter(|&(_, &canonical)| canonical == keyword)
+            .map(|(word, _)| word.as_str())
+            .collect();
+        words.sort_by_key(|word| std::cmp::Reverse(word.len()));
+        let alternatives: Vec<String> = words.iter().map(|word| regex::escape(word)).collect();
+        let pattern = format!(r"\b(?:{})\b", alternatives.join("|"));
         let re = Regex::new(&pattern).unwrap();
-        if let Some(m) = re.find(&lower) {
-            Some(m.start())
-        } else {
-            None
-        }
+        re.find_iter(&lower)
+            .map(|m| (m.start(), m.as_str().to_string()))
+            .collect()
     }
 }
 
+/// `yesterday - 1` as `("yesterday", -1)`. A target without days is 0 days from itself.
+fn split_offset(target: &str) -> (&str, i64) {
+    let re = Regex::new(r"^(.+?)\s*([+-])\s*(\d+)$").unwrap();
+    let Some(captures) = re.captures(target.trim()) else {
+        return (target.trim(), 0);
+    };
+    let days: i64 = captures[3].parse().unwrap_or(0);
+    let word = captures.get(1).map_or("", |m| m.as_str());
+    (word, if &captures[2] == "-" { -days } else { days })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn default_constants() {
-        assert!(Keywords::matches(Keyword::Today, "today"));
-        assert!(Keywords::matches(Keyword::Yesterday, "yesterday"));
+        let keywords = Keywords::default();
+        assert!(keywords.matches(Keyword::Today, "today"));
+        assert!(keywords.matches(Keyword::Yesterday, "yesterday"));
+        assert!(keywords.matches(Keyword::LastWeek, "Last Week"));
+        assert!(keywords.matches(Keyword::Due, "by"));
     }
 
     #[test]
     fn synonyms_extend() {
-        Keywords::extend(&[
+        let mut keywords = Keywords::default();
+        keywords.extend(&[
             ("ytd".into(), "yesterday".into()),
             ("ayer".into(), "yesterday".into()),
             ("tmrw".into(), "tomorrow".into()),
         ]);
-        assert!(Keywords::matches(Keyword::Yesterday, "ytd"));
-        assert!(Keywords::matches(Keyword::Yesterday, "ayer"));
-        assert!(Keywords::matches(Keyword::Tomorrow, "tmrw"));
+        assert!(keywords.matches(Keyword::Yesterday, "ytd"));
+        assert!(keywords.matches(Keyword::Yesterday, "ayer"));
+        assert!(keywords.matches(Keyword::Tomorrow, "tmrw"));
+    }
+
+    #[test]
+    fn synonyms_with_days() {
+        let keywords = Keywords::with_synonyms(&[
+            ("Day  before yesterday".into(), "yesterday - 1".into()),
+            ("pasado mañana".into(), "tomorrow+1".into()),
+            ("someday".into(), "never + 1".into()),
+        ]);
+        assert_eq!(
+            keywords.shifted("day before yesterday"),
+            Some((Keyword::Yesterday, -1))
+        );
+        assert_eq!(keywords.shifted("Pasado mañana"), Some((Keyword::Tomorrow, 1)));
+        assert!(!keywords.matches(Keyword::Yesterday, "day before yesterday"));
+        assert_eq!(keywords.shifted("someday"), None);
+        assert_eq!(keywords.lookup("someday"), None);
+    }
+
+    #[test]
+    fn registries_do_not_share_synonyms() {
+        let spanish = Keywords::with_synonyms(&[("ayer".into(), "yesterday".into())]);
+        let english = Keywords::with_synonyms(&[("ytd".into(), "yesterday".into())]);
+        assert!(spanish.matches(Keyword::Yesterday, "ayer"));
+        assert!(!spanish.matches(Keyword::Yesterday, "ytd"));
+        assert!(english.matches(Keyword::Yesterday, "ytd"));
+        assert!(!english.matches(Keyword::Yesterday, "ayer"));
+        assert!(!Keywords::global().read().unwrap().matches(Keyword::Yesterday, "ayer"));
     }
 
     #[test]
     fn unknown_word_in_matches_returns_none() {
-        if Keywords::matches(Keyword::Tomorrow, "not in registry") {
-            assert!(false);
-        } else {
-            assert!(true);
-        }
+        assert!(!Keywords::default().matches(Keyword::Tomorrow, "not in registry"));
     }
 
     #[test]
     fn find_word_finds_whole_word() {
         assert_eq!(
-            Keywords::find_word(Keyword::At, "text at text"),
+            Keywords::default().find_word(Keyword::At, "text at text"),
             Some("at".to_string())
         );
         assert_eq!(
-            Keywords::find_word(Keyword::Friday, "go friday go"),
+            Keywords::default().find_word(Keyword::Friday, "go friday go"),
             Some("friday".to_string())
         );
     }
 
     #[test]
     fn find_word_does_not_find_substring() {
-        assert_eq!(Keywords::find_word(Keyword::At, "saturday"), None);
-        assert_eq!(Keywords::find_word(Keyword::Friday, "fridaya"), None);
+        assert_eq!(Keywords::default().find_word(Keyword::At, "saturday"), None);
+        assert_eq!(Keywords::default().find_word(Keyword::Friday, "fridaya"), None);
     }
 
     #[test]
     fn find_position_finds_whole_word() {
         assert_eq!(
-            Keywords::find_position(Keyword::At, "text at text"),
+            Keywords::default().find_position(Keyword::At, "text at text"),
             Some(5)
         );
         assert_eq!(
-            Keywords::find_position(Keyword::Friday, "go friday go"),
+            Keywords::default().find_position(Keyword::Friday, "go friday go"),
             Some(3)
         );
     }
 
     #[test]
     fn find_position_does_not_find_substring() {
-        assert_eq!(Keywords::find_position(Keyword::At, "saturday"), None);
-        assert_eq!(Keywords::find_position(Keyword::Friday, "fridaya"), None);
+        assert_eq!(Keywords::default().find_position(Keyword::At, "saturday"), None);
+        assert_eq!(Keywords::default().find_position(Keyword::Friday, "fridaya"), None);
+    }
+
+    #[test]
+    fn find_word_finds_synonyms() {
+        let keywords = Keywords::with_synonyms(&[
+            ("a las".into(), "at".into()),
+            ("a".into(), "at".into()),
+        ]);
+        assert_eq!(
+            keywords.find_word(Keyword::At, "ayer a las 6"),
+            Some("a las".to_string())
+        );
+        assert_eq!(keywords.find_position(Keyword::At, "ayer A las 6"), Some(5));
+        assert_eq!(keywords.find_word(Keyword::At, "ayer a 6"), Some("a".to_string()));
+        assert_eq!(keywords.find_word(Keyword::At, "ayer at 6"), Some("at".to_string()));
+        assert_eq!(keywords.find_word(Keyword::At, "las palmas"), None);
     }
 
     #[test]
     fn unknown_word_in_find_returns_none() {
-        if let Some(_) = Keywords::find_position(Keyword::Tomorrow, "text text text") {
-            assert!(false);
-        } else {
-            assert!(true);
-        }
+        assert!(Keywords::default().find_position(Keyword::Tomorrow, "text text text").is_none());
     }
 }
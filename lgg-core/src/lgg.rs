@@ -1,30 +1,66 @@
 use crate::{
-    journal::Journal,
+    convert::ImportResult,
+    habits::Habits,
+    journal::{Journal, ObsidianVault, ParseCache},
     todos::Todos,
     utils::{
-        parse_input::{parse_date_token, parse_raw_user_input},
-        parsed_input::ParseInputOptions,
+        parse_input::{
+            find_ambiguous_dates, parse_date_token, parse_raw_user_input, parse_todo_input,
+            parse_weekdays,
+        },
+        parsed_input::{ParseInputOptions, ParseTrace, ParsedInput as RawParsedInput},
     },
-    Config,
+    summarize, sync, voice_notes, AllowFuture, AppDirs, Config, EntryChange, FsStorage,
+    GitStorage, JournalEntry, RangeOrder, RemoteStorage, Storage, SyncSummary,
 };
-use anyhow::{Context, Result};
-use chrono::{Local, NaiveDate, NaiveTime};
+use anyhow::{bail, Context, Result};
+use chrono::{Local, NaiveDate, NaiveTime, Weekday};
 use std::fs;
-use crate::utils::date_utils::DateFilter;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use crate::utils::date_utils::{AmbiguousDate, DateFilter};
 
 pub struct ParsedInput {
     pub date: NaiveDate,
+    /// Last day of an event of several days (`monday..friday: Conference`). The entry is
+    /// written on every day from `date` to `until`.
+    pub until: Option<NaiveDate>,
     pub time: NaiveTime,
     pub title: String,
     pub body: String,
     pub explicit_date: bool,
     pub explicit_time: bool,
+    /// Dates of the prefix that the `input_date_formats` read as different days.
+    pub ambiguous_dates: Vec<AmbiguousDate>,
+    pub time_source: TimeSource,
+    /// How the prefix, title and body were read (`lgg explain`).
+    pub trace: ParseTrace,
+}
+
+/// Where the time of a parsed input comes from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeSource {
+    /// Written in the input (`at 6am`).
+    Explicit,
+    /// The `default_time` of the config, for a date without time.
+    Default,
+    /// The current time, for an input without date nor time.
+    Now,
+}
+
+impl ParsedInput {
+    /// Every day the entry goes to: `date`, or from `date` to `until`.
+    pub fn dates(&self) -> Vec<NaiveDate> {
+        let until = self.until.unwrap_or(self.date);
+        self.date.iter_days().take_while(|d| *d <= until).collect()
+    }
 }
 
 pub struct Lgg {
     pub config: Config,
     pub journal: Journal,
     pub todos: Todos,
+    pub habits: Habits,
 }
 impl Lgg {
     /// Creates a new `Lgg` instance, loading configuration from standard paths.
@@ -42,29 +78,87 @@ impl Lgg {
         fs::create_dir_all(&config.todo_list_dir)
             .with_context(|| format!("creating todos dir {}", config.journal_dir.display()))?;
 
+        let dirs = AppDirs::for_journal(config.state_dir.as_deref(), &config.journal_dir);
+        let storage: Arc<dyn Storage> = match &config.remote {
+            Some(remote) => Arc::new(RemoteStorage::new(
+                remote.clone(),
+                &config.journal_dir,
+                dirs.remote_cache_dir(),
+            )),
+            None => Arc::new(FsStorage),
+        };
         let journal = Journal {
             journal_dir: config.journal_dir.clone(),
             journal_date_format: config.journal_date_format.clone(),
             reference_date: config.reference_date,
+            obsidian: config
+                .obsidian
+                .then(|| ObsidianVault::load(&config.journal_dir)),
+            cache: ParseCache::default(),
+            parse_mode: config.parse_mode,
+            default_time: config.default_time,
+            time_precision: config.time_precision,
+            locale: config.locale,
+            keywords: config.keywords.clone(),
+            dirs,
+            device_id: config.device_id.clone(),
+            default_tags: config.default_tags.clone(),
+            auto_tags: config.auto_tags.clone(),
+            tag_pattern: config.tag_pattern.clone(),
+            tag_aliases: config.tag_aliases.clone(),
+            ignored_tags: config.ignored_tags.clone(),
+            index_files: config.index_files,
+            storage,
         };
         let todos = Todos {
             todo_list_dir: config.todo_list_dir.clone(),
             todo_datetime_format: config.todo_datetime_format.clone(),
+            todo_read_formats: config.todo_read_formats.clone(),
             reference_date: config.reference_date,
             default_time: config.default_time,
+            tag_pattern: config.tag_pattern.clone(),
+            tag_aliases: config.tag_aliases.clone(),
+            ignored_tags: config.ignored_tags.clone(),
+            storage: Arc::new(FsStorage),
+        };
+        let habits = Habits {
+            habits_dir: config.todo_list_dir.join("habits"),
         };
         Ok(Self {
             config,
             journal,
             todos,
+            habits,
         })
     }
 
+    /// Uses `date` as "today" from now on: for relative keywords, new entries without date,
+    /// the agenda and the scheduled entries.
+    pub fn set_reference_date(&mut self, date: NaiveDate) {
+        self.config.reference_date = date;
+        self.journal.reference_date = date;
+        self.todos.reference_date = date;
+    }
+
     /// This function orchestrates the parsing of a complete user input, which may
     /// contain a date/time prefix, a title, and a body. It handles the logic for splitting
     /// the prefix from the content and then the title from the body.
     /// The input would look something like this: `(optional DATE-TIME): some title. some body.`
     pub fn parse_user_input(&self, input: &str) -> Result<ParsedInput> {
+        self.parse_with(input, parse_raw_user_input)
+    }
+
+    /// Like [`Lgg::parse_user_input`], for a todo written as free text: the due date and time
+    /// may also be at its end (`call mum friday at 5pm @family`).
+    pub fn parse_todo_input(&self, input: &str) -> Result<ParsedInput> {
+        self.parse_with(input, parse_todo_input)
+    }
+
+    fn parse_with(
+        &self,
+        input: &str,
+        parse: fn(&str, Option<ParseInputOptions>) -> RawParsedInput,
+    ) -> Result<ParsedInput> {
         let mut explicit_date = false;
         let mut explicit_time = false;
         let format_strs: Vec<&str> = self
@@ -76,31 +170,43 @@ impl Lgg {
         let opts = ParseInputOptions {
             reference_date: Some(self.config.reference_date),
             formats: Some(&format_strs),
+            keywords: Some(&self.config.keywords),
+            tags: Some(&self.config.tag_pattern),
         };
-        let parsed_input = parse_raw_user_input(input, Some(opts));
+        let parsed_input = parse(input, Some(opts));
         let date = if let Some(d) = parsed_input.date {
             explicit_date = true;
             d
         } else {
             self.config.reference_date
         };
-        let time = if let Some(t) = parsed_input.time {
+        let (time, time_source) = if let Some(t) = parsed_input.time {
             explicit_time = true;
-            t
+            (t, TimeSource::Explicit)
         } else {
             match parsed_input.date {
-                Some(_) => self.config.default_time,
-                None => Local::now().time(),
+                Some(_) => (self.config.default_time, TimeSource::Default),
+                None => (Local::now().time(), TimeSource::Now),
             }
         };
+        // Only the prefix holds dates, when there is one, and only when they were read as one.
+        let ambiguous_dates = match input.split_once(": ") {
+            _ if !explicit_date => Vec::new(),
+            Some((prefix, _)) => self.ambiguous_dates(prefix),
+            None => self.ambiguous_dates(input),
+        };
 
         Ok(ParsedInput {
             date,
+            until: parsed_input.until,
             time,
             title: parsed_input.title,
             body: parsed_input.body,
             explicit_date,
             explicit_time,
+            ambiguous_dates,
+            time_source,
+            trace: parsed_input.trace,
         })
     }
 
@@ -114,15 +220,143 @@ impl Lgg {
         let opts = ParseInputOptions {
             reference_date: Some(self.config.reference_date),
             formats: Some(&format_strs),
+            keywords: Some(&self.config.keywords),
+            tags: Some(&self.config.tag_pattern),
         };
         parse_date_token(start_date, end_date, Some(opts))
     }
+
+    /// The weekdays of a list like `mon,wed,fri`, with the synonyms of this config.
+    pub fn parse_weekdays(&self, days: &str) -> Option<Vec<Weekday>> {
+        parse_weekdays(days, &self.config.keywords)
+    }
+
+    /// Date and time of an entry written now, as a prefix the user could type
+    /// (`15/08/2025 at 10:30:`). Falls back to `today` without `input_date_formats`.
+    pub fn date_prefix(&self) -> String {
+        let date = match self.config.input_date_formats.first() {
+            Some(format) => self.config.reference_date.format(format).to_string(),
+            None => "today".to_string(),
+        };
+        format!("{date} at {}:", Local::now().time().format("%H:%M"))
+    }
+
+    /// Dates in `date_string` that the `input_date_formats` read as different days. The first
+    /// reading of each is the one used.
+    pub fn ambiguous_dates(&self, date_string: &str) -> Vec<AmbiguousDate> {
+        let format_strs: Vec<&str> = self
+            .config
+            .input_date_formats
+            .iter()
+            .map(AsRef::as_ref)
+            .collect();
+        find_ambiguous_dates(date_string, &format_strs)
+    }
+
+    /// Applies the `range_order` config to `dates`. Returns the dates to use and whether they
+    /// were swapped.
+    pub fn order_dates(&self, dates: DateFilter) -> (DateFilter, bool) {
+        match (dates, self.config.range_order) {
+            (DateFilter::Range(start, end), RangeOrder::Swap) if start > end => {
+                (DateFilter::Range(end, start), true)
+            }
+            _ => (dates, false),
+        }
+    }
+
+    /// Applies the `allow_future` config to a new entry on `date`. Fails if entries after today
+    /// are denied, otherwise returns whether the user should be warned about it.
+    pub fn check_future_date(&self, date: NaiveDate) -> Result<bool> {
+        if date <= self.config.reference_date {
+            return Ok(false);
+        }
+        match self.config.allow_future {
+            AllowFuture::Allow => Ok(false),
+            AllowFuture::Warn => Ok(true),
+            AllowFuture::Deny => {
+                bail!("{date} is in the future, and `allow_future` is set to \"deny\".")
+            }
+        }
+    }
+
+    /// Syncs the journal with the `[sync]` of the config: pulls, merges day files entry by
+    /// entry, and pushes.
+    pub fn sync(&self) -> Result<SyncSummary> {
+        let Some(config) = &self.config.sync else {
+            bail!("Set a [sync] table in the config to sync the journal.")
+        };
+        sync::sync_journal(&self.journal, config)
+    }
+
+    /// Summarizes the entries of `date` with the `summarizer_cmd` of the config, saved as a
+    /// `@summary` entry (see [`summarize`]).
+    pub fn summarize(&self, date: NaiveDate) -> Result<JournalEntry> {
+        let Some(command) = &self.config.summarizer_cmd else {
+            bail!("Set `summarizer_cmd` in the config to summarize days.")
+        };
+        summarize::summarize_day(&self.journal, command, date)
+    }
+
+    /// Imports the voice notes of `path` (a directory or an audio file) with the
+    /// `transcriber_cmd` of the config, as `@voice` entries (see [`voice_notes`]).
+    pub fn import_voice_notes(&self, path: &Path) -> Result<ImportResult<JournalEntry>> {
+        let Some(command) = &self.config.transcriber_cmd else {
+            bail!("Set `transcriber_cmd` in the config to import voice notes.")
+        };
+        voice_notes::import_voice_notes(&self.journal, command, path)
+    }
+
+    /// How the entries changed from the journal of `from` to the one of `to`, or to the current
+    /// one without it (see [`Lgg::snapshot`]).
+    pub fn diff(&self, from: &str, to: Option<&str>) -> Result<Vec<EntryChange>> {
+        let (from_storage, from_root) = self.snapshot(from, false)?;
+        let (to_storage, to_root) = match to {
+            Some(to) => self.snapshot(to, true)?,
+            None => (self.journal.storage.clone(), self.journal.journal_dir.clone()),
+        };
+        self.journal
+            .diff_snapshots(from_storage.as_ref(), &from_root, to_storage.as_ref(), &to_root)
+    }
+
+    /// The journal as it is in `snapshot`, and the path of its root: a directory with a copy of
+    /// it, a commit of the git repository of `journal_dir`, or a date, the last commit before
+    /// it started. A range of dates (`last month`) is its first day, or the day after its last
+    /// one when it's the `end` of the diff, so the changes of the whole range are included.
+    fn snapshot(&self, snapshot: &str, end: bool) -> Result<(Arc<dyn Storage>, PathBuf)> {
+        let journal_dir = &self.journal.journal_dir;
+        let dir = PathBuf::from(snapshot);
+        if dir.is_dir() {
+            return Ok((Arc::new(FsStorage), dir));
+        }
+        let storage = match GitStorage::open(journal_dir, snapshot) {
+            Ok(storage) => storage,
+            Err(error) => match self.parse_dates(snapshot, None) {
+                Some(DateFilter::Single(date)) => GitStorage::at_date(journal_dir, date)?,
+                Some(DateFilter::Range(_, last)) if end => {
+                    GitStorage::at_date(journal_dir, last.succ_opt().unwrap_or(last))?
+                }
+                Some(DateFilter::Range(first, _)) => GitStorage::at_date(journal_dir, first)?,
+                _ => {
+                    return Err(error.context(format!(
+                        "'{snapshot}' is not a directory, a git commit of the journal, a date or a range of dates."
+                    )))
+                }
+            },
+        };
+        Ok((Arc::new(storage), journal_dir.clone()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{tests::mk_config, Config, Lgg};
+    use crate::{
+        tests::mk_config, AllowFuture, Config, DateFilter, DefaultAction, Hyperlinks, Keywords, Lgg,
+        ParseMode, RangeOrder, TimePrecision,
+    };
+    use crate::utils::command::run;
+    use crate::{EntryChange, JournalWriteEntry};
     use chrono::{Local, NaiveDate, NaiveTime, Timelike};
+    use std::collections::{BTreeMap, HashMap};
     use tempfile::tempdir;
 
     fn mk_lgg_with_default(reference_date: Option<NaiveDate>) -> (Lgg, tempfile::TempDir) {
@@ -175,6 +409,89 @@ mod tests {
         assert_eq!(p1.title, "Note 1");
     }
 
+    #[test]
+    fn reference_date_can_be_changed() {
+        let (mut lgg, _) = mk_lgg_with_default(NaiveDate::from_ymd_opt(2025, 8, 15));
+        let new_year = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        lgg.set_reference_date(new_year);
+
+        let p = lgg.parse_user_input("yesterday: Note").unwrap();
+        assert_eq!(p.date, NaiveDate::from_ymd_opt(2024, 12, 31).unwrap());
+        let p = lgg.parse_user_input("Note").unwrap();
+        assert_eq!(p.date, new_year);
+        assert_eq!(lgg.journal.reference_date, new_year);
+        assert_eq!(lgg.todos.reference_date, new_year);
+    }
+
+    #[test]
+    fn inverted_ranges_are_swapped() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 15);
+        let (mut lgg, _) = mk_lgg_with_default(anchor);
+        let d = |day| NaiveDate::from_ymd_opt(2025, 8, day).unwrap();
+
+        let dates = lgg.parse_dates("today", Some("yesterday")).unwrap();
+        assert_eq!(dates, DateFilter::Range(d(15), d(14)));
+        assert_eq!(lgg.order_dates(dates), (DateFilter::Range(d(14), d(15)), true));
+
+        let ordered = DateFilter::Range(d(14), d(15));
+        assert_eq!(lgg.order_dates(ordered), (ordered, false));
+
+        // `--from today --to "last week"`: from the start of last week to today.
+        let dates = lgg.parse_dates("today", Some("last week")).unwrap();
+        assert_eq!(lgg.order_dates(dates), (DateFilter::Range(d(4), d(15)), true));
+
+        lgg.config.range_order = RangeOrder::Strict;
+        assert_eq!(lgg.order_dates(dates), (dates, false));
+    }
+
+    #[test]
+    fn future_dates_follow_allow_future() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 15);
+        let (mut lgg, _) = mk_lgg_with_default(anchor);
+        let today = anchor.unwrap();
+        let tomorrow = NaiveDate::from_ymd_opt(2025, 8, 16).unwrap();
+
+        assert!(!lgg.check_future_date(today).unwrap());
+        assert!(lgg.check_future_date(tomorrow).unwrap());
+
+        lgg.config.allow_future = AllowFuture::Allow;
+        assert!(!lgg.check_future_date(tomorrow).unwrap());
+
+        lgg.config.allow_future = AllowFuture::Deny;
+        assert!(lgg.check_future_date(today).is_ok());
+        assert!(lgg.check_future_date(tomorrow).is_err());
+    }
+
+    #[test]
+    fn date_prefix_is_read_back() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 15);
+        let (lgg, _) = mk_lgg_with_default(anchor);
+        let prefix = lgg.date_prefix();
+        assert!(prefix.starts_with("15/08/2025 at "));
+
+        let parsed = lgg.parse_user_input(&format!("{prefix} Title.")).unwrap();
+        assert_eq!(Some(parsed.date), anchor);
+        assert!(parsed.explicit_time);
+        assert_eq!(parsed.title, "Title.");
+    }
+
+    #[test]
+    fn ambiguous_prefix_dates_are_reported() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 15);
+        let (mut lgg, _) = mk_lgg_with_default(anchor);
+        lgg.config.input_date_formats = vec!["%d/%m/%Y".to_string(), "%m/%d/%Y".to_string()];
+
+        let p = lgg.parse_user_input("03/04/2025: Title. 05/06/2025 in the body").unwrap();
+        assert_eq!(p.date, NaiveDate::from_ymd_opt(2025, 4, 3).unwrap());
+        assert_eq!(p.ambiguous_dates.len(), 1);
+        assert_eq!(p.ambiguous_dates[0].token, "03/04/2025");
+
+        let p = lgg.parse_user_input("Title with 03/04/2025: not a date").unwrap();
+        assert!(p.ambiguous_dates.is_empty());
+        let p = lgg.parse_user_input("14/04/2025: Title").unwrap();
+        assert!(p.ambiguous_dates.is_empty());
+    }
+
     #[test]
     fn no_date_no_time_defaults() {
         let anchor = NaiveDate::from_ymd_opt(2025, 8, 15);
@@ -206,7 +523,44 @@ mod tests {
             reference_date: anchor,
             journal_date_format: "%A, %d %b %Y".to_string(),
             todo_datetime_format: "%d/%b/%Y %H:%M".to_string(),
+            todo_read_formats: vec!["%d/%b/%Y %H:%M".to_string()],
             input_date_formats: fmts,
+            obsidian: false,
+            merge_within_minutes: None,
+            default_action: DefaultAction::Editor,
+            stopwords: Vec::new(),
+            parse_mode: ParseMode::Strict,
+            time_precision: TimePrecision::Minutes,
+            locale: None,
+            range_order: RangeOrder::Swap,
+            allow_future: AllowFuture::Warn,
+            editor_template: Vec::new(),
+            temp_dir: None,
+            state_dir: None,
+            stale_days: 30,
+            stale_threshold: 5,
+            hyperlinks: Hyperlinks::Auto,
+            quiet_days: Vec::new(),
+            device_id: None,
+            summarizer_cmd: None,
+            transcriber_cmd: None,
+            fetch_titles: false,
+            index_files: false,
+            default_tags: Vec::new(),
+            ignored_tags: Vec::new(),
+            tag_pattern: Default::default(),
+            auto_tags: Vec::new(),
+            tag_aliases: Default::default(),
+            keywords: Keywords::default(),
+            anniversaries: Vec::new(),
+            aliases: HashMap::new(),
+            commitments: Vec::new(),
+            journals: BTreeMap::new(),
+            remote: None,
+            sync: None,
+            todo_reminders: HashMap::new(),
+            warnings: Vec::new(),
+            project_file: None,
         };
         let lgg = Lgg::with_config(conf).expect("lgg created");
 
@@ -226,4 +580,36 @@ mod tests {
         assert_eq!(p2.title, "Title 2.");
         assert!(p2.body.is_empty());
     }
+
+    #[test]
+    fn diff_takes_ranges_of_dates_to_whole_days() {
+        let (lgg, _tmp) = mk_lgg_with_default(None);
+        let dir = lgg.journal.journal_dir.clone();
+        let git = |args: &[&str]| {
+            let config = ["-c", "user.name=lgg", "-c", "user.email=lgg@example.com"];
+            run("git", Some(&dir), &[&config[..], args].concat()).unwrap()
+        };
+        let write = |title: &str| {
+            lgg.journal
+                .create_entry(JournalWriteEntry {
+                    date: NaiveDate::from_ymd_opt(2025, 8, 15).unwrap(),
+                    time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                    title: title.to_string(),
+                    body: String::new(),
+                    tags: Vec::new(),
+                })
+                .unwrap();
+            git(&["add", "--all"]);
+            git(&["commit", "--quiet", "--message", title]);
+        };
+        git(&["init", "--quiet"]);
+        write("Standup");
+        write("Review");
+
+        // The end of this year is after both commits.
+        let changes = lgg.diff("HEAD~1", Some("this year")).unwrap();
+        assert!(matches!(&changes[..], [EntryChange::Added(entry)] if entry.title == "Review"));
+        // And its start before them.
+        assert!(lgg.diff("this year", None).is_err());
+    }
 }
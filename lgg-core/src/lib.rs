@@ -1,18 +1,53 @@
+#![allow(clippy::module_inception)]
+#![cfg_attr(test, allow(clippy::zero_prefixed_literal))]
+
+#[cfg(feature = "analysis")]
+pub mod analysis;
+mod anniversaries;
 mod config;
+mod habits;
+pub mod convert;
 mod journal;
+pub mod journals;
 mod keywords;
 mod lgg;
+mod paths;
+mod storage;
+pub mod summarize;
+mod sync;
+pub mod stats;
+pub mod tags;
+#[cfg(test)]
 mod tests;
 mod todos;
 mod utils;
+pub mod voice_notes;
 pub mod entries;
 
-pub use config::Config;
+pub use anniversaries::{anniversaries_in, Anniversary};
+pub use config::{
+    get_config_value, set_config_value, AllowFuture, Config, DateOrder, DefaultAction, Hyperlinks,
+    ParseMode, RangeOrder, TemplatePart, TimePrecision, CONFIG_KEYS,
+};
+pub use habits::{Habit, HabitTicks, Habits, Streaks};
 pub use journal::{
-    JournalEntry, JournalQueryResult, JournalWriteEntry, ReadEntriesOptions,
+    action_items, auto_tags, compose, entry_template, filter, index_files, pomodoro, private, review, roundtrip, snippets, web_links,
+    week_plan, DayFileChange, EntryChange, EntryLink, EntrySort, JournalEntry, JournalQueryResult,
+    JournalSearchResult, JournalWriteEntry, Pin, Pins, ReadEntriesOptions, RelatedEntry, SearchHit,
+    SearchSnippet,
 };
 pub use entries::{QueryError, QueryTagsResult };
-pub use lgg::Lgg;
+pub use keywords::{Keyword, Keywords};
+pub use lgg::{Lgg, TimeSource};
+pub use paths::AppDirs;
+pub use storage::{
+    FileStamp, FsStorage, GitStorage, MemoryStorage, RemoteConfig, RemoteKind, RemoteStorage,
+    Storage, REMOTE_PASSWORD_ENV,
+};
+pub use sync::{SyncConfig, SyncKind, SyncSummary};
+pub use utils::date_utils::{format_date, AmbiguousDate, DateFilter};
+pub use utils::parsed_input::{DateRule, ParseTrace, TitleEnd};
+pub use chrono::Locale;
 pub use todos::{
-    ReadTodoOptions, TodoEntry, TodoQueryResult, TodoStatus, TodoWriteEntry, Todos,
+    ParsedTodosEntry, ReadTodoOptions, TodoEntry, TodoQueryResult, TodoStatus, TodoWriteEntry, Todos,
 };
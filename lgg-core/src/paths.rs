@@ -0,0 +1,99 @@
+//! Where lgg keeps the files it makes for itself (search index, caches...), away from the journal
+//! so they aren't synced or backed up with it.
+use directories::BaseDirs;
+use std::path::{Path, PathBuf};
+
+/// Directories of the files lgg makes for a journal. Every feature that keeps files of its own
+/// gets its directory from here.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppDirs {
+    /// Files of this journal only: `{state_dir}/journals/{name}-{hash}`, or `state_dir` itself
+    /// when it's relative to the journal.
+    pub state_dir: PathBuf,
+}
+
+impl AppDirs {
+    /// The directories of the journal at `journal_dir`, in `state_dir` (the `state_dir` config).
+    /// A relative `state_dir` is inside the journal (`.lgg`), for everything in one place. Else
+    /// every journal has its own directory in it, and `None` is [`AppDirs::default_state_dir`].
+    pub fn for_journal(state_dir: Option<&Path>, journal_dir: &Path) -> Self {
+        let state_dir = match state_dir {
+            Some(dir) if dir.is_relative() => journal_dir.join(dir),
+            Some(dir) => dir.join("journals").join(journal_key(journal_dir)),
+            None => Self::default_state_dir()
+                .join("journals")
+                .join(journal_key(journal_dir)),
+        };
+        Self { state_dir }
+    }
+
+    /// `$XDG_STATE_HOME/lgg` (`~/.local/state/lgg`) on Linux, `{local data dir}/lgg/state`
+    /// elsewhere, like `~/Library/Application Support/lgg/state` on macOS.
+    pub fn default_state_dir() -> PathBuf {
+        match BaseDirs::new() {
+            Some(base) => match base.state_dir() {
+                Some(dir) => dir.join("lgg"),
+                None => base.data_local_dir().join("lgg").join("state"),
+            },
+            None => PathBuf::from("./lgg/state"),
+        }
+    }
+
+    /// The full-text index of the journal (`search` feature).
+    pub fn search_dir(&self) -> PathBuf {
+        self.state_dir.join("search")
+    }
+
+    /// The state of `lgg sync`: the files as they were after the last sync, and the copy of an
+    /// rsync target.
+    pub fn sync_dir(&self) -> PathBuf {
+        self.state_dir.join("sync")
+    }
+
+    /// The copies of the day files read from the `[remote]`.
+    pub fn remote_cache_dir(&self) -> PathBuf {
+        self.state_dir.join("remote")
+    }
+}
+
+/// A directory name for the journal at `journal_dir`: its name, and a hash of its full path to
+/// tell apart journals with the same name (`journal-3f2a9c0e1b4d5a67`).
+fn journal_key(journal_dir: &Path) -> String {
+    let path = journal_dir
+        .canonicalize()
+        .unwrap_or_else(|_| journal_dir.to_path_buf());
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "journal".to_string());
+    // FNV-1a, which unlike the std hasher gives the same value in every build.
+    let hash = path
+        .to_string_lossy()
+        .bytes()
+        .fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        });
+    format!("{name}-{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_journal_has_its_own_state_dir() {
+        let state = Path::new("/state");
+        let work = AppDirs::for_journal(Some(state), Path::new("/home/me/work/journal"));
+        let home = AppDirs::for_journal(Some(state), Path::new("/home/me/journal"));
+        assert!(work.state_dir.starts_with("/state/journals"));
+        assert!(work.state_dir.to_string_lossy().contains("journal-"));
+        assert_ne!(work.state_dir, home.state_dir);
+        assert_eq!(
+            work,
+            AppDirs::for_journal(Some(state), Path::new("/home/me/work/journal"))
+        );
+
+        let inside = AppDirs::for_journal(Some(Path::new(".lgg")), Path::new("/home/me/journal"));
+        assert_eq!(inside.search_dir(), Path::new("/home/me/journal/.lgg/search"));
+    }
+}
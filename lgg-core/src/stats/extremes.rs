@@ -0,0 +1,66 @@
+use crate::JournalEntry;
+use std::cmp::Reverse;
+
+/// The longest and shortest entries, by their words (`lgg stats --extremes`).
+#[derive(Debug)]
+pub struct Extremes<'a> {
+    /// Most words first.
+    pub longest: Vec<&'a JournalEntry>,
+    /// Fewest words first.
+    pub shortest: Vec<&'a JournalEntry>,
+}
+
+/// The `top` entries with the most words and the `top` with the fewest. Between entries with as
+/// many words, the oldest comes first. With fewer than `2 * top` entries, some are in both lists.
+pub fn extremes(entries: &[JournalEntry], top: usize) -> Extremes<'_> {
+    let mut sorted: Vec<&JournalEntry> = entries.iter().collect();
+    sorted.sort_by_key(|e| (e.date, e.time));
+    let mut longest = sorted.clone();
+    longest.sort_by_key(|e| Reverse(e.word_count));
+    longest.truncate(top);
+    let mut shortest = sorted;
+    shortest.sort_by_key(|e| e.word_count);
+    shortest.truncate(top);
+    Extremes { longest, shortest }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::text_counts;
+    use chrono::{NaiveDate, NaiveTime};
+    use std::path::PathBuf;
+
+    fn entry(day: u32, title: &str, body: &str) -> JournalEntry {
+        let (word_count, char_count) = text_counts(title, body);
+        JournalEntry {
+            date: NaiveDate::from_ymd_opt(2025, 8, day).unwrap(),
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            title: title.to_string(),
+            body: body.to_string(),
+            tags: Vec::new(),
+            links: Vec::new(),
+            path: PathBuf::new(),
+            word_count,
+            char_count,
+        }
+    }
+
+    #[test]
+    fn longest_and_shortest_entries_by_words() {
+        let entries = vec![
+            entry(1, "Gym", ""),
+            entry(2, "Retro", "Shipped it, next sprint is about the search index."),
+            entry(3, "Lunch", "Tacos"),
+            entry(4, "Walk", ""),
+        ];
+        let titles = |list: &[&JournalEntry]| -> Vec<String> {
+            list.iter().map(|e| e.title.clone()).collect()
+        };
+
+        let found = extremes(&entries, 2);
+        assert_eq!(titles(&found.longest), vec!["Retro", "Lunch"]);
+        assert_eq!(titles(&found.shortest), vec!["Gym", "Walk"]);
+        assert!(extremes(&[], 2).longest.is_empty());
+    }
+}
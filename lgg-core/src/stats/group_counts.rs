@@ -0,0 +1,155 @@
+use crate::{JournalEntry, TodoEntry};
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+/// How to group entries when counting them.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GroupBy {
+    /// `YYYY-MM`, oldest first.
+    Month,
+    /// Monday to Sunday.
+    Weekday,
+    /// Most used tags first. An entry with several tags counts once for each of them.
+    Tag,
+}
+
+/// Number of entries in a group.
+#[derive(Debug, PartialEq)]
+pub struct GroupCount {
+    pub key: String,
+    pub count: usize,
+}
+
+/// Counts journal entries per month, weekday or tag.
+pub fn count_entries(entries: &[JournalEntry], group_by: GroupBy) -> Vec<GroupCount> {
+    count_by(
+        entries.iter().map(|e| (Some(e.date), e.tags.as_slice())),
+        group_by,
+    )
+}
+
+/// Counts todos per month or weekday of their due date, or per tag.
+/// Todos without due date are grouped under `no due date`.
+pub fn count_todos(todos: &[TodoEntry], group_by: GroupBy) -> Vec<GroupCount> {
+    count_by(
+        todos
+            .iter()
+            .map(|t| (t.due_date.map(|d| d.date()), t.tags.as_slice())),
+        group_by,
+    )
+}
+
+fn count_by<'a>(
+    items: impl Iterator<Item = (Option<NaiveDate>, &'a [String])>,
+    group_by: GroupBy,
+) -> Vec<GroupCount> {
+    // Keys sort in the order they are printed: `YYYY-MM` and weekday numbers are chronological.
+    let mut counts: BTreeMap<(u32, String), usize> = BTreeMap::new();
+    for (date, tags) in items {
+        let keys = match (group_by, date) {
+            (GroupBy::Month, Some(date)) => vec![(0, date.format("%Y-%m").to_string())],
+            (GroupBy::Weekday, Some(date)) => {
+                let weekday = date.weekday();
+                vec![(weekday.num_days_from_monday(), weekday_name(weekday))]
+            }
+            (GroupBy::Month | GroupBy::Weekday, None) => {
+                vec![(u32::MAX, "no due date".to_string())]
+            }
+            (GroupBy::Tag, _) => tags.iter().map(|t| (0, t.clone())).collect(),
+        };
+        for key in keys {
+            *counts.entry(key).or_default() += 1;
+        }
+    }
+
+    let mut groups: Vec<GroupCount> = counts
+        .into_iter()
+        .map(|((_, key), count)| GroupCount { key, count })
+        .collect();
+    if group_by == GroupBy::Tag {
+        // Stable sort: tags with the same count stay in alphabetical order.
+        groups.sort_by_key(|g| Reverse(g.count));
+    }
+    groups
+}
+
+fn weekday_name(weekday: Weekday) -> String {
+    NaiveDate::from_isoywd_opt(2025, 1, weekday)
+        .map(|d| d.format("%A").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::text_counts;
+    use chrono::NaiveTime;
+    use std::path::PathBuf;
+
+    fn entry(y: i32, m: u32, d: u32, tags: &[&str]) -> JournalEntry {
+        let (word_count, char_count) = text_counts("Title", "");
+        JournalEntry {
+            date: NaiveDate::from_ymd_opt(y, m, d).unwrap(),
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            title: "Title".to_string(),
+            body: String::new(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            links: Vec::new(),
+            path: PathBuf::new(),
+            word_count,
+            char_count,
+        }
+    }
+
+    fn counts(groups: &[GroupCount]) -> Vec<(&str, usize)> {
+        groups.iter().map(|g| (g.key.as_str(), g.count)).collect()
+    }
+
+    #[test]
+    fn counts_entries_by_month_weekday_and_tag() {
+        let entries = vec![
+            entry(2025, 9, 1, &["@work"]),
+            entry(2025, 8, 15, &["@work", "@dogs"]),
+            entry(2025, 8, 18, &["@cats"]),
+            entry(2024, 12, 31, &[]),
+        ];
+
+        assert_eq!(
+            counts(&count_entries(&entries, GroupBy::Month)),
+            vec![("2024-12", 1), ("2025-08", 2), ("2025-09", 1)]
+        );
+        assert_eq!(
+            counts(&count_entries(&entries, GroupBy::Weekday)),
+            vec![("Monday", 2), ("Tuesday", 1), ("Friday", 1)]
+        );
+        assert_eq!(
+            counts(&count_entries(&entries, GroupBy::Tag)),
+            vec![("@work", 2), ("@cats", 1), ("@dogs", 1)]
+        );
+    }
+
+    #[test]
+    fn todos_without_due_date_are_grouped_last() {
+        let todo = |due_date| TodoEntry {
+            due_date,
+            done_date: None,
+            created: None,
+            title: "Todo".to_string(),
+            body: String::new(),
+            path: PathBuf::new(),
+            status: crate::TodoStatus::Pending,
+            tags: Vec::new(),
+        };
+        let todos = vec![
+            todo(None),
+            todo(NaiveDate::from_ymd_opt(2025, 8, 20).unwrap().and_hms_opt(7, 0, 0)),
+        ];
+
+        assert_eq!(
+            counts(&count_todos(&todos, GroupBy::Month)),
+            vec![("2025-08", 1), ("no due date", 1)]
+        );
+        assert!(count_todos(&todos, GroupBy::Tag).is_empty());
+    }
+}
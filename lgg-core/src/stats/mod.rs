@@ -0,0 +1,10 @@
+//! Aggregations over journal entries and todos, to spot habits.
+pub mod extremes;
+pub mod group_counts;
+pub mod todo_stats;
+pub mod words;
+
+pub use extremes::{extremes, Extremes};
+pub use group_counts::{count_entries, count_todos, GroupBy, GroupCount};
+pub use todo_stats::{todo_stats, TagStats, TodoStats};
+pub use words::{word_frequencies, WordFrequencies};
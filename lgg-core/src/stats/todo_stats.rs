@@ -0,0 +1,195 @@
+use super::GroupCount;
+use crate::{TodoEntry, TodoStatus};
+use chrono::{Duration, NaiveDate};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+/// How a todo list is going (`todo stats`).
+#[derive(Debug, PartialEq)]
+pub struct TodoStats {
+    /// Todos not done yet, `in_progress` included.
+    pub open: usize,
+    pub in_progress: usize,
+    pub done: usize,
+    /// Abandoned todos, left out of every other count.
+    pub cancelled: usize,
+    /// Open todos due before today.
+    pub overdue: usize,
+    /// Average time from the due date to the completion of the done todos that have both.
+    /// Negative when they were done early. `None` if there are none.
+    pub average_delay: Option<Duration>,
+    /// Todos done per ISO week (`2025-W34`), oldest first.
+    pub done_per_week: Vec<GroupCount>,
+    /// Open and done todos per tag, `@tags` and `#projects`, most used first.
+    pub tags: Vec<TagStats>,
+}
+
+/// Open and done todos with a tag.
+#[derive(Debug, PartialEq)]
+pub struct TagStats {
+    pub tag: String,
+    pub open: usize,
+    pub done: usize,
+}
+
+/// Counts the open, done, cancelled and overdue `todos`, as of `today`.
+pub fn todo_stats(todos: &[TodoEntry], today: NaiveDate) -> TodoStats {
+    let mut stats = TodoStats {
+        open: 0,
+        in_progress: 0,
+        done: 0,
+        cancelled: 0,
+        overdue: 0,
+        average_delay: None,
+        done_per_week: Vec::new(),
+        tags: Vec::new(),
+    };
+    let mut delays = Vec::new();
+    let mut weeks: BTreeMap<String, usize> = BTreeMap::new();
+    let mut tags: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for todo in todos {
+        // Cancelled todos are only counted as such.
+        if todo.status == TodoStatus::Cancelled {
+            stats.cancelled += 1;
+            continue;
+        }
+        let done = todo.status == TodoStatus::Done;
+        if done {
+            stats.done += 1;
+            if let Some(done_date) = todo.done_date {
+                *weeks
+                    .entry(done_date.format("%G-W%V").to_string())
+                    .or_default() += 1;
+                if let Some(due_date) = todo.due_date {
+                    delays.push(done_date - due_date);
+                }
+            }
+        } else {
+            stats.open += 1;
+            if todo.status == TodoStatus::InProgress {
+                stats.in_progress += 1;
+            }
+            if todo.due_date.is_some_and(|due| due.date() < today) {
+                stats.overdue += 1;
+            }
+        }
+        for tag in &todo.tags {
+            let (open, done_count) = tags.entry(tag).or_default();
+            if done {
+                *done_count += 1;
+            } else {
+                *open += 1;
+            }
+        }
+    }
+
+    if !delays.is_empty() {
+        let total: Duration = delays.iter().sum();
+        stats.average_delay = Some(total / delays.len() as i32);
+    }
+    stats.done_per_week = weeks
+        .into_iter()
+        .map(|(key, count)| GroupCount { key, count })
+        .collect();
+    stats.tags = tags
+        .into_iter()
+        .map(|(tag, (open, done))| TagStats {
+            tag: tag.to_string(),
+            open,
+            done,
+        })
+        .collect();
+    // Stable sort: tags used as many times stay in alphabetical order.
+    stats.tags.sort_by_key(|t| Reverse(t.open + t.done));
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+    use std::path::PathBuf;
+
+    fn at(m: u32, d: u32, h: u32) -> Option<NaiveDateTime> {
+        NaiveDate::from_ymd_opt(2025, m, d)?.and_hms_opt(h, 0, 0)
+    }
+
+    fn todo(
+        due_date: Option<NaiveDateTime>,
+        done_date: Option<NaiveDateTime>,
+        tags: &[&str],
+    ) -> TodoEntry {
+        TodoEntry {
+            due_date,
+            done_date,
+            created: None,
+            title: "Title".to_string(),
+            body: String::new(),
+            path: PathBuf::new(),
+            status: match done_date {
+                Some(_) => TodoStatus::Done,
+                None => TodoStatus::Pending,
+            },
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn counts_open_done_and_overdue_todos() {
+        let todos = vec![
+            todo(at(8, 10, 9), None, &["@work"]),
+            todo(at(8, 20, 9), None, &["#home"]),
+            todo(None, None, &[]),
+            // A day late and a day early.
+            todo(at(8, 11, 9), at(8, 12, 9), &["@work", "#home"]),
+            todo(at(8, 14, 9), at(8, 13, 9), &["@work"]),
+            todo(None, at(8, 19, 18), &[]),
+        ];
+        let stats = todo_stats(&todos, NaiveDate::from_ymd_opt(2025, 8, 15).unwrap());
+
+        assert_eq!(stats.open, 3);
+        assert_eq!(stats.in_progress, 0);
+        assert_eq!(stats.done, 3);
+        assert_eq!(stats.overdue, 1);
+        assert_eq!(stats.average_delay, Some(Duration::zero()));
+        let weeks: Vec<(&str, usize)> = stats
+            .done_per_week
+            .iter()
+            .map(|g| (g.key.as_str(), g.count))
+            .collect();
+        assert_eq!(weeks, vec![("2025-W33", 2), ("2025-W34", 1)]);
+        assert_eq!(
+            stats.tags,
+            vec![
+                TagStats {
+                    tag: "@work".to_string(),
+                    open: 1,
+                    done: 2
+                },
+                TagStats {
+                    tag: "#home".to_string(),
+                    open: 1,
+                    done: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn no_delay_without_dates() {
+        let stats = todo_stats(&[todo(None, at(8, 1, 9), &[])], NaiveDate::MIN);
+        assert_eq!(stats.done, 1);
+        assert!(stats.average_delay.is_none());
+    }
+
+    #[test]
+    fn cancelled_todos_are_left_out() {
+        let mut cancelled = todo(at(8, 1, 9), at(8, 2, 9), &["@work"]);
+        cancelled.status = TodoStatus::Cancelled;
+        let stats = todo_stats(&[cancelled], NaiveDate::from_ymd_opt(2025, 8, 15).unwrap());
+        assert_eq!(stats.cancelled, 1);
+        assert_eq!((stats.open, stats.done, stats.overdue), (0, 0, 0));
+        assert!(stats.done_per_week.is_empty());
+        assert!(stats.tags.is_empty());
+    }
+}
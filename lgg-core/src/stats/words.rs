@@ -0,0 +1,157 @@
+use super::GroupCount;
+use crate::JournalEntry;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+/// Common English words left out of the report. `stopwords` in the config adds more.
+pub(crate) const STOPWORDS: &[&str] = &[
+    "a", "about", "after", "again", "all", "also", "am", "an", "and", "any", "are", "as", "at",
+    "be", "because", "been", "before", "being", "but", "by", "can", "could", "did", "do", "does",
+    "doing", "don't", "down", "during", "each", "for", "from", "get", "got", "had", "has", "have",
+    "having", "he", "her", "here", "him", "his", "how", "i", "i'm", "if", "in", "into", "is", "it",
+    "it's", "its", "just", "me", "more", "most", "my", "no", "not", "now", "of", "off", "on",
+    "once", "only", "or", "other", "our", "out", "over", "own", "same", "she", "so", "some",
+    "still", "such", "than", "that", "the", "their", "them", "then", "there", "these", "they",
+    "this", "those", "through", "to", "too", "under", "until", "up", "very", "was", "we", "were",
+    "what", "when", "where", "which", "while", "who", "why", "will", "with", "would", "you",
+    "your",
+];
+
+/// Most frequent words and pairs of consecutive words.
+#[derive(Debug, PartialEq)]
+pub struct WordFrequencies {
+    pub words: Vec<GroupCount>,
+    pub bigrams: Vec<GroupCount>,
+}
+
+/// Counts the words of the entries' titles and bodies, ignoring case, tags, numbers and stopwords
+/// (the built-in ones plus `extra_stopwords`). Only the `top` most frequent are kept.
+/// Bigrams are two consecutive words in the same sentence, none of them a stopword.
+pub fn word_frequencies(
+    entries: &[JournalEntry],
+    extra_stopwords: &[String],
+    top: usize,
+) -> WordFrequencies {
+    let extra: Vec<String> = extra_stopwords.iter().map(|w| w.to_lowercase()).collect();
+    let stopwords: HashSet<&str> = STOPWORDS
+        .iter()
+        .copied()
+        .chain(extra.iter().map(String::as_str))
+        .collect();
+
+    let mut words: HashMap<String, usize> = HashMap::new();
+    let mut bigrams: HashMap<String, usize> = HashMap::new();
+    for entry in entries {
+        let text = format!("{}.\n{}", entry.title, entry.body);
+        for sentence in text.split(['.', '!', '?', ';', ':', '\n']) {
+            let mut previous: Option<String> = None;
+            for token in tokens(sentence) {
+                let Some(word) = token.filter(|w| !stopwords.contains(w.as_str())) else {
+                    previous = None;
+                    continue;
+                };
+                if let Some(previous) = &previous {
+                    *bigrams.entry(format!("{previous} {word}")).or_default() += 1;
+                }
+                *words.entry(word.clone()).or_default() += 1;
+                previous = Some(word);
+            }
+        }
+    }
+
+    WordFrequencies {
+        words: most_frequent(words, top),
+        bigrams: most_frequent(bigrams, top),
+    }
+}
+
+/// Lowercased words of a sentence. Tags and numbers are `None`, so they break bigrams.
+pub(crate) fn tokens(sentence: &str) -> impl Iterator<Item = Option<String>> + '_ {
+    sentence
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '(' || c == ')' || c == '"')
+        .filter(|raw| !raw.is_empty())
+        .map(|raw| {
+            if raw.starts_with(['@', '#']) {
+                return None;
+            }
+            let word: String = raw
+                .trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase();
+            let is_word = word.chars().count() > 1 && word.chars().any(char::is_alphabetic);
+            is_word.then_some(word)
+        })
+}
+
+fn most_frequent(counts: HashMap<String, usize>, top: usize) -> Vec<GroupCount> {
+    let mut groups: Vec<GroupCount> = counts
+        .into_iter()
+        .map(|(key, count)| GroupCount { key, count })
+        .collect();
+    groups.sort_by(|a, b| a.key.cmp(&b.key));
+    groups.sort_by_key(|g| Reverse(g.count));
+    groups.truncate(top);
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::text_counts;
+    use chrono::{NaiveDate, NaiveTime};
+    use std::path::PathBuf;
+
+    fn entry(title: &str, body: &str) -> JournalEntry {
+        let (word_count, char_count) = text_counts(title, body);
+        JournalEntry {
+            date: NaiveDate::from_ymd_opt(2025, 8, 15).unwrap(),
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            title: title.to_string(),
+            body: body.to_string(),
+            tags: Vec::new(),
+            links: Vec::new(),
+            path: PathBuf::new(),
+            word_count,
+            char_count,
+        }
+    }
+
+    fn counts(groups: &[GroupCount]) -> Vec<(&str, usize)> {
+        groups.iter().map(|g| (g.key.as_str(), g.count)).collect()
+    }
+
+    #[test]
+    fn counts_words_and_bigrams_without_stopwords() {
+        let entries = vec![
+            entry("Long walk", "A long walk with the dogs. Dogs were happy @dogs."),
+            entry("Code review", "Code review of the parser, 2 hours."),
+        ];
+        let result = word_frequencies(&entries, &["happy".to_string()], 50);
+
+        assert_eq!(
+            counts(&result.words),
+            vec![
+                ("code", 2),
+                ("dogs", 2),
+                ("long", 2),
+                ("review", 2),
+                ("walk", 2),
+                ("hours", 1),
+                ("parser", 1),
+            ]
+        );
+        assert_eq!(counts(&result.bigrams), vec![("code review", 2), ("long walk", 2)]);
+    }
+
+    #[test]
+    fn keeps_only_the_top_words() {
+        let entries = vec![entry("Rain", "Rain, rain and more rain. Sun.")];
+        let result = word_frequencies(&entries, &[], 1);
+        assert_eq!(
+            result.words,
+            vec![GroupCount {
+                key: "rain".to_string(),
+                count: 4
+            }]
+        );
+    }
+}
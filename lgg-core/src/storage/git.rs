@@ -0,0 +1,107 @@
+//! The files of the journal as they were in a commit of its git repository, read with `git` (see
+//! [`GitStorage`]).
+use super::{markdown_files_in, Storage};
+use crate::utils::command::run;
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// The files under `dir`, a folder of a git repository, in the commit `revision`. Read-only.
+#[derive(Debug)]
+pub struct GitStorage {
+    dir: PathBuf,
+    revision: String,
+    files: BTreeSet<PathBuf>,
+}
+
+impl GitStorage {
+    /// The files of `dir` in `revision` (a commit, branch or tag, like `HEAD~3`). Fails if `dir`
+    /// isn't in a git repository or `revision` isn't one of its commits.
+    pub fn open(dir: &Path, revision: &str) -> Result<Self> {
+        let commit = format!("{revision}^{{commit}}");
+        let revision = run("git", Some(dir), &["rev-parse", "--verify", "--quiet", &commit])
+            .with_context(|| format!("`{revision}` is not a commit of {}", dir.display()))?
+            .trim()
+            .to_string();
+        let listing = run("git", Some(dir), &["ls-tree", "-r", "-z", "--name-only", &revision])?;
+        let files = listing
+            .split('\0')
+            .filter(|name| !name.is_empty())
+            .map(|name| dir.join(name))
+            .collect();
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            revision,
+            files,
+        })
+    }
+
+    /// The files of `dir` as they were when `date` started: the last commit before it.
+    pub fn at_date(dir: &Path, date: NaiveDate) -> Result<Self> {
+        let before = format!("--before={} 00:00", date.format("%Y-%m-%d"));
+        let commit = run("git", Some(dir), &["rev-list", "-1", &before, "HEAD"])?;
+        if commit.trim().is_empty() {
+            bail!("{} has no commit before {date}.", dir.display());
+        }
+        Self::open(dir, commit.trim())
+    }
+}
+
+impl Storage for GitStorage {
+    fn read_file(&self, path: &Path) -> Result<String> {
+        let relative = path
+            .strip_prefix(&self.dir)
+            .with_context(|| format!("{} is not in {}", path.display(), self.dir.display()))?;
+        // `./` makes the path relative to `dir`, not to the root of the repository.
+        let object = format!("{}:./{}", self.revision, relative.to_string_lossy());
+        run("git", Some(&self.dir), &["show", &object])
+    }
+
+    fn write_file(&self, path: &Path, _content: &str) -> Result<()> {
+        bail!("{} can't be written, it's from a git commit", path.display())
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<()> {
+        bail!("{} can't be removed, it's from a git commit", path.display())
+    }
+
+    fn list_files(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        Ok(markdown_files_in(self.files.iter(), dir))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.files.iter().any(|file| file.starts_with(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn files_are_read_from_a_commit() {
+        let tmp = tempdir().unwrap();
+        let dir = tmp.path();
+        let git = |args: &[&str]| {
+            let config = ["-c", "user.name=lgg", "-c", "user.email=lgg@example.com"];
+            run("git", Some(dir), &[&config[..], args].concat()).unwrap()
+        };
+        git(&["init", "--quiet"]);
+        let day = dir.join("2025/08/2025-08-15.md");
+        fs::create_dir_all(day.parent().unwrap()).unwrap();
+        fs::write(&day, "# Friday, 15 Aug 2025\n\n## 09:00 - Standup\n").unwrap();
+        git(&["add", "--all"]);
+        git(&["commit", "--quiet", "--message", "first"]);
+        fs::write(&day, "changed").unwrap();
+
+        let storage = GitStorage::open(dir, "HEAD").unwrap();
+        assert_eq!(storage.list_files(dir).unwrap(), vec![day.clone()]);
+        assert!(storage.read_file(&day).unwrap().contains("Standup"));
+        assert!(storage.exists(&dir.join("2025")));
+        assert!(storage.write_file(&day, "").is_err());
+        assert!(GitStorage::open(dir, "no-such-branch").is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! File access of the journal and the todos. [`FsStorage`] is the disk, other storages keep the
+//! files somewhere else: [`MemoryStorage`] in memory, for tests or a web UI built to WASM,
+//! [`RemoteStorage`] on a WebDAV server or an S3 bucket, and [`GitStorage`] in a git commit.
+mod git;
+mod remote;
+
+pub use git::GitStorage;
+pub use remote::{RemoteConfig, RemoteKind, RemoteStorage, REMOTE_PASSWORD_ENV};
+
+use crate::utils::path_utils::scan_dir_for_md_files;
+use anyhow::{anyhow, Context, Result};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Size and modification time of a file, to know whether it changed since it was read.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FileStamp {
+    pub modified: Option<SystemTime>,
+    pub len: u64,
+}
+
+/// Where the Markdown files are read from and written to.
+pub trait Storage: fmt::Debug + Send + Sync {
+    /// The content of the file at `path`.
+    fn read_file(&self, path: &Path) -> Result<String>;
+
+    /// Replaces the content of the file at `path`, creating it and its directory if needed.
+    fn write_file(&self, path: &Path, content: &str) -> Result<()>;
+
+    /// Removes the file at `path`. Nothing to do if there is none.
+    fn remove_file(&self, path: &Path) -> Result<()>;
+
+    /// The Markdown files under `dir`, at any depth, without the hidden or ignored ones (see
+    /// [`scan_dir_for_md_files`]).
+    fn list_files(&self, dir: &Path) -> Result<Vec<PathBuf>>;
+
+    /// Whether there is a file or a directory at `path`.
+    fn exists(&self, path: &Path) -> bool;
+
+    /// The stamp of the file at `path`. `None` when the storage can't tell, then the file is
+    /// parsed again every time it's read.
+    fn stamp(&self, _path: &Path) -> Option<FileStamp> {
+        None
+    }
+
+    /// Whether the paths are files on disk, that other programs like the editor can open.
+    fn on_disk(&self) -> bool {
+        false
+    }
+}
+
+/// The files on disk.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FsStorage;
+
+impl Storage for FsStorage {
+    fn read_file(&self, path: &Path) -> Result<String> {
+        Ok(fs::read_to_string(path)?)
+    }
+
+    fn write_file(&self, path: &Path, content: &str) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("creating parent directory {}", parent.display()))?;
+        }
+        Ok(fs::write(path, content)?)
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<()> {
+        match fs::remove_file(path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn list_files(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        scan_dir_for_md_files(dir)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn stamp(&self, path: &Path) -> Option<FileStamp> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(FileStamp {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+
+    fn on_disk(&self) -> bool {
+        true
+    }
+}
+
+/// Files kept in memory, lost when it's dropped. `.lggignore` files are not read.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    files: Mutex<BTreeMap<PathBuf, String>>,
+}
+
+impl MemoryStorage {
+    fn files(&self) -> Result<std::sync::MutexGuard<'_, BTreeMap<PathBuf, String>>> {
+        self.files
+            .lock()
+            .map_err(|_| anyhow!("the files in memory can't be read after a panic"))
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn read_file(&self, path: &Path) -> Result<String> {
+        self.files()?
+            .get(path)
+            .cloned()
+            .with_context(|| format!("{} does not exist", path.display()))
+    }
+
+    fn write_file(&self, path: &Path, content: &str) -> Result<()> {
+        self.files()?.insert(path.to_path_buf(), content.to_string());
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<()> {
+        self.files()?.remove(path);
+        Ok(())
+    }
+
+    fn list_files(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        Ok(markdown_files_in(self.files()?.keys(), dir))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        // Directories only exist through the files in them.
+        self.files()
+            .is_ok_and(|files| files.keys().any(|file| file.starts_with(path)))
+    }
+}
+
+/// The Markdown files of `paths` under `dir`, without the ones in hidden directories, for the
+/// storages that know all their files.
+fn markdown_files_in<'a>(paths: impl Iterator<Item = &'a PathBuf>, dir: &Path) -> Vec<PathBuf> {
+    paths
+        .filter(|path| {
+            let Ok(relative) = path.strip_prefix(dir) else {
+                return false;
+            };
+            let hidden = relative.components().any(|component| match component {
+                Component::Normal(name) => name.to_string_lossy().starts_with('.'),
+                _ => false,
+            });
+            let markdown = path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("md"));
+            markdown && !hidden
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_storage_lists_the_markdown_files_of_a_directory() {
+        let storage = MemoryStorage::default();
+        let root = Path::new("/journal");
+        for path in ["2025/08/2025-08-15.md", ".lgg/notes.md", "2025/08/photo.png"] {
+            storage.write_file(&root.join(path), "# Friday, 15 Aug 2025\n").unwrap();
+        }
+        storage.write_file(Path::new("/other/2025-08-16.md"), "").unwrap();
+
+        assert_eq!(
+            storage.list_files(root).unwrap(),
+            vec![root.join("2025/08/2025-08-15.md")]
+        );
+        assert!(storage.exists(&root.join("2025/08")));
+        assert!(!storage.exists(&root.join("2024")));
+        assert!(storage.read_file(&root.join("2024/01/2024-01-01.md")).is_err());
+    }
+}
@@ -0,0 +1,678 @@
+//! Day files on a WebDAV server (Nextcloud, ownCloud...) or an S3-compatible bucket, sent and
+//! received with `curl`. The files read are kept in a cache, downloaded again only when they
+//! change on the server, and read from there when the server can't be reached.
+use super::{markdown_files_in, FileStamp, FsStorage, Storage};
+use crate::summarize::split_command;
+use crate::utils::command::run;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::DateTime;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Environment variable with the password of the WebDAV user, or the secret key of S3.
+pub const REMOTE_PASSWORD_ENV: &str = "LGG_REMOTE_PASSWORD";
+
+/// File of the cache with the ETag of every cached file.
+const ETAGS_FILE: &str = "etags.json";
+
+/// Asks a WebDAV server for what the listing needs.
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:"><d:prop>
+<d:resourcetype/><d:getetag/><d:getcontentlength/><d:getlastmodified/>
+</d:prop></d:propfind>"#;
+
+/// The `[remote]` table of the config: where the day files are kept instead of `journal_dir`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteConfig {
+    pub kind: RemoteKind,
+    /// WebDAV: the folder of the journal, like
+    /// `https://cloud.example.com/remote.php/dav/files/me/journal` on Nextcloud.
+    /// S3: the bucket, like `https://s3.eu-west-1.amazonaws.com/my-journal`.
+    pub url: String,
+    /// The WebDAV user or the S3 access key. The password is read from [`REMOTE_PASSWORD_ENV`],
+    /// or printed by `password_cmd`.
+    pub user: Option<String>,
+    /// Command printing the password when [`REMOTE_PASSWORD_ENV`] is not set, like a lookup in the
+    /// keyring of the system: `secret-tool lookup service lgg`, `security find-generic-password
+    /// -s lgg -w`, `pass show lgg`...
+    pub password_cmd: Option<String>,
+    /// S3: the folder of the journal in the bucket, its root if not set.
+    pub prefix: Option<String>,
+    /// S3: the region of the bucket, `us-east-1` if not set.
+    pub region: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RemoteKind {
+    /// A WebDAV server, like Nextcloud or ownCloud.
+    WebDav,
+    /// An S3-compatible bucket: AWS, MinIO, Backblaze B2, Cloudflare R2...
+    S3,
+}
+
+/// The files on a remote, with the paths they would have under `root` on disk.
+#[derive(Debug)]
+pub struct RemoteStorage {
+    remote: RemoteConfig,
+    /// The directory the paths are in (`journal_dir`).
+    root: PathBuf,
+    /// Copies of the files read, with their ETag.
+    cache_dir: PathBuf,
+    /// Every file on the remote, listed the first time it's needed.
+    files: Mutex<Option<BTreeMap<PathBuf, RemoteFile>>>,
+}
+
+/// A file in the listing of the remote.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct RemoteFile {
+    /// Changes with every version of the file. Empty when it's not known.
+    etag: String,
+    stamp: Option<FileStamp>,
+}
+
+/// Status and body of an HTTP answer.
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    /// The body, if the request succeeded.
+    fn into_body(self, method: &str, url: &str) -> Result<String> {
+        if !(200..300).contains(&self.status) {
+            bail!("{method} {url} failed with HTTP status {}", self.status);
+        }
+        Ok(self.body)
+    }
+}
+
+impl RemoteStorage {
+    /// The files on `remote`, as if they were under `root`, cached in `cache_dir`.
+    pub fn new(remote: RemoteConfig, root: &Path, cache_dir: PathBuf) -> Self {
+        Self {
+            remote,
+            root: root.to_path_buf(),
+            cache_dir,
+            files: Mutex::new(None),
+        }
+    }
+
+    /// Runs `f` on the listing of the remote, made on first use. When the remote can't be
+    /// reached, the cached files are listed instead.
+    fn with_listing<T>(
+        &self,
+        f: impl FnOnce(&mut BTreeMap<PathBuf, RemoteFile>) -> T,
+    ) -> Result<T> {
+        let mut files = self
+            .files
+            .lock()
+            .map_err(|_| anyhow!("the listing of the remote can't be read after a panic"))?;
+        let files = files.get_or_insert_with(|| {
+            let listed = self.list_remote().unwrap_or_else(|error| {
+                log::warn!("{error:#}, listing the cached files");
+                self.cached_etags()
+                    .into_keys()
+                    .map(|relative| (relative, RemoteFile::default()))
+                    .collect()
+            });
+            listed
+                .into_iter()
+                .map(|(relative, file)| (self.root.join(relative), file))
+                .collect()
+        });
+        Ok(f(files))
+    }
+
+    fn list_remote(&self) -> Result<Vec<(String, RemoteFile)>> {
+        match self.remote.kind {
+            RemoteKind::WebDav => self.list_webdav(""),
+            RemoteKind::S3 => self.list_s3(),
+        }
+    }
+
+    /// The files in the folder `relative` and its subfolders, but the hidden ones.
+    fn list_webdav(&self, relative: &str) -> Result<Vec<(String, RemoteFile)>> {
+        let url = format!("{}/", self.url_of(relative));
+        let headers = ["Depth: 1", "Content-Type: application/xml; charset=utf-8"];
+        let response = self.curl("PROPFIND", &url, &headers, Some(PROPFIND_BODY))?;
+        if response.status == 404 && relative.is_empty() {
+            // Nothing was written yet.
+            return Ok(Vec::new());
+        }
+        let body = response.into_body("PROPFIND", &url)?;
+        let (mut files, folders) = parse_propfind(&body, url_path(&self.base_url()));
+        for folder in folders {
+            if folder != relative && !is_hidden(&folder) {
+                files.extend(self.list_webdav(&folder)?);
+            }
+        }
+        Ok(files)
+    }
+
+    /// The files under the prefix of the bucket, asked for a page at a time.
+    fn list_s3(&self) -> Result<Vec<(String, RemoteFile)>> {
+        let prefix = self.s3_prefix();
+        let mut files = Vec::new();
+        let mut token: Option<String> = None;
+        loop {
+            // The parameters in alphabetical order, as the signature wants them.
+            let mut url = format!("{}?", self.base_url());
+            if let Some(token) = &token {
+                url.push_str(&format!("continuation-token={}&", percent_encode(token, false)));
+            }
+            url.push_str(&format!("list-type=2&prefix={}", percent_encode(&prefix, false)));
+            let body = self.curl("GET", &url, &[], None)?.into_body("GET", &url)?;
+            let (listed, next) = parse_s3_listing(&body, &prefix);
+            files.extend(listed);
+            match next {
+                Some(next) => token = Some(next),
+                None => return Ok(files),
+            }
+        }
+    }
+
+    /// The `url` of the config, with the spaces and other characters URLs can't have escaped.
+    fn base_url(&self) -> String {
+        self.remote
+            .url
+            .trim_end_matches('/')
+            .bytes()
+            .map(|byte| match byte {
+                b'!'..=b'~' if !b"\"<>\\^`{|}".contains(&byte) => (byte as char).to_string(),
+                byte => format!("%{byte:02X}"),
+            })
+            .collect()
+    }
+
+    /// `journal/` for the `journal` prefix, empty without one.
+    fn s3_prefix(&self) -> String {
+        match self.remote.prefix.as_deref().map(|prefix| prefix.trim_matches('/')) {
+            Some(prefix) if !prefix.is_empty() => format!("{prefix}/"),
+            _ => String::new(),
+        }
+    }
+
+    /// The URL of the file or folder at `relative` from the root of the journal.
+    fn url_of(&self, relative: &str) -> String {
+        let key = match self.remote.kind {
+            RemoteKind::WebDav => relative.to_string(),
+            RemoteKind::S3 => format!("{}{relative}", self.s3_prefix()),
+        };
+        if key.is_empty() {
+            return self.base_url();
+        }
+        format!("{}/{}", self.base_url(), percent_encode(&key, true))
+    }
+
+    /// `path` from the root of the journal, with `/` between folders on every platform.
+    fn relative(&self, path: &Path) -> Result<String> {
+        let relative = path
+            .strip_prefix(&self.root)
+            .map_err(|_| anyhow!("{} is not in the journal", path.display()))?;
+        let parts: Vec<String> = relative
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect();
+        Ok(parts.join("/"))
+    }
+
+    /// Runs `curl` with `method` on `url`. The credentials and the `body` go through its
+    /// standard input, so they don't show in the list of processes.
+    fn curl(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[&str],
+        body: Option<&str>,
+    ) -> Result<Response> {
+        let mut config = String::new();
+        if let Some(user) = &self.remote.user {
+            let password = remote_password(&self.remote, user)?;
+            config.push_str(&format!("user = {}\n", curl_quote(&format!("{user}:{password}"))));
+        }
+        if let Some(body) = body {
+            // Unlike `data-binary`, `data-raw` doesn't read a file when the body starts with @.
+            config.push_str(&format!("data-raw = {}\n", curl_quote(body)));
+        }
+
+        let mut command = Command::new("curl");
+        command.args(["--silent", "--show-error", "--request", method]);
+        command.args(["--write-out", "\n%{http_code}", "--config", "-"]);
+        for header in headers {
+            command.args(["--header", header]);
+        }
+        if self.remote.kind == RemoteKind::S3 {
+            let region = self.remote.region.as_deref().unwrap_or("us-east-1");
+            command.arg("--aws-sigv4").arg(format!("aws:amz:{region}:s3"));
+        }
+        let mut child = command
+            .arg(url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("could not run `curl`, needed to reach the remote")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(config.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            bail!("{method} {url}: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+        Ok(Response {
+            status: status.trim().parse().unwrap_or(0),
+            body: body.to_string(),
+        })
+    }
+
+    /// Creates the WebDAV folders of `relative` that don't exist yet, from the root down.
+    fn create_folders(&self, relative: &str) -> Result<()> {
+        let parts: Vec<&str> = relative.split('/').collect();
+        for end in 0..parts.len() {
+            let folder = parts[..end].join("/");
+            if self.exists(&self.root.join(&folder)) {
+                continue;
+            }
+            let url = format!("{}/", self.url_of(&folder));
+            let response = self.curl("MKCOL", &url, &[], None)?;
+            // 405: the folder already exists.
+            if response.status != 405 {
+                response.into_body("MKCOL", &url)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn cache_file(&self, relative: &str) -> PathBuf {
+        self.cache_dir.join("files").join(relative)
+    }
+
+    /// The ETag of every cached file.
+    fn cached_etags(&self) -> BTreeMap<String, String> {
+        fs::read_to_string(self.cache_dir.join(ETAGS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Keeps `content` as the cached copy of `relative`, with the ETag of its version.
+    fn cache(&self, relative: &str, content: &str, etag: &str) -> Result<()> {
+        FsStorage.write_file(&self.cache_file(relative), content)?;
+        let mut etags = self.cached_etags();
+        etags.insert(relative.to_string(), etag.to_string());
+        self.write_etags(&etags)
+    }
+
+    fn write_etags(&self, etags: &BTreeMap<String, String>) -> Result<()> {
+        FsStorage.write_file(&self.cache_dir.join(ETAGS_FILE), &serde_json::to_string(etags)?)
+    }
+
+    fn download(&self, relative: &str) -> Result<String> {
+        let url = self.url_of(relative);
+        let response = self.curl("GET", &url, &[], None)?;
+        if response.status == 404 {
+            bail!("{relative} does not exist on the remote");
+        }
+        response.into_body("GET", &url)
+    }
+}
+
+impl Storage for RemoteStorage {
+    fn read_file(&self, path: &Path) -> Result<String> {
+        let relative = self.relative(path)?;
+        let Some(listed) = self.with_listing(|files| files.get(path).cloned())? else {
+            bail!("{} does not exist", path.display());
+        };
+        let cached = fs::read_to_string(self.cache_file(&relative));
+        if let Ok(content) = &cached
+            && self.cached_etags().get(&relative) == Some(&listed.etag)
+        {
+            return Ok(content.clone());
+        }
+        match self.download(&relative) {
+            Ok(content) => {
+                if let Err(error) = self.cache(&relative, &content, &listed.etag) {
+                    log::warn!("caching {relative}: {error:#}");
+                }
+                Ok(content)
+            }
+            Err(error) => match cached {
+                Ok(content) => {
+                    log::warn!("{error:#}, reading the cached copy");
+                    Ok(content)
+                }
+                Err(_) => Err(error),
+            },
+        }
+    }
+
+    fn write_file(&self, path: &Path, content: &str) -> Result<()> {
+        let relative = self.relative(path)?;
+        if self.remote.kind == RemoteKind::WebDav {
+            self.create_folders(&relative)?;
+        }
+        let url = self.url_of(&relative);
+        let headers = ["Content-Type: text/markdown; charset=utf-8"];
+        self.curl("PUT", &url, &headers, Some(content))?
+            .into_body("PUT", &url)?;
+
+        // The server gives it a new ETag, unknown until it's listed again.
+        let file = RemoteFile {
+            etag: String::new(),
+            stamp: Some(FileStamp {
+                modified: Some(SystemTime::now()),
+                len: content.len() as u64,
+            }),
+        };
+        self.with_listing(|files| files.insert(path.to_path_buf(), file))?;
+        if let Err(error) = self.cache(&relative, content, "") {
+            log::warn!("caching {relative}: {error:#}");
+        }
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<()> {
+        let relative = self.relative(path)?;
+        let url = self.url_of(&relative);
+        let response = self.curl("DELETE", &url, &[], None)?;
+        if response.status != 404 {
+            response.into_body("DELETE", &url)?;
+        }
+        self.with_listing(|files| files.remove(path))?;
+        let mut etags = self.cached_etags();
+        if etags.remove(&relative).is_some() {
+            FsStorage.remove_file(&self.cache_file(&relative))?;
+            self.write_etags(&etags)?;
+        }
+        Ok(())
+    }
+
+    fn list_files(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        self.with_listing(|files| markdown_files_in(files.keys(), dir))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        // Folders only exist through the files in them.
+        self.with_listing(|files| files.keys().any(|file| file.starts_with(path)))
+            .unwrap_or(false)
+    }
+
+    fn stamp(&self, path: &Path) -> Option<FileStamp> {
+        self.with_listing(|files| files.get(path).and_then(|file| file.stamp))
+            .ok()
+            .flatten()
+    }
+}
+
+/// The files and folders of a PROPFIND answer, from `base_path`, the path of the URL of the
+/// journal.
+fn parse_propfind(xml: &str, base_path: &str) -> (Vec<(String, RemoteFile)>, Vec<String>) {
+    let base = percent_decode(base_path);
+    let base = base.trim_end_matches('/');
+    let mut files = Vec::new();
+    let mut folders = Vec::new();
+    for response in xml_elements(xml, "response") {
+        let Some(href) = first_text(response, "href") else {
+            continue;
+        };
+        let href = percent_decode(url_path(&href));
+        let Some(relative) = href.strip_prefix(base) else {
+            continue;
+        };
+        let relative = relative.trim_matches('/').to_string();
+        if !xml_elements(response, "collection").is_empty() {
+            folders.push(relative);
+            continue;
+        }
+        let stamp = first_text(response, "getlastmodified")
+            .and_then(|modified| DateTime::parse_from_rfc2822(&modified).ok())
+            .map(|modified| FileStamp {
+                modified: Some(modified.into()),
+                len: first_text(response, "getcontentlength")
+                    .and_then(|len| len.parse().ok())
+                    .unwrap_or(0),
+            });
+        let etag = first_text(response, "getetag").unwrap_or_default();
+        files.push((relative, RemoteFile { etag, stamp }));
+    }
+    (files, folders)
+}
+
+/// The files of a ListObjectsV2 answer, from `prefix`, and the token of the next page.
+fn parse_s3_listing(xml: &str, prefix: &str) -> (Vec<(String, RemoteFile)>, Option<String>) {
+    let files = xml_elements(xml, "Contents")
+        .into_iter()
+        .filter_map(|object| {
+            let key = first_text(object, "Key")?;
+            let relative = key.strip_prefix(prefix)?;
+            // Empty objects ending with `/` are folders made by some clients.
+            if relative.is_empty() || relative.ends_with('/') {
+                return None;
+            }
+            let stamp = first_text(object, "LastModified")
+                .and_then(|modified| DateTime::parse_from_rfc3339(&modified).ok())
+                .map(|modified| FileStamp {
+                    modified: Some(modified.into()),
+                    len: first_text(object, "Size")
+                        .and_then(|len| len.parse().ok())
+                        .unwrap_or(0),
+                });
+            let etag = first_text(object, "ETag").unwrap_or_default();
+            Some((relative.to_string(), RemoteFile { etag, stamp }))
+        })
+        .collect();
+    let truncated = first_text(xml, "IsTruncated").as_deref() == Some("true");
+    let next = first_text(xml, "NextContinuationToken").filter(|_| truncated);
+    (files, next)
+}
+
+/// The content of the `name` elements of `xml`, whatever their namespace prefix (`d:href`,
+/// `D:href`, `href`). Self-closing elements are empty.
+fn xml_elements<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let mut found = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        let after = &rest[start + end + 1..];
+        rest = after;
+        let tag_name = tag.split_whitespace().next().unwrap_or_default();
+        let tag_name = tag_name.trim_end_matches('/');
+        let local_name = tag_name.rsplit(':').next().unwrap_or_default();
+        if tag.starts_with('/') || local_name != name {
+            continue;
+        }
+        if tag.ends_with('/') {
+            found.push("");
+            continue;
+        }
+        let close = format!("</{tag_name}>");
+        if let Some(close_at) = after.find(&close) {
+            found.push(&after[..close_at]);
+            rest = &after[close_at + close.len()..];
+        }
+    }
+    found
+}
+
+/// The text of the first `name` element of `xml`.
+fn first_text(xml: &str, name: &str) -> Option<String> {
+    let text = xml_elements(xml, name).first()?.trim();
+    Some(
+        text.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&"),
+    )
+}
+
+/// The path of `url`: `/remote.php/dav` for `https://cloud.example.com/remote.php/dav`.
+fn url_path(url: &str) -> &str {
+    match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |at| &rest[at..]),
+        None => url,
+    }
+}
+
+/// Whether the last folder of `relative` is hidden, like `.lgg`.
+fn is_hidden(relative: &str) -> bool {
+    relative
+        .rsplit('/')
+        .next()
+        .is_some_and(|name| name.starts_with('.'))
+}
+
+/// `text` for a URL, with the `/` kept as they are if `keep_slash`.
+fn percent_encode(text: &str, keep_slash: bool) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            b'/' if keep_slash => "/".to_string(),
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = text.get(i + 1..i + 3).filter(|_| bytes[i] == b'%');
+        match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// `value` between double quotes, for a curl config file.
+fn curl_quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// The password of `user` for `remote`: [`REMOTE_PASSWORD_ENV`], or what its `password_cmd`
+/// prints, without the line break at the end.
+fn remote_password(remote: &RemoteConfig, user: &str) -> Result<String> {
+    if let Ok(password) = std::env::var(REMOTE_PASSWORD_ENV) {
+        return Ok(password);
+    }
+    let Some(command) = &remote.password_cmd else {
+        bail!("Set the password of `{user}` for the remote in ${REMOTE_PASSWORD_ENV}.");
+    };
+    let Some(words) = split_command(command) else {
+        bail!("Invalid `password_cmd` \"{command}\".");
+    };
+    let args: Vec<&str> = words[1..].iter().map(String::as_str).collect();
+    let password = run(&words[0], None, &args)?;
+    Ok(password.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn reads_the_password_from_the_password_command() {
+        let mut remote = RemoteConfig {
+            kind: RemoteKind::WebDav,
+            url: "https://cloud.example.com/remote.php/dav/files/me/journal".to_string(),
+            user: Some("me".to_string()),
+            password_cmd: Some("printf 'app password\\n'".to_string()),
+            prefix: None,
+            region: None,
+        };
+        assert_eq!(remote_password(&remote, "me").unwrap(), "app password");
+
+        remote.password_cmd = Some("false".to_string());
+        assert!(remote_password(&remote, "me").is_err());
+        remote.password_cmd = None;
+        let error = remote_password(&remote, "me").unwrap_err().to_string();
+        assert!(error.contains(REMOTE_PASSWORD_ENV));
+    }
+
+    #[test]
+    fn reads_the_files_and_folders_of_a_webdav_listing() {
+        let xml = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:">
+<d:response><d:href>/remote.php/dav/files/me/My%20Journal/2025/</d:href>
+<d:propstat><d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop></d:propstat>
+</d:response>
+<d:response><d:href>/remote.php/dav/files/me/My%20Journal/2025/08/2025-08-15.md</d:href>
+<d:propstat><d:prop><d:resourcetype/><d:getetag>&quot;5f1a&quot;</d:getetag>
+<d:getcontentlength>42</d:getcontentlength>
+<d:getlastmodified>Fri, 15 Aug 2025 09:00:00 GMT</d:getlastmodified></d:prop></d:propstat>
+</d:response>
+</d:multistatus>"#;
+        let base = url_path("https://cloud.example.com/remote.php/dav/files/me/My%20Journal");
+        let (files, folders) = parse_propfind(xml, base);
+
+        assert_eq!(folders, vec!["2025"]);
+        assert_eq!(files.len(), 1);
+        let (relative, file) = &files[0];
+        assert_eq!(relative, "2025/08/2025-08-15.md");
+        assert_eq!(file.etag, "\"5f1a\"");
+        assert_eq!(file.stamp.map(|stamp| stamp.len), Some(42));
+    }
+
+    #[test]
+    fn reads_the_pages_of_an_s3_listing() {
+        let xml = r#"<ListBucketResult>
+<IsTruncated>true</IsTruncated>
+<Contents><Key>journal/</Key><Size>0</Size></Contents>
+<Contents><Key>journal/2025/08/2025-08-15.md</Key><ETag>"5f1a"</ETag>
+<LastModified>2025-08-15T09:00:00.000Z</LastModified><Size>42</Size></Contents>
+<NextContinuationToken>1ueGcxLPRx1Tr/XYExHnhbYLgveDs2J/wm36Hy4vbOwM=</NextContinuationToken>
+</ListBucketResult>"#;
+        let (files, next) = parse_s3_listing(xml, "journal/");
+
+        let relatives: Vec<&str> = files.iter().map(|(relative, _)| relative.as_str()).collect();
+        assert_eq!(relatives, vec!["2025/08/2025-08-15.md"]);
+        assert_eq!(
+            next.as_deref(),
+            Some("1ueGcxLPRx1Tr/XYExHnhbYLgveDs2J/wm36Hy4vbOwM=")
+        );
+        assert_eq!(
+            percent_encode(next.as_deref().unwrap(), false),
+            "1ueGcxLPRx1Tr%2FXYExHnhbYLgveDs2J%2Fwm36Hy4vbOwM%3D"
+        );
+    }
+}
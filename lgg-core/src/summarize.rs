@@ -0,0 +1,110 @@
+//! `lgg summarize`: a summary of the entries of a day, written by another program from the
+//! `summarizer_cmd` of the config (like a local LLM) and saved as a `@summary` entry.
+//!
+//! The program reads the entries on its standard input, as they are in the day file, and what
+//! it prints is the body of the summary.
+use crate::journal::format_utils::format_journal_entry_block;
+use crate::journal::private::PRIVATE_TITLE;
+use crate::journal::{Journal, JournalEntry, JournalWriteEntry, ReadEntriesOptions};
+use crate::utils::command::run_with_input;
+use crate::utils::date_utils::DateFilter;
+use anyhow::{bail, Result};
+use chrono::{NaiveDate, NaiveTime};
+
+/// Tag of the summaries, in their title.
+pub const SUMMARY_TAG: &str = "@summary";
+
+/// The program and the arguments of `command`, split as a shell would. `None` if it's empty or
+/// has a quote that isn't closed.
+pub fn split_command(command: &str) -> Option<Vec<String>> {
+    shlex::split(command).filter(|words| !words.is_empty())
+}
+
+/// What the summarizer reads: `entries` as in their day file, without the private ones and the
+/// summaries.
+pub fn summary_input(entries: &[JournalEntry]) -> String {
+    entries
+        .iter()
+        .filter(|entry| entry.title != PRIVATE_TITLE && !is_summary(entry))
+        .map(|entry| format_journal_entry_block(&entry.title, &entry.body, &entry.time))
+        .collect()
+}
+
+/// Runs `command` with the entries of `date` and saves what it prints as a `@summary` entry at
+/// the end of the day. Fails if the day has nothing to summarize or already has a summary, or if
+/// the command fails or prints nothing.
+pub fn summarize_day(journal: &Journal, command: &str, date: NaiveDate) -> Result<JournalEntry> {
+    let Some(words) = split_command(command) else {
+        bail!("Invalid `summarizer_cmd` \"{command}\".");
+    };
+    let options = ReadEntriesOptions {
+        dates: Some(DateFilter::Single(date)),
+        ..Default::default()
+    };
+    let entries = journal.read_entries(&options).entries;
+    if entries.iter().any(is_summary) {
+        bail!("{date} already has a summary.");
+    }
+    let input = summary_input(&entries);
+    if input.is_empty() {
+        bail!("There are no entries to summarize on {date}.");
+    }
+
+    let args: Vec<&str> = words[1..].iter().map(String::as_str).collect();
+    let summary = run_with_input(&words[0], &args, &input)?;
+    if summary.trim().is_empty() {
+        bail!("`{command}` printed no summary.");
+    }
+    journal.create_entry(JournalWriteEntry {
+        date,
+        time: NaiveTime::from_hms_opt(23, 59, 0).expect("valid time"),
+        title: format!("Summary {SUMMARY_TAG}"),
+        body: summary.trim().to_string(),
+        tags: vec![SUMMARY_TAG.to_string()],
+    })
+}
+
+fn is_summary(entry: &JournalEntry) -> bool {
+    entry.tags.iter().any(|tag| tag == SUMMARY_TAG)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `sed` and `cat` stand for the summarizer, they aren't on Windows.
+    #[cfg(unix)]
+    #[test]
+    fn the_summary_of_a_day_is_what_the_command_prints() {
+        use crate::tests::mk_config;
+        use crate::Lgg;
+        use tempfile::tempdir;
+
+        let tmp = tempdir().unwrap();
+        let lgg = Lgg::with_config(mk_config(tmp.path().join("journal"), None)).unwrap();
+        let journal = &lgg.journal;
+        let date = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let entry = |h, title: &str| JournalWriteEntry {
+            date,
+            time: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+            title: title.to_string(),
+            body: "Went well.".to_string(),
+            tags: Vec::new(),
+        };
+        journal.create_entry(entry(9, "Standup")).unwrap();
+        journal.create_entry(entry(14, "Design review")).unwrap();
+
+        // The first line of the entries, without the `## ` of its heading.
+        let summary = summarize_day(journal, "sed -n '1s/## //p'", date).unwrap();
+        assert_eq!(summary.body, "09:00 - Standup");
+        assert_eq!(summary.tags, vec![SUMMARY_TAG]);
+        assert!(summarize_day(journal, "cat", date).is_err());
+        assert!(summarize_day(journal, "cat", date.succ_opt().unwrap()).is_err());
+        assert!(summarize_day(journal, "'cat", date).is_err());
+    }
+
+    #[test]
+    fn commands_are_split_in_words() {
+        assert!(split_command("ollama run llama3 \"Summarize this\"").unwrap().len() == 4);
+    }
+}
@@ -0,0 +1,347 @@
+//! `lgg sync`: pulls the changes of the journal made on other devices, merges them with the
+//! ones made here and pushes the result, through git, rsync or a WebDAV server or S3 bucket.
+//!
+//! Day files changed on both sides are merged entry by entry (see
+//! [`Journal::merge_day_versions`]). For rsync, WebDAV and S3, what changed is known from a copy
+//! of the files as they were after the last sync, kept in the state dir.
+use crate::journal::Journal;
+use crate::storage::{FsStorage, RemoteConfig, RemoteKind, RemoteStorage, Storage};
+use crate::utils::command::run;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The `[sync]` table of the config.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SyncConfig {
+    pub kind: SyncKind,
+    /// git: the remote of the repository, `origin` if not set. rsync: where the journal is
+    /// copied to, like `me@server:journal`.
+    pub target: Option<String>,
+    /// WebDAV and S3: as in `[remote]`.
+    pub url: Option<String>,
+    pub user: Option<String>,
+    pub password_cmd: Option<String>,
+    pub prefix: Option<String>,
+    pub region: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncKind {
+    /// `journal_dir` is a git repository: commits, pulls and pushes.
+    Git,
+    /// Copies the journal to and from `target` with rsync.
+    Rsync,
+    /// A WebDAV server, like Nextcloud.
+    WebDav,
+    /// An S3-compatible bucket.
+    S3,
+}
+
+/// What `lgg sync` did.
+#[derive(Debug, Default, PartialEq)]
+pub struct SyncSummary {
+    /// Files changed here with the changes made on other devices.
+    pub pulled: Vec<PathBuf>,
+    /// Files changed here and sent to the other devices.
+    pub pushed: Vec<PathBuf>,
+    /// Files changed on both sides, merged entry by entry.
+    pub merged: Vec<PathBuf>,
+    /// Files removed here or on the other side, and now on both.
+    pub removed: Vec<PathBuf>,
+    /// Entries changed on both sides, where the local version was kept.
+    pub conflicts: Vec<String>,
+}
+
+/// Syncs the files of `journal` with the ones of `config`.
+pub fn sync_journal(journal: &Journal, config: &SyncConfig) -> Result<SyncSummary> {
+    let sync_dir = journal.dirs.sync_dir();
+    let base_dir = sync_dir.join("base");
+    let on_disk = matches!(config.kind, SyncKind::Git | SyncKind::Rsync);
+    if on_disk && !journal.storage.on_disk() {
+        bail!("Syncing with git or rsync needs the journal on disk, not in a [remote].");
+    }
+    let summary = match config.kind {
+        SyncKind::Git => sync_git(journal, config.target.as_deref().unwrap_or("origin"))?,
+        SyncKind::Rsync => {
+            let Some(target) = &config.target else {
+                bail!("Set the `target` of [sync], like \"me@server:journal\".");
+            };
+            let mirror = sync_dir.join("mirror");
+            fs::create_dir_all(&mirror)
+                .with_context(|| format!("creating {}", mirror.display()))?;
+            let target = format!("{}/", target.trim_end_matches('/'));
+            let mirror_arg = format!("{}/", mirror.display());
+            run("rsync", None, &["-a", "--delete", &target, &mirror_arg])?;
+            let summary = sync_storages(journal, &FsStorage, &mirror, &base_dir)?;
+            if !summary.pushed.is_empty()
+                || !summary.merged.is_empty()
+                || !summary.removed.is_empty()
+            {
+                run("rsync", None, &["-a", "--delete", &mirror_arg, &target])?;
+            }
+            summary
+        }
+        SyncKind::WebDav | SyncKind::S3 => {
+            let Some(url) = &config.url else {
+                bail!("Set the `url` of [sync].");
+            };
+            let remote = RemoteConfig {
+                kind: match config.kind {
+                    SyncKind::S3 => RemoteKind::S3,
+                    _ => RemoteKind::WebDav,
+                },
+                url: url.clone(),
+                user: config.user.clone(),
+                password_cmd: config.password_cmd.clone(),
+                prefix: config.prefix.clone(),
+                region: config.region.clone(),
+            };
+            let cache_dir = sync_dir.join("cache");
+            let storage = RemoteStorage::new(remote, &journal.journal_dir, cache_dir);
+            sync_storages(journal, &storage, &journal.journal_dir, &base_dir)?
+        }
+    };
+    journal.invalidate();
+    Ok(summary)
+}
+
+/// Syncs the files of `journal` with the ones of `other` under `other_root`, three-way: a file is
+/// compared with its copy in `base_dir`, from the last sync, to know which side changed it.
+fn sync_storages(
+    journal: &Journal,
+    other: &dyn Storage,
+    other_root: &Path,
+    base_dir: &Path,
+) -> Result<SyncSummary> {
+    let local_root = &journal.journal_dir;
+    let relative_files = |storage: &dyn Storage, root: &Path| -> Result<BTreeSet<PathBuf>> {
+        if !storage.exists(root) {
+            return Ok(BTreeSet::new());
+        }
+        Ok(storage
+            .list_files(root)?
+            .into_iter()
+            .filter_map(|path| path.strip_prefix(root).ok().map(Path::to_path_buf))
+            .collect())
+    };
+    let mut files = relative_files(journal.storage.as_ref(), local_root)?;
+    files.extend(relative_files(other, other_root)?);
+    files.extend(relative_files(&FsStorage, base_dir)?);
+
+    let read = |storage: &dyn Storage, path: &Path| -> Result<Option<String>> {
+        if !storage.exists(path) {
+            return Ok(None);
+        }
+        let content = storage
+            .read_file(path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        Ok(Some(content))
+    };
+    let mut summary = SyncSummary::default();
+    for relative in files {
+        let (local_path, other_path) = (local_root.join(&relative), other_root.join(&relative));
+        let base_path = base_dir.join(&relative);
+        let base = read(&FsStorage, &base_path)?;
+        let local = read(journal.storage.as_ref(), &local_path)?;
+        let remote = read(other, &other_path)?;
+
+        let synced = match (local, remote) {
+            (Some(local), Some(remote)) if local == remote => Some(local),
+            (Some(local), Some(remote)) if base.as_ref() == Some(&local) => {
+                journal.storage.write_file(&local_path, &remote)?;
+                summary.pulled.push(local_path);
+                Some(remote)
+            }
+            (Some(local), Some(remote)) if base.as_ref() == Some(&remote) => {
+                other.write_file(&other_path, &local)?;
+                summary.pushed.push(local_path);
+                Some(local)
+            }
+            (Some(local), Some(remote)) => {
+                let (merged, conflicts) =
+                    journal.merge_day_versions(&local_path, base.as_deref(), &local, &remote);
+                journal.storage.write_file(&local_path, &merged)?;
+                other.write_file(&other_path, &merged)?;
+                summary.conflicts.extend(conflicts);
+                summary.merged.push(local_path);
+                Some(merged)
+            }
+            // Removed on the other side, and not changed here.
+            (Some(local), None) if base.as_ref() == Some(&local) => {
+                journal.storage.remove_file(&local_path)?;
+                summary.removed.push(local_path);
+                None
+            }
+            (Some(local), None) => {
+                other.write_file(&other_path, &local)?;
+                summary.pushed.push(local_path);
+                Some(local)
+            }
+            (None, Some(remote)) if base.as_ref() == Some(&remote) => {
+                other.remove_file(&other_path)?;
+                summary.removed.push(local_path);
+                None
+            }
+            (None, Some(remote)) => {
+                journal.storage.write_file(&local_path, &remote)?;
+                summary.pulled.push(local_path);
+                Some(remote)
+            }
+            (None, None) => None,
+        };
+        match synced {
+            Some(content) if base.as_ref() != Some(&content) => {
+                FsStorage.write_file(&base_path, &content)?
+            }
+            Some(_) => {}
+            None => FsStorage.remove_file(&base_path)?,
+        }
+    }
+    Ok(summary)
+}
+
+/// Commits the changes of the repository of `journal`, merges the ones of `remote` and pushes.
+/// Day files that git can't merge are merged entry by entry.
+fn sync_git(journal: &Journal, remote: &str) -> Result<SyncSummary> {
+    let dir = journal.journal_dir.as_path();
+    let git = |args: &[&str]| run("git", Some(dir), args);
+    if git(&["rev-parse", "--is-inside-work-tree"]).is_err() {
+        bail!(
+            "{} is not a git repository. Run `git init` and `git remote add {remote} <url>` in it.",
+            dir.display()
+        );
+    }
+    let changed_files = |output: String| -> Vec<PathBuf> {
+        output.lines().map(|line| dir.join(line.trim())).collect()
+    };
+
+    git(&["add", "--all"])?;
+    if !git(&["status", "--porcelain"])?.trim().is_empty() {
+        git(&["commit", "--quiet", "--message", "lgg sync"])?;
+    }
+    let branch = git(&["symbolic-ref", "--short", "HEAD"])?.trim().to_string();
+    git(&["fetch", "--quiet", remote])?;
+    let upstream = format!("{remote}/{branch}");
+    let mut summary = SyncSummary::default();
+    if git(&["rev-parse", "--verify", "--quiet", &upstream]).is_ok() {
+        // Without a common commit, like on a new clone or when both sides started on their own,
+        // every file of a side counts as changed.
+        let files_of = |rev: &str| git(&["ls-tree", "-r", "--name-only", rev]);
+        let has_commits = git(&["rev-parse", "--verify", "--quiet", "HEAD"]).is_ok();
+        match git(&["merge-base", "HEAD", &upstream]) {
+            Ok(base) => {
+                let base = base.trim();
+                summary.pushed = changed_files(git(&["diff", "--name-only", base, "HEAD"])?);
+                summary.pulled = changed_files(git(&["diff", "--name-only", base, &upstream])?);
+            }
+            Err(_) => {
+                if has_commits {
+                    summary.pushed = changed_files(files_of("HEAD")?);
+                }
+                summary.pulled = changed_files(files_of(&upstream)?);
+            }
+        }
+        let merge = ["merge", "--quiet", "--no-edit", "--allow-unrelated-histories", &upstream];
+        if git(&merge).is_err() {
+            let conflicted = git(&["diff", "--name-only", "--diff-filter=U"])?;
+            if conflicted.trim().is_empty() {
+                bail!("Could not merge {upstream}, see `git status` in {}.", dir.display());
+            }
+            for relative in conflicted.lines().map(str::trim) {
+                // The stages of the file: 1 is the common version, 2 ours and 3 theirs.
+                let stage = |n: u8| git(&["show", &format!(":{n}:{relative}")]);
+                let (Ok(local), Ok(remote)) = (stage(2), stage(3)) else {
+                    bail!("Could not merge {relative}, see `git status` in {}.", dir.display());
+                };
+                let base = stage(1).ok();
+                let path = dir.join(relative);
+                let (merged, conflicts) =
+                    journal.merge_day_versions(&path, base.as_deref(), &local, &remote);
+                FsStorage.write_file(&path, &merged)?;
+                git(&["add", "--", relative])?;
+                summary.conflicts.extend(conflicts);
+                summary.merged.push(path);
+            }
+            git(&["commit", "--quiet", "--no-edit"])?;
+        }
+        // Changed on both sides, and merged by git.
+        for path in &summary.pulled {
+            if summary.pushed.contains(path) && !summary.merged.contains(path) {
+                summary.merged.push(path.clone());
+            }
+        }
+        summary.pulled.retain(|path| !summary.merged.contains(path));
+        summary.pushed.retain(|path| !summary.merged.contains(path));
+    } else {
+        summary.pushed = changed_files(git(&["ls-files"])?);
+    }
+    if !summary.pushed.is_empty() || !summary.merged.is_empty() {
+        git(&["push", "--quiet", "--set-upstream", remote, &branch])?;
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::mk_config;
+    use crate::{Lgg, MemoryStorage};
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    #[test]
+    fn sync_pulls_pushes_and_merges_day_files() {
+        let tmp = tempdir().unwrap();
+        let mut lgg = Lgg::with_config(mk_config(tmp.path().join("journal"), None)).unwrap();
+        lgg.journal.storage = Arc::new(MemoryStorage::default());
+        let journal = &lgg.journal;
+        let other = MemoryStorage::default();
+        let other_root = Path::new("/other");
+        let base_dir = tmp.path().join("base");
+        let day = |date: &str, entries: &str| format!("# {date}\n\n{entries}");
+        let (friday, saturday) = ("Friday, 15 Aug 2025", "Saturday, 16 Aug 2025");
+        let local_file = |name: &str| journal.journal_dir.join(format!("2025/08/{name}"));
+        let other_file = |name: &str| other_root.join(format!("2025/08/{name}"));
+
+        let standup = "## 09:00 - Standup\n\n";
+        journal
+            .storage
+            .write_file(&local_file("2025-08-15.md"), &day(friday, standup))
+            .unwrap();
+        other
+            .write_file(&other_file("2025-08-16.md"), &day(saturday, "## 10:00 - Run\n\n"))
+            .unwrap();
+        let first = sync_storages(journal, &other, other_root, &base_dir).unwrap();
+        assert_eq!(first.pushed, vec![local_file("2025-08-15.md")]);
+        assert_eq!(first.pulled, vec![local_file("2025-08-16.md")]);
+
+        // Both sides add an entry to the same day.
+        let lunch = "## 13:00 - Lunch\n\n";
+        journal
+            .storage
+            .write_file(&local_file("2025-08-15.md"), &day(friday, &format!("{standup}{lunch}")))
+            .unwrap();
+        let review = "## 18:00 - Review\n\n";
+        other
+            .write_file(&other_file("2025-08-15.md"), &day(friday, &format!("{standup}{review}")))
+            .unwrap();
+        let second = sync_storages(journal, &other, other_root, &base_dir).unwrap();
+        assert_eq!(second.merged, vec![local_file("2025-08-15.md")]);
+        assert!(second.conflicts.is_empty());
+        let merged = journal.storage.read_file(&local_file("2025-08-15.md")).unwrap();
+        assert!(merged.contains("Standup") && merged.contains("Lunch"));
+        assert!(merged.contains("Review"));
+        assert_eq!(other.read_file(&other_file("2025-08-15.md")).unwrap(), merged);
+
+        // A day removed on the other side is removed here too.
+        other.remove_file(&other_file("2025-08-16.md")).unwrap();
+        let third = sync_storages(journal, &other, other_root, &base_dir).unwrap();
+        assert_eq!(third.removed, vec![local_file("2025-08-16.md")]);
+        assert!(!journal.storage.exists(&local_file("2025-08-16.md")));
+    }
+}
@@ -0,0 +1,226 @@
+//! What a tag is, and how the tags of the entries are read in queries and tag listings.
+//!
+//! A tag is a match of a [`TagPattern`]: `@` or `#` and letters of any language, digits and `_`
+//! by default, or the `tag_pattern` of the config (like `[@#]\w+(?:[.-]\w+)*` for `@v1.2`). The
+//! journal and the todos find tags with it when entries are read and written, and the renderer
+//! highlights them with it when printed.
+//!
+//! Tags written in several ways, from the `[tag_aliases]` of the config (`"@dogs" = "@dog"`),
+//! are read as their canonical tag, and `lgg tags normalize` rewrites the aliases of the day files
+//! with it. The `ignored_tags` of the config, like `@the` in "email me @the office", are left out.
+use anyhow::{bail, Result};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Pattern of the tags without a `tag_pattern` in the config.
+pub const DEFAULT_TAG_PATTERN: &str = r"[@#]\w+";
+
+/// The regex of the tags, [`DEFAULT_TAG_PATTERN`] by default.
+#[derive(Debug, Clone)]
+pub struct TagPattern(Regex);
+
+impl TagPattern {
+    /// The tags of `pattern`, which can't match an empty text.
+    pub fn new(pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern)?;
+        if regex.is_match("") {
+            bail!("the pattern matches an empty text");
+        }
+        Ok(TagPattern(regex))
+    }
+
+    /// The pattern as it was written.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// The tags of `text`, lowercased, sorted and without repeats.
+    pub fn extract(&self, text: &str) -> Vec<String> {
+        let tags: HashSet<String> = self
+            .0
+            .find_iter(text)
+            .map(|found| found.as_str().to_lowercase())
+            .collect();
+        let mut tags: Vec<String> = tags.into_iter().collect();
+        tags.sort();
+        tags
+    }
+
+    /// Byte ranges of the tags of `text`.
+    pub fn find_ranges(&self, text: &str) -> Vec<(usize, usize)> {
+        self.0
+            .find_iter(text)
+            .map(|found| (found.start(), found.end()))
+            .collect()
+    }
+
+    /// Whether all of `text` is one tag.
+    pub fn is_tag(&self, text: &str) -> bool {
+        self.0
+            .find(text)
+            .is_some_and(|found| found.start() == 0 && found.end() == text.len())
+    }
+
+    /// `text` with each tag at the start of a word (after a space or at the start of a line, so
+    /// not the `@example` of `me@example.com`) replaced by what `replace` returns for it.
+    pub fn replace(&self, text: &str, mut replace: impl FnMut(&str) -> String) -> String {
+        let mut replaced = String::with_capacity(text.len());
+        let mut last = 0;
+        for found in self.0.find_iter(text) {
+            let at_word_start = text[..found.start()]
+                .chars()
+                .next_back()
+                .is_none_or(char::is_whitespace);
+            if !at_word_start {
+                continue;
+            }
+            replaced.push_str(&text[last..found.start()]);
+            replaced.push_str(&replace(found.as_str()));
+            last = found.end();
+        }
+        replaced.push_str(&text[last..]);
+        replaced
+    }
+}
+
+impl Default for TagPattern {
+    fn default() -> Self {
+        TagPattern::new(DEFAULT_TAG_PATTERN).expect("valid tag pattern")
+    }
+}
+
+/// The aliases of `[tag_aliases]`, lowercased, with their canonical tag.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagAliases(HashMap<String, String>);
+
+impl TagAliases {
+    /// The aliases of `pairs` of alias and canonical tag.
+    pub fn new(pairs: impl IntoIterator<Item = (String, String)>) -> Self {
+        let aliases = pairs
+            .into_iter()
+            .map(|(alias, tag)| (alias.to_lowercase(), tag.to_lowercase()))
+            .collect();
+        TagAliases(aliases)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The canonical tag of `tag`, lowercased. `tag` itself when it's not an alias.
+    pub fn canonical(&self, tag: &str) -> String {
+        let tag = tag.trim().to_lowercase();
+        self.0.get(&tag).cloned().unwrap_or(tag)
+    }
+
+    /// The canonical `tags`, sorted and without repeats.
+    pub fn canonical_tags(&self, tags: &[String]) -> Vec<String> {
+        let mut canonical: Vec<String> = tags.iter().map(|tag| self.canonical(tag)).collect();
+        canonical.sort();
+        canonical.dedup();
+        canonical
+    }
+
+    /// `text` with the aliases in it, tags of `pattern`, replaced by their canonical tag.
+    pub fn normalize(&self, pattern: &TagPattern, text: &str) -> String {
+        pattern
+            .0
+            .replace_all(text, |caps: &regex::Captures| {
+                let tag = &caps[0];
+                self.0
+                    .get(&tag.to_lowercase())
+                    .cloned()
+                    .unwrap_or_else(|| tag.to_string())
+            })
+            .into_owned()
+    }
+}
+
+/// `tags` as they are read: their canonical tag, without the `ignored` ones, sorted.
+pub fn read_tags(tags: &[String], aliases: &TagAliases, ignored: &[String]) -> Vec<String> {
+    let mut read = aliases.canonical_tags(tags);
+    read.retain(|tag| !ignored.contains(tag));
+    read
+}
+
+/// The tags of at least `min_count` entries, sorted, from the tags of each entry.
+pub fn tags_with_min_count(
+    tags_of_entries: impl IntoIterator<Item = Vec<String>>,
+    min_count: usize,
+) -> Vec<String> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for tag in tags_of_entries.into_iter().flatten() {
+        *counts.entry(tag).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count >= min_count)
+        .map(|(tag, _)| tag)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_words_of_any_language_after_at_or_hash() {
+        let tags = TagPattern::default();
+        assert_eq!(
+            tags.extract("Partido de @Fútbol #日本 con @ana_2, ana@example.com"),
+            vec!["#日本", "@ana_2", "@example", "@fútbol"]
+        );
+        assert!(tags.is_tag("@bills") && tags.is_tag("#work_2") && tags.is_tag("@café"));
+        assert!(!tags.is_tag("bills") && !tags.is_tag("@") && !tags.is_tag("@two words"));
+
+        let dotted = TagPattern::new(r"[@#]\w+(?:[.-]\w+)*").unwrap();
+        assert!(dotted.is_tag("@v1.2-beta") && !tags.is_tag("@v1.2-beta"));
+        assert_eq!(dotted.extract("Released @v1.2-beta"), vec!["@v1.2-beta"]);
+        assert!(TagPattern::new("@?").is_err());
+        assert!(TagPattern::new("[@#").is_err());
+    }
+
+    #[test]
+    fn only_tags_starting_a_word_are_replaced() {
+        let text = "@work on me@example.com\n#home";
+        assert_eq!(
+            TagPattern::default().replace(text, |tag| format!("`{tag}`")),
+            "`@work` on me@example.com\n`#home`"
+        );
+    }
+
+    #[test]
+    fn aliases_are_read_and_written_as_their_canonical_tag() {
+        let aliases = TagAliases::new([
+            ("@dogs".to_string(), "@dog".to_string()),
+            ("@Perro".to_string(), "@dog".to_string()),
+        ]);
+        assert_eq!(aliases.canonical("@DOGS"), "@dog");
+        assert_eq!(aliases.canonical("@cat"), "@cat");
+        let tags = ["@perro".to_string(), "@dog".to_string(), "@cat".to_string()];
+        assert_eq!(aliases.canonical_tags(&tags), vec!["@cat", "@dog"]);
+        assert_eq!(
+            aliases.normalize(
+                &TagPattern::default(),
+                "Walk with @Dogs and @perro, not @dogsitter #dogs"
+            ),
+            "Walk with @dog and @dog, not @dogsitter #dogs"
+        );
+    }
+
+    #[test]
+    fn listings_skip_ignored_and_rare_tags() {
+        let aliases = TagAliases::new([("@dogs".to_string(), "@dog".to_string())]);
+        let ignored = vec!["@the".to_string()];
+        let tags = ["@the".to_string(), "@dogs".to_string(), "@dog".to_string()];
+        assert_eq!(read_tags(&tags, &aliases, &ignored), vec!["@dog"]);
+
+        let entries = vec![
+            vec!["@dog".to_string(), "@walk".to_string()],
+            vec!["@dog".to_string()],
+            vec!["@vet".to_string()],
+        ];
+        assert_eq!(tags_with_min_count(entries.clone(), 1), vec!["@dog", "@vet", "@walk"]);
+        assert_eq!(tags_with_min_count(entries, 2), vec!["@dog"]);
+    }
+}
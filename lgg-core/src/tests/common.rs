@@ -1,5 +1,8 @@
-use crate::Config;
+use crate::{
+    AllowFuture, Config, DefaultAction, Hyperlinks, Keywords, ParseMode, RangeOrder, TimePrecision,
+};
 use chrono::{Local, NaiveDate, NaiveTime};
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 /// Test helper to create a default `Config` for testing purposes.
@@ -15,6 +18,44 @@ pub fn mk_config(tmp_dir: PathBuf, reference_date: Option<NaiveDate>) -> Config
         reference_date: reference_date.unwrap_or(Local::now().date_naive()),
         journal_date_format: "%A, %d %b %Y".to_string(),
         todo_datetime_format: "%d/%b/%Y %H:%M".to_string(),
+        todo_read_formats: vec!["%d/%b/%Y %H:%M".to_string()],
         input_date_formats: ["%d/%m/%Y".to_string()].to_vec(),
+        obsidian: false,
+        merge_within_minutes: None,
+        default_action: DefaultAction::Editor,
+        stopwords: Vec::new(),
+        parse_mode: ParseMode::Strict,
+        time_precision: TimePrecision::Minutes,
+        locale: None,
+        range_order: RangeOrder::Swap,
+        allow_future: AllowFuture::Warn,
+        editor_template: Vec::new(),
+        temp_dir: None,
+        // Inside the temporary journal, never in the state dir of whoever runs the tests.
+        state_dir: Some(PathBuf::from(".lgg")),
+        stale_days: 30,
+        stale_threshold: 5,
+        hyperlinks: Hyperlinks::Auto,
+        quiet_days: Vec::new(),
+        device_id: None,
+        summarizer_cmd: None,
+        transcriber_cmd: None,
+        fetch_titles: false,
+        index_files: false,
+        default_tags: Vec::new(),
+        ignored_tags: Vec::new(),
+        tag_pattern: Default::default(),
+        auto_tags: Vec::new(),
+        tag_aliases: Default::default(),
+        keywords: Keywords::default(),
+        anniversaries: Vec::new(),
+        aliases: HashMap::new(),
+        commitments: Vec::new(),
+        journals: BTreeMap::new(),
+        remote: None,
+        sync: None,
+        todo_reminders: HashMap::new(),
+        warnings: Vec::new(),
+        project_file: None,
     }
 }
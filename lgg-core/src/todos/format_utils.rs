@@ -1,31 +1,38 @@
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+/// ISO 8601 dates, read whatever the `todo_datetime_format` is.
+const ISO_FORMATS: [&str; 5] = [
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d",
+];
 
 pub fn format_todo_entry_block(
     title: &str,
     body: &str,
     due_date: Option<NaiveDateTime>,
     done_date: Option<NaiveDateTime>,
+    created: Option<NaiveDateTime>,
     date_format: &str,
 ) -> String {
     let mut entry = format!("- [ ] {title}");
 
-    match due_date {
-        Some(d) => {
-            let formatted_date = d.format(date_format);
+    if let Some(d) = due_date {
+        let formatted_date = d.format(date_format);
+        entry = format!("{entry} | {formatted_date}");
+    };
+    if let Some(d) = done_date {
+        let formatted_date = d.format(date_format);
+        if due_date.is_none() {
+            entry = format!("{entry} | | {formatted_date}");
+        } else {
             entry = format!("{entry} | {formatted_date}");
-        }
-        None => (),
+        };
     };
-    match done_date {
-        Some(d) => {
-            let formatted_date = d.format(date_format);
-            if due_date.is_none() {
-                entry = format!("{entry} | | {formatted_date}");
-            } else {
-                entry = format!("{entry} | {formatted_date}");
-            };
-        }
-        None => (),
+    if let Some(d) = created {
+        entry = format!("{entry} (added: {})", d.format(date_format));
     };
     if body.trim().is_empty() {
         entry = format!("{entry}\n");
@@ -38,14 +45,26 @@ pub fn format_todo_entry_block(
     }
 }
 
-pub fn parse_datetime(s: &str, date_format: &str) -> Result<Option<NaiveDateTime>, String> {
+/// `s` read with the first of `formats` it fits, or as an ISO date (`2025-09-01 09:00`). Dates
+/// without a time are at 00:00. `None` if it's empty.
+pub fn parse_datetime(s: &str, formats: &[String]) -> Result<Option<NaiveDateTime>, String> {
     let s = s.trim();
     if s.is_empty() {
         return Ok(None);
     }
-    NaiveDateTime::parse_from_str(s, date_format)
-        .map(Some)
-        .map_err(|_| format!("Invalid datetime `{s}`. Expected `DD/MM/YYYY HH:MM`."))
+    for format in formats.iter().map(String::as_str).chain(ISO_FORMATS) {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(s, format) {
+            return Ok(Some(datetime));
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(s, format) {
+            return Ok(Some(date.and_time(NaiveTime::MIN)));
+        }
+    }
+    let example = NaiveDate::from_ymd_opt(2025, 9, 1)
+        .and_then(|date| date.and_hms_opt(9, 0, 0))
+        .expect("valid date");
+    let format = formats.first().map_or(ISO_FORMATS[0], String::as_str);
+    Err(format!("Invalid datetime `{s}`. Expected a date like `{}`.", example.format(format)))
 }
 
 #[cfg(test)]
@@ -56,7 +75,7 @@ mod tests {
     #[test]
     fn todo_entry_block_only_title() {
         let format = "%d/%m/%Y %H:%M";
-        let e = format_todo_entry_block("Item 1", "", None, None, format);
+        let e = format_todo_entry_block("Item 1", "", None, None, None, format);
 
         assert_eq!(e, "- [ ] Item 1\n");
     }
@@ -64,7 +83,7 @@ mod tests {
     #[test]
     fn todo_entry_block_with_body() {
         let format = "%d/%m/%Y %H:%M";
-        let e = format_todo_entry_block("Item 1", "With body", None, None, format);
+        let e = format_todo_entry_block("Item 1", "With body", None, None, None, format);
 
         assert_eq!(e, "- [ ] Item 1\n      With body\n");
     }
@@ -75,7 +94,7 @@ mod tests {
         let t = NaiveTime::from_hms_opt(7, 0, 0).unwrap();
         let due_date = NaiveDateTime::new(d, t);
         let format = "%d/%m/%Y %H:%M";
-        let e = format_todo_entry_block("Item 1", "", Some(due_date), None, format);
+        let e = format_todo_entry_block("Item 1", "", Some(due_date), None, None, format);
 
         assert_eq!(e, "- [ ] Item 1 | 20/08/2025 07:00\n");
     }
@@ -89,7 +108,14 @@ mod tests {
         let due_date = NaiveDateTime::new(d, t);
         let done_date = NaiveDateTime::new(dd, td);
         let format = "%d/%m/%Y %H:%M";
-        let e = format_todo_entry_block("Item 1", "", Some(due_date), Some(done_date), format);
+        let e = format_todo_entry_block(
+            "Item 1",
+            "",
+            Some(due_date),
+            Some(done_date),
+            None,
+            format,
+        );
 
         assert_eq!(e, "- [ ] Item 1 | 20/08/2025 07:00 | 22/08/2025 18:00\n");
     }
@@ -99,8 +125,35 @@ mod tests {
         let td = NaiveTime::from_hms_opt(18, 0, 0).unwrap();
         let done_date = NaiveDateTime::new(dd, td);
         let format = "%d/%m/%Y %H:%M";
-        let e = format_todo_entry_block("Item 1", "", None, Some(done_date), format);
+        let e = format_todo_entry_block("Item 1", "", None, Some(done_date), None, format);
 
         assert_eq!(e, "- [ ] Item 1 | | 22/08/2025 18:00\n");
     }
+
+    #[test]
+    fn todo_entry_block_with_created_date() {
+        let d = NaiveDate::from_ymd_opt(2025, 08, 15).unwrap();
+        let t = NaiveTime::from_hms_opt(10, 30, 0).unwrap();
+        let created = NaiveDateTime::new(d, t);
+        let format = "%d/%m/%Y %H:%M";
+        let e = format_todo_entry_block("Item 1", "", None, None, Some(created), format);
+
+        assert_eq!(e, "- [ ] Item 1 (added: 15/08/2025 10:30)\n");
+    }
+
+    #[test]
+    fn datetimes_are_read_with_every_format_then_iso() {
+        let formats = vec!["%d/%m/%Y %H:%M".to_string(), "%m-%d-%Y".to_string()];
+        let nine = NaiveDate::from_ymd_opt(2025, 9, 1).unwrap().and_hms_opt(9, 0, 0);
+        let midnight = NaiveDate::from_ymd_opt(2025, 9, 1).unwrap().and_hms_opt(0, 0, 0);
+
+        assert_eq!(parse_datetime("01/09/2025 09:00", &formats), Ok(nine));
+        assert_eq!(parse_datetime("09-01-2025", &formats), Ok(midnight));
+        assert_eq!(parse_datetime("2025-09-01 09:00", &formats), Ok(nine));
+        assert_eq!(parse_datetime("2025-09-01T09:00:00", &formats), Ok(nine));
+        assert_eq!(parse_datetime("2025-09-01", &formats), Ok(midnight));
+        assert_eq!(parse_datetime(" ", &formats), Ok(None));
+        let err = parse_datetime("tomorrow", &formats).unwrap_err();
+        assert_eq!(err, "Invalid datetime `tomorrow`. Expected a date like `01/09/2025 09:00`.");
+    }
 }
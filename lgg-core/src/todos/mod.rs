@@ -1,5 +1,6 @@
 mod format_utils;
 mod parse_todos;
+pub mod reminders;
 mod todo_entry;
 mod todos;
 mod todos_paths;
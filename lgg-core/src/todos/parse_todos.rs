@@ -1,13 +1,18 @@
 use super::{format_utils, todo_entry::ReadTodosResult, ParsedTodosEntry, TodoStatus};
-use crate::utils::parse_input::extract_tags;
+use crate::tags::TagPattern;
 
 /// Reads all todo entries from the list and applies optional filters.
 /// - Loads entries from the pending todos file.
 /// - Sorts by `due_date`.
 /// - Applies `due_date` filter (`Single` or `Range`) if provided.
 /// - Applies `tags` filter if provided.
-/// Returns all matching entries plus any parsing errors.
-pub fn parse_todo_file_content(content: &str, date_format: &str) -> ReadTodosResult {
+///
+/// Returns all matching entries, with the matches of `tags` in them, plus any parsing errors.
+pub fn parse_todo_file_content(
+    content: &str,
+    date_formats: &[String],
+    tags: &TagPattern,
+) -> ReadTodosResult {
     let mut entries = Vec::new();
     let mut errors = Vec::new();
     let mut lines = content.lines().peekable();
@@ -23,10 +28,7 @@ pub fn parse_todo_file_content(content: &str, date_format: &str) -> ReadTodosRes
         }
     }
 
-    let is_entry_start = |s: &str| {
-        let t = s.trim_start();
-        t.starts_with("- [ ] ") || t.starts_with("- [x] ") || t.starts_with("- [X] ")
-    };
+    let is_entry_start = |s: &str| checkbox_status(s.trim_start()).is_some();
 
     while let Some(line) = lines.peek() {
         if line.trim().is_empty() || !is_entry_start(line) {
@@ -35,36 +37,38 @@ pub fn parse_todo_file_content(content: &str, date_format: &str) -> ReadTodosRes
         }
 
         let header = lines.next().unwrap();
-        let trimmed = header.trim_start();
+        let (status, rest) = checkbox_status(header.trim_start()).unwrap();
 
-        let (status, rest) = if trimmed.starts_with("- [ ]") {
-            (
-                TodoStatus::Pending,
-                trimmed.trim_start_matches("- [ ]").trim_start(),
-            )
-        } else {
-            (
-                TodoStatus::Done,
-                trimmed
-                    .trim_start_matches("- [x]")
-                    .trim_start_matches("- [X]")
-                    .trim_start(),
-            )
+        let (rest, created_str) = match rest
+            .strip_suffix(')')
+            .and_then(|r| r.rsplit_once(" (added: "))
+        {
+            Some((rest, created)) => (rest, created),
+            None => (rest, ""),
         };
 
-        let mut parts = rest.split(" | ").map(str::trim);
+        // Split on " |" so an empty due date (`Title | | done date`) is read as empty.
+        let mut parts = rest.split(" |").map(str::trim);
         let title = parts.next().unwrap_or("").to_string();
         let due_str = parts.next().unwrap_or("");
         let done_str = parts.next().unwrap_or("");
 
-        let due_date = match format_utils::parse_datetime(due_str, date_format) {
+        let due_date = match format_utils::parse_datetime(due_str, date_formats) {
+            Ok(dt) => dt,
+            Err(e) => {
+                errors.push(format!("In `{header}`: {e}"));
+                None
+            }
+        };
+        let done_date = match format_utils::parse_datetime(done_str, date_formats) {
             Ok(dt) => dt,
             Err(e) => {
                 errors.push(format!("In `{header}`: {e}"));
                 None
             }
         };
-        let done_date = match format_utils::parse_datetime(done_str, date_format) {
+
+        let created = match format_utils::parse_datetime(created_str, date_formats) {
             Ok(dt) => dt,
             Err(e) => {
                 errors.push(format!("In `{header}`: {e}"));
@@ -74,7 +78,8 @@ pub fn parse_todo_file_content(content: &str, date_format: &str) -> ReadTodosRes
 
         let mut body_lines: Vec<String> = Vec::new();
         while let Some(next) = lines.peek().cloned() {
-            if is_entry_start(next) {
+            // The `## Done` section heading is not part of the last pending todo.
+            if is_entry_start(next) || next.starts_with("## ") {
                 break;
             }
 
@@ -88,11 +93,12 @@ pub fn parse_todo_file_content(content: &str, date_format: &str) -> ReadTodosRes
         } else {
             format!("{title}\n{body}")
         };
-        let tags = extract_tags(&tag_source);
+        let tags = tags.extract(&tag_source);
 
         entries.push(ParsedTodosEntry {
             due_date,
             done_date,
+            created,
             title,
             body,
             tags,
@@ -102,3 +108,19 @@ pub fn parse_todo_file_content(content: &str, date_format: &str) -> ReadTodosRes
 
     ReadTodosResult { entries, errors }
 }
+
+/// Status of a todo line (`- [ ] Title`) and the text after its checkbox. `None` if it's not a
+/// todo line.
+fn checkbox_status(line: &str) -> Option<(TodoStatus, &str)> {
+    let rest = line.strip_prefix("- [")?;
+    let (mark, rest) = rest.split_at_checked(1)?;
+    let rest = rest.strip_prefix("] ")?;
+    let status = match mark {
+        " " => TodoStatus::Pending,
+        "/" | "~" => TodoStatus::InProgress,
+        "x" | "X" => TodoStatus::Done,
+        "-" => TodoStatus::Cancelled,
+        _ => return None,
+    };
+    Some((status, rest.trim_start()))
+}
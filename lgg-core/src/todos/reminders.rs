@@ -0,0 +1,50 @@
+//! Reminders ahead of the due date of the todos with some tags, from `[todo.reminders]` of the
+//! config:
+//!
+//! ```toml
+//! [todo.reminders]
+//! "@bills" = "3 days before"
+//! ```
+
+use chrono::TimeDelta;
+
+/// Reminders can't be earlier than these days before the due date.
+const MAX_DAYS_BEFORE: i64 = 10 * 365;
+
+/// How long before the due date `expression` says: a number, a unit (minutes, hours, days or
+/// weeks) and `before`, like `3 days before` or `1 hour before`. `None` if it's not one, or it's
+/// more than [`MAX_DAYS_BEFORE`].
+pub fn parse_before(expression: &str) -> Option<TimeDelta> {
+    let expression = expression.to_lowercase();
+    let words: Vec<&str> = expression.split_whitespace().collect();
+    let [amount, unit, "before"] = words.as_slice() else {
+        return None;
+    };
+    let amount = i64::from(amount.parse::<u32>().ok()?);
+    let before = match unit.strip_suffix('s').unwrap_or(unit) {
+        "minute" | "min" => TimeDelta::try_minutes(amount),
+        "hour" => TimeDelta::try_hours(amount),
+        "day" => TimeDelta::try_days(amount),
+        "week" => TimeDelta::try_weeks(amount),
+        _ => None,
+    }?;
+    (before <= TimeDelta::days(MAX_DAYS_BEFORE)).then_some(before)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_before_the_due_date_are_parsed() {
+        assert_eq!(parse_before("3 days before"), Some(TimeDelta::days(3)));
+        assert_eq!(parse_before("1 hour before"), Some(TimeDelta::hours(1)));
+        assert_eq!(parse_before(" 2  Weeks BEFORE "), Some(TimeDelta::weeks(2)));
+        assert_eq!(parse_before("30 mins before"), Some(TimeDelta::minutes(30)));
+        assert_eq!(parse_before("3 days"), None);
+        assert_eq!(parse_before("-1 day before"), None);
+        assert_eq!(parse_before("2 months before"), None);
+        assert_eq!(parse_before("520 weeks before"), Some(TimeDelta::weeks(520)));
+        assert_eq!(parse_before("100000000 days before"), None);
+    }
+}
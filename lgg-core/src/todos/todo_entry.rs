@@ -3,16 +3,40 @@ use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use std::path::PathBuf;
 use crate::QueryError;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum TodoStatus {
+    /// `- [ ]`
     Pending,
+    /// `- [/]`, also read from `- [~]`. Started, but not done yet.
+    InProgress,
+    /// `- [x]`
     Done,
+    /// `- [-]`. Abandoned: closed, but not counted as done.
+    Cancelled,
+}
+
+impl TodoStatus {
+    /// Statuses of todos not done yet.
+    pub const OPEN: [TodoStatus; 2] = [TodoStatus::Pending, TodoStatus::InProgress];
+
+    /// The checkbox of the status in the todos file.
+    pub fn checkbox(&self) -> &'static str {
+        match self {
+            TodoStatus::Pending => "[ ]",
+            TodoStatus::InProgress => "[/]",
+            TodoStatus::Done => "[x]",
+            TodoStatus::Cancelled => "[-]",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct TodoEntry {
     pub due_date: Option<NaiveDateTime>,
     pub done_date: Option<NaiveDateTime>,
+    /// When the todo was added, `(added: ...)` at the end of its line. `None` for todos written
+    /// before it was recorded.
+    pub created: Option<NaiveDateTime>,
     pub title: String,
     pub body: String,
     pub path: PathBuf,
@@ -35,22 +59,43 @@ pub struct TodoWriteEntry {
 #[derive(Debug)]
 pub struct TodoQueryResult {
     pub todos: Vec<TodoEntry>,
+    /// Number of each of `todos`: its place in the todos file, from 1. It stays the same however
+    /// the todos are filtered, so it can be used to pick one of them later (`todo edit 3`).
+    pub indexes: Vec<usize>,
     pub errors: Vec<QueryError>,
 }
 
+impl TodoQueryResult {
+    /// The todos with their numbers.
+    pub fn numbered(&self) -> impl Iterator<Item = (usize, &TodoEntry)> {
+        self.indexes.iter().copied().zip(&self.todos)
+    }
+
+    /// The todo numbered `index`, if it's among the results.
+    pub fn get(&self, index: usize) -> Option<&TodoEntry> {
+        self.numbered()
+            .find(|(i, _)| *i == index)
+            .map(|(_, todo)| todo)
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct ReadTodoOptions<'a> {
     pub due_date: Option<DateFilter>,
     pub done_date: Option<DateFilter>,
     pub time: Option<&'a str>,
     pub tags: Option<&'a Vec<String>>,
-    pub status: Option<TodoStatus>,
+    /// Fuzzy match on the todo title (see [`crate::utils::text_utils::title_matches`]).
+    pub title: Option<&'a str>,
+    /// Todos with any of these statuses (e.g., [`TodoStatus::OPEN`]).
+    pub status: Option<&'a [TodoStatus]>,
 }
 
 #[derive(Debug)]
 pub struct ParsedTodosEntry {
     pub due_date: Option<NaiveDateTime>,
     pub done_date: Option<NaiveDateTime>,
+    pub created: Option<NaiveDateTime>,
     pub title: String,
     pub body: String,
     pub tags: Vec<String>,
@@ -1,27 +1,40 @@
 use super::{
     parse_todos::parse_todo_file_content,
-    todo_entry::{ReadTodoOptions, TodoEntry, TodoQueryResult, TodoStatus, TodoWriteEntry},
+    todo_entry::{
+        ParsedTodosEntry, ReadTodoOptions, TodoEntry, TodoQueryResult, TodoStatus, TodoWriteEntry,
+    },
     todos_paths::todos_file,
 };
-use crate::todos::format_utils::format_todo_entry_block;
+use crate::journal::private::{is_sealed, open_sealed, seal_text, PRIVATE_TITLE};
+use crate::todos::format_utils::{format_todo_entry_block, parse_datetime};
 use crate::utils::date_utils::DateFilter;
-use crate::{QueryError, QueryTagsResult};
+use crate::utils::text_utils::title_matches;
+use crate::tags::{self, TagAliases, TagPattern};
+use crate::{QueryError, QueryTagsResult, Storage};
 use anyhow::anyhow;
-use anyhow::{Context, Result};
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
-use std::{collections::HashSet, io::Write};
-use std::{
-    fs::{self, OpenOptions},
-    path::PathBuf,
-};
+use anyhow::{bail, Context, Result};
+use chrono::{Days, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct Todos {
     pub todo_list_dir: PathBuf,
     pub todo_datetime_format: String,
+    /// Formats the dates of the todo file are read with, in order. ISO dates are read too.
+    pub todo_read_formats: Vec<String>,
     /// The date to use as "today" for relative keywords.
     pub reference_date: NaiveDate,
     pub default_time: NaiveTime,
+    /// The tags found in the todos.
+    pub tag_pattern: TagPattern,
+    /// Tags read as another one, in queries and [`Todos::search_all_tags`].
+    pub tag_aliases: TagAliases,
+    /// Tags left out of queries and [`Todos::search_all_tags`], lowercased.
+    pub ignored_tags: Vec<String>,
+    /// Where the todo list is read and written, the disk unless it's replaced.
+    pub storage: Arc<dyn Storage>,
 }
 impl Todos {
     pub fn create_entry(&self, input: TodoWriteEntry) -> Result<TodoEntry> {
@@ -33,86 +46,252 @@ impl Todos {
             None => None,
         };
         let todos_file = todos_file(&self.todo_list_dir);
-        if let Some(parent) = todos_file.parent() {
-            fs::create_dir_all(parent)
-                .with_context(|| format!("creating parent directory {}", parent.display()))?;
-        }
-
-        let is_new = !todos_file.exists();
+        let is_new = !self.storage.exists(&todos_file);
         let header = "# Todos\n\n".to_string();
         let todo_subheader = "## Pending\n\n".to_string();
-        let done_subheader = "## Done\n\n".to_string();
+        // Added now, on the day taken as today.
+        let now = Local::now().time();
+        let created = NaiveTime::from_hms_opt(now.hour(), now.minute(), 0)
+            .map(|time| NaiveDateTime::new(self.reference_date, time));
         let block = format_todo_entry_block(
             &input.title,
             &input.body,
             due_date,
             None,
+            created,
             &self.todo_datetime_format,
         );
 
         let new_entry = TodoEntry {
             due_date,
             done_date: None,
+            created,
             title: input.title,
             body: input.body,
             path: todos_file.clone(),
             status: TodoStatus::Pending,
             tags: input.tags,
         };
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&todos_file)
-            .with_context(|| format!("opening {}", todos_file.display()))?;
-
         if is_new {
-            writeln!(file, "{header}{todo_subheader}{block}")
+            self.storage
+                .write_file(&todos_file, &format!("{header}{todo_subheader}{block}\n"))
                 .with_context(|| format!("writing day header to {}", todos_file.display()))?;
         } else {
             let all_entries = self.parse_file(&todos_file);
             let mut all_todos = Vec::new();
             all_todos.extend(all_entries.todos);
             all_todos.push(new_entry.clone());
-            all_todos.sort_by_key(|e| e.due_date);
-            let pending_todos: Vec<&TodoEntry> = all_todos
-                .iter()
-                .filter(|td| matches!(td.status, TodoStatus::Pending))
-                .collect();
-            let done_todos: Vec<&TodoEntry> = all_todos
+            self.write_todos_file(&todos_file, all_todos)?;
+        }
+
+        Ok(new_entry)
+    }
+
+    /// Saves todos coming from another tool, skipping the ones already in the list
+    /// (same title and due date). Returns how many todos were saved.
+    pub fn import_entries(&self, entries: Vec<ParsedTodosEntry>) -> Result<usize> {
+        let todos_file = todos_file(&self.todo_list_dir);
+        let mut all_todos = if self.storage.exists(&todos_file) {
+            self.parse_file(&todos_file).todos
+        } else {
+            Vec::new()
+        };
+        let mut imported = 0;
+        for entry in entries {
+            let is_duplicate = all_todos
                 .iter()
-                .filter(|td| matches!(td.status, TodoStatus::Done))
-                .collect();
+                .any(|td| td.title == entry.title && td.due_date == entry.due_date);
+            if is_duplicate {
+                continue;
+            }
+            all_todos.push(TodoEntry {
+                due_date: entry.due_date,
+                done_date: entry.done_date,
+                created: entry.created,
+                title: entry.title,
+                body: entry.body,
+                path: todos_file.clone(),
+                status: entry.status,
+                tags: entry.tags,
+            });
+            imported += 1;
+        }
+
+        self.write_todos_file(&todos_file, all_todos)?;
+        Ok(imported)
+    }
+
+    /// The file the todos are kept in.
+    pub fn file_path(&self) -> PathBuf {
+        todos_file(&self.todo_list_dir)
+    }
+
+    /// Buffer to edit `todo` alone: its title and due date on the first line, its body below.
+    pub fn edit_template(&self, todo: &TodoEntry) -> String {
+        let example = NaiveDateTime::new(self.reference_date, self.default_time)
+            .format(&self.todo_datetime_format);
+        let mut out = format!(
+            "<!-- The first line is the title, then ` | ` and the due date ({example}) if any. \
+             The rest is the body. -->\n"
+        );
+        out.push_str(&todo.title);
+        if let Some(due_date) = todo.due_date {
+            out.push_str(&format!(" | {}", due_date.format(&self.todo_datetime_format)));
+        }
+        out.push('\n');
+        if !todo.body.trim().is_empty() {
+            out.push_str(todo.body.trim());
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Reads a buffer made with [`Todos::edit_template`] for `todo`. Its status and done date are
+    /// kept.
+    pub fn parse_edit_buffer(&self, content: &str, todo: &TodoEntry) -> Result<ParsedTodosEntry> {
+        let text = content
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("<!--"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let (first_line, body) = text.trim().split_once('\n').unwrap_or((text.trim(), ""));
+        let (title, due_date) = match first_line.rsplit_once(" | ") {
+            Some((title, due)) => (
+                title.trim(),
+                parse_datetime(due, &self.todo_read_formats).map_err(|e| anyhow!(e))?,
+            ),
+            None => (first_line.trim(), None),
+        };
+        if title.is_empty() {
+            bail!("The todo has no title.");
+        }
+        let body = body.trim().to_string();
+        Ok(ParsedTodosEntry {
+            due_date,
+            done_date: todo.done_date,
+            created: todo.created,
+            title: title.to_string(),
+            tags: self.tag_pattern.extract(&format!("{title}\n{body}")),
+            body,
+            status: todo.status.clone(),
+        })
+    }
+
+    /// Replaces `old` with `new` in the todos file. Fails if `old` is not in it anymore.
+    pub fn update_entry(&self, old: &TodoEntry, new: ParsedTodosEntry) -> Result<TodoEntry> {
+        let todos_file = todos_file(&self.todo_list_dir);
+        let mut all_todos = self.parse_file(&todos_file).todos;
+        let todo = all_todos
+            .iter_mut()
+            .find(|td| {
+                td.title == old.title
+                    && td.body == old.body
+                    && td.due_date == old.due_date
+                    && td.done_date == old.done_date
+                    && td.status == old.status
+            })
+            .with_context(|| {
+                format!("the todo '{}' is not in {} anymore", old.title, todos_file.display())
+            })?;
+        *todo = TodoEntry {
+            due_date: new.due_date,
+            done_date: new.done_date,
+            created: new.created,
+            title: new.title,
+            body: new.body,
+            path: todos_file.clone(),
+            status: new.status,
+            tags: new.tags,
+        };
+        let updated = todo.clone();
+        self.write_todos_file(&todos_file, all_todos)?;
+        Ok(updated)
+    }
+
+    /// Marks `todo` as in progress, or as pending again if it already was. Fails if it's closed.
+    pub fn start_entry(&self, todo: &TodoEntry) -> Result<TodoEntry> {
+        let status = match todo.status {
+            TodoStatus::Pending => TodoStatus::InProgress,
+            TodoStatus::InProgress => TodoStatus::Pending,
+            TodoStatus::Done => bail!("The todo '{}' is already done.", todo.title),
+            TodoStatus::Cancelled => bail!("The todo '{}' is cancelled.", todo.title),
+        };
+        self.set_status(todo, status)
+    }
+
+    /// Marks `todo` as cancelled. Fails if it's closed already.
+    pub fn cancel_entry(&self, todo: &TodoEntry) -> Result<TodoEntry> {
+        match todo.status {
+            TodoStatus::Done => bail!("The todo '{}' is already done.", todo.title),
+            TodoStatus::Cancelled => bail!("The todo '{}' is already cancelled.", todo.title),
+            TodoStatus::Pending | TodoStatus::InProgress => {
+                self.set_status(todo, TodoStatus::Cancelled)
+            }
+        }
+    }
+
+    fn set_status(&self, todo: &TodoEntry, status: TodoStatus) -> Result<TodoEntry> {
+        let updated = ParsedTodosEntry {
+            due_date: todo.due_date,
+            done_date: todo.done_date,
+            created: todo.created,
+            title: todo.title.clone(),
+            body: todo.body.clone(),
+            tags: todo.tags.clone(),
+            status,
+        };
+        self.update_entry(todo, updated)
+    }
+
+    /// Rewrites the todos file: pending and in progress todos first, then done and cancelled ones,
+    /// both sorted by due date.
+    fn write_todos_file(&self, todos_file: &Path, mut all_todos: Vec<TodoEntry>) -> Result<()> {
+        all_todos.sort_by_key(|e| e.due_date);
+        let pending_todos: Vec<&TodoEntry> = all_todos
+            .iter()
+            .filter(|td| TodoStatus::OPEN.contains(&td.status))
+            .collect();
+        let done_todos: Vec<&TodoEntry> = all_todos
+            .iter()
+            .filter(|td| !TodoStatus::OPEN.contains(&td.status))
+            .collect();
+
+        let mut new_content = "# Todos\n\n".to_string();
+        new_content.push_str("## Pending\n\n");
+        for td in pending_todos {
+            let block = format_todo_entry_block(
+                &td.title,
+                &td.body,
+                td.due_date,
+                td.done_date,
+                td.created,
+                &self.todo_datetime_format,
+            );
+            // In progress todos keep their mark.
+            let checkbox = format!("- {}", td.status.checkbox());
+            new_content.push_str(&block.replacen("- [ ]", &checkbox, 1));
+        }
 
-            let mut new_content = header;
-            new_content.push_str(&todo_subheader);
-            for td in pending_todos {
+        if !done_todos.is_empty() {
+            new_content.push_str("## Done\n\n");
+            for td in done_todos {
                 let block = format_todo_entry_block(
                     &td.title,
                     &td.body,
                     td.due_date,
                     td.done_date,
+                    td.created,
                     &self.todo_datetime_format,
                 );
-                new_content.push_str(&block);
-            }
-
-            if !done_todos.is_empty() {
-                new_content.push_str(&done_subheader);
-                for td in done_todos {
-                    let block = format_todo_entry_block(
-                        &td.title,
-                        &td.body,
-                        td.due_date,
-                        td.done_date,
-                        &self.todo_datetime_format,
-                    );
-                    new_content.push_str(&block);
-                }
+                // Done todos keep their checkbox ticked, and cancelled ones their mark.
+                let checkbox = format!("- {}", td.status.checkbox());
+                new_content.push_str(&block.replacen("- [ ]", &checkbox, 1));
             }
-            fs::write(&todos_file, new_content)?;
         }
-
-        Ok(new_entry)
+        self.storage
+            .write_file(todos_file, &new_content)
+            .with_context(|| format!("writing {}", todos_file.display()))?;
+        Ok(())
     }
 
     /// Reads and returns all entries, the results can be filtered by `options`.
@@ -120,130 +299,248 @@ impl Todos {
     /// resilient, returning a [`TodoQueryResult `] that contains both parsed entries and
     /// any errors that occurred.
     pub fn read_entries(&self, options: &ReadTodoOptions) -> TodoQueryResult {
-        let mut entries = Vec::new();
         let mut errors = Vec::new();
         let pending_file = todos_file(&self.todo_list_dir);
-        if pending_file.exists() {}
-
         let results = self.parse_file(&pending_file);
 
-        entries.extend(results.todos);
+        // Each todo with its number, so it's kept while filtering.
+        let mut entries: Vec<(usize, TodoEntry)> =
+            results.indexes.into_iter().zip(results.todos).collect();
         errors.extend(results.errors);
 
-        entries.sort_by_key(|k| k.due_date);
+        entries.sort_by_key(|(_, k)| k.due_date);
 
         if let Some(dates) = options.due_date {
             match dates {
                 DateFilter::Single(s_date) => {
-                    entries = entries
-                        .into_iter()
-                        .filter(|e| e.due_date.map(|d| d.date() == s_date).unwrap_or(false))
-                        .collect();
+                    entries.retain(|(_, e)| {
+                        e.due_date.map(|d| d.date() == s_date).unwrap_or(false)
+                    });
                 }
                 DateFilter::Range(s_date, e_date) => {
-                    entries = entries
-                        .into_iter()
-                        .filter(|e| {
-                            e.due_date
-                                .map(|d| d.date() >= s_date && d.date() <= e_date)
-                                .unwrap_or(false)
-                        })
-                        .collect();
+                    entries.retain(|(_, e)| {
+                        e.due_date
+                            .map(|d| d.date() >= s_date && d.date() <= e_date)
+                            .unwrap_or(false)
+                    });
                 }
             }
         }
 
+        if let Some(dates) = options.done_date {
+            let (start, end) = match dates {
+                DateFilter::Single(date) => (date, date),
+                DateFilter::Range(start, end) => (start, end),
+            };
+            entries.retain(|(_, e)| {
+                e.done_date
+                    .is_some_and(|d| d.date() >= start && d.date() <= end)
+            });
+        }
+
+        if !self.tag_aliases.is_empty() || !self.ignored_tags.is_empty() {
+            for (_, entry) in &mut entries {
+                entry.tags = tags::read_tags(&entry.tags, &self.tag_aliases, &self.ignored_tags);
+            }
+        }
         if let Some(tags) = &options.tags {
             let found_tags: Vec<String> = tags
-                .into_iter()
-                .map(|t| t.trim().to_ascii_lowercase())
+                .iter()
+                .map(|t| self.tag_aliases.canonical(t))
                 .collect();
 
-            entries = entries
-                .into_iter()
-                .filter(|e| found_tags.iter().any(|t| e.tags.contains(t)))
-                .collect();
+            entries.retain(|(_, e)| found_tags.iter().any(|t| e.tags.contains(t)));
+        }
+
+        if let Some(title) = options.title {
+            entries.retain(|(_, e)| title_matches(&e.title, title));
         }
 
+        if let Some(status) = options.status {
+            entries.retain(|(_, e)| status.contains(&e.status));
+        }
+
+        let (indexes, todos) = entries.into_iter().unzip();
         TodoQueryResult {
-            todos: entries,
+            todos,
+            indexes,
             errors,
         }
     }
 
-    pub fn search_all_tags(&self) -> QueryTagsResult {
-        let mut tags: Vec<String> = Vec::new();
-        let mut errors = Vec::new();
-        let pending_file = todos_file(&self.todo_list_dir);
-        let done_file = todos_file(&self.todo_list_dir);
-        let pending_todos_result = self.parse_file(&pending_file);
-        let done_todos_result = self.parse_file(&done_file);
-
-        errors.extend(pending_todos_result.errors);
-        errors.extend(done_todos_result.errors);
+    /// `todo` with its title and body sealed with `passphrase`, see [`crate::private`]. The file
+    /// only keeps its dates and status.
+    pub fn sealed_entry(&self, todo: TodoWriteEntry, passphrase: &str) -> Result<TodoWriteEntry> {
+        Ok(TodoWriteEntry {
+            title: PRIVATE_TITLE.to_string(),
+            body: seal_text(&todo.title, &todo.body, passphrase)?,
+            tags: Vec::new(),
+            ..todo
+        })
+    }
 
-        for todo in pending_todos_result.todos {
-            tags.extend(todo.tags);
+    /// Opens the private todos of `todos` with `passphrase`. Without one, or when it's wrong, they
+    /// keep their `[private]` title and lose the sealed body.
+    ///
+    /// Only for showing them: an opened todo isn't in the todos file anymore, so it can't be
+    /// updated.
+    pub fn open_private_entries(
+        &self,
+        todos: &mut [TodoEntry],
+        passphrase: Option<&str>,
+        errors: &mut Vec<QueryError>,
+    ) {
+        for todo in todos.iter_mut().filter(|todo| is_sealed(&todo.body)) {
+            match passphrase.map(|passphrase| open_sealed(&todo.body, passphrase)) {
+                Some(Ok((title, body))) => {
+                    todo.tags = self.tag_pattern.extract(&format!("{title}\n{body}"));
+                    todo.title = title;
+                    todo.body = body;
+                }
+                Some(Err(error)) => {
+                    todo.body.clear();
+                    errors.push(QueryError::FileError {
+                        path: todo.path.clone(),
+                        error: anyhow!("{error} for a private todo"),
+                    });
+                }
+                None => todo.body.clear(),
+            }
         }
-        for todo in done_todos_result.todos {
-            tags.extend(todo.tags);
+    }
+
+    /// Open todos that are overdue or due in the next `days` days, sorted by due date.
+    pub fn read_agenda(&self, days: u64) -> TodoQueryResult {
+        let until = self
+            .reference_date
+            .checked_add_days(Days::new(days))
+            .unwrap_or(NaiveDate::MAX);
+        let options = ReadTodoOptions {
+            due_date: Some(DateFilter::Range(NaiveDate::MIN, until)),
+            status: Some(&TodoStatus::OPEN),
+            ..Default::default()
+        };
+        self.read_entries(&options)
+    }
+
+    /// Open todos without a due date added more than `days` days ago, oldest first. Todos added
+    /// before lgg recorded when are left out.
+    pub fn read_stale(&self, days: u64) -> TodoQueryResult {
+        let options = ReadTodoOptions {
+            status: Some(&TodoStatus::OPEN),
+            ..Default::default()
+        };
+        let result = self.read_entries(&options);
+        let cutoff = self
+            .reference_date
+            .checked_sub_days(Days::new(days))
+            .unwrap_or(NaiveDate::MIN);
+        let mut stale: Vec<(usize, TodoEntry)> = result
+            .indexes
+            .into_iter()
+            .zip(result.todos)
+            .filter(|(_, todo)| todo.due_date.is_none())
+            .filter(|(_, todo)| todo.created.is_some_and(|created| created.date() < cutoff))
+            .collect();
+        stale.sort_by_key(|(_, todo)| todo.created);
+        let (indexes, todos) = stale.into_iter().unzip();
+        TodoQueryResult {
+            todos,
+            indexes,
+            errors: result.errors,
         }
+    }
 
-        tags = tags
-            .iter()
-            .map(|mat| mat.as_str().to_string().trim().to_ascii_lowercase())
-            .collect::<HashSet<_>>()
+    /// Open todos with a due date and a tag of `reminders`, with when to remind of them: their
+    /// due date less the longest time of their tags. Sorted by that time.
+    pub fn read_reminders(
+        &self,
+        reminders: &HashMap<String, TimeDelta>,
+    ) -> Vec<(NaiveDateTime, TodoEntry)> {
+        if reminders.is_empty() {
+            return Vec::new();
+        }
+        let options = ReadTodoOptions {
+            status: Some(&TodoStatus::OPEN),
+            ..Default::default()
+        };
+        let mut due: Vec<(NaiveDateTime, TodoEntry)> = self
+            .read_entries(&options)
+            .todos
             .into_iter()
+            .filter_map(|todo| {
+                let due_date = todo.due_date?;
+                let before = todo.tags.iter().filter_map(|tag| reminders.get(tag)).max()?;
+                // A due date too close to the earliest date lgg knows has no reminder.
+                Some((due_date.checked_sub_signed(*before)?, todo))
+            })
             .collect();
-        tags.sort();
+        due.sort_by_key(|(at, _)| *at);
+        due
+    }
 
-        QueryTagsResult { tags, errors }
+    /// The tags of at least `min_count` todos, sorted.
+    pub fn search_all_tags(&self, min_count: usize) -> QueryTagsResult {
+        let result = self.parse_file(&todos_file(&self.todo_list_dir));
+        let tags_of_todos = result
+            .todos
+            .iter()
+            .map(|todo| tags::read_tags(&todo.tags, &self.tag_aliases, &self.ignored_tags));
+        QueryTagsResult {
+            tags: tags::tags_with_min_count(tags_of_todos, min_count),
+            errors: result.errors,
+        }
     }
 
-    pub fn parse_file(&self, path: &PathBuf) -> TodoQueryResult {
+    pub fn parse_file(&self, path: &Path) -> TodoQueryResult {
         let mut entries = Vec::new();
         let mut errors = Vec::new();
-        if !path.exists() {
+        if !self.storage.exists(path) {
             errors.push(QueryError::FileError {
-                path: path.clone(),
+                path: path.to_path_buf(),
                 error: anyhow!(format!("File does not exist in path: {}", path.display())),
             });
             return TodoQueryResult {
                 todos: entries,
+                indexes: Vec::new(),
                 errors,
             };
         }
-        match fs::read_to_string(&path) {
+        log::debug!("parsing {}", path.display());
+        match self.storage.read_file(path) {
             Ok(file_content) => {
+                let formats = &self.todo_read_formats;
                 let parse_result =
-                    parse_todo_file_content(&file_content, &self.todo_datetime_format);
+                    parse_todo_file_content(&file_content, formats, &self.tag_pattern);
                 for entry in parse_result.entries {
                     entries.push(TodoEntry {
                         due_date: entry.due_date,
                         done_date: entry.done_date,
+                        created: entry.created,
                         title: entry.title,
                         body: entry.body,
                         tags: entry.tags,
                         status: entry.status,
-                        path: path.clone(),
+                        path: path.to_path_buf(),
                     });
                 }
 
                 for error in parse_result.errors {
                     errors.push(QueryError::FileError {
-                        path: path.clone(),
+                        path: path.to_path_buf(),
                         error: anyhow!(error),
                     });
                 }
             }
             Err(error) => {
                 errors.push(QueryError::FileError {
-                    path: path.clone(),
-                    error: error.into(),
+                    path: path.to_path_buf(),
+                    error,
                 });
             }
         }
         TodoQueryResult {
+            indexes: (1..=entries.len()).collect(),
             todos: entries,
             errors,
         }
@@ -252,16 +549,19 @@ impl Todos {
 
 #[cfg(test)]
 mod tests {
-    use chrono::{NaiveDate, NaiveTime};
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+    use std::collections::HashMap;
     use std::fs;
+    use std::sync::Arc;
     use tempfile::tempdir;
 
     use super::Todos;
     use crate::utils::date_utils::DateFilter;
     use crate::{
         tests::mk_config,
+        FsStorage,
         todos::{
-            todo_entry::{ReadTodoOptions, TodoStatus, TodoWriteEntry},
+            todo_entry::{ParsedTodosEntry, ReadTodoOptions, TodoStatus, TodoWriteEntry},
             todos_paths::todos_file,
         },
     };
@@ -274,12 +574,54 @@ mod tests {
         let todos = Todos {
             todo_list_dir: config.todo_list_dir,
             todo_datetime_format: config.todo_datetime_format,
+            todo_read_formats: config.todo_read_formats,
             reference_date: config.reference_date,
             default_time: config.default_time,
+            tag_pattern: config.tag_pattern,
+            tag_aliases: Default::default(),
+            ignored_tags: Vec::new(),
+            storage: Arc::new(FsStorage),
         };
         (todos, tmp)
     }
 
+    #[test]
+    fn private_todos_are_sealed_and_opened_with_their_passphrase() {
+        let (t, _tmp) = mk_todo_list_with_default(None);
+        let todo = TodoWriteEntry {
+            due_date: NaiveDate::from_ymd_opt(2025, 8, 20),
+            time: None,
+            title: "Call the lawyer @legal".to_string(),
+            body: "About the will.".to_string(),
+            tags: Vec::new(),
+        };
+        t.create_entry(t.sealed_entry(todo, "pw").unwrap()).unwrap();
+        let content = fs::read_to_string(todos_file(&t.todo_list_dir)).unwrap();
+        assert!(content.contains("- [ ] [private] | "));
+        assert!(!content.contains("lawyer"));
+
+        // The sealed todo can still be updated, it's opened only to be shown.
+        let todos = t.read_entries(&ReadTodoOptions::default()).todos;
+        let started = t.start_entry(&todos[0]).unwrap();
+        assert_eq!(started.status, TodoStatus::InProgress);
+
+        let mut todos = t.read_entries(&ReadTodoOptions::default()).todos;
+        let mut errors = Vec::new();
+        t.open_private_entries(&mut todos, Some("pw"), &mut errors);
+        assert!(errors.is_empty());
+        assert_eq!(todos[0].title, "Call the lawyer @legal");
+        assert_eq!(todos[0].body, "About the will.");
+        assert_eq!(todos[0].tags, vec!["@legal"]);
+        assert_eq!(todos[0].status, TodoStatus::InProgress);
+
+        let mut todos = t.read_entries(&ReadTodoOptions::default()).todos;
+        t.open_private_entries(&mut todos, Some("wrong"), &mut errors);
+        assert_eq!(errors.len(), 1);
+        t.open_private_entries(&mut todos, None, &mut errors);
+        assert_eq!(todos[0].title, "[private]");
+        assert!(todos[0].body.is_empty());
+    }
+
     #[test]
     fn write_first_todo_creates_file_and_appends() {
         let (t, _tmp) = mk_todo_list_with_default(None);
@@ -330,7 +672,8 @@ mod tests {
 
     #[test]
     fn write_todo_returns_valid_entry() {
-        let (t, _tmp) = mk_todo_list_with_default(None);
+        let today = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let (t, _tmp) = mk_todo_list_with_default(Some(today));
         let entry = TodoWriteEntry {
             due_date: None,
             time: None,
@@ -342,6 +685,117 @@ mod tests {
         assert_eq!(res.title, "Test entry.");
         assert_eq!(res.body, "With body.");
         assert!(matches!(res.status, TodoStatus::Pending));
+        assert_eq!(res.created.map(|c| c.date()), Some(today));
+
+        let content = fs::read_to_string(todos_file(&t.todo_list_dir)).unwrap();
+        assert!(content.contains("- [ ] Test entry. (added: 15/Aug/2025 "));
+        let read = t.read_entries(&ReadTodoOptions::default());
+        assert!(read.errors.is_empty());
+        assert_eq!(read.todos[0].title, "Test entry.");
+        assert_eq!(read.todos[0].created, res.created);
+    }
+
+    #[test]
+    fn edit_one_todo_through_a_buffer() {
+        let (t, _tmp) = mk_todo_list_with_default(NaiveDate::from_ymd_opt(2025, 8, 15));
+        for title in ["Call mum", "Buy milk"] {
+            t.create_entry(TodoWriteEntry {
+                due_date: Some(NaiveDate::from_ymd_opt(2025, 8, 20).unwrap()),
+                time: Some(NaiveTime::from_hms_opt(17, 0, 0).unwrap()),
+                title: title.to_string(),
+                body: String::new(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        }
+        let todos = t.read_entries(&ReadTodoOptions::default()).todos;
+        let milk = todos.iter().find(|td| td.title == "Buy milk").unwrap();
+
+        let template = t.edit_template(milk);
+        assert!(template.starts_with("<!-- The first line is the title"));
+        assert!(template.ends_with("\nBuy milk | 20/Aug/2025 17:00\n"));
+
+        let buffer = template.replace("Buy milk | 20/Aug/2025 17:00", "Buy oat milk @shop\nTwo.");
+        let edited = t.parse_edit_buffer(&buffer, milk).unwrap();
+        assert_eq!(edited.title, "Buy oat milk @shop");
+        assert_eq!(edited.body, "Two.");
+        assert!(edited.due_date.is_none());
+        assert_eq!(edited.tags, vec!["@shop"]);
+        t.update_entry(milk, edited).unwrap();
+
+        let todos = t.read_entries(&ReadTodoOptions::default()).todos;
+        assert_eq!(todos.len(), 2);
+        assert!(todos.iter().any(|td| td.title == "Call mum"));
+        assert!(todos.iter().any(|td| td.title == "Buy oat milk @shop" && td.body == "Two."));
+        assert!(t.update_entry(milk, t.parse_edit_buffer(&template, milk).unwrap()).is_err());
+
+        assert!(t.parse_edit_buffer("Buy milk | next week", milk).is_err());
+        assert!(t.parse_edit_buffer("<!-- help -->\n", milk).is_err());
+    }
+
+    #[test]
+    fn todo_numbers_survive_filters() {
+        let (t, _tmp) = mk_todo_list_with_default(None);
+        for (day, title) in [(20, "Later @work"), (15, "Sooner"), (18, "Middle @work")] {
+            t.create_entry(TodoWriteEntry {
+                due_date: NaiveDate::from_ymd_opt(2025, 8, day),
+                time: None,
+                title: title.to_string(),
+                body: String::new(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        }
+
+        let all = t.read_entries(&ReadTodoOptions::default());
+        assert_eq!(all.indexes, vec![1, 2, 3]);
+        assert_eq!(all.get(2).unwrap().title, "Middle @work");
+
+        let tags = vec!["@work".to_string()];
+        let options = ReadTodoOptions {
+            tags: Some(&tags),
+            ..Default::default()
+        };
+        let work = t.read_entries(&options);
+        let numbered: Vec<(usize, &str)> =
+            work.numbered().map(|(i, td)| (i, td.title.as_str())).collect();
+        assert_eq!(numbered, vec![(2, "Middle @work"), (3, "Later @work")]);
+        assert!(work.get(1).is_none());
+    }
+
+    #[test]
+    fn start_and_cancel_change_the_status_of_a_todo() {
+        let (t, _tmp) = mk_todo_list_with_default(None);
+        let path = todos_file(&t.todo_list_dir);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let content = "# Todos\n\n## Pending\n\n- [ ] Call mum\n- [~] Report\n\n## Done\n\n- [x] Invoice\n";
+        fs::write(&path, content).unwrap();
+
+        let all = t.read_entries(&ReadTodoOptions::default());
+        assert!(all.errors.is_empty());
+        assert_eq!(all.get(2).unwrap().status, TodoStatus::InProgress);
+        assert!(all.get(2).unwrap().body.is_empty());
+
+        let started = t.start_entry(all.get(1).unwrap()).unwrap();
+        assert_eq!(started.status, TodoStatus::InProgress);
+        assert!(t.start_entry(all.get(3).unwrap()).is_err());
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("- [/] Call mum\n- [/] Report\n## Done\n\n- [x] Invoice\n"));
+
+        let options = ReadTodoOptions {
+            status: Some(&[TodoStatus::InProgress]),
+            ..Default::default()
+        };
+        let in_progress = t.read_entries(&options);
+        assert_eq!(in_progress.todos.len(), 2);
+        let stopped = t.start_entry(in_progress.get(2).unwrap()).unwrap();
+        assert_eq!(stopped.status, TodoStatus::Pending);
+
+        let cancelled = t.cancel_entry(&stopped).unwrap();
+        assert!(t.cancel_entry(&cancelled).is_err());
+        assert!(t.start_entry(&cancelled).is_err());
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.ends_with("## Done\n\n- [-] Report\n- [x] Invoice\n"));
     }
 
     #[test]
@@ -514,7 +968,7 @@ mod tests {
             })
             .unwrap();
 
-        let results = todos.search_all_tags();
+        let results = todos.search_all_tags(1);
         assert_eq!(results.errors.len(), 0);
         assert_eq!(results.tags.len(), 3);
 
@@ -522,4 +976,157 @@ mod tests {
         assert!(results.tags.contains(&"@double_tag".to_string()));
         assert!(results.tags.contains(&"@future".to_string()));
     }
+
+    #[test]
+    fn import_entries_keeps_status_and_skips_existing_ones() {
+        let (todos, _tmp) = mk_todo_list_with_default(None);
+        todos
+            .create_entry(TodoWriteEntry {
+                due_date: None,
+                time: None,
+                title: "Already here".to_string(),
+                body: String::new(),
+                tags: Vec::new(),
+            })
+            .unwrap();
+        let done_date = NaiveDate::from_ymd_opt(2025, 8, 22)
+            .unwrap()
+            .and_hms_opt(18, 0, 0);
+        let imported = todos
+            .import_entries(vec![
+                ParsedTodosEntry {
+                    due_date: None,
+                    done_date: None,
+                    created: None,
+                    title: "Already here".to_string(),
+                    body: String::new(),
+                    tags: Vec::new(),
+                    status: TodoStatus::Pending,
+                },
+                ParsedTodosEntry {
+                    due_date: None,
+                    done_date,
+                    created: None,
+                    title: "Send invoices".to_string(),
+                    body: String::new(),
+                    tags: Vec::new(),
+                    status: TodoStatus::Done,
+                },
+            ])
+            .unwrap();
+        assert_eq!(imported, 1);
+
+        let content = fs::read_to_string(todos_file(&todos.todo_list_dir)).unwrap();
+        assert!(content.contains("## Done\n\n- [x] Send invoices | | 22/Aug/2025 18:00\n"));
+
+        let result = todos.read_entries(&ReadTodoOptions::default());
+        assert!(result.errors.is_empty());
+        assert_eq!(result.todos.len(), 2);
+
+        let week = DateFilter::Range(
+            NaiveDate::from_ymd_opt(2025, 8, 18).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 8, 24).unwrap(),
+        );
+        let options = ReadTodoOptions {
+            done_date: Some(week),
+            ..Default::default()
+        };
+        let done = todos.read_entries(&options);
+        assert_eq!(done.todos.len(), 1);
+        assert_eq!(done.todos[0].title, "Send invoices");
+    }
+
+    #[test]
+    fn read_agenda_returns_overdue_and_upcoming_pending_todos() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let (todos, _tmp) = mk_todo_list_with_default(Some(anchor));
+        let todo = |day: Option<u32>, title: &str| TodoWriteEntry {
+            due_date: day.map(|d| NaiveDate::from_ymd_opt(2025, 8, d).unwrap()),
+            time: None,
+            title: title.to_string(),
+            body: String::new(),
+            tags: Vec::new(),
+        };
+        for entry in [
+            todo(Some(10), "Overdue"),
+            todo(Some(15), "Today"),
+            todo(Some(22), "Next week"),
+            todo(Some(23), "Too far"),
+            todo(None, "No date"),
+        ] {
+            todos.create_entry(entry).unwrap();
+        }
+
+        let result = todos.read_agenda(7);
+        let titles: Vec<&str> = result.todos.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Overdue", "Today", "Next week"]);
+        assert_eq!(todos.read_agenda(u64::MAX).todos.len(), 4);
+    }
+
+    #[test]
+    fn read_stale_returns_old_todos_without_due_date() {
+        let (t, _tmp) = mk_todo_list_with_default(NaiveDate::from_ymd_opt(2025, 8, 15));
+        let path = todos_file(&t.todo_list_dir);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let content = "# Todos
+
+## Pending
+
+- [ ] Fresh (added: 10/Aug/2025 10:00)
+- [/] Old (added: 01/Jul/2025 10:00)
+- [ ] Older (added: 01/Jun/2025 10:00)
+- [ ] Due | 20/Aug/2025 10:00 (added: 01/Jun/2025 10:00)
+- [ ] Unknown
+## Done
+
+- [x] Closed (added: 01/Jun/2025 10:00)
+";
+        fs::write(&path, content).unwrap();
+
+        let stale = t.read_stale(30);
+        assert!(stale.errors.is_empty());
+        let numbered: Vec<(usize, &str)> =
+            stale.numbered().map(|(i, td)| (i, td.title.as_str())).collect();
+        assert_eq!(numbered, vec![(3, "Older"), (2, "Old")]);
+        assert!(t.read_stale(60).todos.iter().all(|td| td.title == "Older"));
+        assert!(t.read_stale(u64::MAX).todos.is_empty());
+    }
+
+    #[test]
+    fn read_reminders_returns_due_todos_with_reminded_tags() {
+        let (t, _tmp) = mk_todo_list_with_default(NaiveDate::from_ymd_opt(2025, 8, 15));
+        let path = todos_file(&t.todo_list_dir);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let content = "# Todos
+
+## Pending
+
+- [ ] Pay rent @bills | 01/Sep/2025 09:00
+- [ ] Call the bank @bills @calls | 20/Aug/2025 10:00
+- [ ] Water plants @home | 16/Aug/2025 10:00
+- [ ] Gas @bills
+## Done
+
+- [x] Pay water @bills | 10/Aug/2025 09:00 | 09/Aug/2025 09:00
+";
+        fs::write(&path, content).unwrap();
+        let reminders = HashMap::from([
+            ("@bills".to_string(), TimeDelta::days(3)),
+            ("@calls".to_string(), TimeDelta::hours(1)),
+        ]);
+
+        let at = |day, hour| {
+            NaiveDate::from_ymd_opt(2025, 8, day).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+        };
+        let reminders = t.read_reminders(&reminders);
+        let reminded: Vec<(NaiveDateTime, &str)> = reminders
+            .iter()
+            .map(|(at, td)| (*at, td.title.as_str()))
+            .collect();
+        assert_eq!(
+            reminded,
+            vec![(at(17, 10), "Call the bank @bills @calls"), (at(29, 9), "Pay rent @bills")]
+        );
+        assert!(t.read_reminders(&HashMap::new()).is_empty());
+    }
 }
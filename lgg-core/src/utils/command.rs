@@ -0,0 +1,52 @@
+//! Running other programs, like git and rsync.
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+/// Runs `program` with `args` (in `dir`) and returns what it printed.
+pub fn run(program: &str, dir: Option<&Path>, args: &[&str]) -> Result<String> {
+    let mut command = Command::new(program);
+    if let Some(dir) = dir {
+        command.current_dir(dir);
+    }
+    let output = command
+        .args(args)
+        .output()
+        .with_context(|| format!("could not run `{program}`, is it installed?"))?;
+    command_stdout(program, args, output)
+}
+
+/// Runs `program` with `args`, writing `input` to its standard input, and returns what it
+/// printed.
+pub fn run_with_input(program: &str, args: &[&str], input: &str) -> Result<String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("could not run `{program}`, is it installed?"))?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    // Written from another thread, so a program that prints before reading it all can't block.
+    let output = std::thread::scope(|scope| {
+        let writer = scope.spawn(move || stdin.write_all(input.as_bytes()));
+        let output = child.wait_with_output();
+        // A program that doesn't read its input is fine.
+        let _ = writer.join();
+        output
+    })
+    .with_context(|| format!("running `{program}`"))?;
+    command_stdout(program, args, output)
+}
+
+/// What a finished command printed, or an error with what it printed to stderr if it failed.
+fn command_stdout(program: &str, args: &[&str], output: Output) -> Result<String> {
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let message = if stderr.trim().is_empty() { stdout } else { stderr };
+        bail!("`{program} {}` failed: {}", args.join(" "), message.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -1,4 +1,12 @@
-use chrono::{NaiveDate, NaiveTime, Timelike};
+use chrono::{Locale, NaiveDate, NaiveTime, Timelike};
+
+/// `date` in `format`, with the weekday and month names of `locale` (English when `None`).
+pub fn format_date(date: NaiveDate, format: &str, locale: Option<Locale>) -> String {
+    match locale {
+        Some(locale) => date.format_localized(format, locale).to_string(),
+        None => date.format(format).to_string(),
+    }
+}
 
 /// Check whether `time` satisfies the time filter.
 /// - `Single(s)`: matches any time WITHIN the hour.
@@ -23,6 +31,15 @@ pub enum DateFilter {
     Range(NaiveDate, NaiveDate),
 }
 
+/// A date typed by the user that the input date formats read as different days (`03/04/2025`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct AmbiguousDate {
+    /// The date as typed.
+    pub token: String,
+    /// Every possible day, the one used first.
+    pub readings: Vec<NaiveDate>,
+}
+
 /// The result of parsing a time string, which can be a single time or a range.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum TimeFilter {
@@ -0,0 +1,101 @@
+//! Line diffs of files, to show what a write changes (`lgg --dry-run`).
+
+#[derive(Clone, Copy, PartialEq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// The hunks of the unified diff from `before` to `after` (`@@ -3,2 +3,4 @@` and their lines),
+/// with `context` unchanged lines around each change. Empty if they have the same lines.
+pub fn unified_diff(before: &str, after: &str, context: usize) -> String {
+    let ops = diff_lines(before, after);
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (i, _) in ops.iter().enumerate().filter(|(_, (op, _))| *op != Op::Equal) {
+        let start = i.saturating_sub(context);
+        let end = (i + context + 1).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut out = String::new();
+    for (start, end) in hunks {
+        let old_before = ops[..start].iter().filter(|(op, _)| *op != Op::Insert).count();
+        let new_before = ops[..start].iter().filter(|(op, _)| *op != Op::Delete).count();
+        let hunk = &ops[start..end];
+        let old_len = hunk.iter().filter(|(op, _)| *op != Op::Insert).count();
+        let new_len = hunk.iter().filter(|(op, _)| *op != Op::Delete).count();
+        // An empty side starts at the line before the hunk.
+        let old_start = old_before + usize::from(old_len > 0);
+        let new_start = new_before + usize::from(new_len > 0);
+        out.push_str(&format!("@@ -{old_start},{old_len} +{new_start},{new_len} @@\n"));
+        for (op, line) in hunk {
+            let sign = match op {
+                Op::Equal => ' ',
+                Op::Delete => '-',
+                Op::Insert => '+',
+            };
+            out.push(sign);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// The lines of `before` and `after` in order, kept, deleted or inserted, with as many kept as
+/// possible (longest common subsequence).
+fn diff_lines<'a>(before: &'a str, after: &'a str) -> Vec<(Op, &'a str)> {
+    let a: Vec<&str> = before.lines().collect();
+    let b: Vec<&str> = after.lines().collect();
+    // common[i][j]: lines in common between a[i..] and b[j..].
+    let mut common = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            common[i][j] = if a[i] == b[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            ops.push((Op::Equal, a[i]));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            ops.push((Op::Delete, a[i]));
+            i += 1;
+        } else {
+            ops.push((Op::Insert, b[j]));
+            j += 1;
+        }
+    }
+    ops.extend(a[i..].iter().map(|line| (Op::Delete, *line)));
+    ops.extend(b[j..].iter().map(|line| (Op::Insert, *line)));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs_changed_lines_with_context() {
+        let before = "# Day\n\n## 08:00 - Coffee\n\n## 20:00 - Dinner\n";
+        let after = "# Day\n\n## 08:00 - Coffee\n\n## 12:00 - Lunch\n\n## 20:00 - Dinner\n";
+        assert_eq!(
+            unified_diff(before, after, 1),
+            "@@ -4,2 +4,4 @@\n \n+## 12:00 - Lunch\n+\n ## 20:00 - Dinner\n"
+        );
+        assert_eq!(unified_diff("", "# Day\n", 3), "@@ -0,0 +1,1 @@\n+# Day\n");
+        assert!(unified_diff(before, before, 3).is_empty());
+    }
+}
@@ -1,4 +1,7 @@
+pub mod command;
 pub mod date_utils;
+pub mod diff;
 pub mod parse_input;
 pub mod parsed_input;
 pub mod path_utils;
+pub mod text_utils;
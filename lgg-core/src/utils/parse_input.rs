@@ -1,12 +1,12 @@
-use super::parsed_input::{ParseInputOptions, ParsedInput};
+use super::parsed_input::{DateRule, ParseInputOptions, ParseTrace, ParsedInput, TitleEnd};
+use crate::journal::web_links::lone_url;
 use crate::keywords::{Keyword, Keywords};
-use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use crate::tags::TagPattern;
+use unicode_segmentation::UnicodeSegmentation;
+use chrono::{Datelike, Duration, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
 use regex::Regex;
-use std::collections::HashSet;
-use crate::utils::date_utils::{DateFilter, TimeFilter};
-
-/// Default accepted input date formats (parsing only).
-const DEFAULT_FORMATS: &[&str] = &["%d/%m/%Y"];
+use crate::utils::date_utils::{AmbiguousDate, DateFilter, TimeFilter};
+use crate::DateOrder;
 
 /// The main entry point for parsing an inline journal entry from a single string.
 ///
@@ -25,29 +25,178 @@ const DEFAULT_FORMATS: &[&str] = &["%d/%m/%Y"];
 /// A [`ParsedInline`] struct containing the resolved date, optional time, title, and body.
 /// If no date prefix is found, the date defaults to the reference date.
 pub fn parse_raw_user_input(input: &str, options: Option<ParseInputOptions>) -> ParsedInput {
-    let (date_opt, time_opt, rest) = parse_prefix(input, options);
-    let (title_raw, body) = split_title_body(rest.trim());
+    let mut trace = ParseTrace::default();
+    let (date_opt, time_opt, rest) = with_keywords(options, |options, keywords| {
+        parse_prefix(input, options, keywords, &mut trace)
+    });
+    let default_tags = TagPattern::default();
+    let tags = options.and_then(|options| options.tags).unwrap_or(&default_tags);
+    let (title_raw, body, title_end) = split_title_body_at(rest.trim(), tags);
+    trace.title_end = title_end;
+    trace.tags = tags.extract(&format!("{title_raw}\n{body}"));
     let title = normalize_title(&title_raw);
+    // An explicit `start..end` prefix is an event of several days.
+    let is_span = input[..input.len() - rest.len()].contains("..");
 
-    let date = match date_opt {
-        Some(DateFilter::Single(d)) => Some(d),
+    let (date, until) = match date_opt {
+        Some(DateFilter::Single(d)) => (Some(d), None),
+        Some(DateFilter::Range(start, end)) if is_span => (Some(start), Some(end)),
         // Only the start of the range. For 'this week' it'll be Monday.
-        Some(DateFilter::Range(start, _)) => Some(start),
-        None => None,
+        Some(DateFilter::Range(start, _)) => (Some(start), None),
+        None => (None, None),
     };
 
-    let time = match time_opt {
-        Some(TimeFilter::Single(d)) => Some(d),
-        // We need the 'from' part, where the hour starts.
-        // That way 'morning' becomes 06:00
-        Some(TimeFilter::Range(from, _to)) => Some(from),
-        None => None,
-    };
     ParsedInput {
         date,
-        time,
+        until,
+        time: start_time(time_opt),
         title,
         body,
+        trace,
+    }
+}
+
+/// Runs `parse` with the keywords of `options`, or with the global registry if it has none.
+fn with_keywords<R>(
+    options: Option<ParseInputOptions>,
+    parse: impl FnOnce(ParseInputOptions, &Keywords) -> R,
+) -> R {
+    let options = options.unwrap_or_default();
+    match options.keywords {
+        Some(keywords) => parse(options, keywords),
+        None => {
+            let global = Keywords::global().read().unwrap();
+            let options = ParseInputOptions {
+                keywords: Some(&global),
+                ..options
+            };
+            parse(options, &global)
+        }
+    }
+}
+
+/// Most words read as the due date and time at the end of a todo (`on 20/08/2025 at 9:30 pm`).
+const MAX_DUE_WORDS: usize = 6;
+
+/// Parses a todo written as free text, like `call mum friday at 5pm @family`.
+///
+/// The due date and time are a `date: text` prefix, as in [`parse_raw_user_input`], or else the
+/// last words before the trailing tags (`friday at 5pm`, `on 20/08/2025`, `by tomorrow`), which
+/// are left out of the title. A weekday is the next one, not the last. The first line is the
+/// title and the rest the body: the title isn't cut at the end of its first sentence.
+pub fn parse_todo_input(input: &str, options: Option<ParseInputOptions>) -> ParsedInput {
+    with_keywords(options, |options, keywords| {
+        parse_todo_with(input, Some(options), keywords)
+    })
+}
+
+fn parse_todo_with(
+    input: &str,
+    options: Option<ParseInputOptions>,
+    keywords: &Keywords,
+) -> ParsedInput {
+    let prefixed = input.split_once(": ").and_then(|(prefix, rest)| {
+        let words: Vec<&str> = prefix.split_whitespace().collect();
+        parse_due_words(&words, options, keywords).map(|(date, time)| (date, time, rest))
+    });
+    let (date, time, text) = prefixed.unwrap_or((None, None, input));
+    let (first_line, body) = match text.trim().split_once('\n') {
+        Some((first_line, body)) => (first_line, body.trim().to_string()),
+        None => (text.trim(), String::new()),
+    };
+    let (date, time, title) = match (date, time) {
+        (None, None) => find_due_words(first_line, options, keywords)
+            .unwrap_or((None, None, first_line.to_string())),
+        (date, time) => (date, time, first_line.to_string()),
+    };
+
+    ParsedInput {
+        date: date.map(|date| date_bounds(date).0),
+        until: None,
+        time: start_time(time),
+        title: normalize_title(&title),
+        body,
+        trace: ParseTrace::default(),
+    }
+}
+
+/// The due date and time at the end of `line`, before its tags, and `line` without them.
+fn find_due_words(
+    line: &str,
+    options: Option<ParseInputOptions>,
+    keywords: &Keywords,
+) -> Option<(Option<DateFilter>, Option<TimeFilter>, String)> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let tags = words
+        .iter()
+        .rev()
+        .take_while(|w| w.starts_with('@') || w.starts_with('#'))
+        .count();
+    let (text, tags) = words.split_at(words.len() - tags);
+    // At least one word is left for the title.
+    let longest = text.len().saturating_sub(1).min(MAX_DUE_WORDS);
+    (1..=longest).rev().find_map(|n| {
+        let (title, due) = text.split_at(text.len() - n);
+        let (date, time) = parse_due_words(due, options, keywords)?;
+        let title: Vec<&str> = title.iter().chain(tags).copied().collect();
+        Some((date, time, title.join(" ")))
+    })
+}
+
+/// `[on|by|due] date [at time]` or `at time`, all the words. A weekday is the next one.
+fn parse_due_words(
+    words: &[&str],
+    options: Option<ParseInputOptions>,
+    keywords: &Keywords,
+) -> Option<(Option<DateFilter>, Option<TimeFilter>)> {
+    if let [word] = words
+        && let Some((date, time)) = parse_iso_datetime(word)
+    {
+        return Some((Some(date), Some(time)));
+    }
+    let words = match words.split_first() {
+        Some((first, rest)) if keywords.matches(Keyword::Due, first) => rest,
+        _ => words,
+    };
+    let text = words.join(" ");
+    // `at` or a synonym of it, which can be several words (`a las`).
+    let (date_string, time_string) = match keywords.find_word(Keyword::At, &text) {
+        Some(word) => {
+            let pos = keywords.find_position(Keyword::At, &text)?;
+            (text[..pos].trim(), Some(text[pos + word.len()..].trim()))
+        }
+        None => (text.as_str(), None),
+    };
+    let time = match time_string {
+        Some(time_string) => Some(parse_time_token(time_string, keywords)?),
+        None => None,
+    };
+    let date = match date_string {
+        "" => None,
+        date_string => {
+            let date = parse_date_token(date_string, None, options)?;
+            let reference_date = options
+                .and_then(|o| o.reference_date)
+                .unwrap_or_else(|| Local::now().date_naive());
+            Some(match date {
+                DateFilter::Single(date)
+                    if date < reference_date && weekday_keyword(date_string, keywords).is_some() =>
+                {
+                    DateFilter::Single(date + Duration::days(7))
+                }
+                date => date,
+            })
+        }
+    };
+    (date.is_some() || time.is_some()).then_some((date, time))
+}
+
+/// Where `time` starts: `morning` is 06:00.
+fn start_time(time: Option<TimeFilter>) -> Option<NaiveTime> {
+    match time {
+        Some(TimeFilter::Single(time)) => Some(time),
+        Some(TimeFilter::Range(from, _to)) => Some(from),
+        None => None,
     }
 }
 
@@ -56,11 +205,13 @@ pub fn parse_raw_user_input(input: &str, options: Option<ParseInputOptions>) ->
 /// This function resolves the **start** token (`start_date`) and, optionally, an **end** token
 /// (`end_date`) into either a single day or a date range. Resolution happens in this order:
 ///
-/// 1. **Relative keywords** (case-insensitive), resolved against `reference_date`:
+/// 1. **Ranges in one token**: `start..end` (e.g. `monday..friday`), each side resolved as below.
+/// 2. **Relative keywords** (case-insensitive), resolved against `reference_date`:
 ///    - Singles: `today`, `yesterday`, `tomorrow`, weekdays (`monday` … `sunday`)
 ///    - Ranges:  `last week`, `last month`
-///    - User-defined synonyms are supported via the global `Keywords` registry.
-/// 2. **Formatted dates** using any format string provided by `formats` (e.g. `"%Y-%m-%d"`).
+///    - User-defined synonyms are supported via `options.keywords`, or else the global registry.
+/// 3. **ISO weeks**: `week 34`, `week 34 2024`, `2025-W34`.
+/// 4. **Formatted dates** using any format string provided by `formats` (e.g. `"%Y-%m-%d"`).
 ///
 /// # Behavior
 ///
@@ -73,7 +224,7 @@ pub fn parse_raw_user_input(input: &str, options: Option<ParseInputOptions>) ->
 ///
 /// Defaults:
 /// - If `options.reference_date` is `None`, `Local::now().date_naive()` is used.
-/// - If `options.formats` is `None`, `DEFAULT_FORMATS` is used.
+/// - If `options.formats` is `None`, the formats of the default [`DateOrder`] are used.
 ///
 /// # Arguments
 ///
@@ -90,21 +241,43 @@ pub fn parse_date_token(
     end_date: Option<&str>,
     options: Option<ParseInputOptions>,
 ) -> Option<DateFilter> {
-    let options = options.unwrap_or_default();
+    with_keywords(options, |options, keywords| {
+        resolve_dates(start_date, end_date, options, keywords)
+    })
+}
+
+fn resolve_dates(
+    start_date: &str,
+    end_date: Option<&str>,
+    options: ParseInputOptions,
+    keywords: &Keywords,
+) -> Option<DateFilter> {
     let reference_date = options
         .reference_date
         .unwrap_or_else(|| Local::now().date_naive());
-    let formats = options.formats.unwrap_or(DEFAULT_FORMATS);
+    let formats = options
+        .formats
+        .unwrap_or(DateOrder::default().input_date_formats());
 
-    let a = resolve_date_token(start_date, reference_date, &formats)?;
-    let b = end_date.and_then(|date| resolve_date_token(date, reference_date, &formats));
+    let Some(a) = resolve_date_token(start_date, reference_date, formats, keywords) else {
+        log::debug!("`{start_date}` is not a date keyword nor matches {formats:?}");
+        return None;
+    };
+    log::debug!("`{start_date}` read as {a:?}");
+    let b = end_date.and_then(|date| resolve_date_token(date, reference_date, formats, keywords));
 
     match (a, b) {
         // If either side is an range, always return the range.
         (DateFilter::Range(s_date, e_date), _) => Some(DateFilter::Range(s_date, e_date)),
 
-        (DateFilter::Single(_), Some(DateFilter::Range(s_date, e_date))) => {
-            Some(DateFilter::Range(s_date, e_date))
+        // A single start and a range end span both, from the earliest to the latest date. When
+        // the start is after the range the dates are kept inverted, for `range_order` to handle.
+        (DateFilter::Single(a_single_date), Some(DateFilter::Range(s_date, e_date))) => {
+            if a_single_date > e_date {
+                Some(DateFilter::Range(a_single_date, s_date))
+            } else {
+                Some(DateFilter::Range(a_single_date.min(s_date), e_date))
+            }
         }
 
         // Two singles
@@ -129,32 +302,33 @@ pub fn parse_date_token(
 /// # Arguments
 ///
 /// * `s` - The string slice to parse.
+/// * `keywords` - The words read as `noon`, `midnight`...
 ///
 /// # Returns
 ///
 /// `Some(NaiveTime)` if parsing is successful, `None` otherwise.
-pub fn parse_time_token(s: &str) -> Option<TimeFilter> {
-    if Keywords::matches(Keyword::Morning, s) {
+pub fn parse_time_token(s: &str, keywords: &Keywords) -> Option<TimeFilter> {
+    if keywords.matches(Keyword::Morning, s) {
         let from = NaiveTime::from_hms_opt(6, 0, 0)?;
         let to = NaiveTime::from_hms_opt(12, 0, 0)?;
         return Some(TimeFilter::Range(from, to));
     }
-    if Keywords::matches(Keyword::Noon, s) {
+    if keywords.matches(Keyword::Noon, s) {
         let from = NaiveTime::from_hms_opt(12, 0, 0)?;
         let to = NaiveTime::from_hms_opt(18, 0, 0)?;
         return Some(TimeFilter::Range(from, to));
     }
-    if Keywords::matches(Keyword::Evening, s) {
+    if keywords.matches(Keyword::Evening, s) {
         let from = NaiveTime::from_hms_opt(18, 0, 0)?;
         let to = NaiveTime::from_hms_opt(21, 0, 0)?;
         return Some(TimeFilter::Range(from, to));
     }
-    if Keywords::matches(Keyword::Night, s) {
+    if keywords.matches(Keyword::Night, s) {
         let from = NaiveTime::from_hms_opt(21, 0, 0)?;
         let to = NaiveTime::from_hms_opt(0, 0, 0)?;
         return Some(TimeFilter::Range(from, to));
     }
-    if Keywords::matches(Keyword::Midnight, s) {
+    if keywords.matches(Keyword::Midnight, s) {
         let from = NaiveTime::from_hms_opt(00, 0, 0)?;
         let to = NaiveTime::from_hms_opt(6, 0, 0)?;
         return Some(TimeFilter::Range(from, to));
@@ -163,7 +337,7 @@ pub fn parse_time_token(s: &str) -> Option<TimeFilter> {
     let lower_s = s.to_ascii_lowercase();
     if lower_s.ends_with("am") || lower_s.ends_with("pm") {
         let (core_str, suffix) = s.split_at(s.len() - 2);
-        let is_pm = suffix.to_ascii_lowercase() == "pm";
+        let is_pm = suffix.eq_ignore_ascii_case("pm");
         let core = core_str.trim();
 
         let parts = if let Some(colon) = core.find(':') {
@@ -211,10 +385,10 @@ pub fn parse_time_token(s: &str) -> Option<TimeFilter> {
         return Some(TimeFilter::Single(nt));
     }
     // Single hour (24h format implied): "H" or "HH"
-    if let Ok(h) = s.parse::<u32>() {
-        if h <= 23 {
-            return Some(TimeFilter::Single(NaiveTime::from_hms_opt(h, 0, 0)?));
-        }
+    if let Ok(h) = s.parse::<u32>()
+        && h <= 23
+    {
+        return Some(TimeFilter::Single(NaiveTime::from_hms_opt(h, 0, 0)?));
     }
     None
 }
@@ -223,36 +397,61 @@ fn resolve_date_token(
     date_string: &str,
     reference_date: NaiveDate,
     formats: &[&str],
+    keywords: &Keywords,
 ) -> Option<DateFilter> {
-    if Keywords::matches(Keyword::Today, date_string) {
+    // `start..end` in one token: from the first day of `start` to the last day of `end`.
+    if let Some((start_token, end_token)) = date_string.split_once("..") {
+        let (start_token, end_token) = (start_token.trim(), end_token.trim());
+        let start = resolve_date_token(start_token, reference_date, formats, keywords)?;
+        let end = resolve_date_token(end_token, reference_date, formats, keywords)?;
+        let ((start, _), (_, mut end)) = (date_bounds(start), date_bounds(end));
+        // Weekdays are the last one, but in `monday..friday` on a Wednesday, Friday is the next one.
+        if end < start && weekday_keyword(end_token, keywords).is_some() {
+            end += Duration::days(7);
+        }
+        return Some(DateFilter::Range(start, end));
+    }
+    if let Some((keyword, days)) = keywords.shifted(date_string) {
+        let date = resolve_date_token(keyword.as_ref(), reference_date, formats, keywords)?;
+        // An offset past the dates lgg knows isn't a date.
+        let days = Duration::try_days(days)?;
+        return Some(match date {
+            DateFilter::Single(date) => DateFilter::Single(date.checked_add_signed(days)?),
+            DateFilter::Range(start, end) => DateFilter::Range(
+                start.checked_add_signed(days)?,
+                end.checked_add_signed(days)?,
+            ),
+        });
+    }
+    if keywords.matches(Keyword::Today, date_string) {
         return Some(DateFilter::Single(reference_date));
     }
-    if Keywords::matches(Keyword::Yesterday, date_string) {
+    if keywords.matches(Keyword::Yesterday, date_string) {
         return Some(DateFilter::Single(reference_date - Duration::days(1)));
     }
-    if Keywords::matches(Keyword::Tomorrow, date_string) {
+    if keywords.matches(Keyword::Tomorrow, date_string) {
         return Some(DateFilter::Single(reference_date + Duration::days(1)));
     }
-    if Keywords::matches(Keyword::LastWeek, date_string) {
+    if keywords.matches(Keyword::LastWeek, date_string) {
         let today_wd = reference_date.weekday();
         let days_to_last_sunday = today_wd.num_days_from_sunday();
         let last_sunday = reference_date - Duration::days(days_to_last_sunday as i64);
         let start_of_last_week = last_sunday - Duration::days(6);
         return Some(DateFilter::Range(start_of_last_week, last_sunday));
     }
-    if Keywords::matches(Keyword::ThisWeek, date_string) {
+    if keywords.matches(Keyword::ThisWeek, date_string) {
         let days_from_monday = reference_date.weekday().num_days_from_monday();
         let start_of_week = reference_date - Duration::days(days_from_monday as i64);
         let end_of_week = start_of_week + Duration::days(6);
         return Some(DateFilter::Range(start_of_week, end_of_week));
     }
-    if Keywords::matches(Keyword::LastMonth, date_string) {
+    if keywords.matches(Keyword::LastMonth, date_string) {
         let first_of_this_month = reference_date.with_day(1)?;
         let end_of_last_month = first_of_this_month - Duration::days(1);
         let start_of_last_month = end_of_last_month.with_day(1)?;
         return Some(DateFilter::Range(start_of_last_month, end_of_last_month));
     }
-    if Keywords::matches(Keyword::ThisMonth, date_string) {
+    if keywords.matches(Keyword::ThisMonth, date_string) {
         let start_of_month = reference_date.with_day(1)?;
         let (y, m) = if start_of_month.month() == 12 {
             (start_of_month.year() + 1, 1)
@@ -263,20 +462,166 @@ fn resolve_date_token(
         return Some(DateFilter::Range(start_of_month, end_of_month));
     }
 
-    if Keywords::matches(Keyword::LastYear, date_string) {
+    if keywords.matches(Keyword::LastYear, date_string) {
         let y = reference_date.year() - 1;
         let start = NaiveDate::from_ymd_opt(y, 1, 1)?;
         let end = NaiveDate::from_ymd_opt(y, 12, 31)?;
         return Some(DateFilter::Range(start, end));
     }
-    if Keywords::matches(Keyword::ThisYear, date_string) {
+    if keywords.matches(Keyword::ThisYear, date_string) {
         let y = reference_date.year();
         let start = NaiveDate::from_ymd_opt(y, 1, 1)?;
         let end = NaiveDate::from_ymd_opt(y, 12, 31)?;
         return Some(DateFilter::Range(start, end));
     }
 
-    let day_keyword = [
+    if let Some(weekday) = weekday_keyword(date_string, keywords) {
+        let today_wd = reference_date.weekday();
+        let days_ago = (today_wd.num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+        return Some(DateFilter::Single(
+            reference_date - Duration::days(days_ago as i64),
+        ));
+    }
+
+    if let Some(week) = parse_iso_week(date_string, reference_date.year()) {
+        return Some(week);
+    }
+
+    // Fallback to formatted dates
+    let formatted = formats.iter().find_map(|fmt| {
+        let date = NaiveDate::parse_from_str(date_string, fmt).ok()?;
+        log::trace!("`{date_string}` matched the format `{fmt}`");
+        Some(DateFilter::Single(date))
+    });
+    if formatted.is_some() {
+        return formatted;
+    }
+
+    // A date moved by some days, weeks, months or years.
+    let (date, _, offsets) = split_date_offsets(date_string, reference_date, formats, keywords)?;
+    offsets.into_iter().try_fold(date, |date, offset| {
+        Some(match date {
+            DateFilter::Single(date) => DateFilter::Single(offset.shift(date)?),
+            DateFilter::Range(start, end) => {
+                DateFilter::Range(offset.shift(start)?, offset.shift(end)?)
+            }
+        })
+    })
+}
+
+/// A step of a date expression: `-3` or `+10d` are days, `+1w` weeks, `-1m` months, `+1y` years.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DateOffset {
+    Days(i64),
+    Months(i64),
+}
+
+impl DateOffset {
+    /// `date` moved by this offset. Months that don't have its day end at their last one.
+    fn shift(self, date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            DateOffset::Days(days) => date.checked_add_signed(Duration::try_days(days)?),
+            DateOffset::Months(months) => {
+                let abs = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+                if months < 0 {
+                    date.checked_sub_months(abs)
+                } else {
+                    date.checked_add_months(abs)
+                }
+            }
+        }
+    }
+}
+
+/// `date_string` as a date followed by offsets (`today-3`, `friday+1w`, `2025-08-01+10d`,
+/// `today-1m+2d`): the date, its token and the offsets. The date is split at the first `+` or
+/// `-` after which everything is an offset and before which there is a date, so the dashes of
+/// `2025-08-01` are not offsets.
+fn split_date_offsets<'s>(
+    date_string: &'s str,
+    reference_date: NaiveDate,
+    formats: &[&str],
+    keywords: &Keywords,
+) -> Option<(DateFilter, &'s str, Vec<DateOffset>)> {
+    let offsets_re = Regex::new(r"^(?:\s*[+-]\s*\d+\s*[dwmy]?)+$").unwrap();
+    let offset_re = Regex::new(r"([+-])\s*(\d+)\s*([dwmy]?)").unwrap();
+    date_string
+        .char_indices()
+        .filter(|&(i, ch)| i > 0 && (ch == '+' || ch == '-'))
+        .find_map(|(i, _)| {
+            let (base, rest) = date_string.split_at(i);
+            if !offsets_re.is_match(rest) {
+                return None;
+            }
+            let base = base.trim();
+            let date = resolve_date_token(base, reference_date, formats, keywords)?;
+            let offsets = offset_re
+                .captures_iter(rest)
+                .map(|captures| {
+                    let amount: i64 = captures[2].parse().ok()?;
+                    let amount = if &captures[1] == "-" { -amount } else { amount };
+                    Some(match &captures[3] {
+                        "w" => DateOffset::Days(amount.checked_mul(7)?),
+                        "m" => DateOffset::Months(amount),
+                        "y" => DateOffset::Months(amount.checked_mul(12)?),
+                        _ => DateOffset::Days(amount),
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            log::trace!("`{date_string}` read as `{base}` moved by {offsets:?}");
+            Some((date, base, offsets))
+        })
+}
+
+/// Dates in `date_string` that `formats` read as different days, like `03/04/2025` with both
+/// `%d/%m/%Y` and `%m/%d/%Y`. Readings that only differ in the year (`14/08/25` with `%y` and
+/// `%Y`) are the same one.
+pub fn find_ambiguous_dates(date_string: &str, formats: &[&str]) -> Vec<AmbiguousDate> {
+    let mut found: Vec<AmbiguousDate> = Vec::new();
+    for side in date_string.split("..").map(str::trim) {
+        for token in std::iter::once(side).chain(side.split_whitespace()) {
+            if found.iter().any(|a| a.token == token) {
+                continue;
+            }
+            let mut readings: Vec<NaiveDate> = Vec::new();
+            for date in formats
+                .iter()
+                .filter_map(|fmt| NaiveDate::parse_from_str(token, fmt).ok())
+            {
+                let seen = readings
+                    .iter()
+                    .any(|r| (r.month(), r.day()) == (date.month(), date.day()));
+                if !seen {
+                    readings.push(date);
+                }
+            }
+            if readings.len() > 1 {
+                found.push(AmbiguousDate {
+                    token: token.to_string(),
+                    readings,
+                });
+            }
+        }
+    }
+    found
+}
+
+/// The weekdays of a comma separated list (`mon,wed,fri`, `monday, friday`). The names can be
+/// the keywords, their synonyms or their first three letters. `None` if one isn't a weekday.
+pub fn parse_weekdays(input: &str, keywords: &Keywords) -> Option<Vec<Weekday>> {
+    let mut days = Vec::new();
+    for name in input.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let day = weekday_keyword(name, keywords).or_else(|| name.parse().ok())?;
+        if !days.contains(&day) {
+            days.push(day);
+        }
+    }
+    Some(days)
+}
+
+/// The weekday named by `date_string` (`monday`, or any of its synonyms).
+fn weekday_keyword(date_string: &str, keywords: &Keywords) -> Option<Weekday> {
+    [
         (Keyword::Monday, Weekday::Mon),
         (Keyword::Tuesday, Weekday::Tue),
         (Keyword::Wednesday, Weekday::Wed),
@@ -285,58 +630,99 @@ fn resolve_date_token(
         (Keyword::Saturday, Weekday::Sat),
         (Keyword::Sunday, Weekday::Sun),
     ]
-    .iter()
-    .find(|(keyword, _)| Keywords::matches(*keyword, date_string));
+    .into_iter()
+    .find(|(keyword, _)| keywords.matches(*keyword, date_string))
+    .map(|(_, weekday)| weekday)
+}
 
-    if let Some((_, weekday)) = day_keyword {
-        let today_wd = reference_date.weekday();
-        let days_ago = (today_wd.num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
-        return Some(DateFilter::Single(
-            reference_date - Duration::days(days_ago as i64),
-        ));
+/// First and last day of `filter`.
+fn date_bounds(filter: DateFilter) -> (NaiveDate, NaiveDate) {
+    match filter {
+        DateFilter::Single(date) => (date, date),
+        DateFilter::Range(start, end) => (start, end),
     }
+}
 
-    // Fallback to formatted dates
-    formats
-        .iter()
-        .filter_map(|fmt| NaiveDate::parse_from_str(date_string, fmt).ok())
-        .map(|d| DateFilter::Single(d))
-        .next()
+/// Monday to Sunday of an ISO week: `week 34` (of `reference_year`), `week 34 2024` or `2025-W34`.
+fn parse_iso_week(date_string: &str, reference_year: i32) -> Option<DateFilter> {
+    let token = date_string.trim().to_lowercase();
+    let (year, week) = match token.strip_prefix("week ") {
+        Some(rest) => match rest.split_whitespace().collect::<Vec<_>>()[..] {
+            [week] => (reference_year, week.parse().ok()?),
+            [week, year] => (year.parse().ok()?, week.parse().ok()?),
+            _ => return None,
+        },
+        None => {
+            let (year, week) = token.split_once("-w")?;
+            (year.parse().ok()?, week.parse().ok()?)
+        }
+    };
+    let monday = NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)?;
+    Some(DateFilter::Range(monday, monday + Duration::days(6)))
 }
 
 /// Try to parse `<prefix>:` where prefix may contain date and/or time.
-/// Returns (date, time, remainder_after_colon).
+/// Returns (date, time, remainder_after_colon), and tells `trace` how the prefix was read.
 fn parse_prefix<'a>(
     input: &'a str,
-    options: Option<ParseInputOptions>,
+    options: ParseInputOptions,
+    keywords: &Keywords,
+    trace: &mut ParseTrace,
 ) -> (Option<DateFilter>, Option<TimeFilter>, &'a str) {
+    let formats = options
+        .formats
+        .unwrap_or(DateOrder::default().input_date_formats());
     if let Some(idx) = input.find(": ") {
         let (prefix, rest_with_colon) = input.split_at(idx);
         let rest = &rest_with_colon[1..]; // skip ':'
         let prefix_trim = prefix.trim();
         // Try full ISO-like datetime (no timezone): YYYY-MM-DDTHH:MM[:SS]
         if let Some((d, t)) = parse_iso_datetime(prefix_trim) {
+            log::debug!("prefix `{prefix_trim}` read as an ISO date and time");
+            trace.prefix = Some(prefix_trim.to_string());
+            trace.date_token = Some(prefix_trim.to_string());
+            trace.date_rule = Some(DateRule::IsoDateTime);
+            trace.time_token = Some(prefix_trim.to_string());
             return (Some(d), Some(t), rest);
         }
-        // Split on " at "
-        if let Some(word) = Keywords::find_word(Keyword::At, prefix_trim) {
-            if let Some(pos) = Keywords::find_position(Keyword::At, prefix_trim) {
-                let (date_part, time_part) = prefix_trim.split_at(pos);
-                let date_part = date_part.trim();
-                let time_part = time_part[word.len()..].trim(); // skip keyword
-                return if let Some(date) = parse_date_token(date_part, None, options) {
-                    let time = parse_time_token(time_part);
+        // Only a date word or formatted date (no time). The whole prefix first, the longest
+        // match, for synonyms of several words with an `at` in them.
+        if let Some(date) = resolve_dates(prefix_trim, None, options, keywords) {
+            trace.prefix = Some(prefix_trim.to_string());
+            trace.date_token = Some(prefix_trim.to_string());
+            trace.date_rule = date_rule(prefix_trim, formats, keywords);
+            return (Some(date), None, rest);
+        }
+        // Split on " at ", the last one with a date before it (`de aquí a dos días a las 5`).
+        let splits = keywords.find_all(Keyword::At, prefix_trim);
+        let split = splits
+            .iter()
+            .rev()
+            .find(|(pos, _)| {
+                let date_part = prefix_trim[..*pos].trim();
+                resolve_dates(date_part, None, options, keywords).is_some()
+            })
+            .or(splits.first());
+        if let Some((pos, word)) = split {
+            let (date_part, time_part) = prefix_trim.split_at(*pos);
+            let date_part = date_part.trim();
+            let time_part = time_part[word.len()..].trim(); // skip keyword
+            let time = parse_time_token(time_part, keywords);
+            log::debug!("time `{time_part}` after `{word}` read as {time:?}");
+            trace.prefix = Some(prefix_trim.to_string());
+            trace.time_token = Some(time_part.to_string());
+            return match resolve_dates(date_part, None, options, keywords) {
+                Some(date) => {
+                    trace.date_token = Some(date_part.to_string());
+                    trace.date_rule = date_rule(date_part, formats, keywords);
                     (Some(date), time, rest)
-                } else {
-                    let time = parse_time_token(time_part);
-                    (None, time, rest)
                 }
-            }
-        }
-        // Only a date word or formatted date (no time)
-        if let Some(date) = parse_date_token(prefix_trim, None, options) {
-            return (Some(date), None, rest);
+                None => (None, time, rest),
+            };
         }
+        log::debug!("prefix `{prefix_trim}` is not a date nor a time, it's part of the text");
+    } else {
+        log::debug!("no `date:` prefix in the input");
     }
     // Not recognized: fall through and treat entire input as text.
     (None, None, input)
@@ -349,23 +735,76 @@ fn parse_iso_datetime(s: &str) -> Option<(DateFilter, TimeFilter)> {
     None
 }
 
-fn split_title_body(text: &str) -> (String, String) {
+/// What reads `date_string` as a date, tried in the order of [`resolve_date_token`].
+fn date_rule(date_string: &str, formats: &[&str], keywords: &Keywords) -> Option<DateRule> {
+    if date_string.contains("..") {
+        return Some(DateRule::Range);
+    }
+    if let Some(keyword) = keywords.lookup(date_string) {
+        return Some(DateRule::Keyword(keyword));
+    }
+    if let Some((keyword, days)) = keywords.shifted(date_string) {
+        return Some(DateRule::Shifted(keyword, days));
+    }
+    if parse_iso_week(date_string, 2000).is_some() {
+        return Some(DateRule::IsoWeek);
+    }
+    if let Some(fmt) = formats
+        .iter()
+        .find(|fmt| NaiveDate::parse_from_str(date_string, fmt).is_ok())
+    {
+        return Some(DateRule::Format(fmt.to_string()));
+    }
+    let reference_date = NaiveDate::from_ymd_opt(2000, 1, 1)?;
+    let (_, base, _) = split_date_offsets(date_string, reference_date, formats, keywords)?;
+    Some(DateRule::Offset {
+        base: base.to_string(),
+        offset: date_string[base.len()..].trim().to_string(),
+    })
+}
+
+/// `text` split at the end of its first sentence, its title, and the rest, its body. The dots of
+/// the `tags` don't end it.
+pub fn split_title_body(text: &str, tags: &TagPattern) -> (String, String) {
+    let (title, body, _) = split_title_body_at(text, tags);
+    (title, body)
+}
+
+/// Characters ending the first sentence of an entry, its title. The full-width ones are the
+/// ones of Chinese and Japanese.
+const SENTENCE_ENDS: [char; 6] = ['.', '?', '!', '。', '？', '！'];
+
+/// Like [`split_title_body`], telling where the title ends. A lone link is all title, its dots
+/// don't end it. The text is split between graphemes: a `!` with a variation selector or a
+/// combining mark is part of an emoji or a letter, not the end of a sentence.
+fn split_title_body_at(text: &str, tags: &TagPattern) -> (String, String, TitleEnd) {
+    if let Some(url) = lone_url(text) {
+        return (url.to_string(), String::new(), TitleEnd::EndOfText);
+    }
     if let Some((i, ch)) = text
         .char_indices()
         .find(|&(_, ch)| ch == '\n' || ch == '\r')
     {
         let title = text[..(i + ch.len_utf8())].trim().to_string();
         let body = text[i + ch.len_utf8()..].trim().to_string();
-        return (title, body);
+        return (title, body, TitleEnd::LineBreak);
     }
-    for (i, ch) in text.char_indices() {
-        if ch == '.' || ch == '?' || ch == '!' {
-            let title = text[..(i + ch.len_utf8())].trim().to_string();
-            let body = text[i + ch.len_utf8()..].trim().to_string();
-            return (title, body);
+    // A `tag_pattern` can allow dots in tags (`@v1.2`), which don't end the title.
+    let tags = tags.find_ranges(text);
+    for (i, grapheme) in text.grapheme_indices(true) {
+        let end = i + grapheme.len();
+        let in_tag = tags.iter().any(|&(start, tag_end)| start < i && end < tag_end);
+        let mut chars = grapheme.chars();
+        if let (Some(ch), None) = (chars.next(), chars.next())
+            && SENTENCE_ENDS.contains(&ch)
+            && !in_tag
+        {
+            let title = text[..end].trim().to_string();
+            let body = text[end..].trim().to_string();
+            return (title, body, TitleEnd::Punctuation(ch));
         }
     }
-    (text.trim().to_string(), String::new())
+    (text.trim().to_string(), String::new(), TitleEnd::EndOfText)
 }
 
 /// Remove leading/trailing Markdown `#` and surrounding spaces from the title.
@@ -420,6 +859,44 @@ mod tests {
         assert_eq!(p1.title, "Note 1");
     }
 
+    #[test]
+    fn trace_tells_how_the_input_was_read() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let p = parse_raw_user_input("yesterday at 6am: Note. More", opts(anchor));
+        assert_eq!(
+            p.trace,
+            ParseTrace {
+                prefix: Some("yesterday at 6am".to_string()),
+                date_token: Some("yesterday".to_string()),
+                date_rule: Some(DateRule::Keyword(Keyword::Yesterday)),
+                time_token: Some("6am".to_string()),
+                title_end: TitleEnd::Punctuation('.'),
+                tags: Vec::new(),
+            }
+        );
+
+        let p = parse_raw_user_input("14/08/2025: Title @work\nBody #home", opts(anchor));
+        assert_eq!(p.trace.date_rule, Some(DateRule::Format("%d/%m/%Y".to_string())));
+        assert_eq!(p.trace.title_end, TitleEnd::LineBreak);
+        assert_eq!(p.trace.tags, vec!["#home", "@work"]);
+
+        let p = parse_raw_user_input("Note: no date here", opts(anchor));
+        assert_eq!(p.trace.prefix, None);
+        assert_eq!(p.trace.title_end, TitleEnd::EndOfText);
+    }
+
+    #[test]
+    fn titles_end_between_graphemes() {
+        let tags = TagPattern::default();
+        let (title, body) = split_title_body("Concierto 🎸!\u{fe0f} Muy bueno. Volvería", &tags);
+        assert_eq!(title, "Concierto 🎸!\u{fe0f} Muy bueno.");
+        assert_eq!(body, "Volvería");
+        let (title, body) = split_title_body("東京に着いた。ホテルは駅の近く", &tags);
+        assert_eq!((title.as_str(), body.as_str()), ("東京に着いた。", "ホテルは駅の近く"));
+        let (title, body) = split_title_body("👨‍👩‍👧 Family day! Zoo", &tags);
+        assert_eq!((title.as_str(), body.as_str()), ("👨‍👩‍👧 Family day!", "Zoo"));
+    }
+
     #[test]
     fn natural_single_hour_with_time() {
         let anchor = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
@@ -460,6 +937,14 @@ mod tests {
         assert!(p.time.is_none());
     }
 
+    #[test]
+    fn a_lone_link_is_the_whole_title() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let p = parse_raw_user_input("yesterday: https://example.com/post.html", opts(anchor));
+        assert_eq!(p.title, "https://example.com/post.html");
+        assert_eq!(p.body, "");
+    }
+
     #[test]
     fn body_with_sub_headers() {
         let anchor = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
@@ -486,6 +971,7 @@ mod tests {
         let custom_opts = Some(ParseInputOptions {
             reference_date: Some(anchor),
             formats: Some(fmts),
+            ..Default::default()
         });
         let p1 = parse_raw_user_input("01-08-2025: Title 1.", custom_opts);
         let p2 = parse_raw_user_input("01/09/2025: Title 2.", custom_opts);
@@ -499,6 +985,205 @@ mod tests {
         assert!(p2.body.is_empty());
     }
 
+    #[test]
+    fn synonyms_come_from_the_given_keywords() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let spanish = Keywords::with_synonyms(&[("ayer".into(), "yesterday".into())]);
+        let with_synonyms = Some(ParseInputOptions {
+            reference_date: Some(anchor),
+            keywords: Some(&spanish),
+            ..Default::default()
+        });
+
+        let p = parse_raw_user_input("ayer: Cena.", with_synonyms);
+        assert_eq!(p.date, NaiveDate::from_ymd_opt(2025, 8, 14));
+        assert_eq!(p.trace.date_rule, Some(DateRule::Keyword(Keyword::Yesterday)));
+        // Without them, `ayer` is not a date.
+        let p = parse_raw_user_input("ayer: Cena.", opts(anchor));
+        assert_eq!(p.date, None);
+        assert_eq!(p.title, "ayer: Cena.");
+    }
+
+    #[test]
+    fn synonyms_of_times_and_at() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let spanish = Keywords::with_synonyms(&[
+            ("ayer".into(), "yesterday".into()),
+            ("viernes".into(), "friday".into()),
+            ("a las".into(), "at".into()),
+            ("lunch".into(), "noon".into()),
+            ("madrugada".into(), "night".into()),
+        ]);
+        let options = Some(ParseInputOptions {
+            reference_date: Some(anchor),
+            keywords: Some(&spanish),
+            ..Default::default()
+        });
+        let time = |h| NaiveTime::from_hms_opt(h, 0, 0);
+
+        let p = parse_raw_user_input("ayer a las 6: Cena.", options);
+        assert_eq!(p.date, NaiveDate::from_ymd_opt(2025, 8, 14));
+        assert_eq!(p.time, time(6));
+        assert_eq!(p.title, "Cena.");
+        assert_eq!(p.trace.time_token.as_deref(), Some("6"));
+
+        let p = parse_raw_user_input("ayer at lunch: Paella.", options);
+        assert_eq!(p.time, time(12));
+        let p = parse_raw_user_input("ayer a las madrugada: Insomnio.", options);
+        assert_eq!(p.time, time(21));
+
+        let p = parse_todo_input("llamar a mamá viernes a las 5pm", options);
+        assert_eq!(p.title, "llamar a mamá");
+        assert_eq!(p.date, NaiveDate::from_ymd_opt(2025, 8, 15));
+        assert_eq!(p.time, time(17));
+    }
+
+    #[test]
+    fn synonyms_of_several_words_and_days() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let keywords = Keywords::with_synonyms(&[
+            ("day before yesterday".into(), "yesterday - 1".into()),
+            ("de aquí a dos días".into(), "today + 2".into()),
+            ("a las".into(), "at".into()),
+            ("a".into(), "at".into()),
+        ]);
+        let options = Some(ParseInputOptions {
+            reference_date: Some(anchor),
+            keywords: Some(&keywords),
+            ..Default::default()
+        });
+        let date = |d| NaiveDate::from_ymd_opt(2025, 8, d);
+
+        let p = parse_raw_user_input("day before yesterday: Cinema.", options);
+        assert_eq!(p.date, date(13));
+        assert_eq!(p.trace.date_rule, Some(DateRule::Shifted(Keyword::Yesterday, -1)));
+        let p = parse_raw_user_input("Day before yesterday at 8pm: Cinema.", options);
+        assert_eq!(p.date, date(13));
+        assert_eq!(p.time, NaiveTime::from_hms_opt(20, 0, 0));
+
+        // The `a` inside the synonym is not where the time starts.
+        let p = parse_raw_user_input("de aquí a dos días: Viaje.", options);
+        assert_eq!(p.date, date(17));
+        assert!(p.time.is_none());
+        let p = parse_raw_user_input("de aquí a dos días a las 9: Viaje.", options);
+        assert_eq!(p.date, date(17));
+        assert_eq!(p.time, NaiveTime::from_hms_opt(9, 0, 0));
+        assert_eq!(p.title, "Viaje.");
+
+        // Past the dates lgg knows, it's not a date.
+        let keywords = Keywords::with_synonyms(&[("forever".into(), "today + 999999999".into())]);
+        let options = Some(ParseInputOptions {
+            reference_date: Some(anchor),
+            keywords: Some(&keywords),
+            ..Default::default()
+        });
+        assert!(parse_raw_user_input("forever: Never.", options).date.is_none());
+    }
+
+    #[test]
+    fn dates_with_offsets() {
+        // Friday
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let p_opts = Some(ParseInputOptions {
+            reference_date: Some(anchor),
+            formats: Some(&["%Y-%m-%d", "%d-%m-%Y"]),
+            ..Default::default()
+        });
+        let single = |y, m, d| Some(DateFilter::Single(NaiveDate::from_ymd_opt(y, m, d).unwrap()));
+
+        assert_eq!(parse_date_token("today-3", None, p_opts), single(2025, 8, 12));
+        assert_eq!(parse_date_token("friday+1w", None, p_opts), single(2025, 8, 22));
+        assert_eq!(parse_date_token("2025-08-01+10d", None, p_opts), single(2025, 8, 11));
+        assert_eq!(parse_date_token("01-08-2025 - 1", None, p_opts), single(2025, 7, 31));
+        assert_eq!(parse_date_token("today-1m+2d", None, p_opts), single(2025, 7, 17));
+        assert_eq!(parse_date_token("2024-02-29+1y", None, p_opts), single(2025, 2, 28));
+        let week = parse_date_token("this week-1w", None, p_opts);
+        assert_eq!(
+            week,
+            Some(DateFilter::Range(
+                NaiveDate::from_ymd_opt(2025, 8, 4).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 8, 10).unwrap(),
+            ))
+        );
+        assert_eq!(parse_date_token("today-", None, p_opts), None);
+        assert_eq!(parse_date_token("someday+1", None, p_opts), None);
+
+        let p = parse_raw_user_input("yesterday-1 at 9: Backfilled.", p_opts);
+        assert_eq!(p.date, NaiveDate::from_ymd_opt(2025, 8, 13));
+        assert_eq!(
+            p.trace.date_rule,
+            Some(DateRule::Offset {
+                base: "yesterday".to_string(),
+                offset: "-1".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn ambiguous_dates_have_every_reading() {
+        let fmts = &["%d/%m/%y", "%d/%m/%Y", "%m/%d/%Y"];
+        let d = |m, day| NaiveDate::from_ymd_opt(2025, m, day).unwrap();
+
+        let found = find_ambiguous_dates("03/04/2025", fmts);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].token, "03/04/2025");
+        assert_eq!(found[0].readings, vec![d(4, 3), d(3, 4)]);
+
+        let found = find_ambiguous_dates("01/04/2025..03/04/2025", fmts);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[1].readings, vec![d(4, 3), d(3, 4)]);
+
+        // Only one of them is a valid month, or both readings are the same day.
+        assert!(find_ambiguous_dates("14/08/2025", fmts).is_empty());
+        assert!(find_ambiguous_dates("03/03/2025", fmts).is_empty());
+        assert!(find_ambiguous_dates("14/08/25", fmts).is_empty());
+        assert!(find_ambiguous_dates("yesterday", fmts).is_empty());
+    }
+
+    #[test]
+    fn todo_due_date_at_the_end() {
+        // Friday
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        let fmts = &["%d/%m/%Y"];
+        let options = Some(ParseInputOptions {
+            reference_date: Some(anchor),
+            formats: Some(fmts),
+            ..Default::default()
+        });
+        let date = |d| NaiveDate::from_ymd_opt(2025, 8, d);
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0);
+
+        let p = parse_todo_input("call mum monday at 5pm @family", options);
+        assert_eq!(p.title, "call mum @family");
+        assert_eq!(p.date, date(18));
+        assert_eq!(p.time, time(17, 0));
+
+        let p = parse_todo_input("Send the invoice. To Ana by 20/08/2025", options);
+        assert_eq!(p.title, "Send the invoice. To Ana");
+        assert_eq!(p.date, date(20));
+        assert!(p.time.is_none());
+
+        let p = parse_todo_input("Standup at 9:30\nWith the notes", options);
+        assert_eq!(p.title, "Standup");
+        assert!(p.date.is_none());
+        assert_eq!(p.time, time(9, 30));
+        assert_eq!(p.body, "With the notes");
+
+        let p = parse_todo_input("tomorrow at noon: Lunch with Ana. Book a table", options);
+        assert_eq!(p.title, "Lunch with Ana. Book a table");
+        assert_eq!(p.date, date(16));
+        assert_eq!(p.time, time(12, 0));
+
+        let p = parse_todo_input("Meet at the station", options);
+        assert_eq!(p.title, "Meet at the station");
+        assert!(p.date.is_none() && p.time.is_none());
+
+        // A weekday alone is the title, not a due date.
+        let p = parse_todo_input("friday", options);
+        assert_eq!(p.title, "friday");
+        assert!(p.date.is_none());
+    }
+
     #[test]
     fn hashes_stripped_from_title() {
         let anchor = NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
@@ -685,6 +1370,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ranges_in_one_token() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 20).unwrap(); // Wednesday
+        let d = |day| NaiveDate::from_ymd_opt(2025, 8, day).unwrap();
+        assert_eq!(
+            parse_date_token("01/08/2025..15/08/2025", None, opts(anchor)),
+            Some(DateFilter::Range(d(1), d(15)))
+        );
+        // Friday is the one after Monday, not the last one.
+        assert_eq!(
+            parse_date_token("monday..friday", None, opts(anchor)),
+            Some(DateFilter::Range(d(18), d(22)))
+        );
+        assert_eq!(
+            parse_date_token("last week..yesterday", None, opts(anchor)),
+            Some(DateFilter::Range(d(11), d(19)))
+        );
+        assert_eq!(parse_date_token("monday..someday", None, opts(anchor)), None);
+
+        let p = parse_raw_user_input("monday..wednesday at 9: Conference.", opts(anchor));
+        assert_eq!(p.date, Some(d(18)));
+        assert_eq!(p.until, Some(d(20)));
+        assert_eq!(p.time, Some(NaiveTime::from_hms_opt(9, 0, 0).unwrap()));
+        assert_eq!(p.title, "Conference.");
+
+        // Other ranges are still written on their first day.
+        let p = parse_raw_user_input("last week: Title", opts(anchor));
+        assert_eq!(p.date, Some(d(11)));
+        assert_eq!(p.until, None);
+    }
+
+    #[test]
+    fn iso_weeks_are_ranges() {
+        let anchor = NaiveDate::from_ymd_opt(2025, 8, 20).unwrap();
+        let week_34 = DateFilter::Range(
+            NaiveDate::from_ymd_opt(2025, 8, 18).unwrap(), // Mon
+            NaiveDate::from_ymd_opt(2025, 8, 24).unwrap(), // Sun
+        );
+        assert_eq!(parse_date_token("week 34", None, opts(anchor)), Some(week_34));
+        assert_eq!(parse_date_token("2025-W34", None, opts(anchor)), Some(week_34));
+        assert_eq!(parse_date_token("Week 34 2025", None, opts(anchor)), Some(week_34));
+
+        // Week 1 of 2025 starts in 2024.
+        assert_eq!(
+            parse_date_token("2025-w01", None, opts(anchor)),
+            Some(DateFilter::Range(
+                NaiveDate::from_ymd_opt(2024, 12, 30).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 1, 5).unwrap(),
+            ))
+        );
+        assert_eq!(parse_date_token("week 54", None, opts(anchor)), None);
+    }
+
     #[test]
     fn start_range_ignores_end_single() {
         let anchor = NaiveDate::from_ymd_opt(2025, 8, 20).unwrap();
@@ -747,11 +1485,20 @@ mod tests {
     }
 
     #[test]
-    fn start_single_end_is_range_returns_that_range() {
+    fn start_single_end_is_range_spans_both() {
         let anchor = NaiveDate::from_ymd_opt(2025, 8, 20).unwrap();
         let p_opts = opts(anchor);
 
         let res = parse_date_token("10/08/2025", Some("last week"), p_opts).unwrap();
+        assert_eq!(
+            res,
+            DateFilter::Range(
+                NaiveDate::from_ymd_opt(2025, 8, 10).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 8, 17).unwrap(),
+            )
+        );
+
+        let res = parse_date_token("13/08/2025", Some("last week"), p_opts).unwrap();
         assert_eq!(
             res,
             DateFilter::Range(
@@ -759,19 +1506,27 @@ mod tests {
                 NaiveDate::from_ymd_opt(2025, 8, 17).unwrap(),
             )
         );
+
+        // After the range: inverted, from the single date back to the start of the range.
+        let res = parse_date_token("today", Some("last week"), p_opts).unwrap();
+        assert_eq!(
+            res,
+            DateFilter::Range(
+                NaiveDate::from_ymd_opt(2025, 8, 20).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 8, 11).unwrap(),
+            )
+        );
     }
-}
 
-/// Finds words starting with # or @
-/// Matches one or more letters, numbers, or underscores.
-pub fn extract_tags(text: &str) -> Vec<String> {
-    let re = Regex::new(r"[@#]\w+").unwrap();
-    let mut tags: Vec<String> = re
-        .find_iter(text)
-        .map(|mat| mat.as_str().to_string().trim().to_ascii_lowercase())
-        .collect::<HashSet<_>>()
-        .into_iter()
-        .collect();
-    tags.sort();
-    tags
-}
\ No newline at end of file
+    #[test]
+    fn weekday_lists() {
+        let keywords = Keywords::with_synonyms(&[("lunes".to_string(), "monday".to_string())]);
+        assert_eq!(
+            parse_weekdays("mon, Wednesday,fri,mon", &keywords),
+            Some(vec![Weekday::Mon, Weekday::Wed, Weekday::Fri])
+        );
+        assert_eq!(parse_weekdays("lunes", &keywords), Some(vec![Weekday::Mon]));
+        assert_eq!(parse_weekdays("", &keywords), Some(Vec::new()));
+        assert_eq!(parse_weekdays("mon,someday", &keywords), None);
+    }
+}
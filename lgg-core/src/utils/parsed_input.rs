@@ -1,3 +1,5 @@
+use crate::keywords::{Keyword, Keywords};
+use crate::tags::TagPattern;
 use chrono::{NaiveDate, NaiveTime};
 
 /// Configuration options for parsing functions.
@@ -7,12 +9,70 @@ pub struct ParseInputOptions<'a> {
     pub reference_date: Option<NaiveDate>,
     /// A slice of `chrono` format strings to try for parsing dates.
     pub formats: Option<&'a [&'a str]>,
+    /// The words read as keywords and their synonyms. If `None`, [`Keywords::global`].
+    pub keywords: Option<&'a Keywords>,
+    /// The tags of the title and body. If `None`, [`TagPattern::default`].
+    pub tags: Option<&'a TagPattern>,
 }
 
 /// Parsed result of inline text (e.g., "yesterday: Title. Body").
 pub struct ParsedInput {
     pub date: Option<NaiveDate>,
+    /// Last day of an event of several days (`monday..friday: Conference`).
+    pub until: Option<NaiveDate>,
     pub time: Option<NaiveTime>,
     pub title: String,
     pub body: String,
+    /// How the input was read (`lgg explain`).
+    pub trace: ParseTrace,
+}
+
+/// How [`crate::utils::parse_input::parse_raw_user_input`] read an input, step by step.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseTrace {
+    /// The `date:` prefix, without the colon. `None` if there's none, or it's not a date nor a
+    /// time and is part of the text.
+    pub prefix: Option<String>,
+    /// Part of the prefix read as the date (`yesterday`, `14/08/2025`).
+    pub date_token: Option<String>,
+    /// What read `date_token` as a date.
+    pub date_rule: Option<DateRule>,
+    /// Part of the prefix read as the time (`6am`), even if it isn't one.
+    pub time_token: Option<String>,
+    /// Where the title ends and the body starts.
+    pub title_end: TitleEnd,
+    /// Tags of the title and body (`@work`, `#project`).
+    pub tags: Vec<String>,
+}
+
+/// What read a date.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DateRule {
+    /// A date and time in one (`2025-08-14T09:30`).
+    IsoDateTime,
+    /// A keyword or one of its synonyms (`yesterday`, `friday`, `last week`).
+    Keyword(Keyword),
+    /// A synonym of some days from a keyword (`"day before yesterday" = "yesterday - 1"`).
+    Shifted(Keyword, i64),
+    /// A date moved by some days, weeks, months or years (`today-3`, `friday+1w`): the date and
+    /// what it's moved by.
+    Offset { base: String, offset: String },
+    /// An ISO week (`2025-W33`, `week 33`).
+    IsoWeek,
+    /// One of the `input_date_formats` (`%d/%m/%Y`).
+    Format(String),
+    /// Two dates (`monday..friday`), an event of several days.
+    Range,
+}
+
+/// Where the title of an entry ends.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TitleEnd {
+    /// At the end of the first line.
+    LineBreak,
+    /// At the first `.`, `?` or `!`, or their full-width `。`, `？` and `！`.
+    Punctuation(char),
+    /// The whole text is the title.
+    #[default]
+    EndOfText,
 }
@@ -1,25 +1,58 @@
 use anyhow::Result;
+use glob::{MatchOptions, Pattern};
 use std::{
+    collections::HashSet,
     ffi::OsStr,
     fs,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
 };
 
+/// Optional file at the root of a scanned directory with glob patterns of paths to skip.
+pub const IGNORE_FILE_NAME: &str = ".lggignore";
+
+/// Recursively collects all the Markdown files found under `path`.
+///
+/// - Hidden directories (`.git`, `.lgg`, `.obsidian`...) are skipped.
+/// - Symlinks are followed, but every directory is visited only once, so link cycles are safe.
+/// - Paths matching a pattern of the optional `.lggignore` file in `path` are skipped.
 pub fn scan_dir_for_md_files(path: &Path) -> Result<Vec<PathBuf>> {
+    let ignore = IgnoreRules::load(path);
+    let mut visited = HashSet::new();
     let mut file_paths = Vec::new();
 
-    for entry in fs::read_dir(path)? {
+    scan_dir(path, path, &ignore, &mut visited, &mut file_paths)?;
+
+    Ok(file_paths)
+}
+
+fn scan_dir(
+    root: &Path,
+    dir: &Path,
+    ignore: &IgnoreRules,
+    visited: &mut HashSet<PathBuf>,
+    file_paths: &mut Vec<PathBuf>,
+) -> Result<()> {
+    // The canonical path resolves symlinks, a directory reached twice is a cycle (or a duplicate).
+    if !visited.insert(fs::canonicalize(dir)?) {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
         let entry = entry?;
         let p = entry.path();
+        let relative = p.strip_prefix(root).unwrap_or(&p);
 
         if p.is_dir() {
-            file_paths.extend(scan_dir_for_md_files(&p)?);
-        } else if p.is_file() && is_markdown(&p) {
+            if is_hidden(&p) || ignore.is_ignored(relative, true) {
+                continue;
+            }
+            scan_dir(root, &p, ignore, visited, file_paths)?;
+        } else if p.is_file() && is_markdown(&p) && !ignore.is_ignored(relative, false) {
             file_paths.push(p);
         }
     }
 
-    Ok(file_paths)
+    Ok(())
 }
 
 fn is_markdown(p: &Path) -> bool {
@@ -28,3 +61,194 @@ fn is_markdown(p: &Path) -> bool {
         .map(|ext| ext.eq_ignore_ascii_case("md"))
         .unwrap_or(false)
 }
+
+fn is_hidden(p: &Path) -> bool {
+    p.file_name()
+        .and_then(OsStr::to_str)
+        .map(|name| name.starts_with('.'))
+        .unwrap_or(false)
+}
+
+/// Names Windows keeps for devices, even with an extension (`nul.md`).
+const WINDOWS_DEVICE_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// The first name in `path` that Windows can't use for a file or directory: a device name like
+/// `con` or `aux.md`, one with `<>:"|?*`, or one ending with a dot or a space.
+pub fn windows_reserved_name(path: &Path) -> Option<String> {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy()),
+            _ => None,
+        })
+        .find(|name| {
+            let stem = name.split('.').next().unwrap_or_default().trim_end();
+            WINDOWS_DEVICE_NAMES
+                .iter()
+                .any(|device| device.eq_ignore_ascii_case(stem))
+                || name.contains(['<', '>', ':', '"', '|', '?', '*'])
+                || name.ends_with(['.', ' '])
+        })
+        .map(|name| name.to_string())
+}
+
+/// Glob patterns read from a `.lggignore` file, one per line.
+/// Empty lines and lines starting with `#` are skipped.
+///
+/// - A pattern with a `/` is matched against the path relative to the scanned root (`drafts/*.md`).
+/// - A pattern without a `/` is matched against the file or directory name (`*.tmp.md`).
+/// - A trailing `/` only matches directories (`templates/`).
+#[derive(Debug, Default)]
+struct IgnoreRules {
+    rules: Vec<IgnoreRule>,
+}
+
+#[derive(Debug)]
+struct IgnoreRule {
+    pattern: Pattern,
+    match_full_path: bool,
+    only_dirs: bool,
+}
+
+impl IgnoreRules {
+    fn load(root: &Path) -> Self {
+        match fs::read_to_string(root.join(IGNORE_FILE_NAME)) {
+            Ok(content) => Self::parse(&content),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let only_dirs = line.ends_with('/');
+                let line = line.trim_end_matches('/');
+                let match_full_path = line.contains('/');
+                let line = line.trim_start_matches('/');
+                Pattern::new(line).ok().map(|pattern| IgnoreRule {
+                    pattern,
+                    match_full_path,
+                    only_dirs,
+                })
+            })
+            .collect();
+
+        Self { rules }
+    }
+
+    fn is_ignored(&self, relative: &Path, is_dir: bool) -> bool {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        let full_path = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let name = relative
+            .file_name()
+            .map(|n| n.to_string_lossy())
+            .unwrap_or_default();
+
+        self.rules.iter().any(|rule| {
+            if rule.only_dirs && !is_dir {
+                return false;
+            }
+            if rule.match_full_path {
+                rule.pattern.matches_with(&full_path, options)
+            } else {
+                rule.pattern.matches_with(&name, options)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn touch(root: &Path, relative: &str) {
+        let p = root.join(relative);
+        fs::create_dir_all(p.parent().unwrap()).unwrap();
+        fs::write(p, "# Friday, 15 Aug 2025\n").unwrap();
+    }
+
+    fn scanned_names(root: &Path) -> Vec<String> {
+        let mut names: Vec<String> = scan_dir_for_md_files(root)
+            .unwrap()
+            .iter()
+            .map(|p| {
+                p.strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn skips_hidden_directories() {
+        let tmp = tempdir().unwrap();
+        let root = tmp.path();
+        touch(root, "2025/08/2025-08-15.md");
+        touch(root, ".git/notes.md");
+        touch(root, ".obsidian/templates/daily.md");
+
+        assert_eq!(scanned_names(root), vec!["2025/08/2025-08-15.md"]);
+    }
+
+    #[test]
+    fn honors_lggignore_patterns() {
+        let tmp = tempdir().unwrap();
+        let root = tmp.path();
+        touch(root, "2025/08/2025-08-15.md");
+        touch(root, "2025/08/2025-08-16.tmp.md");
+        touch(root, "templates/daily.md");
+        touch(root, "drafts/idea.md");
+        touch(root, "README.md");
+        fs::write(
+            root.join(IGNORE_FILE_NAME),
+            "# Not journal files\n*.tmp.md\ntemplates/\n/drafts/*.md\nREADME.md\n",
+        )
+        .unwrap();
+
+        assert_eq!(scanned_names(root), vec!["2025/08/2025-08-15.md"]);
+    }
+
+    #[test]
+    fn finds_names_windows_cant_use() {
+        let reserved = |path: &str| windows_reserved_name(Path::new(path));
+        assert_eq!(reserved("notes/2025/08/2025-08-15.md"), None);
+        assert_eq!(reserved("notes/.lgg/pins"), None);
+        assert_eq!(reserved("notes/Con/journal"), Some("Con".to_string()));
+        assert_eq!(reserved("notes/aux.md"), Some("aux.md".to_string()));
+        assert_eq!(reserved("notes/what?"), Some("what?".to_string()));
+        assert_eq!(reserved("notes/draft."), Some("draft.".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follows_symlinks_without_looping() {
+        let tmp = tempdir().unwrap();
+        let root = tmp.path().join("journal");
+        let outside = tmp.path().join("outside");
+        touch(&root, "2025/08/2025-08-15.md");
+        touch(&outside, "2025-08-16.md");
+        std::os::unix::fs::symlink(&outside, root.join("linked")).unwrap();
+        std::os::unix::fs::symlink(&root, root.join("2025/loop")).unwrap();
+
+        assert_eq!(
+            scanned_names(&root),
+            vec!["2025/08/2025-08-15.md", "linked/2025-08-16.md"]
+        );
+    }
+}
@@ -0,0 +1,22 @@
+/// Fuzzy title match: every word of `query` appears in `title`, in any order and ignoring case.
+/// e.g. `kickoff project` matches "Project kickoff meeting".
+pub fn title_matches(title: &str, query: &str) -> bool {
+    let title = title.to_lowercase();
+    query
+        .to_lowercase()
+        .split_whitespace()
+        .all(|word| title.contains(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_words_in_any_order_ignoring_case() {
+        assert!(title_matches("Project kickoff meeting", "kickoff"));
+        assert!(title_matches("Project kickoff meeting", "KICKOFF project"));
+        assert!(title_matches("Project kickoff meeting", "kick meet"));
+        assert!(!title_matches("Project kickoff meeting", "kickoff lunch"));
+    }
+}
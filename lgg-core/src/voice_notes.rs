@@ -0,0 +1,193 @@
+//! `lgg import --audio`: entries from voice notes, written by another program from the
+//! `transcriber_cmd` of the config (like whisper.cpp).
+//!
+//! The program gets the path of each audio file as its last argument, and what it prints is the
+//! entry, tagged `@voice`. The entry is dated from a timestamp in the file name, like the
+//! `20250815_093000.m4a` of phone recorders, or else from when the file was last modified.
+use crate::convert::ImportResult;
+use crate::journal::{Journal, JournalEntry, JournalWriteEntry, ReadEntriesOptions};
+use crate::summarize::split_command;
+use crate::utils::command::run;
+use crate::utils::date_utils::DateFilter;
+use crate::utils::parse_input::split_title_body;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Tag of the entries of voice notes, in their title.
+pub const VOICE_TAG: &str = "@voice";
+
+/// Extensions of the files taken as voice notes.
+const AUDIO_EXTENSIONS: &[&str] = &[
+    "aac", "amr", "flac", "m4a", "mp3", "oga", "ogg", "opus", "wav", "webm",
+];
+
+/// A date and a time in a file name: `2025-08-15 09.30`, `20250815_093000`,
+/// `Recording 2025-08-15 at 09:30:12`...
+static NAME_TIMESTAMP: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(\d{4})-?(\d{2})-?(\d{2})(?:[ _T-]*(?:at )?(\d{2})[.:h-]?(\d{2})(?:[.:m-]?(\d{2}))?)?",
+    )
+    .unwrap()
+});
+
+/// Whether `path` has the extension of an audio file.
+pub fn is_audio(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// The date and time in the name of `path`, if it has one. Without a time, it's midnight.
+pub fn name_timestamp(path: &Path) -> Option<NaiveDateTime> {
+    let name = path.file_stem()?.to_str()?;
+    let captures = NAME_TIMESTAMP.captures(name)?;
+    let number = |i: usize| -> Option<u32> {
+        captures.get(i).map_or(Some(0), |m| m.as_str().parse().ok())
+    };
+    let date = NaiveDate::from_ymd_opt(number(1)? as i32, number(2)?, number(3)?)?;
+    let time = NaiveTime::from_hms_opt(number(4)?, number(5)?, 0)?;
+    Some(date.and_time(time))
+}
+
+/// When the voice note of `path` was recorded: the timestamp of its name, or else when it was
+/// last modified.
+pub fn recorded_at(path: &Path) -> Result<NaiveDateTime> {
+    if let Some(timestamp) = name_timestamp(path) {
+        return Ok(timestamp);
+    }
+    let modified = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .with_context(|| format!("reading the time of {}", path.display()))?;
+    let modified = DateTime::<Local>::from(modified).naive_local();
+    Ok(modified.with_second(0).unwrap_or(modified))
+}
+
+/// The audio files of `path`, by name: the ones in it if it's a directory, or `path` itself.
+pub fn audio_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files: Vec<PathBuf> = fs::read_dir(path)
+        .with_context(|| format!("reading {}", path.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|file| file.is_file() && is_audio(file))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Runs `command` with each audio file of `path` and saves what it prints as a `@voice` entry.
+/// A voice note whose time already has a `@voice` entry is skipped, so importing a directory
+/// again only adds the new ones. The files that can't be transcribed are in the errors.
+pub fn import_voice_notes(
+    journal: &Journal,
+    command: &str,
+    path: &Path,
+) -> Result<ImportResult<JournalEntry>> {
+    let Some(words) = split_command(command) else {
+        bail!("Invalid `transcriber_cmd` \"{command}\".");
+    };
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    for file in audio_files(path)? {
+        match import_voice_note(journal, &words, &file) {
+            Ok(Some(entry)) => entries.push(entry),
+            Ok(None) => {}
+            Err(error) => errors.push(format!("{}: {error:#}", file.display())),
+        }
+    }
+    Ok(ImportResult { entries, errors })
+}
+
+/// The entry of the voice note `file`, `None` if it was already imported.
+fn import_voice_note(
+    journal: &Journal,
+    words: &[String],
+    file: &Path,
+) -> Result<Option<JournalEntry>> {
+    let recorded = recorded_at(file)?;
+    let options = ReadEntriesOptions {
+        dates: Some(DateFilter::Single(recorded.date())),
+        ..Default::default()
+    };
+    let is_imported = journal.read_entries(&options).entries.iter().any(|entry| {
+        entry.time == recorded.time() && entry.tags.iter().any(|tag| tag == VOICE_TAG)
+    });
+    if is_imported {
+        return Ok(None);
+    }
+
+    let file_arg = file.to_string_lossy();
+    let mut args: Vec<&str> = words[1..].iter().map(String::as_str).collect();
+    args.push(&file_arg);
+    let transcript = run(&words[0], None, &args)?;
+    let (title, body) = split_title_body(transcript.trim(), &journal.tag_pattern);
+    if title.is_empty() {
+        bail!("the transcription is empty.");
+    }
+    let entry = journal.create_entry(JournalWriteEntry {
+        date: recorded.date(),
+        time: recorded.time(),
+        title: format!("{title} {VOICE_TAG}"),
+        body,
+        tags: vec![VOICE_TAG.to_string()],
+    })?;
+    Ok(Some(entry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        let date = NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        date.and_hms_opt(h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn reads_the_timestamp_of_recorder_file_names() {
+        let stamp = |name: &str| name_timestamp(Path::new(name));
+        assert_eq!(stamp("20250815_093012.m4a"), Some(at(2025, 8, 15, 9, 30)));
+        assert_eq!(stamp("2025-08-15 09.30.m4a"), Some(at(2025, 8, 15, 9, 30)));
+        assert_eq!(stamp("Recording 2025-08-15 at 18:05:12.mp3"), Some(at(2025, 8, 15, 18, 5)));
+        assert_eq!(stamp("2025-08-15.ogg"), Some(at(2025, 8, 15, 0, 0)));
+        assert_eq!(stamp("walk.ogg"), None);
+        assert_eq!(stamp("20251315.ogg"), None);
+        assert!(is_audio(Path::new("notes/walk.M4A")));
+        assert!(!is_audio(Path::new("notes/walk.txt")));
+    }
+
+    // `cat` stands for the transcriber, it isn't on Windows.
+    #[cfg(unix)]
+    #[test]
+    fn voice_notes_become_entries_once() {
+        use crate::tests::mk_config;
+        use crate::Lgg;
+        use tempfile::tempdir;
+
+        let tmp = tempdir().unwrap();
+        let lgg = Lgg::with_config(mk_config(tmp.path().join("journal"), None)).unwrap();
+        let notes = tmp.path().join("notes");
+        fs::create_dir(&notes).unwrap();
+        fs::write(notes.join("20250815_093000.m4a"), "Standup went well.\nShip on Friday.").unwrap();
+        fs::write(notes.join("20250815_180000.ogg"), "").unwrap();
+        fs::write(notes.join("20250815_200000.txt"), "Not a voice note.").unwrap();
+
+        // `cat` prints the "audio", as a transcriber would print what it hears.
+        let result = import_voice_notes(&lgg.journal, "cat", &notes).unwrap();
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.errors.len(), 1);
+        let entry = &result.entries[0];
+        assert_eq!(entry.title, "Standup went well. @voice");
+        assert_eq!(entry.body, "Ship on Friday.");
+        assert_eq!(entry.date.and_time(entry.time), at(2025, 8, 15, 9, 30));
+        assert_eq!(entry.tags, vec![VOICE_TAG]);
+
+        let again = import_voice_notes(&lgg.journal, "cat", &notes).unwrap();
+        assert!(again.entries.is_empty());
+    }
+}